@@ -0,0 +1,222 @@
+use codama_nodes::{
+    BooleanValueNode, BytesEncoding, BytesTypeNode, BytesValueNode, CamelCaseString,
+    ConstantPdaSeedNode, DefaultValueStrategy, DiscriminatorNode, FieldDiscriminatorNode,
+    FixedSizeTypeNode, InstructionArgumentNode, InstructionInputValueNode, Number, NumberFormat,
+    NumberTypeNode, NumberValueNode, PdaLinkNode, PdaNode, PdaSeedNode, PdaSeedValueNode,
+    PdaValueNode, PublicKeyValueNode, SizePrefixTypeNode, StringTypeNode, StringValueNode,
+    StructFieldTypeNode, TypeNode, ValueNode, U32,
+};
+use sha2::{Digest, Sha256};
+
+pub fn sized_string() -> TypeNode {
+    SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U32)).into()
+}
+
+pub fn sized_bytes() -> TypeNode {
+    SizePrefixTypeNode::new(BytesTypeNode::new(), NumberTypeNode::le(U32)).into()
+}
+
+/// Anchor encodes seeds without their size prefix.
+pub fn seed_type(r#type: TypeNode) -> TypeNode {
+    match r#type {
+        TypeNode::SizePrefix(node)
+            if matches!(*node.r#type, TypeNode::String(_) | TypeNode::Bytes(_)) =>
+        {
+            *node.r#type
+        }
+        r#type => r#type,
+    }
+}
+
+pub fn discriminator_field(discriminator: &[u8]) -> StructFieldTypeNode {
+    StructFieldTypeNode {
+        default_value_strategy: Some(DefaultValueStrategy::Omitted),
+        default_value: Box::new(Some(discriminator_value(discriminator).into())),
+        ..StructFieldTypeNode::new("discriminator", discriminator_type(discriminator))
+    }
+}
+
+pub fn discriminator_argument(discriminator: &[u8]) -> InstructionArgumentNode {
+    InstructionArgumentNode {
+        default_value_strategy: Some(DefaultValueStrategy::Omitted),
+        default_value: Box::new(Some(discriminator_value(discriminator).into())),
+        ..InstructionArgumentNode::new("discriminator", discriminator_type(discriminator))
+    }
+}
+
+pub fn field_discriminator() -> DiscriminatorNode {
+    FieldDiscriminatorNode::new("discriminator", 0).into()
+}
+
+fn discriminator_type(discriminator: &[u8]) -> FixedSizeTypeNode<TypeNode> {
+    FixedSizeTypeNode::new(BytesTypeNode::new(), discriminator.len())
+}
+
+fn discriminator_value(discriminator: &[u8]) -> BytesValueNode {
+    BytesValueNode::base16(to_hex(discriminator))
+}
+
+/// The first 8 bytes of `sha256("{namespace}:{name}")`, which legacy
+/// IDLs leave implicit for accounts, instructions and events.
+pub fn sighash(namespace: &str, name: &str) -> Vec<u8> {
+    Sha256::digest(format!("{namespace}:{name}"))[..8].to_vec()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Readable seeds such as `b"counter"` become string constants.
+pub fn constant_seed(bytes: &[u8]) -> PdaSeedNode {
+    match std::str::from_utf8(bytes) {
+        Ok(string) if !string.is_empty() && string.chars().all(|c| c.is_ascii_graphic()) => {
+            ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new(string)).into()
+        }
+        _ => ConstantPdaSeedNode::new(BytesTypeNode::new(), BytesValueNode::base16(to_hex(bytes)))
+            .into(),
+    }
+}
+
+/// Registers the PDA of an instruction account and returns a value pointing to it.
+pub fn pda_value(
+    pdas: &mut Vec<PdaNode>,
+    pda: PdaNode,
+    instruction_name: &str,
+    seeds: Vec<PdaSeedValueNode>,
+) -> InstructionInputValueNode {
+    let fallback_name = CamelCaseString::new(format!("{instruction_name}_{}", pda.name.as_str()));
+    let name = register_pda(pdas, pda, fallback_name);
+    PdaValueNode::new(PdaLinkNode::new(name), seeds).into()
+}
+
+/// Adds the PDA to the program unless an identical one already exists and returns
+/// its name. The fallback name is used when the preferred name is already taken.
+fn register_pda(
+    pdas: &mut Vec<PdaNode>,
+    mut pda: PdaNode,
+    fallback_name: CamelCaseString,
+) -> CamelCaseString {
+    let same_pda = |existing: &PdaNode, name: &CamelCaseString| {
+        &existing.name == name
+            && existing.seeds == pda.seeds
+            && existing.program_id == pda.program_id
+    };
+    for name in [pda.name.clone(), fallback_name] {
+        if pdas.iter().any(|existing| same_pda(existing, &name)) {
+            return name;
+        }
+        if !pdas.iter().any(|existing| existing.name == name) {
+            pda.name = name.clone();
+            pdas.push(pda);
+            return name;
+        }
+    }
+    let name = pda.name.clone();
+    pdas.push(pda);
+    name
+}
+
+/// Anchor constants are the raw Rust expressions of their value, so only
+/// literals can be parsed. Any other expression returns `None`.
+pub fn constant_value(r#type: &TypeNode, raw: &str) -> Option<ValueNode> {
+    let raw = raw.trim();
+    match r#type {
+        TypeNode::Boolean(_) => raw
+            .parse::<bool>()
+            .ok()
+            .map(|b| BooleanValueNode::new(b).into()),
+        TypeNode::Number(number) => {
+            let raw = raw.replace('_', "");
+            let number = match number.format {
+                NumberFormat::F32 | NumberFormat::F64 => Number::Float(raw.parse().ok()?),
+                NumberFormat::I8
+                | NumberFormat::I16
+                | NumberFormat::I32
+                | NumberFormat::I64
+                | NumberFormat::I128 => Number::SignedInteger(raw.parse().ok()?),
+                _ => Number::UnsignedInteger(raw.parse().ok()?),
+            };
+            Some(NumberValueNode::new(number).into())
+        }
+        TypeNode::PublicKey(_) => {
+            let raw = parse_string(raw).unwrap_or_else(|| raw.to_string());
+            let bytes = BytesEncoding::Base58.decode(&raw).ok()?;
+            (bytes.len() == 32).then(|| PublicKeyValueNode::new(raw).into())
+        }
+        TypeNode::SizePrefix(node) => constant_value(&node.r#type, raw),
+        TypeNode::String(_) => parse_string(raw).map(|string| StringValueNode::new(string).into()),
+        TypeNode::Bytes(_) => {
+            if let Some(string) = raw.strip_prefix("b\"").and_then(|s| s.strip_suffix('"')) {
+                return Some(BytesValueNode::utf8(string).into());
+            }
+            let bytes: Vec<u8> = serde_json::from_str(raw).ok()?;
+            Some(BytesValueNode::base16(to_hex(&bytes)).into())
+        }
+        _ => None,
+    }
+}
+
+fn parse_string(raw: &str) -> Option<String> {
+    serde_json::from_str(raw).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codama_nodes::{BooleanTypeNode, PublicKeyTypeNode, I64, U64};
+
+    #[test]
+    fn sighash_of_instruction() {
+        assert_eq!(to_hex(&sighash("global", "initialize")), "afaf6d1f0d989bed");
+    }
+
+    #[test]
+    fn constant_values() {
+        let value = |r#type: TypeNode, raw: &str| constant_value(&r#type, raw);
+        assert_eq!(
+            value(NumberTypeNode::le(U64).into(), "1_000_000"),
+            Some(NumberValueNode::new(Number::UnsignedInteger(1_000_000)).into())
+        );
+        assert_eq!(
+            value(NumberTypeNode::le(I64).into(), "-5"),
+            Some(NumberValueNode::new(-5).into())
+        );
+        assert_eq!(value(NumberTypeNode::le(U64).into(), "60 * 60"), None);
+        assert_eq!(
+            value(BooleanTypeNode::default().into(), "true"),
+            Some(BooleanValueNode::new(true).into())
+        );
+        assert_eq!(
+            value(sized_string(), "\"seed\""),
+            Some(StringValueNode::new("seed").into())
+        );
+        assert_eq!(
+            value(sized_bytes(), "[1, 255]"),
+            Some(BytesValueNode::base16("01ff").into())
+        );
+        assert_eq!(
+            value(sized_bytes(), "b\"seed\""),
+            Some(BytesValueNode::utf8("seed").into())
+        );
+        assert_eq!(
+            value(
+                PublicKeyTypeNode::new().into(),
+                "11111111111111111111111111111111"
+            ),
+            Some(PublicKeyValueNode::new("11111111111111111111111111111111").into())
+        );
+    }
+
+    #[test]
+    fn constant_seeds() {
+        assert_eq!(
+            constant_seed(b"counter"),
+            ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new("counter"))
+                .into()
+        );
+        assert_eq!(
+            constant_seed(&[0, 1]),
+            ConstantPdaSeedNode::new(BytesTypeNode::new(), BytesValueNode::base16("0001")).into()
+        );
+    }
+}
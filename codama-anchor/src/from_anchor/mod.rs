@@ -0,0 +1,30 @@
+pub(crate) mod helpers;
+mod v00;
+mod v01;
+
+use codama_errors::CodamaResult;
+use codama_nodes::{ProgramNode, RootNode};
+use serde_json::Value;
+
+/// Converts an Anchor IDL into a `RootNode`.
+///
+/// Both the legacy format (before Anchor 0.30) and the 0.30+ format are supported.
+pub fn root_node_from_anchor(json: &str) -> CodamaResult<RootNode> {
+    Ok(RootNode::new(program_node_from_anchor(json)?))
+}
+
+/// Converts an Anchor IDL into a `ProgramNode`, e.g. to use
+/// it as an additional program of an existing `RootNode`.
+pub fn program_node_from_anchor(json: &str) -> CodamaResult<ProgramNode> {
+    let value: Value = serde_json::from_str(json)?;
+    match is_legacy_idl(&value) {
+        true => v00::program_node(serde_json::from_value(value)?),
+        false => v01::program_node(serde_json::from_value(value)?),
+    }
+}
+
+/// The 0.30+ format moved the program address and name under
+/// `address` and `metadata` and introduced the `metadata.spec` field.
+fn is_legacy_idl(value: &Value) -> bool {
+    value.get("address").is_none() && value.pointer("/metadata/spec").is_none()
+}
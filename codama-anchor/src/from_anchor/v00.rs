@@ -0,0 +1,497 @@
+//! Legacy Anchor IDLs, before version 0.30.
+
+use super::helpers::{
+    constant_seed, constant_value, discriminator_argument, discriminator_field,
+    field_discriminator, pda_value, seed_type, sighash, sized_bytes, sized_string,
+};
+use codama_errors::{CodamaError, CodamaResult};
+use codama_nodes::{
+    AccountNode, AccountValueNode, ArgumentValueNode, ArrayTypeNode, BooleanTypeNode,
+    CamelCaseString, ConstantNode, ConstantPdaSeedNode, DefinedTypeLinkNode, DefinedTypeNode,
+    EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EnumTypeNode,
+    EnumVariantTypeNode, ErrorNode, EventNode, InstructionAccountNode, InstructionArgumentNode,
+    InstructionInputValueNode, InstructionNode, NumberTypeNode, NumberValueNode, OptionTypeNode,
+    PdaNode, PdaSeedNode, PdaSeedValueNode, ProgramNode, ProgramOrigin, PublicKeyTypeNode,
+    PublicKeyValueNode, StringTypeNode, StringValueNode, StructFieldTypeNode, StructTypeNode,
+    TupleTypeNode, TypeNode, VariablePdaSeedNode, F32, F64, I128, I16, I32, I64, I8, U128, U16,
+    U32, U64, U8,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+pub struct Idl {
+    version: String,
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    #[serde(default)]
+    instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    accounts: Vec<IdlTypeDef>,
+    #[serde(default)]
+    types: Vec<IdlTypeDef>,
+    #[serde(default)]
+    events: Vec<IdlEvent>,
+    #[serde(default)]
+    errors: Vec<IdlErrorCode>,
+    #[serde(default)]
+    constants: Vec<IdlConst>,
+    metadata: Option<IdlMetadata>,
+}
+
+#[derive(Deserialize)]
+struct IdlMetadata {
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdlInstruction {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    accounts: Vec<IdlAccountItem>,
+    args: Vec<IdlField>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdlAccountItem {
+    Composite { accounts: Vec<IdlAccountItem> },
+    Single(IdlAccount),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IdlAccount {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    is_mut: bool,
+    is_signer: bool,
+    #[serde(default, alias = "optional")]
+    is_optional: bool,
+    pda: Option<IdlPda>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IdlPda {
+    seeds: Vec<IdlSeed>,
+    program_id: Option<IdlSeed>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum IdlSeed {
+    Const { r#type: IdlType, value: Value },
+    Arg { r#type: IdlType, path: String },
+    Account { path: String },
+}
+
+#[derive(Deserialize)]
+struct IdlField {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    r#type: IdlType,
+}
+
+#[derive(Deserialize)]
+struct IdlEvent {
+    name: String,
+    fields: Vec<IdlField>,
+}
+
+#[derive(Deserialize)]
+struct IdlErrorCode {
+    code: u32,
+    name: String,
+    msg: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdlTypeDef {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    r#type: IdlTypeDefTy,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: IdlDefinedFields,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Alias {
+        value: IdlType,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdlDefinedFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+impl Default for IdlDefinedFields {
+    fn default() -> Self {
+        Self::Named(vec![])
+    }
+}
+
+#[derive(Deserialize)]
+struct IdlEnumVariant {
+    name: String,
+    fields: Option<IdlDefinedFields>,
+}
+
+#[derive(Deserialize)]
+struct IdlConst {
+    name: String,
+    r#type: IdlType,
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    U256,
+    I256,
+    Bytes,
+    String,
+    PublicKey,
+    Defined(String),
+    Option(Box<IdlType>),
+    #[serde(rename = "coption")]
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, u64),
+}
+
+pub fn program_node(idl: Idl) -> CodamaResult<ProgramNode> {
+    let mut pdas = vec![];
+    let instructions = idl
+        .instructions
+        .iter()
+        .map(|instruction| instruction_node(instruction, &mut pdas))
+        .collect::<CodamaResult<_>>()?;
+    let address = idl.metadata.and_then(|metadata| metadata.address);
+
+    Ok(ProgramNode {
+        origin: Some(ProgramOrigin::Anchor),
+        version: idl.version,
+        docs: idl.docs.into(),
+        accounts: idl
+            .accounts
+            .iter()
+            .map(|account| {
+                let IdlTypeDefTy::Struct {
+                    fields: IdlDefinedFields::Named(fields),
+                } = &account.r#type
+                else {
+                    return Err(CodamaError::InvalidIdl(format!(
+                        "account `{}` must be a struct with named fields",
+                        account.name
+                    )));
+                };
+                let data = struct_node(&sighash("account", &account.name), fields)?;
+                Ok(AccountNode {
+                    docs: account.docs.clone().into(),
+                    discriminators: vec![field_discriminator()],
+                    ..AccountNode::new(account.name.as_str(), data)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        instructions,
+        defined_types: idl
+            .types
+            .iter()
+            .map(|def| {
+                Ok(DefinedTypeNode {
+                    docs: def.docs.clone().into(),
+                    ..DefinedTypeNode::new(def.name.as_str(), type_def_node(&def.r#type)?)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        pdas,
+        events: idl
+            .events
+            .iter()
+            .map(|event| {
+                let data = struct_node(&sighash("event", &event.name), &event.fields)?;
+                Ok(EventNode {
+                    discriminators: vec![field_discriminator()],
+                    ..EventNode::new(event.name.as_str(), data)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        errors: idl
+            .errors
+            .into_iter()
+            .map(|error| ErrorNode::new(error.name, error.code, error.msg.unwrap_or_default()))
+            .collect(),
+        constants: idl
+            .constants
+            .iter()
+            .filter_map(|constant| {
+                let r#type = match type_node(&constant.r#type) {
+                    Ok(r#type) => r#type,
+                    Err(error) => return Some(Err(error)),
+                };
+                let value = constant_value(&r#type, &constant.value)?;
+                Some(Ok(ConstantNode::new(constant.name.as_str(), r#type, value)))
+            })
+            .collect::<CodamaResult<_>>()?,
+        ..ProgramNode::new(idl.name, address.unwrap_or_default())
+    })
+}
+
+/// Legacy IDLs leave discriminators implicit so they are prepended to the fields.
+fn struct_node(discriminator: &[u8], fields: &[IdlField]) -> CodamaResult<StructTypeNode> {
+    Ok(StructTypeNode::new(
+        std::iter::once(Ok(discriminator_field(discriminator)))
+            .chain(fields.iter().map(struct_field_node))
+            .collect::<CodamaResult<_>>()?,
+    ))
+}
+
+fn instruction_node(
+    instruction: &IdlInstruction,
+    pdas: &mut Vec<PdaNode>,
+) -> CodamaResult<InstructionNode> {
+    let mut accounts = vec![];
+    flatten_accounts(&instruction.accounts, &mut accounts);
+    let discriminator = sighash(
+        "global",
+        &CamelCaseString::new(&instruction.name).to_snake_case(),
+    );
+    let arguments = instruction
+        .args
+        .iter()
+        .map(|arg| {
+            Ok(InstructionArgumentNode {
+                docs: arg.docs.clone().into(),
+                ..InstructionArgumentNode::new(arg.name.as_str(), type_node(&arg.r#type)?)
+            })
+        })
+        .collect::<CodamaResult<Vec<_>>>()?;
+
+    Ok(InstructionNode {
+        name: instruction.name.as_str().into(),
+        docs: instruction.docs.clone().into(),
+        accounts: accounts
+            .into_iter()
+            .map(|account| {
+                let default_value = account
+                    .pda
+                    .as_ref()
+                    .and_then(|pda| pda_value_node(account, pda, instruction, pdas));
+                InstructionAccountNode {
+                    is_optional: account.is_optional.then_some(true),
+                    docs: account.docs.clone().into(),
+                    default_value: Box::new(default_value),
+                    ..InstructionAccountNode::new(
+                        account.name.as_str(),
+                        account.is_mut,
+                        account.is_signer,
+                    )
+                }
+            })
+            .collect(),
+        arguments: std::iter::once(discriminator_argument(&discriminator))
+            .chain(arguments)
+            .collect(),
+        discriminators: vec![field_discriminator()],
+        ..InstructionNode::default()
+    })
+}
+
+fn flatten_accounts<'a>(items: &'a [IdlAccountItem], accounts: &mut Vec<&'a IdlAccount>) {
+    for item in items {
+        match item {
+            IdlAccountItem::Composite { accounts: items } => flatten_accounts(items, accounts),
+            IdlAccountItem::Single(account) => accounts.push(account),
+        }
+    }
+}
+
+/// Seeds pointing to nested fields of accounts or arguments cannot be represented.
+fn pda_value_node(
+    account: &IdlAccount,
+    pda: &IdlPda,
+    instruction: &IdlInstruction,
+    pdas: &mut Vec<PdaNode>,
+) -> Option<InstructionInputValueNode> {
+    let mut seeds = vec![];
+    let mut values = vec![];
+    for seed in &pda.seeds {
+        match seed {
+            IdlSeed::Const { r#type, value } => seeds.push(constant_seed_node(r#type, value)?),
+            IdlSeed::Account { path } if !path.contains('.') => {
+                seeds
+                    .push(VariablePdaSeedNode::new(path.as_str(), PublicKeyTypeNode::new()).into());
+                values.push(PdaSeedValueNode::new(
+                    path.as_str(),
+                    AccountValueNode::new(path.as_str()),
+                ));
+            }
+            IdlSeed::Arg { r#type, path } if !path.contains('.') => {
+                let r#type = seed_type(type_node(r#type).ok()?);
+                seeds.push(VariablePdaSeedNode::new(path.as_str(), r#type).into());
+                values.push(PdaSeedValueNode::new(
+                    path.as_str(),
+                    ArgumentValueNode::new(path.as_str()),
+                ));
+            }
+            _ => return None,
+        }
+    }
+    let program_id = match &pda.program_id {
+        None => None,
+        Some(IdlSeed::Const {
+            r#type: IdlType::PublicKey,
+            value: Value::String(address),
+        }) => Some(address.clone()),
+        Some(_) => return None,
+    };
+    let pda = PdaNode {
+        program_id,
+        ..PdaNode::new(account.name.as_str(), seeds)
+    };
+    Some(pda_value(pdas, pda, &instruction.name, values))
+}
+
+fn constant_seed_node(r#type: &IdlType, value: &Value) -> Option<PdaSeedNode> {
+    Some(match (r#type, value) {
+        (IdlType::String, Value::String(string)) => {
+            ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new(string)).into()
+        }
+        (IdlType::PublicKey, Value::String(address)) => {
+            ConstantPdaSeedNode::new(PublicKeyTypeNode::new(), PublicKeyValueNode::new(address))
+                .into()
+        }
+        (IdlType::Bytes | IdlType::Array(..), Value::Array(_)) => {
+            constant_seed(&serde_json::from_value::<Vec<u8>>(value.clone()).ok()?)
+        }
+        (_, Value::Number(number)) => {
+            let r#type = type_node(r#type).ok()?;
+            ConstantPdaSeedNode::new(r#type, NumberValueNode::new(number.clone())).into()
+        }
+        _ => return None,
+    })
+}
+
+fn struct_field_node(field: &IdlField) -> CodamaResult<StructFieldTypeNode> {
+    Ok(StructFieldTypeNode {
+        docs: field.docs.clone().into(),
+        ..StructFieldTypeNode::new(field.name.as_str(), type_node(&field.r#type)?)
+    })
+}
+
+fn type_def_node(def: &IdlTypeDefTy) -> CodamaResult<TypeNode> {
+    Ok(match def {
+        IdlTypeDefTy::Struct { fields } => defined_fields_node(fields)?,
+        IdlTypeDefTy::Enum { variants } => EnumTypeNode::new(
+            variants
+                .iter()
+                .map(|variant| {
+                    let name = variant.name.as_str();
+                    Ok::<EnumVariantTypeNode, CodamaError>(match &variant.fields {
+                        None => EnumEmptyVariantTypeNode::new(name).into(),
+                        Some(IdlDefinedFields::Named(fields)) => EnumStructVariantTypeNode::new(
+                            name,
+                            StructTypeNode::new(
+                                fields
+                                    .iter()
+                                    .map(struct_field_node)
+                                    .collect::<CodamaResult<_>>()?,
+                            ),
+                        )
+                        .into(),
+                        Some(IdlDefinedFields::Tuple(items)) => EnumTupleVariantTypeNode::new(
+                            name,
+                            TupleTypeNode::new(
+                                items.iter().map(type_node).collect::<CodamaResult<_>>()?,
+                            ),
+                        )
+                        .into(),
+                    })
+                })
+                .collect::<CodamaResult<_>>()?,
+        )
+        .into(),
+        IdlTypeDefTy::Alias { value } => type_node(value)?,
+    })
+}
+
+fn defined_fields_node(fields: &IdlDefinedFields) -> CodamaResult<TypeNode> {
+    Ok(match fields {
+        IdlDefinedFields::Named(fields) => StructTypeNode::new(
+            fields
+                .iter()
+                .map(struct_field_node)
+                .collect::<CodamaResult<_>>()?,
+        )
+        .into(),
+        IdlDefinedFields::Tuple(items) => {
+            TupleTypeNode::new(items.iter().map(type_node).collect::<CodamaResult<_>>()?).into()
+        }
+    })
+}
+
+fn type_node(r#type: &IdlType) -> CodamaResult<TypeNode> {
+    Ok(match r#type {
+        IdlType::Bool => BooleanTypeNode::default().into(),
+        IdlType::U8 => NumberTypeNode::le(U8).into(),
+        IdlType::I8 => NumberTypeNode::le(I8).into(),
+        IdlType::U16 => NumberTypeNode::le(U16).into(),
+        IdlType::I16 => NumberTypeNode::le(I16).into(),
+        IdlType::U32 => NumberTypeNode::le(U32).into(),
+        IdlType::I32 => NumberTypeNode::le(I32).into(),
+        IdlType::F32 => NumberTypeNode::le(F32).into(),
+        IdlType::U64 => NumberTypeNode::le(U64).into(),
+        IdlType::I64 => NumberTypeNode::le(I64).into(),
+        IdlType::F64 => NumberTypeNode::le(F64).into(),
+        IdlType::U128 => NumberTypeNode::le(U128).into(),
+        IdlType::I128 => NumberTypeNode::le(I128).into(),
+        IdlType::U256 | IdlType::I256 => {
+            return Err(CodamaError::InvalidIdl(
+                "256-bit numbers are not supported".to_string(),
+            ))
+        }
+        IdlType::Bytes => sized_bytes(),
+        IdlType::String => sized_string(),
+        IdlType::PublicKey => PublicKeyTypeNode::new().into(),
+        IdlType::Defined(name) => DefinedTypeLinkNode::new(name.as_str()).into(),
+        IdlType::Option(item) => OptionTypeNode::new(type_node(item)?).into(),
+        IdlType::COption(item) => OptionTypeNode {
+            prefix: NumberTypeNode::le(U32).into(),
+            ..OptionTypeNode::fixed(type_node(item)?)
+        }
+        .into(),
+        IdlType::Vec(item) => {
+            ArrayTypeNode::prefixed(type_node(item)?, NumberTypeNode::le(U32)).into()
+        }
+        IdlType::Array(item, size) => ArrayTypeNode::fixed(type_node(item)?, *size).into(),
+    })
+}
@@ -0,0 +1,398 @@
+//! Anchor IDLs from version 0.30 onwards.
+
+use super::helpers::{
+    constant_seed, constant_value, discriminator_argument, discriminator_field,
+    field_discriminator, pda_value, seed_type, sized_bytes, sized_string,
+};
+use crate::idl::*;
+use codama_errors::{CodamaError, CodamaResult};
+use codama_nodes::{
+    AccountNode, AccountValueNode, ArgumentValueNode, ArrayTypeNode, BooleanTypeNode,
+    BytesEncoding, CamelCaseString, ConstantNode, DefinedTypeLinkNode, DefinedTypeNode,
+    EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EnumTypeNode,
+    EnumVariantTypeNode, ErrorNode, EventNode, InstructionAccountNode, InstructionArgumentNode,
+    InstructionInputValueNode, InstructionNode, NumberTypeNode, OptionTypeNode, PdaNode,
+    PdaSeedValueNode, ProgramNode, ProgramOrigin, PublicKeyTypeNode, PublicKeyValueNode,
+    StructFieldTypeNode, StructTypeNode, TupleTypeNode, TypeNode, VariablePdaSeedNode, F32, F64,
+    I128, I16, I32, I64, I8, U128, U16, U32, U64, U8,
+};
+use std::collections::HashSet;
+
+pub fn program_node(idl: Idl) -> CodamaResult<ProgramNode> {
+    let mut pdas = vec![];
+    let instructions = idl
+        .instructions
+        .iter()
+        .map(|instruction| instruction_node(instruction, &mut pdas))
+        .collect::<CodamaResult<_>>()?;
+
+    // Types backing accounts and events only become defined
+    // types if they are referenced by other types or arguments.
+    let mut referenced = HashSet::new();
+    idl.types
+        .iter()
+        .for_each(|def| collect_type_def_links(&def.r#type, &mut referenced));
+    idl.instructions
+        .iter()
+        .flat_map(|instruction| &instruction.args)
+        .for_each(|arg| collect_links(&arg.r#type, &mut referenced));
+    let backing_types = idl
+        .accounts
+        .iter()
+        .chain(idl.events.iter())
+        .map(|node| node.name.as_str())
+        .filter(|name| !referenced.contains(name))
+        .collect::<HashSet<_>>();
+
+    Ok(ProgramNode {
+        origin: Some(ProgramOrigin::Anchor),
+        version: idl.metadata.version,
+        docs: idl.docs.into(),
+        accounts: idl
+            .accounts
+            .iter()
+            .map(|account| {
+                let (docs, data) = discriminated_data(account, &idl.types, "account")?;
+                Ok(AccountNode {
+                    docs: docs.into(),
+                    discriminators: vec![field_discriminator()],
+                    ..AccountNode::new(account.name.as_str(), data)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        instructions,
+        defined_types: idl
+            .types
+            .iter()
+            .filter(|def| def.generics.is_empty() && !backing_types.contains(def.name.as_str()))
+            .map(|def| {
+                Ok(DefinedTypeNode {
+                    docs: def.docs.clone().into(),
+                    ..DefinedTypeNode::new(def.name.as_str(), type_def_node(&def.r#type)?)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        pdas,
+        events: idl
+            .events
+            .iter()
+            .map(|event| {
+                let (docs, data) = discriminated_data(event, &idl.types, "event")?;
+                Ok(EventNode {
+                    docs: docs.into(),
+                    discriminators: vec![field_discriminator()],
+                    ..EventNode::new(event.name.as_str(), data)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        errors: idl
+            .errors
+            .into_iter()
+            .map(|error| ErrorNode::new(error.name, error.code, error.msg.unwrap_or_default()))
+            .collect(),
+        constants: idl
+            .constants
+            .iter()
+            .filter_map(|constant| {
+                let r#type = match type_node(&constant.r#type) {
+                    Ok(r#type) => r#type,
+                    Err(error) => return Some(Err(error)),
+                };
+                let value = constant_value(&r#type, &constant.value)?;
+                Some(Ok(ConstantNode {
+                    docs: constant.docs.clone().into(),
+                    ..ConstantNode::new(constant.name.as_str(), r#type, value)
+                }))
+            })
+            .collect::<CodamaResult<_>>()?,
+        ..ProgramNode::new(idl.metadata.name, idl.address)
+    })
+}
+
+/// Accounts and events store their fields in the type of the same
+/// name, prefixed with their discriminator.
+fn discriminated_data(
+    node: &IdlDiscriminated,
+    types: &[IdlTypeDef],
+    label: &str,
+) -> CodamaResult<(Vec<String>, StructTypeNode)> {
+    let def = types
+        .iter()
+        .find(|def| def.name == node.name)
+        .ok_or_else(|| {
+            CodamaError::InvalidIdl(format!("missing type for {label} `{}`", node.name))
+        })?;
+    let fields = match &def.r#type {
+        IdlTypeDefTy::Struct { fields: None } => vec![],
+        IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(fields)),
+        } => fields
+            .iter()
+            .map(struct_field_node)
+            .collect::<CodamaResult<_>>()?,
+        _ => {
+            return Err(CodamaError::InvalidIdl(format!(
+                "the type of {label} `{}` must be a struct with named fields",
+                node.name
+            )))
+        }
+    };
+    let fields = std::iter::once(discriminator_field(&node.discriminator))
+        .chain(fields)
+        .collect();
+    Ok((def.docs.clone(), StructTypeNode::new(fields)))
+}
+
+fn instruction_node(
+    instruction: &IdlInstruction,
+    pdas: &mut Vec<PdaNode>,
+) -> CodamaResult<InstructionNode> {
+    let mut accounts = vec![];
+    flatten_accounts(&instruction.accounts, &mut accounts);
+    let arguments = instruction
+        .args
+        .iter()
+        .map(|arg| {
+            Ok(InstructionArgumentNode {
+                docs: arg.docs.clone().into(),
+                ..InstructionArgumentNode::new(arg.name.as_str(), type_node(&arg.r#type)?)
+            })
+        })
+        .collect::<CodamaResult<Vec<_>>>()?;
+
+    Ok(InstructionNode {
+        name: instruction.name.as_str().into(),
+        docs: instruction.docs.clone().into(),
+        accounts: accounts
+            .into_iter()
+            .map(|account| instruction_account_node(account, instruction, &arguments, pdas))
+            .collect(),
+        arguments: std::iter::once(discriminator_argument(&instruction.discriminator))
+            .chain(arguments)
+            .collect(),
+        discriminators: vec![field_discriminator()],
+        ..InstructionNode::default()
+    })
+}
+
+fn flatten_accounts<'a>(
+    items: &'a [IdlInstructionAccountItem],
+    accounts: &mut Vec<&'a IdlInstructionAccount>,
+) {
+    for item in items {
+        match item {
+            IdlInstructionAccountItem::Composite { accounts: items } => {
+                flatten_accounts(items, accounts)
+            }
+            IdlInstructionAccountItem::Single(account) => accounts.push(account),
+        }
+    }
+}
+
+fn instruction_account_node(
+    account: &IdlInstructionAccount,
+    instruction: &IdlInstruction,
+    arguments: &[InstructionArgumentNode],
+    pdas: &mut Vec<PdaNode>,
+) -> InstructionAccountNode {
+    let default_value = match (&account.address, &account.pda) {
+        (Some(address), _) => Some(PublicKeyValueNode::new(address).into()),
+        (None, Some(pda)) => pda_value_node(account, pda, instruction, arguments, pdas),
+        (None, None) => None,
+    };
+    InstructionAccountNode {
+        is_optional: account.optional.then_some(true),
+        docs: account.docs.clone().into(),
+        default_value: Box::new(default_value),
+        ..InstructionAccountNode::new(account.name.as_str(), account.writable, account.signer)
+    }
+}
+
+/// Registers the PDA of the account and returns a value pointing to it. Seeds pointing
+/// to nested fields of accounts or arguments cannot be represented and return `None`.
+fn pda_value_node(
+    account: &IdlInstructionAccount,
+    pda: &IdlPda,
+    instruction: &IdlInstruction,
+    arguments: &[InstructionArgumentNode],
+    pdas: &mut Vec<PdaNode>,
+) -> Option<InstructionInputValueNode> {
+    let mut seeds = vec![];
+    let mut values = vec![];
+    for seed in &pda.seeds {
+        match seed {
+            IdlSeed::Const { value } => seeds.push(constant_seed(value)),
+            IdlSeed::Account { path } if !path.contains('.') => {
+                seeds
+                    .push(VariablePdaSeedNode::new(path.as_str(), PublicKeyTypeNode::new()).into());
+                values.push(PdaSeedValueNode::new(
+                    path.as_str(),
+                    AccountValueNode::new(path.as_str()),
+                ));
+            }
+            IdlSeed::Arg { path } if !path.contains('.') => {
+                let name = CamelCaseString::new(path);
+                let argument = arguments.iter().find(|argument| argument.name == name)?;
+                let r#type = seed_type(argument.r#type.as_ref().clone());
+                seeds.push(VariablePdaSeedNode::new(name.clone(), r#type).into());
+                values.push(PdaSeedValueNode::new(
+                    name.clone(),
+                    ArgumentValueNode::new(name),
+                ));
+            }
+            _ => return None,
+        }
+    }
+    let program_id = match &pda.program {
+        None => None,
+        Some(IdlSeed::Const { value }) => Some(BytesEncoding::Base58.encode(value).ok()?),
+        Some(_) => return None,
+    };
+    let pda = PdaNode {
+        program_id,
+        ..PdaNode::new(account.name.as_str(), seeds)
+    };
+    Some(pda_value(pdas, pda, &instruction.name, values))
+}
+
+fn struct_field_node(field: &IdlField) -> CodamaResult<StructFieldTypeNode> {
+    Ok(StructFieldTypeNode {
+        docs: field.docs.clone().into(),
+        ..StructFieldTypeNode::new(field.name.as_str(), type_node(&field.r#type)?)
+    })
+}
+
+fn type_def_node(def: &IdlTypeDefTy) -> CodamaResult<TypeNode> {
+    Ok(match def {
+        IdlTypeDefTy::Struct { fields } => defined_fields_node(fields.as_ref())?,
+        IdlTypeDefTy::Enum { variants } => EnumTypeNode::new(
+            variants
+                .iter()
+                .map(|variant| {
+                    let name = variant.name.as_str();
+                    Ok::<EnumVariantTypeNode, CodamaError>(match &variant.fields {
+                        None => EnumEmptyVariantTypeNode::new(name).into(),
+                        Some(IdlDefinedFields::Named(fields)) => EnumStructVariantTypeNode::new(
+                            name,
+                            StructTypeNode::new(
+                                fields
+                                    .iter()
+                                    .map(struct_field_node)
+                                    .collect::<CodamaResult<_>>()?,
+                            ),
+                        )
+                        .into(),
+                        Some(IdlDefinedFields::Tuple(items)) => EnumTupleVariantTypeNode::new(
+                            name,
+                            TupleTypeNode::new(
+                                items.iter().map(type_node).collect::<CodamaResult<_>>()?,
+                            ),
+                        )
+                        .into(),
+                    })
+                })
+                .collect::<CodamaResult<_>>()?,
+        )
+        .into(),
+        IdlTypeDefTy::Type { alias } => type_node(alias)?,
+    })
+}
+
+fn defined_fields_node(fields: Option<&IdlDefinedFields>) -> CodamaResult<TypeNode> {
+    Ok(match fields {
+        None => StructTypeNode::new(vec![]).into(),
+        Some(IdlDefinedFields::Named(fields)) => StructTypeNode::new(
+            fields
+                .iter()
+                .map(struct_field_node)
+                .collect::<CodamaResult<_>>()?,
+        )
+        .into(),
+        Some(IdlDefinedFields::Tuple(items)) => {
+            TupleTypeNode::new(items.iter().map(type_node).collect::<CodamaResult<_>>()?).into()
+        }
+    })
+}
+
+fn type_node(r#type: &IdlType) -> CodamaResult<TypeNode> {
+    Ok(match r#type {
+        IdlType::Bool => BooleanTypeNode::default().into(),
+        IdlType::U8 => NumberTypeNode::le(U8).into(),
+        IdlType::I8 => NumberTypeNode::le(I8).into(),
+        IdlType::U16 => NumberTypeNode::le(U16).into(),
+        IdlType::I16 => NumberTypeNode::le(I16).into(),
+        IdlType::U32 => NumberTypeNode::le(U32).into(),
+        IdlType::I32 => NumberTypeNode::le(I32).into(),
+        IdlType::F32 => NumberTypeNode::le(F32).into(),
+        IdlType::U64 => NumberTypeNode::le(U64).into(),
+        IdlType::I64 => NumberTypeNode::le(I64).into(),
+        IdlType::F64 => NumberTypeNode::le(F64).into(),
+        IdlType::U128 => NumberTypeNode::le(U128).into(),
+        IdlType::I128 => NumberTypeNode::le(I128).into(),
+        IdlType::U256 | IdlType::I256 => {
+            return Err(CodamaError::InvalidIdl(
+                "256-bit numbers are not supported".to_string(),
+            ))
+        }
+        IdlType::Bytes => sized_bytes(),
+        IdlType::String => sized_string(),
+        IdlType::Pubkey => PublicKeyTypeNode::new().into(),
+        IdlType::Option(item) => OptionTypeNode::new(type_node(item)?).into(),
+        IdlType::COption(item) => OptionTypeNode {
+            prefix: NumberTypeNode::le(U32).into(),
+            ..OptionTypeNode::fixed(type_node(item)?)
+        }
+        .into(),
+        IdlType::Vec(item) => {
+            ArrayTypeNode::prefixed(type_node(item)?, NumberTypeNode::le(U32)).into()
+        }
+        IdlType::Array(item, IdlArrayLen::Value(size)) => {
+            ArrayTypeNode::fixed(type_node(item)?, *size).into()
+        }
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            DefinedTypeLinkNode::new(name.as_str()).into()
+        }
+        IdlType::Array(_, IdlArrayLen::Generic { generic: name })
+        | IdlType::Defined { name, .. }
+        | IdlType::Generic(name) => {
+            return Err(CodamaError::InvalidIdl(format!(
+                "generic type `{name}` is not supported"
+            )))
+        }
+    })
+}
+
+fn collect_type_def_links<'a>(def: &'a IdlTypeDefTy, links: &mut HashSet<&'a str>) {
+    match def {
+        IdlTypeDefTy::Struct { fields } => collect_fields_links(fields, links),
+        IdlTypeDefTy::Enum { variants } => variants
+            .iter()
+            .for_each(|variant| collect_fields_links(&variant.fields, links)),
+        IdlTypeDefTy::Type { alias } => collect_links(alias, links),
+    }
+}
+
+fn collect_fields_links<'a>(fields: &'a Option<IdlDefinedFields>, links: &mut HashSet<&'a str>) {
+    match fields {
+        Some(IdlDefinedFields::Named(fields)) => fields
+            .iter()
+            .for_each(|field| collect_links(&field.r#type, links)),
+        Some(IdlDefinedFields::Tuple(items)) => {
+            items.iter().for_each(|item| collect_links(item, links))
+        }
+        None => {}
+    }
+}
+
+fn collect_links<'a>(r#type: &'a IdlType, links: &mut HashSet<&'a str>) {
+    match r#type {
+        IdlType::Option(item)
+        | IdlType::COption(item)
+        | IdlType::Vec(item)
+        | IdlType::Array(item, _) => collect_links(item, links),
+        IdlType::Defined { name, .. } => {
+            links.insert(name);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,198 @@
+//! The Anchor IDL format from version 0.30 onwards.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Idl {
+    pub(crate) address: String,
+    pub(crate) metadata: IdlMetadata,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    #[serde(default)]
+    pub(crate) instructions: Vec<IdlInstruction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) accounts: Vec<IdlDiscriminated>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) events: Vec<IdlDiscriminated>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) errors: Vec<IdlErrorCode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) types: Vec<IdlTypeDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) constants: Vec<IdlConst>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlMetadata {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) spec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlInstruction {
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    pub(crate) discriminator: Vec<u8>,
+    pub(crate) accounts: Vec<IdlInstructionAccountItem>,
+    pub(crate) args: Vec<IdlField>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum IdlInstructionAccountItem {
+    Composite {
+        accounts: Vec<IdlInstructionAccountItem>,
+    },
+    Single(IdlInstructionAccount),
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlInstructionAccount {
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) writable: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) signer: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) optional: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pda: Option<IdlPda>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlPda {
+    pub(crate) seeds: Vec<IdlSeed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) program: Option<IdlSeed>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub(crate) enum IdlSeed {
+    Const { value: Vec<u8> },
+    Arg { path: String },
+    Account { path: String },
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlField {
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    pub(crate) r#type: IdlType,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlDiscriminated {
+    pub(crate) name: String,
+    pub(crate) discriminator: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlErrorCode {
+    pub(crate) code: u32,
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) msg: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlTypeDef {
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) generics: Vec<serde_json::Value>,
+    pub(crate) r#type: IdlTypeDefTy,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub(crate) enum IdlTypeDefTy {
+    Struct {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fields: Option<IdlDefinedFields>,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Type {
+        alias: IdlType,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum IdlDefinedFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlEnumVariant {
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) fields: Option<IdlDefinedFields>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IdlConst {
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) docs: Vec<String>,
+    pub(crate) r#type: IdlType,
+    pub(crate) value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    U256,
+    I256,
+    Bytes,
+    String,
+    Pubkey,
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, IdlArrayLen),
+    Defined {
+        name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        generics: Vec<serde_json::Value>,
+    },
+    Generic(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum IdlArrayLen {
+    Value(u64),
+    Generic { generic: String },
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
@@ -0,0 +1,6 @@
+mod from_anchor;
+mod idl;
+mod to_anchor;
+
+pub use from_anchor::*;
+pub use to_anchor::*;
@@ -0,0 +1,727 @@
+use crate::from_anchor::helpers::sighash;
+use crate::idl::*;
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    encode, AccountNode, BytesEncoding, CamelCaseString, ConstantPdaSeedValue, CountNode,
+    DiscriminatorNode, Endianness, EnumTypeNode, EnumVariantTypeNode, EventNode, HasKind,
+    InstructionAccountNode, InstructionArgumentNode, InstructionInputValueNode, InstructionNode,
+    IsSigner, LinkResolver, NodePath, NumberFormat, NumberTypeNode, PdaSeedNode, PdaSeedValueValue,
+    PdaValueNode, PdaValuePda, PdaValueProgramId, ProgramNode, RootNode, StructFieldTypeNode,
+    StructTypeNode, TypeNode, ValueNode,
+};
+use serde_json::Number as JsonNumber;
+
+/// The version of the Anchor IDL specification produced by the export.
+const IDL_SPEC: &str = "0.1.0";
+
+pub use codama_export_issues::ExportIssue;
+
+const DEFAULT_DISCRIMINATOR: &str =
+    "no discriminator at offset 0 was found, the default Anchor discriminator was used";
+
+/// An Anchor IDL exported from a `RootNode`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorExport {
+    pub json: String,
+    /// The Codama constructs that could not be represented in the IDL,
+    /// or that were replaced by their closest Anchor equivalent.
+    pub issues: Vec<ExportIssue>,
+}
+
+/// Converts the main program of a `RootNode` into an Anchor IDL (0.30+ format).
+pub fn root_node_to_anchor(root: &RootNode) -> CodamaResult<AnchorExport> {
+    let mut exporter = Exporter {
+        resolver: LinkResolver::new(root).with_program(&root.program),
+        path: NodePath::new(),
+        issues: vec![],
+    };
+    let idl = exporter.program(&root.program);
+    if !root.additional_programs.is_empty() {
+        exporter.report("additional programs cannot be represented and were ignored");
+    }
+    Ok(AnchorExport {
+        json: serde_json::to_string_pretty(&idl)?,
+        issues: exporter.issues,
+    })
+}
+
+struct Exporter<'a> {
+    resolver: LinkResolver<'a>,
+    path: NodePath,
+    issues: Vec<ExportIssue>,
+}
+
+impl ExportIssueReporter for Exporter<'_> {
+    const UNSUPPORTED: &'static str = "cannot be represented";
+
+    fn report<T: Into<String>>(&mut self, message: T) {
+        self.issues.push(ExportIssue {
+            path: self.path.clone(),
+            message: message.into(),
+        });
+    }
+}
+
+impl<'a> Exporter<'a> {
+    fn scoped<T>(
+        &mut self,
+        kind: &'static str,
+        name: &CamelCaseString,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.path.push(kind, Some(name.clone()));
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn program(&mut self, program: &'a ProgramNode) -> Idl {
+        self.scoped(program.kind(), &program.name, |this| {
+            let mut types = vec![];
+            let accounts = program
+                .accounts
+                .iter()
+                .map(|account| this.account(account, &mut types))
+                .collect();
+            let events = program
+                .events
+                .iter()
+                .map(|event| this.event(event, &mut types))
+                .collect();
+            for defined_type in &program.defined_types {
+                let r#type = this.scoped(defined_type.kind(), &defined_type.name, |this| {
+                    this.type_def(&defined_type.r#type)
+                });
+                types.push(IdlTypeDef {
+                    name: defined_type.name.to_pascal_case(),
+                    docs: defined_type.docs.to_vec(),
+                    generics: vec![],
+                    r#type,
+                });
+            }
+
+            Idl {
+                address: program.public_key.clone(),
+                metadata: IdlMetadata {
+                    name: program.name.to_snake_case(),
+                    version: program.version.clone(),
+                    spec: Some(IDL_SPEC.to_string()),
+                    description: None,
+                },
+                docs: program.docs.to_vec(),
+                instructions: program
+                    .instructions
+                    .iter()
+                    .map(|instruction| this.instruction(instruction))
+                    .collect(),
+                accounts,
+                events,
+                errors: program
+                    .errors
+                    .iter()
+                    .map(|error| IdlErrorCode {
+                        code: error.code,
+                        name: error.name.to_pascal_case(),
+                        msg: (!error.message.is_empty()).then(|| error.message.clone()),
+                    })
+                    .collect(),
+                types,
+                constants: program
+                    .constants
+                    .iter()
+                    .filter_map(|constant| {
+                        this.scoped(constant.kind(), &constant.name, |this| {
+                            let value = this.constant_value(&constant.value)?;
+                            Some(IdlConst {
+                                name: constant.name.to_snake_case().to_uppercase(),
+                                docs: constant.docs.to_vec(),
+                                r#type: this.r#type(&constant.r#type),
+                                value,
+                            })
+                        })
+                    })
+                    .collect(),
+            }
+        })
+    }
+
+    /// Anchor accounts and events store their fields in the type of the same name.
+    fn account(&mut self, account: &AccountNode, types: &mut Vec<IdlTypeDef>) -> IdlDiscriminated {
+        self.scoped(account.kind(), &account.name, |this| {
+            let name = account.name.to_pascal_case();
+            let data = this.nested(&account.data);
+            let (discriminator, fields) =
+                this.discriminated_fields(data, &account.discriminators, || {
+                    sighash("account", &name)
+                });
+            types.push(IdlTypeDef {
+                name: name.clone(),
+                docs: account.docs.to_vec(),
+                generics: vec![],
+                r#type: IdlTypeDefTy::Struct {
+                    fields: Some(IdlDefinedFields::Named(fields)),
+                },
+            });
+            IdlDiscriminated {
+                name,
+                discriminator,
+            }
+        })
+    }
+
+    fn event(&mut self, event: &EventNode, types: &mut Vec<IdlTypeDef>) -> IdlDiscriminated {
+        self.scoped(event.kind(), &event.name, |this| {
+            let name = event.name.to_pascal_case();
+            let default_discriminator = || sighash("event", &name);
+            let (discriminator, r#type) = match event.data.as_ref() {
+                TypeNode::Struct(data) => {
+                    let (discriminator, fields) = this.discriminated_fields(
+                        data,
+                        &event.discriminators,
+                        default_discriminator,
+                    );
+                    let fields = Some(IdlDefinedFields::Named(fields));
+                    (discriminator, IdlTypeDefTy::Struct { fields })
+                }
+                data => {
+                    this.report(DEFAULT_DISCRIMINATOR);
+                    (default_discriminator(), this.type_def(data))
+                }
+            };
+            types.push(IdlTypeDef {
+                name: name.clone(),
+                docs: event.docs.to_vec(),
+                generics: vec![],
+                r#type,
+            });
+            IdlDiscriminated {
+                name,
+                discriminator,
+            }
+        })
+    }
+
+    fn instruction(&mut self, instruction: &'a InstructionNode) -> IdlInstruction {
+        let resolver = self.resolver.with_instruction(instruction);
+        let parent = std::mem::replace(&mut self.resolver, resolver);
+        let idl = self.scoped(instruction.kind(), &instruction.name, |this| {
+            let name = instruction.name.to_snake_case();
+            let (discriminator, discriminator_argument) =
+                this.instruction_discriminator(instruction);
+            if !instruction.remaining_accounts.is_empty() {
+                this.report("remaining accounts cannot be represented and were ignored");
+            }
+            IdlInstruction {
+                discriminator: discriminator.unwrap_or_else(|| sighash("global", &name)),
+                name,
+                docs: instruction.docs.to_vec(),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|account| this.instruction_account(account))
+                    .map(IdlInstructionAccountItem::Single)
+                    .collect(),
+                args: instruction
+                    .arguments
+                    .iter()
+                    .filter(|argument| Some(&argument.name) != discriminator_argument)
+                    .map(|argument| this.argument(argument))
+                    .collect(),
+            }
+        });
+        self.resolver = parent;
+        idl
+    }
+
+    fn argument(&mut self, argument: &InstructionArgumentNode) -> IdlField {
+        self.scoped(argument.kind(), &argument.name, |this| {
+            if argument.default_value.is_some() {
+                this.report("argument default values cannot be represented and were ignored");
+            }
+            IdlField {
+                name: argument.name.to_snake_case(),
+                docs: argument.docs.to_vec(),
+                r#type: this.r#type(&argument.r#type),
+            }
+        })
+    }
+
+    /// Returns the discriminator bytes of the instruction, if any, alongside
+    /// the name of the argument that holds them so it can be removed from the IDL.
+    fn instruction_discriminator(
+        &mut self,
+        instruction: &'a InstructionNode,
+    ) -> (Option<Vec<u8>>, Option<&'a CamelCaseString>) {
+        for discriminator in instruction.discriminators.iter() {
+            match discriminator {
+                DiscriminatorNode::Field(node) if node.offset == 0 => {
+                    let argument = instruction
+                        .arguments
+                        .first()
+                        .filter(|argument| argument.name == node.name);
+                    let value = argument.and_then(|argument| {
+                        let value = argument.default_value.as_ref().clone()?;
+                        Some((ValueNode::try_from(value).ok()?, &argument.r#type))
+                    });
+                    if let Some((value, r#type)) = value {
+                        if let Ok(bytes) = encode(&value, r#type, &self.resolver) {
+                            return (Some(bytes), Some(&node.name));
+                        }
+                    }
+                }
+                DiscriminatorNode::Constant(node) if node.offset == 0 => {
+                    if let Ok(bytes) =
+                        encode(&node.constant.value, &node.constant.r#type, &self.resolver)
+                    {
+                        return (Some(bytes), None);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.report(DEFAULT_DISCRIMINATOR);
+        (None, None)
+    }
+
+    /// Returns the discriminator bytes of an account or an event and its
+    /// fields, without the field holding the discriminator.
+    fn discriminated_fields(
+        &mut self,
+        data: &StructTypeNode,
+        discriminators: &[DiscriminatorNode],
+        default_discriminator: impl FnOnce() -> Vec<u8>,
+    ) -> (Vec<u8>, Vec<IdlField>) {
+        let mut discriminator = None;
+        let mut skipped_field = None;
+        for node in discriminators {
+            match node {
+                DiscriminatorNode::Field(node) if node.offset == 0 => {
+                    let field = data.fields.first().filter(|field| field.name == node.name);
+                    let bytes = field.and_then(|field| {
+                        let value = field.default_value.as_ref().as_ref()?;
+                        encode(value, &field.r#type, &self.resolver).ok()
+                    });
+                    if bytes.is_some() {
+                        discriminator = bytes;
+                        skipped_field = Some(&node.name);
+                        break;
+                    }
+                }
+                DiscriminatorNode::Constant(node) if node.offset == 0 => {
+                    let bytes = encode(&node.constant.value, &node.constant.r#type, &self.resolver);
+                    if let Ok(bytes) = bytes {
+                        discriminator = Some(bytes);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let discriminator = discriminator.unwrap_or_else(|| {
+            self.report(DEFAULT_DISCRIMINATOR);
+            default_discriminator()
+        });
+        let fields = data
+            .fields
+            .iter()
+            .filter(|field| Some(&field.name) != skipped_field)
+            .map(|field| self.field(field))
+            .collect();
+        (discriminator, fields)
+    }
+
+    fn instruction_account(&mut self, account: &InstructionAccountNode) -> IdlInstructionAccount {
+        self.scoped(account.kind(), &account.name, |this| {
+            if account.is_signer == IsSigner::Either {
+                this.report(
+                    "`either` signers cannot be represented and were exported as non-signers",
+                );
+            }
+            let mut idl = IdlInstructionAccount {
+                name: account.name.to_snake_case(),
+                docs: account.docs.to_vec(),
+                writable: account.is_writable,
+                signer: account.is_signer == IsSigner::True,
+                optional: account.is_optional == Some(true),
+                address: None,
+                pda: None,
+            };
+            match account.default_value.as_ref() {
+                None => {}
+                Some(InstructionInputValueNode::PublicKeyValue(node)) => {
+                    idl.address = Some(node.public_key.clone());
+                }
+                Some(InstructionInputValueNode::ProgramIdValue(_)) => {
+                    idl.address = this
+                        .resolver
+                        .program()
+                        .map(|program| program.public_key.clone());
+                }
+                Some(InstructionInputValueNode::ProgramLink(link)) => {
+                    match this.resolver.resolve_program(link) {
+                        Ok(program) => idl.address = Some(program.public_key.clone()),
+                        Err(error) => this.report(error.to_string()),
+                    }
+                }
+                Some(InstructionInputValueNode::PdaValue(node)) => idl.pda = this.pda(node),
+                Some(node) => this.report(format!(
+                    "`{}` default values cannot be represented and were ignored",
+                    node.kind()
+                )),
+            }
+            idl
+        })
+    }
+
+    /// Anchor PDAs are described inline with seeds pointing to accounts
+    /// and arguments of the instruction or holding constant bytes.
+    fn pda(&mut self, value: &PdaValueNode) -> Option<IdlPda> {
+        let pda = match value.pda.as_ref() {
+            PdaValuePda::Pda(pda) => pda,
+            PdaValuePda::PdaLink(link) => match self.resolver.resolve_pda(link) {
+                Ok(pda) => pda,
+                Err(error) => {
+                    self.report(error.to_string());
+                    return None;
+                }
+            },
+        };
+        let seeds = pda
+            .seeds
+            .iter()
+            .map(|seed| match seed {
+                PdaSeedNode::Constant(seed) => {
+                    let value = match seed.value.as_ref() {
+                        ConstantPdaSeedValue::ProgramId(_) => {
+                            return self.program_id_seed();
+                        }
+                        value => ValueNode::try_from(value.clone()).ok()?,
+                    };
+                    self.constant_seed(&value, &seed.r#type)
+                }
+                PdaSeedNode::Variable(seed) => {
+                    let seed_value = value.seeds.iter().find(|value| value.name == seed.name);
+                    match seed_value.map(|value| value.value.as_ref()) {
+                        Some(PdaSeedValueValue::Account(node)) => Some(IdlSeed::Account {
+                            path: node.name.to_snake_case(),
+                        }),
+                        Some(PdaSeedValueValue::Argument(node)) => Some(IdlSeed::Arg {
+                            path: node.name.to_snake_case(),
+                        }),
+                        Some(node) => {
+                            let value = ValueNode::try_from(node.clone()).ok()?;
+                            self.constant_seed(&value, &seed.r#type)
+                        }
+                        None => {
+                            self.report(format!(
+                                "PDA seed `{}` has no value, the PDA was ignored",
+                                seed.name.as_str()
+                            ));
+                            None
+                        }
+                    }
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let program = match (value.program_id.as_ref(), &pda.program_id) {
+            (Some(PdaValueProgramId::Account(node)), _) => Some(IdlSeed::Account {
+                path: node.name.to_snake_case(),
+            }),
+            (Some(PdaValueProgramId::Argument(node)), _) => Some(IdlSeed::Arg {
+                path: node.name.to_snake_case(),
+            }),
+            (None, Some(program_id)) => Some(IdlSeed::Const {
+                value: BytesEncoding::Base58.decode(program_id).ok()?,
+            }),
+            (None, None) => None,
+        };
+        Some(IdlPda { seeds, program })
+    }
+
+    fn constant_seed(&mut self, value: &ValueNode, r#type: &TypeNode) -> Option<IdlSeed> {
+        // Anchor seeds are raw bytes, without the size prefix of their type.
+        let r#type = match r#type {
+            TypeNode::SizePrefix(node) => node.r#type.as_ref(),
+            r#type => r#type,
+        };
+        match encode(value, r#type, &self.resolver) {
+            Ok(value) => Some(IdlSeed::Const { value }),
+            Err(error) => {
+                self.report(format!("{error}, the PDA was ignored"));
+                None
+            }
+        }
+    }
+
+    fn program_id_seed(&mut self) -> Option<IdlSeed> {
+        let program = self.resolver.program()?;
+        Some(IdlSeed::Const {
+            value: BytesEncoding::Base58.decode(&program.public_key).ok()?,
+        })
+    }
+
+    fn field(&mut self, field: &StructFieldTypeNode) -> IdlField {
+        self.scoped(field.kind(), &field.name, |this| IdlField {
+            name: field.name.to_snake_case(),
+            docs: field.docs.to_vec(),
+            r#type: this.r#type(&field.r#type),
+        })
+    }
+
+    fn type_def(&mut self, r#type: &TypeNode) -> IdlTypeDefTy {
+        match r#type {
+            TypeNode::Struct(node) => IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(
+                    node.fields.iter().map(|field| self.field(field)).collect(),
+                )),
+            },
+            TypeNode::Tuple(node) => IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Tuple(
+                    node.items.iter().map(|item| self.r#type(item)).collect(),
+                )),
+            },
+            TypeNode::Enum(node) => self.r#enum(node),
+            r#type => IdlTypeDefTy::Type {
+                alias: self.r#type(r#type),
+            },
+        }
+    }
+
+    fn r#enum(&mut self, node: &EnumTypeNode) -> IdlTypeDefTy {
+        self.borsh_prefix(&node.size, NumberFormat::U8);
+        let variants = node
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let (name, discriminator, fields) = match variant {
+                    EnumVariantTypeNode::Empty(variant) => {
+                        (&variant.name, variant.discriminator, None)
+                    }
+                    EnumVariantTypeNode::Struct(variant) => {
+                        let fields = self.nested(&variant.r#struct).fields.iter();
+                        let fields = fields.map(|field| self.field(field)).collect();
+                        (
+                            &variant.name,
+                            variant.discriminator,
+                            Some(IdlDefinedFields::Named(fields)),
+                        )
+                    }
+                    EnumVariantTypeNode::Tuple(variant) => {
+                        let items = self.nested(&variant.tuple).items.iter();
+                        let items = items.map(|item| self.r#type(item)).collect();
+                        (
+                            &variant.name,
+                            variant.discriminator,
+                            Some(IdlDefinedFields::Tuple(items)),
+                        )
+                    }
+                };
+                if discriminator.is_some_and(|discriminator| discriminator as usize != index) {
+                    self.report(format!(
+                        "the explicit discriminator of variant `{}` cannot be represented",
+                        name.as_str()
+                    ));
+                }
+                IdlEnumVariant {
+                    name: name.to_pascal_case(),
+                    fields,
+                }
+            })
+            .collect();
+        IdlTypeDefTy::Enum { variants }
+    }
+
+    /// Maps a type to its Anchor equivalent. Types without an exact
+    /// equivalent are reported and replaced by their closest one.
+    fn r#type(&mut self, r#type: &TypeNode) -> IdlType {
+        match r#type {
+            TypeNode::Amount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::DateTime(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::SolAmount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::Number(node) => self.number(node),
+            TypeNode::Boolean(node) => {
+                self.borsh_prefix(&node.size, NumberFormat::U8);
+                IdlType::Bool
+            }
+            TypeNode::PublicKey(_) => IdlType::Pubkey,
+            TypeNode::Link(node) => IdlType::Defined {
+                name: node.name.to_pascal_case(),
+                generics: vec![],
+            },
+            TypeNode::SizePrefix(node) => {
+                self.borsh_prefix(&node.prefix, NumberFormat::U32);
+                match node.r#type.as_ref() {
+                    TypeNode::String(string) => {
+                        if string.encoding != BytesEncoding::Utf8 {
+                            self.report("non-UTF-8 strings were exported as UTF-8 strings");
+                        }
+                        IdlType::String
+                    }
+                    TypeNode::Bytes(_) => IdlType::Bytes,
+                    r#type => {
+                        self.report_kind(node.kind());
+                        self.r#type(r#type)
+                    }
+                }
+            }
+            TypeNode::FixedSize(node) => match node.r#type.as_ref() {
+                TypeNode::Bytes(_) => {
+                    IdlType::Array(Box::new(IdlType::U8), IdlArrayLen::Value(node.size as u64))
+                }
+                r#type => {
+                    self.report_kind(node.kind());
+                    self.r#type(r#type)
+                }
+            },
+            TypeNode::String(_) => {
+                self.report("strings without a `u32` prefix were exported as prefixed strings");
+                IdlType::String
+            }
+            TypeNode::Bytes(_) => {
+                self.report("bytes without a `u32` prefix were exported as prefixed bytes");
+                IdlType::Bytes
+            }
+            TypeNode::Option(node) => {
+                let item = Box::new(self.r#type(&node.item));
+                let prefix = self.nested(&node.prefix);
+                match (
+                    node.fixed == Some(true),
+                    prefix.is_le(NumberFormat::U8),
+                    prefix.is_le(NumberFormat::U32),
+                ) {
+                    (false, true, _) => IdlType::Option(item),
+                    (true, _, true) => IdlType::COption(item),
+                    _ => {
+                        self.report("custom option prefixes were exported as `Option`");
+                        IdlType::Option(item)
+                    }
+                }
+            }
+            TypeNode::ZeroableOption(_) | TypeNode::RemainderOption(_) => {
+                self.report(format!(
+                    "`{}` cannot be represented and was exported as `Option`",
+                    r#type.kind()
+                ));
+                let item = match r#type {
+                    TypeNode::ZeroableOption(node) => &node.item,
+                    TypeNode::RemainderOption(node) => &node.item,
+                    _ => unreachable!(),
+                };
+                IdlType::Option(Box::new(self.r#type(item)))
+            }
+            TypeNode::Array(node) => self.collection(&node.item, &node.count),
+            // Borsh encodes sets exactly like vectors.
+            TypeNode::Set(node) => self.collection(&node.item, &node.count),
+            TypeNode::Map(_) | TypeNode::Struct(_) | TypeNode::Tuple(_) | TypeNode::Enum(_) => {
+                self.report(format!(
+                    "inline `{}` cannot be represented and was exported as `bytes`",
+                    r#type.kind()
+                ));
+                IdlType::Bytes
+            }
+            TypeNode::Unknown(node) => {
+                self.report(format!(
+                    "`{}` is unknown and was exported as `bytes`",
+                    node.kind
+                ));
+                IdlType::Bytes
+            }
+            TypeNode::HiddenPrefix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(&node.r#type)
+            }
+            TypeNode::HiddenSuffix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(&node.r#type)
+            }
+            TypeNode::PreOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(&node.r#type)
+            }
+            TypeNode::PostOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(&node.r#type)
+            }
+            TypeNode::Sentinel(node) => {
+                self.report_kind(node.kind());
+                self.r#type(&node.r#type)
+            }
+        }
+    }
+
+    fn collection(&mut self, item: &TypeNode, count: &CountNode) -> IdlType {
+        let item = Box::new(self.r#type(item));
+        match count {
+            CountNode::Fixed(count) => IdlType::Array(item, IdlArrayLen::Value(count.value)),
+            CountNode::Prefixed(count) => {
+                self.borsh_prefix(&count.prefix, NumberFormat::U32);
+                IdlType::Vec(item)
+            }
+            CountNode::Remainder(_) => {
+                self.report(
+                    "remainder collections cannot be represented and were exported as `Vec`",
+                );
+                IdlType::Vec(item)
+            }
+        }
+    }
+
+    fn number(&mut self, node: &NumberTypeNode) -> IdlType {
+        if node.endian == Endianness::Be {
+            self.report(
+                "big-endian numbers cannot be represented and were exported as little-endian",
+            );
+        }
+        match node.format {
+            NumberFormat::U8 => IdlType::U8,
+            NumberFormat::U16 => IdlType::U16,
+            NumberFormat::U32 => IdlType::U32,
+            NumberFormat::U64 => IdlType::U64,
+            NumberFormat::U128 => IdlType::U128,
+            NumberFormat::I8 => IdlType::I8,
+            NumberFormat::I16 => IdlType::I16,
+            NumberFormat::I32 => IdlType::I32,
+            NumberFormat::I64 => IdlType::I64,
+            NumberFormat::I128 => IdlType::I128,
+            NumberFormat::F32 => IdlType::F32,
+            NumberFormat::F64 => IdlType::F64,
+            NumberFormat::ShortU16 => {
+                self.report("`shortU16` numbers were exported as `u16`");
+                IdlType::U16
+            }
+        }
+    }
+
+    /// Anchor constants hold the Rust expression of their value.
+    fn constant_value(&mut self, value: &ValueNode) -> Option<String> {
+        Some(match value {
+            ValueNode::Boolean(node) => node.boolean.to_string(),
+            ValueNode::Number(node) => JsonNumber::from(node.number).to_string(),
+            ValueNode::String(node) => serde_json::to_string(&node.string).ok()?,
+            ValueNode::PublicKey(node) => node.public_key.clone(),
+            ValueNode::Bytes(node) => {
+                let bytes = node.encoding.decode(&node.data).ok()?;
+                let bytes = bytes.iter().map(u8::to_string).collect::<Vec<_>>();
+                format!("[{}]", bytes.join(", "))
+            }
+            value => {
+                self.report(format!(
+                    "`{}` constants cannot be represented and were ignored",
+                    value.kind()
+                ));
+                return None;
+            }
+        })
+    }
+}
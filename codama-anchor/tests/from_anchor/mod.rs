@@ -0,0 +1,179 @@
+use crate::get_path;
+use codama_anchor::root_node_from_anchor;
+use codama_errors::CodamaError;
+use codama_nodes::{
+    validate, AccountValueNode, ArgumentValueNode, BytesValueNode, ConstantPdaSeedNode,
+    DefinedTypeLinkNode, HasName, NestedTypeNodeTrait, PdaLinkNode, PdaNode, PdaSeedValueNode,
+    PdaValueNode, ProgramOrigin, PublicKeyTypeNode, PublicKeyValueNode, RootNode, StringTypeNode,
+    StringValueNode, TypeNode, VariablePdaSeedNode,
+};
+use pretty_assertions::assert_eq;
+
+fn get_root(file: &str) -> RootNode {
+    let json = std::fs::read_to_string(get_path(file)).unwrap();
+    root_node_from_anchor(&json).unwrap()
+}
+
+fn names<T: HasName>(nodes: &[T]) -> Vec<&str> {
+    nodes.iter().map(|node| node.name().as_str()).collect()
+}
+
+fn counter_pda() -> PdaNode {
+    PdaNode::new(
+        "counter",
+        vec![
+            ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new("counter"))
+                .into(),
+            VariablePdaSeedNode::new("authority", PublicKeyTypeNode::new()).into(),
+        ],
+    )
+}
+
+fn counter_pda_value() -> PdaValueNode {
+    PdaValueNode::new(
+        PdaLinkNode::new("counter"),
+        vec![PdaSeedValueNode::new(
+            "authority",
+            AccountValueNode::new("authority"),
+        )],
+    )
+}
+
+#[test]
+fn from_legacy_idl() {
+    let root = get_root("from_anchor/v00.json");
+    let program = &root.program;
+    assert_eq!(program.name.as_str(), "counter");
+    assert_eq!(
+        program.public_key,
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    );
+    assert_eq!(program.version, "0.1.0");
+    assert_eq!(program.origin, Some(ProgramOrigin::Anchor));
+    assert_eq!(validate(&root), vec![]);
+
+    // Discriminators are derived from the sighash of each node.
+    let initialize = &program.instructions[0];
+    assert_eq!(
+        initialize.arguments[0].default_value.as_ref(),
+        &Some(BytesValueNode::base16("afaf6d1f0d989bed").into())
+    );
+    assert_eq!(names(&initialize.arguments), ["discriminator", "start"]);
+    assert_eq!(
+        initialize.accounts[0].default_value.as_ref(),
+        &Some(counter_pda_value().into())
+    );
+    assert_eq!(program.pdas, vec![counter_pda()]);
+
+    // Nested accounts are flattened.
+    let set_mode = &program.instructions[1];
+    assert_eq!(names(&set_mode.accounts), ["counter", "authority"]);
+    assert_eq!(set_mode.accounts[1].is_optional, Some(true));
+    assert_eq!(
+        *set_mode.arguments[1].r#type,
+        TypeNode::Link(DefinedTypeLinkNode::new("mode"))
+    );
+
+    assert_eq!(names(&program.accounts), ["counter"]);
+    assert_eq!(
+        names(&program.accounts[0].data.get_nested_type_node().fields),
+        ["discriminator", "authority", "count", "history"]
+    );
+    assert_eq!(names(&program.events), ["incremented"]);
+    assert_eq!(names(&program.defined_types), ["mode"]);
+    assert_eq!(names(&program.errors), ["overflow"]);
+    assert_eq!(program.errors[0].code, 6000);
+
+    // Constants that are not literals are skipped.
+    assert_eq!(names(&program.constants), ["maxCount"]);
+}
+
+#[test]
+fn from_idl() {
+    let root = get_root("from_anchor/v01.json");
+    let program = &root.program;
+    assert_eq!(program.name.as_str(), "counter");
+    assert_eq!(
+        program.public_key,
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    );
+    assert_eq!(program.origin, Some(ProgramOrigin::Anchor));
+    assert_eq!(validate(&root), vec![]);
+
+    let initialize = &program.instructions[0];
+    assert_eq!(
+        initialize.accounts[0].default_value.as_ref(),
+        &Some(counter_pda_value().into())
+    );
+    assert_eq!(
+        initialize.accounts[2].default_value.as_ref(),
+        &Some(PublicKeyValueNode::new("11111111111111111111111111111111").into())
+    );
+
+    // PDAs sharing a name but not their seeds are renamed after their instruction.
+    let create_tag = &program.instructions[1];
+    assert_eq!(names(&create_tag.accounts), ["counter", "authority"]);
+    assert_eq!(
+        create_tag.accounts[0].default_value.as_ref(),
+        &Some(
+            PdaValueNode::new(
+                PdaLinkNode::new("createTagCounter"),
+                vec![PdaSeedValueNode::new(
+                    "label",
+                    ArgumentValueNode::new("label")
+                )],
+            )
+            .into()
+        )
+    );
+    assert_eq!(
+        program.pdas,
+        vec![
+            counter_pda(),
+            PdaNode::new(
+                "createTagCounter",
+                vec![
+                    ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new("tag"))
+                        .into(),
+                    VariablePdaSeedNode::new("label", StringTypeNode::utf8()).into(),
+                ],
+            ),
+        ]
+    );
+
+    // Types backing accounts and events are not defined types.
+    assert_eq!(
+        names(&program.accounts[0].data.get_nested_type_node().fields),
+        ["discriminator", "authority", "count", "checkpoints"]
+    );
+    assert_eq!(
+        program.accounts[0].docs,
+        vec!["The counter account.".to_string()].into()
+    );
+    assert_eq!(names(&program.events), ["incremented"]);
+    assert_eq!(names(&program.defined_types), ["mode"]);
+    assert_eq!(names(&program.constants), ["seed"]);
+}
+
+#[test]
+fn from_idl_with_unsupported_type() {
+    let json = r#"{
+        "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "metadata": { "name": "counter", "version": "0.1.0", "spec": "0.1.0" },
+        "instructions": [{
+            "name": "initialize",
+            "discriminator": [0, 0, 0, 0, 0, 0, 0, 0],
+            "accounts": [],
+            "args": [{ "name": "amount", "type": "u256" }]
+        }]
+    }"#;
+    assert!(matches!(
+        root_node_from_anchor(json),
+        Err(CodamaError::InvalidIdl(_))
+    ));
+}
+
+#[test]
+fn from_invalid_json() {
+    assert!(root_node_from_anchor("{").is_err());
+}
@@ -0,0 +1,136 @@
+use crate::get_path;
+use codama_anchor::{root_node_from_anchor, root_node_to_anchor};
+use codama_nodes::{
+    AccountNode, BytesEncoding, ConstantValueNode, HiddenPrefixTypeNode, InstructionAccountNode,
+    InstructionArgumentNode, InstructionNode, IsSigner, NumberTypeNode, NumberValueNode,
+    ProgramNode, RootNode, SentinelTypeNode, StringTypeNode, StructFieldTypeNode, StructTypeNode,
+    U32, U8,
+};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+
+fn round_trip(file: &str) {
+    let json = std::fs::read_to_string(get_path(file)).unwrap();
+    let root = root_node_from_anchor(&json).unwrap();
+    let export = root_node_to_anchor(&root).unwrap();
+    assert_eq!(export.issues, vec![]);
+    assert_eq!(root_node_from_anchor(&export.json).unwrap(), root);
+}
+
+#[test]
+fn round_trip_legacy_idl() {
+    round_trip("from_anchor/v00.json");
+}
+
+#[test]
+fn round_trip_idl() {
+    round_trip("from_anchor/v01.json");
+}
+
+#[test]
+fn export_idl() {
+    let json = std::fs::read_to_string(get_path("from_anchor/v01.json")).unwrap();
+    let root = root_node_from_anchor(&json).unwrap();
+    let export = root_node_to_anchor(&root).unwrap();
+    let idl: Value = serde_json::from_str(&export.json).unwrap();
+    assert_eq!(
+        idl["address"],
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    );
+    assert_eq!(
+        idl["metadata"],
+        json!({ "name": "counter", "version": "0.1.0", "spec": "0.1.0" })
+    );
+    assert_eq!(
+        idl["instructions"][0]["accounts"],
+        json!([
+            {
+                "name": "counter",
+                "writable": true,
+                "pda": {
+                    "seeds": [
+                        { "kind": "const", "value": [99, 111, 117, 110, 116, 101, 114] },
+                        { "kind": "account", "path": "authority" }
+                    ]
+                }
+            },
+            { "name": "authority", "writable": true, "signer": true },
+            { "name": "system_program", "address": "11111111111111111111111111111111" }
+        ])
+    );
+    assert_eq!(
+        idl["instructions"][1]["accounts"][1],
+        json!({ "name": "authority", "signer": true, "optional": true })
+    );
+    assert_eq!(
+        idl["accounts"],
+        json!([{ "name": "Counter", "discriminator": [255, 176, 4, 245, 188, 253, 124, 25] }])
+    );
+    assert_eq!(
+        idl["types"][0]["type"]["fields"][2],
+        json!({ "name": "checkpoints", "type": { "array": [{ "coption": "u16" }, 2] } })
+    );
+}
+
+#[test]
+fn export_unrepresentable_nodes() {
+    let sentinel = SentinelTypeNode::new(
+        StringTypeNode::utf8(),
+        ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+    );
+    let hidden_prefix = HiddenPrefixTypeNode::new(
+        NumberTypeNode::le(U32),
+        vec![ConstantValueNode::new(
+            NumberTypeNode::le(U8),
+            NumberValueNode::new(42u8),
+        )],
+    );
+    let program = ProgramNode {
+        accounts: vec![AccountNode::new(
+            "token",
+            StructTypeNode::new(vec![
+                StructFieldTypeNode::new("label", sentinel),
+                StructFieldTypeNode::new("amount", hidden_prefix),
+            ]),
+        )],
+        instructions: vec![InstructionNode {
+            accounts: vec![InstructionAccountNode::new(
+                "authority",
+                false,
+                IsSigner::Either,
+            )],
+            arguments: vec![InstructionArgumentNode {
+                default_value: Box::new(Some(NumberValueNode::new(1u8).into())),
+                ..InstructionArgumentNode::new("amount", NumberTypeNode::le(U8))
+            }],
+            name: "transfer".into(),
+            ..InstructionNode::default()
+        }],
+        ..ProgramNode::new("myProgram", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+    };
+    let export = root_node_to_anchor(&RootNode::new(program)).unwrap();
+    let issues = export
+        .issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            "[programNode]myProgram > [accountNode]token: no discriminator at offset 0 was found, the default Anchor discriminator was used",
+            "[programNode]myProgram > [accountNode]token > [structFieldTypeNode]label: `sentinelTypeNode` cannot be represented and was ignored",
+            "[programNode]myProgram > [accountNode]token > [structFieldTypeNode]label: strings without a `u32` prefix were exported as prefixed strings",
+            "[programNode]myProgram > [accountNode]token > [structFieldTypeNode]amount: `hiddenPrefixTypeNode` cannot be represented and was ignored",
+            "[programNode]myProgram > [instructionNode]transfer: no discriminator at offset 0 was found, the default Anchor discriminator was used",
+            "[programNode]myProgram > [instructionNode]transfer > [instructionAccountNode]authority: `either` signers cannot be represented and were exported as non-signers",
+            "[programNode]myProgram > [instructionNode]transfer > [instructionArgumentNode]amount: argument default values cannot be represented and were ignored",
+        ]
+    );
+
+    // The closest Anchor equivalents are still exported.
+    let idl: Value = serde_json::from_str(&export.json).unwrap();
+    assert_eq!(
+        idl["types"][0]["type"]["fields"],
+        json!([{ "name": "label", "type": "string" }, { "name": "amount", "type": "u32" }])
+    );
+}
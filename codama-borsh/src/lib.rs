@@ -0,0 +1,3 @@
+mod to_borsh;
+
+pub use to_borsh::*;
@@ -0,0 +1,506 @@
+use borsh::schema::{BorshSchemaContainer, Declaration, Definition, Fields};
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    AccountNode, BytesEncoding, CamelCaseString, CountNode, DefinedTypeLinkNode, DefinedTypeNode,
+    Endianness, EnumTypeNode, EnumVariantTypeNode, HasKind, HasName, InstructionNode, LinkResolver,
+    NodePath, NumberFormat, NumberTypeNode, StringTypeNode, StructTypeNode, TypeNode,
+};
+use std::collections::BTreeMap;
+
+pub use codama_export_issues::ExportIssue;
+
+/// A Borsh schema exported from Codama type nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorshSchemaExport {
+    pub container: BorshSchemaContainer,
+    /// The Codama constructs that are not Borsh and were
+    /// replaced by their closest Borsh equivalent in the container.
+    pub issues: Vec<ExportIssue>,
+}
+
+impl BorshSchemaExport {
+    /// Whether the container describes the exact encoding of the exported type.
+    pub fn is_borsh(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Converts a defined type into a Borsh schema declared under its PascalCase name.
+/// The resolver must be scoped to the program of the defined type.
+pub fn defined_type_to_borsh_schema(
+    defined_type: &DefinedTypeNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<BorshSchemaExport> {
+    let mut exporter = Exporter::new(*resolver);
+    let declaration = exporter.scoped(defined_type.kind(), &defined_type.name, |this| {
+        this.named(&defined_type.name.to_pascal_case(), &defined_type.r#type)
+    })?;
+    Ok(exporter.finish(declaration))
+}
+
+/// Converts the data of an account into a Borsh schema declared under its PascalCase name.
+/// The resolver must be scoped to the program of the account.
+pub fn account_to_borsh_schema(
+    account: &AccountNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<BorshSchemaExport> {
+    let mut exporter = Exporter::new(*resolver);
+    let declaration = exporter.scoped(account.kind(), &account.name, |this| {
+        let data = this.nested(&account.data);
+        this.r#struct(&account.name.to_pascal_case(), data)
+    })?;
+    Ok(exporter.finish(declaration))
+}
+
+/// Converts the arguments of an instruction into a Borsh schema declared as `{Name}InstructionData`.
+/// The resolver must be scoped to the program of the instruction.
+pub fn instruction_to_borsh_schema(
+    instruction: &InstructionNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<BorshSchemaExport> {
+    let mut exporter = Exporter::new(resolver.with_instruction(instruction));
+    let name = format!("{}InstructionData", instruction.name.to_pascal_case());
+    let fields = exporter.scoped(instruction.kind(), &instruction.name, |this| {
+        let fields = instruction
+            .arguments
+            .iter()
+            .map(|argument| (argument.kind(), &argument.name, argument.r#type.as_ref()));
+        this.named_fields(&name, fields)
+    })?;
+    let declaration = exporter.define(name, Definition::Struct { fields });
+    Ok(exporter.finish(declaration))
+}
+
+struct Exporter<'a> {
+    resolver: LinkResolver<'a>,
+    path: NodePath,
+    definitions: BTreeMap<Declaration, Definition>,
+    /// The declarations of the linked types visited so far, by name.
+    links: BTreeMap<String, Declaration>,
+    issues: Vec<ExportIssue>,
+}
+
+impl ExportIssueReporter for Exporter<'_> {
+    const UNSUPPORTED: &'static str = "is not Borsh";
+
+    fn report<T: Into<String>>(&mut self, message: T) {
+        self.issues.push(ExportIssue {
+            path: self.path.clone(),
+            message: message.into(),
+        });
+    }
+}
+
+impl<'a> Exporter<'a> {
+    fn new(resolver: LinkResolver<'a>) -> Self {
+        Self {
+            resolver,
+            path: NodePath::new(),
+            definitions: BTreeMap::new(),
+            links: BTreeMap::new(),
+            issues: vec![],
+        }
+    }
+
+    fn finish(self, declaration: Declaration) -> BorshSchemaExport {
+        BorshSchemaExport {
+            container: BorshSchemaContainer::new(declaration, self.definitions),
+            issues: self.issues,
+        }
+    }
+
+    fn scoped<T>(
+        &mut self,
+        kind: &'static str,
+        name: &CamelCaseString,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.path.push(kind, Some(name.clone()));
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn define(&mut self, declaration: Declaration, definition: Definition) -> Declaration {
+        self.definitions
+            .entry(declaration.clone())
+            .or_insert(definition);
+        declaration
+    }
+
+    /// Defines a type the way Borsh derives it for a Rust item of the given name.
+    /// Types that are neither structs, tuples nor enums are used as aliases.
+    fn named(&mut self, name: &str, r#type: &TypeNode) -> CodamaResult<Declaration> {
+        match r#type {
+            TypeNode::Tuple(node) => {
+                let fields = self.unnamed_fields(name, &node.items)?;
+                Ok(self.define(name.to_string(), Definition::Struct { fields }))
+            }
+            r#type => self.r#type(name, r#type),
+        }
+    }
+
+    /// Maps a type to its Borsh declaration, where `name` is used to declare inline
+    /// structs and enums. Types that are not Borsh are reported and replaced.
+    fn r#type(&mut self, name: &str, r#type: &TypeNode) -> CodamaResult<Declaration> {
+        Ok(match r#type {
+            TypeNode::Amount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::DateTime(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::SolAmount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::Number(node) => self.number(node),
+            TypeNode::Boolean(node) => {
+                self.borsh_prefix(&node.size, NumberFormat::U8);
+                self.define("bool".to_string(), Definition::Primitive(1))
+            }
+            TypeNode::PublicKey(_) => {
+                let bytes = self.fixed_bytes(32);
+                let fields = Fields::UnnamedFields(vec![bytes]);
+                self.define("Pubkey".to_string(), Definition::Struct { fields })
+            }
+            TypeNode::String(node) => {
+                self.report("strings without a `u32` prefix were exported as prefixed strings");
+                self.string(node)
+            }
+            TypeNode::Bytes(_) => {
+                self.report("bytes without a `u32` prefix were exported as prefixed bytes");
+                self.bytes()
+            }
+            TypeNode::SizePrefix(node) => {
+                self.borsh_prefix(&node.prefix, NumberFormat::U32);
+                match node.r#type.as_ref() {
+                    TypeNode::String(string) => self.string(string),
+                    TypeNode::Bytes(_) => self.bytes(),
+                    r#type => {
+                        self.report_kind(node.kind());
+                        self.r#type(name, r#type)?
+                    }
+                }
+            }
+            TypeNode::FixedSize(node) => match node.r#type.as_ref() {
+                TypeNode::String(_) | TypeNode::Bytes(_) => self.fixed_bytes(node.size as u64),
+                r#type => {
+                    self.report_kind(node.kind());
+                    self.r#type(name, r#type)?
+                }
+            },
+            TypeNode::Option(node) => {
+                let prefix = self.nested(&node.prefix);
+                if node.fixed == Some(true) || !prefix.is_le(NumberFormat::U8) {
+                    self.report("custom option prefixes were exported as `Option`");
+                }
+                self.option(name, &node.item)?
+            }
+            TypeNode::ZeroableOption(node) => {
+                self.report_kind_as_option(node.kind());
+                self.option(name, &node.item)?
+            }
+            TypeNode::RemainderOption(node) => {
+                self.report_kind_as_option(node.kind());
+                self.option(name, &node.item)?
+            }
+            TypeNode::Array(node) => self.collection(name, "Vec", &node.item, &node.count)?,
+            TypeNode::Set(node) => self.collection(name, "BTreeSet", &node.item, &node.count)?,
+            TypeNode::Map(node) => {
+                let key = self.r#type(&format!("{name}__Key"), &node.key)?;
+                let value = self.r#type(&format!("{name}__Value"), &node.value)?;
+                let entry = self.tuple(vec![key.clone(), value.clone()]);
+                match node.count.as_ref() {
+                    CountNode::Fixed(count) => self.array(entry, count.value),
+                    count => {
+                        self.borsh_count(count);
+                        let declaration = format!("BTreeMap<{key}, {value}>");
+                        self.sequence(declaration, entry)
+                    }
+                }
+            }
+            TypeNode::Tuple(node) => {
+                let items = node
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| self.r#type(&format!("{name}__{index}"), item))
+                    .collect::<CodamaResult<Vec<_>>>()?;
+                self.tuple(items)
+            }
+            TypeNode::Struct(node) => self.r#struct(name, node)?,
+            TypeNode::Enum(node) => self.r#enum(name, node)?,
+            TypeNode::Link(node) => self.link(node)?,
+            TypeNode::Unknown(node) => {
+                self.report(format!(
+                    "`{}` is unknown and was exported as `()`",
+                    node.kind
+                ));
+                self.tuple(vec![])
+            }
+            TypeNode::HiddenPrefix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::HiddenSuffix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::PreOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::PostOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::Sentinel(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+        })
+    }
+
+    /// Linked types are defined once under their PascalCase name, which also supports recursive types.
+    fn link(&mut self, link: &DefinedTypeLinkNode) -> CodamaResult<Declaration> {
+        let resolver = match &link.program {
+            Some(program) => self
+                .resolver
+                .with_program(self.resolver.resolve_program(program)?),
+            None => self.resolver,
+        };
+        let defined_type = resolver.resolve_defined_type(link)?;
+        let name = link.name.to_pascal_case();
+        if let Some(declaration) = self.links.get(&name) {
+            return Ok(declaration.clone());
+        }
+
+        self.links.insert(name.clone(), name.clone());
+        let parent_resolver = std::mem::replace(&mut self.resolver, resolver);
+        let declaration = self.scoped(defined_type.kind(), &defined_type.name, |this| {
+            this.named(&name, &defined_type.r#type)
+        });
+        self.resolver = parent_resolver;
+        let declaration = declaration?;
+        self.links.insert(name, declaration.clone());
+        Ok(declaration)
+    }
+
+    fn r#struct(&mut self, name: &str, node: &StructTypeNode) -> CodamaResult<Declaration> {
+        let fields = node
+            .fields
+            .iter()
+            .map(|field| (field.kind(), &field.name, field.r#type.as_ref()));
+        let fields = self.named_fields(name, fields)?;
+        Ok(self.define(name.to_string(), Definition::Struct { fields }))
+    }
+
+    fn named_fields<'b>(
+        &mut self,
+        name: &str,
+        fields: impl Iterator<Item = (&'static str, &'b CamelCaseString, &'b TypeNode)>,
+    ) -> CodamaResult<Fields> {
+        let fields = fields
+            .map(|(kind, field_name, r#type)| {
+                self.scoped(kind, field_name, |this| {
+                    let inline_name = format!("{name}__{}", field_name.to_pascal_case());
+                    let declaration = this.r#type(&inline_name, r#type)?;
+                    Ok((field_name.to_snake_case(), declaration))
+                })
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(match fields.is_empty() {
+            true => Fields::Empty,
+            false => Fields::NamedFields(fields),
+        })
+    }
+
+    fn unnamed_fields(&mut self, name: &str, items: &[TypeNode]) -> CodamaResult<Fields> {
+        let items = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.r#type(&format!("{name}__{index}"), item))
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(match items.is_empty() {
+            true => Fields::Empty,
+            false => Fields::UnnamedFields(items),
+        })
+    }
+
+    /// Variants are declared as `{Enum}__{Variant}` structs, as derived by Borsh.
+    fn r#enum(&mut self, name: &str, node: &EnumTypeNode) -> CodamaResult<Declaration> {
+        self.borsh_prefix(&node.size, NumberFormat::U8);
+        let variants =
+            node.variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    self.scoped(variant.kind(), variant.name(), |this| {
+                        let variant_name = variant.name().to_pascal_case();
+                        let declaration = format!("{name}__{variant_name}");
+                        let fields = match variant {
+                            EnumVariantTypeNode::Empty(_) => Fields::Empty,
+                            EnumVariantTypeNode::Struct(variant) => {
+                                let node = this.nested(&variant.r#struct);
+                                let fields = node.fields.iter().map(|field| {
+                                    (field.kind(), &field.name, field.r#type.as_ref())
+                                });
+                                this.named_fields(&declaration, fields)?
+                            }
+                            EnumVariantTypeNode::Tuple(variant) => {
+                                let node = this.nested(&variant.tuple);
+                                this.unnamed_fields(&declaration, &node.items)?
+                            }
+                        };
+                        let declaration = this.define(declaration, Definition::Struct { fields });
+                        let discriminator = variant.discriminator().unwrap_or(index as u32);
+                        if discriminator > u8::MAX as u32 {
+                            this.report("discriminators that do not fit in a `u8` are not Borsh");
+                        }
+                        Ok((discriminator as i64, variant_name, declaration))
+                    })
+                })
+                .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(self.define(
+            name.to_string(),
+            Definition::Enum {
+                tag_width: 1,
+                variants,
+            },
+        ))
+    }
+
+    fn option(&mut self, name: &str, item: &TypeNode) -> CodamaResult<Declaration> {
+        let item = self.r#type(name, item)?;
+        let unit = self.define("()".to_string(), Definition::Primitive(0));
+        let variants = vec![
+            (0, "None".to_string(), unit),
+            (1, "Some".to_string(), item.clone()),
+        ];
+        Ok(self.define(
+            format!("Option<{item}>"),
+            Definition::Enum {
+                tag_width: 1,
+                variants,
+            },
+        ))
+    }
+
+    fn collection(
+        &mut self,
+        name: &str,
+        collection: &str,
+        item: &TypeNode,
+        count: &CountNode,
+    ) -> CodamaResult<Declaration> {
+        let item = self.r#type(name, item)?;
+        Ok(match count {
+            CountNode::Fixed(count) => self.array(item, count.value),
+            count => {
+                self.borsh_count(count);
+                self.sequence(format!("{collection}<{item}>"), item)
+            }
+        })
+    }
+
+    fn tuple(&mut self, elements: Vec<Declaration>) -> Declaration {
+        match elements.as_slice() {
+            [] => self.define("()".to_string(), Definition::Primitive(0)),
+            [element] => {
+                let declaration = format!("({element},)");
+                self.define(declaration, Definition::Tuple { elements })
+            }
+            _ => {
+                let declaration = format!("({})", elements.join(", "));
+                self.define(declaration, Definition::Tuple { elements })
+            }
+        }
+    }
+
+    /// A `u32`-prefixed sequence of elements.
+    fn sequence(&mut self, declaration: Declaration, elements: Declaration) -> Declaration {
+        let definition = Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements,
+        };
+        self.define(declaration, definition)
+    }
+
+    fn array(&mut self, elements: Declaration, length: u64) -> Declaration {
+        let definition = Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: length..=length,
+            elements: elements.clone(),
+        };
+        self.define(format!("[{elements}; {length}]"), definition)
+    }
+
+    fn string(&mut self, node: &StringTypeNode) -> Declaration {
+        if node.encoding != BytesEncoding::Utf8 {
+            return self.bytes();
+        }
+        let byte = self.byte();
+        self.sequence("String".to_string(), byte)
+    }
+
+    fn bytes(&mut self) -> Declaration {
+        let byte = self.byte();
+        self.sequence("Vec<u8>".to_string(), byte)
+    }
+
+    fn fixed_bytes(&mut self, length: u64) -> Declaration {
+        let byte = self.byte();
+        self.array(byte, length)
+    }
+
+    fn byte(&mut self) -> Declaration {
+        self.define("u8".to_string(), Definition::Primitive(1))
+    }
+
+    fn number(&mut self, node: &NumberTypeNode) -> Declaration {
+        if node.endian == Endianness::Be {
+            self.report("big-endian numbers are not Borsh and were exported as little-endian");
+        }
+        let (declaration, size) = match node.format {
+            NumberFormat::U8 => ("u8", 1),
+            NumberFormat::U16 => ("u16", 2),
+            NumberFormat::U32 => ("u32", 4),
+            NumberFormat::U64 => ("u64", 8),
+            NumberFormat::U128 => ("u128", 16),
+            NumberFormat::I8 => ("i8", 1),
+            NumberFormat::I16 => ("i16", 2),
+            NumberFormat::I32 => ("i32", 4),
+            NumberFormat::I64 => ("i64", 8),
+            NumberFormat::I128 => ("i128", 16),
+            NumberFormat::F32 => ("f32", 4),
+            NumberFormat::F64 => ("f64", 8),
+            NumberFormat::ShortU16 => {
+                self.report("`shortU16` numbers are not Borsh and were exported as `u16`");
+                ("u16", 2)
+            }
+        };
+        self.define(declaration.to_string(), Definition::Primitive(size))
+    }
+
+    fn report_kind_as_option(&mut self, kind: &str) {
+        self.report(format!(
+            "`{kind}` is not Borsh and was exported as `Option`"
+        ));
+    }
+
+    /// Reports collection counts that are not `u32` prefixes.
+    fn borsh_count(&mut self, count: &CountNode) {
+        match count {
+            CountNode::Prefixed(count) => self.borsh_prefix(&count.prefix, NumberFormat::U32),
+            CountNode::Remainder(_) => self.report(
+                "remainder collections are not Borsh and were exported with a `u32` prefix",
+            ),
+            CountNode::Fixed(_) => {}
+        }
+    }
+}
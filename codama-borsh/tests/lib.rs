@@ -0,0 +1 @@
+mod to_borsh;
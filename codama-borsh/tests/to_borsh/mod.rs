@@ -0,0 +1,229 @@
+use borsh::schema::{BorshSchemaContainer, Definition, Fields};
+use borsh::BorshSchema;
+use codama_borsh::{
+    account_to_borsh_schema, defined_type_to_borsh_schema, instruction_to_borsh_schema,
+};
+use codama_nodes::{
+    AccountNode, ArrayTypeNode, BooleanTypeNode, BytesEncoding, BytesTypeNode, ConstantValueNode,
+    DefinedTypeLinkNode, DefinedTypeNode, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode,
+    EnumTupleVariantTypeNode, EnumTypeNode, FixedSizeTypeNode, InstructionArgumentNode,
+    InstructionNode, LinkResolver, MapTypeNode, NumberTypeNode, OptionTypeNode, PreOffsetTypeNode,
+    ProgramNode, PublicKeyTypeNode, RootNode, SentinelTypeNode, SizePrefixTypeNode, StringTypeNode,
+    StructFieldTypeNode, StructTypeNode, TupleTypeNode, TypeNode, I32, U16, U32, U64, U8,
+};
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+
+#[allow(dead_code)]
+#[derive(BorshSchema)]
+struct Counter {
+    count: u64,
+    label: String,
+    tag: Option<[u8; 8]>,
+    scores: Vec<u16>,
+    owners: BTreeMap<String, (u32, bool)>,
+}
+
+#[allow(dead_code)]
+#[derive(BorshSchema)]
+enum Direction {
+    Up,
+    Jump { height: u8 },
+    Move(u8, i32),
+}
+
+#[allow(dead_code)]
+#[derive(BorshSchema)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+fn string() -> SizePrefixTypeNode<TypeNode> {
+    SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U32))
+}
+
+fn get_root() -> RootNode {
+    let counter = StructTypeNode::new(vec![
+        StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+        StructFieldTypeNode::new("label", string()),
+        StructFieldTypeNode::new(
+            "tag",
+            OptionTypeNode::new(FixedSizeTypeNode::new(BytesTypeNode::new(), 8)),
+        ),
+        StructFieldTypeNode::new(
+            "scores",
+            ArrayTypeNode::prefixed(NumberTypeNode::le(U16), NumberTypeNode::le(U32)),
+        ),
+        StructFieldTypeNode::new(
+            "owners",
+            MapTypeNode::prefixed(
+                string(),
+                TupleTypeNode::new(vec![
+                    NumberTypeNode::le(U32).into(),
+                    BooleanTypeNode::default().into(),
+                ]),
+                NumberTypeNode::le(U32),
+            ),
+        ),
+    ]);
+    let direction = EnumTypeNode::new(vec![
+        EnumEmptyVariantTypeNode::new("up").into(),
+        EnumStructVariantTypeNode::new(
+            "jump",
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "height",
+                NumberTypeNode::le(U8),
+            )]),
+        )
+        .into(),
+        EnumTupleVariantTypeNode::new(
+            "move",
+            TupleTypeNode::new(vec![
+                NumberTypeNode::le(U8).into(),
+                NumberTypeNode::le(I32).into(),
+            ]),
+        )
+        .into(),
+    ]);
+    let tree = StructTypeNode::new(vec![StructFieldTypeNode::new(
+        "children",
+        ArrayTypeNode::prefixed(DefinedTypeLinkNode::new("tree"), NumberTypeNode::le(U32)),
+    )]);
+    RootNode::new(
+        ProgramNode::new("myProgram", "1111")
+            .add_account(AccountNode::new("counter", counter))
+            .add_defined_type(DefinedTypeNode::new("direction", direction))
+            .add_defined_type(DefinedTypeNode::new("tree", tree))
+            .add_instruction(InstructionNode {
+                name: "move".into(),
+                arguments: vec![
+                    InstructionArgumentNode::new(
+                        "direction",
+                        DefinedTypeLinkNode::new("direction"),
+                    ),
+                    InstructionArgumentNode::new("authority", PublicKeyTypeNode::new()),
+                ],
+                ..InstructionNode::default()
+            }),
+    )
+}
+
+#[test]
+fn export_account() {
+    let root = get_root();
+    let export =
+        account_to_borsh_schema(&root.program.accounts[0], &LinkResolver::new(&root)).unwrap();
+    assert!(export.is_borsh());
+    assert_eq!(
+        export.container,
+        BorshSchemaContainer::for_type::<Counter>()
+    );
+}
+
+#[test]
+fn export_enum() {
+    let root = get_root();
+    let export =
+        defined_type_to_borsh_schema(&root.program.defined_types[0], &LinkResolver::new(&root))
+            .unwrap();
+    assert!(export.is_borsh());
+    assert_eq!(
+        export.container,
+        BorshSchemaContainer::for_type::<Direction>()
+    );
+}
+
+#[test]
+fn export_recursive_defined_type() {
+    let root = get_root();
+    let export =
+        defined_type_to_borsh_schema(&root.program.defined_types[1], &LinkResolver::new(&root))
+            .unwrap();
+    assert!(export.is_borsh());
+    assert_eq!(export.container, BorshSchemaContainer::for_type::<Tree>());
+}
+
+#[test]
+fn export_instruction() {
+    let root = get_root();
+    let export =
+        instruction_to_borsh_schema(&root.program.instructions[0], &LinkResolver::new(&root))
+            .unwrap();
+    assert!(export.is_borsh());
+    assert_eq!(export.container.declaration(), "MoveInstructionData");
+    assert_eq!(
+        export.container.get_definition("MoveInstructionData"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("direction".to_string(), "Direction".to_string()),
+                ("authority".to_string(), "Pubkey".to_string()),
+            ])
+        })
+    );
+    assert_eq!(
+        export.container.get_definition("Pubkey"),
+        Some(&Definition::Struct {
+            fields: Fields::UnnamedFields(vec!["[u8; 32]".to_string()])
+        })
+    );
+    assert!(export.container.get_definition("Direction__Jump").is_some());
+    assert_eq!(export.container.validate(), Ok(()));
+}
+
+#[test]
+fn export_non_borsh_types() {
+    let root = RootNode::new(ProgramNode::new("myProgram", "1111"));
+    let data = StructTypeNode::new(vec![
+        StructFieldTypeNode::new(
+            "label",
+            SentinelTypeNode::new(
+                StringTypeNode::utf8(),
+                ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+            ),
+        ),
+        StructFieldTypeNode::new(
+            "amount",
+            PreOffsetTypeNode::relative(NumberTypeNode::le(U32), 4),
+        ),
+        StructFieldTypeNode::new(
+            "name",
+            SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U8)),
+        ),
+    ]);
+    let export =
+        account_to_borsh_schema(&AccountNode::new("token", data), &LinkResolver::new(&root))
+            .unwrap();
+    let issues = export
+        .issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            "[accountNode]token > [structFieldTypeNode]label: `sentinelTypeNode` is not Borsh and was ignored",
+            "[accountNode]token > [structFieldTypeNode]label: strings without a `u32` prefix were exported as prefixed strings",
+            "[accountNode]token > [structFieldTypeNode]amount: `preOffsetTypeNode` is not Borsh and was ignored",
+            "[accountNode]token > [structFieldTypeNode]name: `u8` prefixes are not Borsh and were replaced by `u32` prefixes",
+        ]
+    );
+
+    // The closest Borsh equivalents are still exported.
+    assert_eq!(
+        export.container.get_definition("Token"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("label".to_string(), "String".to_string()),
+                ("amount".to_string(), "u32".to_string()),
+                ("name".to_string(), "String".to_string()),
+            ])
+        })
+    );
+}
+
+#[test]
+fn unresolved_links_fail() {
+    let root = RootNode::new(ProgramNode::new("myProgram", "1111"));
+    let defined_type = DefinedTypeNode::new("wrapper", DefinedTypeLinkNode::new("missing"));
+    assert!(defined_type_to_borsh_schema(&defined_type, &LinkResolver::new(&root)).is_err());
+}
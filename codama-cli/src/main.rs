@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
 use codama::Codama;
 use codama_errors::CodamaResult;
+use codama_renderers_rust::render_rust_client;
+use codama_renderers_typescript::{render_typescript_types, TypeScriptOptions};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -26,6 +28,18 @@ enum Commands {
         /// Prettyprint the JSON output
         #[arg(short, long)]
         pretty: bool,
+
+        /// Also render a Rust client crate inside the given directory
+        #[arg(long, value_name = "DIR")]
+        rust_client: Option<PathBuf>,
+
+        /// Also render TypeScript type declarations inside the given directory
+        #[arg(long, value_name = "DIR")]
+        ts_types: Option<PathBuf>,
+
+        /// Also render encoder and decoder functions with the TypeScript types
+        #[arg(long, requires = "ts_types")]
+        ts_codecs: bool,
     },
 }
 
@@ -37,6 +51,9 @@ fn main() -> CodamaResult<()> {
             path,
             output,
             pretty,
+            rust_client,
+            ts_types,
+            ts_codecs,
         } => {
             let codama = Codama::load(&path)?;
             let idl = codama.get_idl()?;
@@ -56,6 +73,25 @@ fn main() -> CodamaResult<()> {
                     println!("{}", json);
                 }
             }
+
+            if let Some(directory) = rust_client {
+                let client = render_rust_client(&idl)?;
+                for issue in &client.issues {
+                    eprintln!("warning: {}", issue);
+                }
+                client.write(&directory)?;
+                eprintln!("✓ Rust client written to: {}", directory.display());
+            }
+
+            if let Some(directory) = ts_types {
+                let options = TypeScriptOptions { codecs: ts_codecs };
+                let types = render_typescript_types(&idl, options)?;
+                for issue in &types.issues {
+                    eprintln!("warning: {}", issue);
+                }
+                types.write(&directory)?;
+                eprintln!("✓ TypeScript types written to: {}", directory.display());
+            }
         }
     }
 
@@ -84,3 +84,68 @@ fn test_version_flag() {
         .success()
         .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")));
 }
+
+#[test]
+fn test_generate_idl_with_rust_client_flag() {
+    let temp_dir = std::env::temp_dir();
+    let client_dir = temp_dir.join(format!("test_client_{:?}", std::thread::current().id()));
+    let _ = fs::remove_dir_all(&client_dir);
+
+    let mut cmd = get_cli_command();
+    let crate_path = simple_account_fixture_path();
+
+    cmd.arg("generate-idl")
+        .arg(crate_path)
+        .arg("--rust-client")
+        .arg(&client_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""kind":"rootNode""#))
+        .stderr(predicate::str::contains("Rust client written to"));
+
+    let cargo_toml = fs::read_to_string(client_dir.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains(r#"name = "test-simple-account-client""#));
+    assert!(client_dir.join("src/lib.rs").exists());
+
+    fs::remove_dir_all(&client_dir).unwrap();
+}
+
+#[test]
+fn test_generate_idl_with_ts_types_flag() {
+    let temp_dir = std::env::temp_dir();
+    let types_dir = temp_dir.join(format!("test_ts_types_{:?}", std::thread::current().id()));
+    let _ = fs::remove_dir_all(&types_dir);
+
+    let mut cmd = get_cli_command();
+    let crate_path = simple_account_fixture_path();
+
+    cmd.arg("generate-idl")
+        .arg(crate_path)
+        .arg("--ts-types")
+        .arg(&types_dir)
+        .arg("--ts-codecs")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""kind":"rootNode""#))
+        .stderr(predicate::str::contains("TypeScript types written to"));
+
+    let files = fs::read_dir(&types_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].ends_with(".ts") && !files[0].ends_with(".d.ts"));
+
+    fs::remove_dir_all(&types_dir).unwrap();
+}
+
+#[test]
+fn test_ts_codecs_flag_requires_ts_types() {
+    let mut cmd = get_cli_command();
+    cmd.arg("generate-idl")
+        .arg(simple_account_fixture_path())
+        .arg("--ts-codecs")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--ts-types <DIR>"));
+}
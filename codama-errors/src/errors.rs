@@ -24,9 +24,60 @@ pub enum CodamaError {
     #[error("Node not found")]
     NodeNotFound,
 
+    #[error("Could not resolve `{kind}` with name `{name}`")]
+    UnresolvedLink { kind: String, name: String },
+
+    #[error("Ambiguous `{kind}` with name `{name}`, found {count} matching nodes")]
+    AmbiguousLink {
+        kind: String,
+        name: String,
+        count: usize,
+    },
+
     #[error("Invalid encoding: {0}")]
     InvalidBytesEncoding(String),
 
+    #[error("Could not decode `{data}` as {encoding} bytes")]
+    InvalidBytesData { encoding: String, data: String },
+
+    #[error("Could not encode value at `{path}`: {message}")]
+    EncodingFailed { path: String, message: String },
+
+    #[error("Could not decode value at `{path}` (offset {offset}): {message}")]
+    DecodingFailed {
+        offset: usize,
+        path: String,
+        message: String,
+    },
+
+    #[error("Could not convert JSON value at `{path}`: {message}")]
+    JsonConversionFailed { path: String, message: String },
+
+    #[error("Value at `{path}` does not fit its type: {message}")]
+    TypeMismatch { path: String, message: String },
+
+    #[error("Could not derive PDA `{name}`: {message}")]
+    PdaDerivationFailed { name: String, message: String },
+
+    #[error("Could not render the display of instruction `{name}`: {message}")]
+    DisplayRenderingFailed { name: String, message: String },
+
+    #[error("Unsupported Codama version `{version}`, expected {expected}")]
+    UnsupportedVersion { version: String, expected: String },
+
+    #[error("Invalid IDL: {0}")]
+    InvalidIdl(String),
+
+    #[error("Invalid selector `{selector}`: {message}")]
+    InvalidSelector { selector: String, message: String },
+
+    #[error("Could not merge `{kind}` with name `{name}`: {message}")]
+    MergeConflict {
+        kind: String,
+        name: String,
+        message: String,
+    },
+
     #[error("Invalid number format: {0}")]
     InvalidNumberFormat(String),
 
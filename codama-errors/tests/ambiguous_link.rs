@@ -0,0 +1,14 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::AmbiguousLink {
+        kind: "accountLinkNode".to_string(),
+        name: "mint".to_string(),
+        count: 2,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Ambiguous `accountLinkNode` with name `mint`, found 2 matching nodes"
+    );
+}
@@ -0,0 +1,14 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::DecodingFailed {
+        offset: 12,
+        path: "$.amount".to_string(),
+        message: "expected 8 bytes, found 3".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not decode value at `$.amount` (offset 12): expected 8 bytes, found 3"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::DisplayRenderingFailed {
+        name: "transfer".to_string(),
+        message: "unknown argument `amount`".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not render the display of instruction `transfer`: unknown argument `amount`"
+    );
+}
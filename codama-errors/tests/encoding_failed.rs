@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::EncodingFailed {
+        path: "$.amount".to_string(),
+        message: "number 256 does not fit in `u8`".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not encode value at `$.amount`: number 256 does not fit in `u8`"
+    );
+}
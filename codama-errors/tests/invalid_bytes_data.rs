@@ -0,0 +1,10 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::InvalidBytesData {
+        encoding: "base16".to_string(),
+        data: "zz".to_string(),
+    };
+    assert_eq!(error.to_string(), "Could not decode `zz` as base16 bytes");
+}
@@ -0,0 +1,10 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::InvalidIdl("generic types are not supported".to_string());
+    assert_eq!(
+        error.to_string(),
+        "Invalid IDL: generic types are not supported"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::InvalidSelector {
+        selector: "program.accounts[mint".to_string(),
+        message: "unclosed `[`".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Invalid selector `program.accounts[mint`: unclosed `[`"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::JsonConversionFailed {
+        path: "$.amount".to_string(),
+        message: "expected a number".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not convert JSON value at `$.amount`: expected a number"
+    );
+}
@@ -0,0 +1,14 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::MergeConflict {
+        kind: "accountNode".to_string(),
+        name: "mint".to_string(),
+        message: "duplicate name".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not merge `accountNode` with name `mint`: duplicate name"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::PdaDerivationFailed {
+        name: "counter".to_string(),
+        message: "missing value for seed `authority`".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not derive PDA `counter`: missing value for seed `authority`"
+    );
+}
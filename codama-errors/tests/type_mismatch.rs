@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::TypeMismatch {
+        path: "$.amount".to_string(),
+        message: "number 256 is out of range [0, 255]".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Value at `$.amount` does not fit its type: number 256 is out of range [0, 255]"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::UnresolvedLink {
+        kind: "definedTypeLinkNode".to_string(),
+        name: "counter".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Could not resolve `definedTypeLinkNode` with name `counter`"
+    );
+}
@@ -0,0 +1,13 @@
+use codama_errors::CodamaError;
+
+#[test]
+fn display() {
+    let error = CodamaError::UnsupportedVersion {
+        version: "2.0.0".to_string(),
+        expected: "1.0.0 to 1.8.0".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Unsupported Codama version `2.0.0`, expected 1.0.0 to 1.8.0"
+    );
+}
@@ -0,0 +1,131 @@
+use codama_nodes::{
+    Endianness, HasKind, NestedTypeNode, NestedTypeNodeTrait, NodePath, NumberFormat,
+    NumberTypeNode, TypeNodeTrait,
+};
+use std::fmt::{Display, Formatter};
+
+/// A Codama construct that could not be exported or rendered as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportIssue {
+    /// The path of the node that holds the issue.
+    pub path: NodePath,
+    pub message: String,
+}
+
+impl Display for ExportIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+/// Reports the Codama constructs that an exporter or a renderer
+/// cannot represent in its output.
+pub trait ExportIssueReporter {
+    /// How unsupported nodes are described, e.g. `is not Borsh`.
+    const UNSUPPORTED: &'static str;
+
+    fn report<T: Into<String>>(&mut self, message: T);
+
+    fn report_kind(&mut self, kind: &str) {
+        self.report(format!("`{kind}` {} and was ignored", Self::UNSUPPORTED));
+    }
+
+    /// Returns the type wrapped by any nested type node, reporting the wrappers.
+    fn nested<'b, T: TypeNodeTrait>(&mut self, node: &'b NestedTypeNode<T>) -> &'b T {
+        if !matches!(node, NestedTypeNode::Value(_)) {
+            self.report_kind(node.kind());
+        }
+        node.get_nested_type_node()
+    }
+
+    /// Reports prefixes that differ from the ones Borsh uses.
+    fn borsh_prefix(&mut self, prefix: &NestedTypeNode<NumberTypeNode>, format: NumberFormat) {
+        let prefix = self.nested(prefix);
+        if !prefix.is_le(format) {
+            self.report(format!(
+                "`{}` prefixes are not Borsh and were replaced by `{}` prefixes",
+                number_name(prefix),
+                number_name(&NumberTypeNode::le(format)),
+            ));
+        }
+    }
+}
+
+fn number_name(node: &NumberTypeNode) -> String {
+    let format = serde_json::to_value(node.format).ok();
+    let format = format.as_ref().and_then(|format| format.as_str());
+    let format = format.unwrap_or_default();
+    match node.endian {
+        Endianness::Le => format.to_string(),
+        Endianness::Be => format!("{format} (big-endian)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codama_nodes::{FixedSizeTypeNode, U16, U32};
+    use pretty_assertions::assert_eq;
+
+    #[derive(Default)]
+    struct Reporter {
+        issues: Vec<String>,
+    }
+
+    impl ExportIssueReporter for Reporter {
+        const UNSUPPORTED: &'static str = "is not supported";
+
+        fn report<T: Into<String>>(&mut self, message: T) {
+            self.issues.push(message.into());
+        }
+    }
+
+    #[test]
+    fn display() {
+        let mut path = NodePath::new();
+        let issue = |path: &NodePath| ExportIssue {
+            path: path.clone(),
+            message: "something was ignored".into(),
+        };
+        assert_eq!(issue(&path).to_string(), "something was ignored");
+        path.push("accountNode", Some("token"));
+        assert_eq!(
+            issue(&path).to_string(),
+            "[accountNode]token: something was ignored"
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let mut reporter = Reporter::default();
+        let node = NestedTypeNode::Value(NumberTypeNode::le(U32));
+        assert_eq!(reporter.nested(&node), &NumberTypeNode::le(U32));
+        let node = NestedTypeNode::FixedSize(FixedSizeTypeNode::new(
+            NestedTypeNode::Value(NumberTypeNode::le(U32)),
+            8,
+        ));
+        assert_eq!(reporter.nested(&node), &NumberTypeNode::le(U32));
+        assert_eq!(
+            reporter.issues,
+            ["`fixedSizeTypeNode` is not supported and was ignored"]
+        );
+    }
+
+    #[test]
+    fn borsh_prefix() {
+        let mut reporter = Reporter::default();
+        reporter.borsh_prefix(&NumberTypeNode::le(U32).into(), U32);
+        reporter.borsh_prefix(&NumberTypeNode::le(U16).into(), U32);
+        reporter.borsh_prefix(&NumberTypeNode::be(U32).into(), U32);
+        assert_eq!(
+            reporter.issues,
+            [
+                "`u16` prefixes are not Borsh and were replaced by `u32` prefixes",
+                "`u32 (big-endian)` prefixes are not Borsh and were replaced by `u32` prefixes",
+            ]
+        );
+    }
+}
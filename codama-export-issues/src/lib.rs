@@ -0,0 +1,3 @@
+mod export_issue;
+
+pub use export_issue::*;
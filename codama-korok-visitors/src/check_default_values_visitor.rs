@@ -0,0 +1,122 @@
+use crate::KorokVisitor;
+use codama_attributes::{CodamaAttribute, CodamaDirective, TryFromFilter};
+use codama_errors::CodamaResult;
+use codama_koroks::{KorokMut, KorokTrait};
+use codama_nodes::{
+    check_value_type, LinkResolver, Node, RegisteredTypeNode, RootNode, TypeNode, ValueNode,
+};
+use codama_syn_helpers::extensions::ToTokensExtension;
+
+/// Ensures default values set via `#[codama(default_value = ...)]` fit
+/// the type of their struct field or instruction argument. Linked types
+/// are only checked once the root node is available.
+#[derive(Default)]
+pub struct CheckDefaultValuesVisitor {
+    root: Option<RootNode>,
+}
+
+impl CheckDefaultValuesVisitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KorokVisitor for CheckDefaultValuesVisitor {
+    fn visit_root(&mut self, korok: &mut codama_koroks::RootKorok) -> CodamaResult<()> {
+        self.root = match &korok.node {
+            Some(Node::Root(root)) => Some(root.clone()),
+            _ => None,
+        };
+        self.visit_children(korok)
+    }
+
+    fn visit_crate(&mut self, korok: &mut codama_koroks::CrateKorok) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_file_module(
+        &mut self,
+        korok: &mut codama_koroks::FileModuleKorok,
+    ) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_module(&mut self, korok: &mut codama_koroks::ModuleKorok) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_struct(&mut self, korok: &mut codama_koroks::StructKorok) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_enum(&mut self, korok: &mut codama_koroks::EnumKorok) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_unsupported_item(
+        &mut self,
+        korok: &mut codama_koroks::UnsupportedItemKorok,
+    ) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_enum_variant(
+        &mut self,
+        korok: &mut codama_koroks::EnumVariantKorok,
+    ) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+
+    fn visit_field(&mut self, korok: &mut codama_koroks::FieldKorok) -> CodamaResult<()> {
+        self.visit_children(korok)?;
+        self.check_default_value(korok.into())
+    }
+}
+
+impl CheckDefaultValuesVisitor {
+    fn check_default_value(&self, korok: KorokMut) -> CodamaResult<()> {
+        // Ensure the default value was set by an attribute.
+        let Some(attribute) = korok.attributes().and_then(|attributes| {
+            attributes
+                .iter()
+                .rev()
+                .filter_map(CodamaAttribute::filter)
+                .find(|a| matches!(a.directive.as_ref(), CodamaDirective::DefaultValue(_)))
+        }) else {
+            return Ok(());
+        };
+
+        // Ensure there is a default value to check.
+        let Some((value, r#type)) = get_default_value(korok.node()) else {
+            return Ok(());
+        };
+
+        let resolver = self.root.as_ref().map(LinkResolver::new);
+        check_value_type(&value, r#type, resolver.as_ref()).map_err(|error| {
+            attribute
+                .ast
+                .error(format!("Invalid default value: {error}"))
+                .into()
+        })
+    }
+}
+
+fn get_default_value(node: &Option<Node>) -> Option<(ValueNode, &TypeNode)> {
+    match node {
+        Some(Node::Type(RegisteredTypeNode::StructField(field))) => (*field.default_value)
+            .clone()
+            .map(|value| (value, field.r#type.as_ref())),
+        Some(Node::InstructionArgument(argument)) => (*argument.default_value)
+            .clone()
+            .and_then(|value| ValueNode::try_from(value).ok())
+            .map(|value| (value, argument.r#type.as_ref())),
+        _ => None,
+    }
+}
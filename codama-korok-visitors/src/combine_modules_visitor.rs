@@ -2,7 +2,7 @@ use crate::KorokVisitor;
 use codama_attributes::ProgramDirective;
 use codama_errors::CodamaResult;
 use codama_koroks::KorokTrait;
-use codama_nodes::{HasName, Node, ProgramNode, RootNode};
+use codama_nodes::{merge, HasName, MergeOptions, Node, RootNode};
 
 #[derive(Default)]
 pub struct CombineModulesVisitor {
@@ -27,13 +27,13 @@ impl KorokVisitor for CombineModulesVisitor {
         }
 
         self.visit_children(korok)?;
-        korok.node = combine_koroks(&korok.node, &korok.crates);
+        korok.node = combine_koroks(&korok.node, &korok.crates)?;
         Ok(())
     }
 
     fn visit_crate(&mut self, korok: &mut codama_koroks::CrateKorok) -> CodamaResult<()> {
         self.visit_children(korok)?;
-        korok.node = combine_koroks(&korok.node, &korok.items);
+        korok.node = combine_koroks(&korok.node, &korok.items)?;
         Ok(())
     }
 
@@ -42,13 +42,13 @@ impl KorokVisitor for CombineModulesVisitor {
         korok: &mut codama_koroks::FileModuleKorok,
     ) -> CodamaResult<()> {
         self.visit_children(korok)?;
-        korok.node = combine_koroks(&korok.node, &korok.items);
+        korok.node = combine_koroks(&korok.node, &korok.items)?;
         Ok(())
     }
 
     fn visit_module(&mut self, korok: &mut codama_koroks::ModuleKorok) -> CodamaResult<()> {
         self.visit_children(korok)?;
-        korok.node = combine_koroks(&korok.node, &korok.items);
+        korok.node = combine_koroks(&korok.node, &korok.items)?;
 
         // Apply program directive on the module if present.
         if let Some(node) = korok.node.take() {
@@ -60,7 +60,10 @@ impl KorokVisitor for CombineModulesVisitor {
 }
 
 /// Create a single RootNode from an initial node and a list of nodes to merge.
-fn combine_koroks<T: KorokTrait>(initial_node: &Option<Node>, koroks: &[T]) -> Option<Node> {
+fn combine_koroks<T: KorokTrait>(
+    initial_node: &Option<Node>,
+    koroks: &[T],
+) -> CodamaResult<Option<Node>> {
     // Create the new RootNode to bind all items together from the exisiting node, in any.
     // - If there is already a RootNode or ProgramNode, use this as a starting point.
     // - If there is no existing node, use None and let the merging create a new one if needed.
@@ -69,7 +72,7 @@ fn combine_koroks<T: KorokTrait>(initial_node: &Option<Node>, koroks: &[T]) -> O
         Some(Node::Root(root)) => Some(root.clone()),
         Some(Node::Program(program)) => Some(RootNode::new(program.clone())),
         None => None,
-        _ => return initial_node.clone(),
+        _ => return Ok(initial_node.clone()),
     };
 
     // Get all nodes from the koroks to merge.
@@ -81,10 +84,10 @@ fn combine_koroks<T: KorokTrait>(initial_node: &Option<Node>, koroks: &[T]) -> O
     // Convert all nodes into RootNodes and merge them with the binding root node.
     let from_parent = this_root_node.is_some();
     for that_root_node in get_root_nodes_to_merge(nodes_to_merge) {
-        merge_root_nodes(&mut this_root_node, that_root_node, from_parent);
+        merge_root_nodes(&mut this_root_node, that_root_node, from_parent)?;
     }
 
-    this_root_node.map(Into::into)
+    Ok(this_root_node.map(Into::into))
 }
 
 /// Convert all nodes to merge into RootNodes.
@@ -157,80 +160,20 @@ fn get_scraps_root_node(nodes: Vec<Node>) -> Option<RootNode> {
 }
 
 /// Merge `that` RootNode into `this` RootNode.
-fn merge_root_nodes(this: &mut Option<RootNode>, that: RootNode, from_parent: bool) {
-    // If there is no root node yet, set it to the one provided.
-    let Some(this) = this else {
-        *this = Some(that);
-        return;
+fn merge_root_nodes(
+    this: &mut Option<RootNode>,
+    that: RootNode,
+    from_parent: bool,
+) -> CodamaResult<()> {
+    let options = MergeOptions {
+        merge_programs_without_public_key: from_parent,
+        ..MergeOptions::default()
     };
-
-    // Get an array of all programs to merge.
-    let mut those_programs = Vec::new();
-    those_programs.push(that.program);
-    those_programs.extend(that.additional_programs);
-
-    // For each program to merge.
-    for that_program in those_programs {
-        // Check if it can be merged with the main root program.
-        if should_merge_program_nodes(&this.program, &that_program, from_parent) {
-            merge_program_nodes(&mut this.program, that_program);
-            continue;
-        }
-
-        // Then, check if it can be merged with any additional program.
-        let found = this
-            .additional_programs
-            .iter_mut()
-            .find(|p| should_merge_program_nodes(p, &that_program, from_parent));
-
-        if let Some(additional_program) = found {
-            // If so, merge it with the additional program found.
-            merge_program_nodes(additional_program, that_program);
-        } else {
-            // Otherwise, add it as another additional program.
-            this.additional_programs.push(that_program);
-        }
-    }
-}
-
-/// Check if two ProgramNodes should be merged together.
-fn should_merge_program_nodes(this: &ProgramNode, that: &ProgramNode, from_parent: bool) -> bool {
-    this.public_key == that.public_key || (from_parent && that.public_key.is_empty())
-}
-
-/// Merge `that` ProgramNode into `this` ProgramNode.
-fn merge_program_nodes(this: &mut ProgramNode, that: ProgramNode) {
-    if this.name.is_empty() {
-        this.name = that.name;
-    }
-    if this.public_key.is_empty() {
-        this.public_key = that.public_key;
-    }
-    if this.version.is_empty() {
-        this.version = that.version;
-    }
-    if this.origin.is_none() {
-        this.origin = that.origin;
-    }
-    if this.docs.is_empty() {
-        this.docs = that.docs;
-    }
-    merge_nodes_with_name(&mut this.accounts, that.accounts);
-    merge_nodes_with_name(&mut this.instructions, that.instructions);
-    merge_nodes_with_name(&mut this.defined_types, that.defined_types);
-    merge_nodes_with_name(&mut this.pdas, that.pdas);
-    merge_nodes_with_name(&mut this.events, that.events);
-    merge_nodes_with_name(&mut this.errors, that.errors);
-    merge_nodes_with_name(&mut this.constants, that.constants);
-}
-
-fn merge_nodes_with_name<T>(nodes: &mut Vec<T>, new_nodes: Vec<T>)
-where
-    T: HasName,
-{
-    for that in new_nodes {
-        add_or_replace_node_with_name(nodes, that);
-    }
+    *this = Some(match this.take() {
+        Some(this) => merge(this, that, options)?,
+        None => that,
+    });
+    Ok(())
 }
 
 fn add_or_replace_node_with_name<T>(nodes: &mut Vec<T>, new_node: T)
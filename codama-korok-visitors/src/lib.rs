@@ -1,6 +1,7 @@
 mod apply_display_visitor;
 mod apply_type_modifiers_visitor;
 mod apply_type_overrides_visitor;
+mod check_default_values_visitor;
 mod combine_modules_visitor;
 mod combine_types_visitor;
 mod compose_visitor;
@@ -22,6 +23,7 @@ mod visitor;
 pub use apply_display_visitor::*;
 pub use apply_type_modifiers_visitor::*;
 pub use apply_type_overrides_visitor::*;
+pub use check_default_values_visitor::*;
 pub use combine_modules_visitor::*;
 pub use combine_types_visitor::*;
 pub use compose_visitor::*;
@@ -0,0 +1,131 @@
+use codama_errors::CodamaResult;
+use codama_korok_visitors::{
+    CheckDefaultValuesVisitor, IdentifyFieldTypesVisitor, KorokVisitable, SetDefaultValuesVisitor,
+};
+use codama_koroks::{FieldKorok, ItemKorok, RootKorok};
+use codama_nodes::{
+    DefinedTypeNode, InstructionArgumentNode, NumberFormat::U8, NumberTypeNode, ProgramNode,
+    PublicKeyTypeNode, RootNode, StringValueNode, StructFieldTypeNode,
+};
+use codama_stores::RootStore;
+use quote::quote;
+
+#[test]
+fn it_accepts_default_values_that_fit_their_type() -> CodamaResult<()> {
+    let item: syn::Field = syn::parse_quote! {
+        #[codama(default_value = 255)]
+        pub amount: u8
+    };
+    let mut korok = FieldKorok::parse(&item)?;
+    korok.accept(&mut IdentifyFieldTypesVisitor::new())?;
+    korok.accept(&mut SetDefaultValuesVisitor::new())?;
+    korok.accept(&mut CheckDefaultValuesVisitor::new())?;
+    Ok(())
+}
+
+#[test]
+fn it_fails_on_out_of_range_numbers() -> CodamaResult<()> {
+    let item: syn::Field = syn::parse_quote! {
+        #[codama(default_value = 256)]
+        pub amount: u8
+    };
+    let mut korok = FieldKorok::parse(&item)?;
+    korok.accept(&mut IdentifyFieldTypesVisitor::new())?;
+    korok.accept(&mut SetDefaultValuesVisitor::new())?;
+    let error = korok
+        .accept(&mut CheckDefaultValuesVisitor::new())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid default value: Value at `$` does not fit its type: number 256 is out of range [0, 255]"
+    );
+    Ok(())
+}
+
+#[test]
+fn it_fails_on_values_of_the_wrong_kind() -> CodamaResult<()> {
+    let item: syn::Field = syn::parse_quote! {
+        #[codama(default_value = "hello")]
+        pub amount: u64
+    };
+    let mut korok = FieldKorok::parse(&item)?;
+    korok.accept(&mut IdentifyFieldTypesVisitor::new())?;
+    korok.accept(&mut SetDefaultValuesVisitor::new())?;
+    let error = korok
+        .accept(&mut CheckDefaultValuesVisitor::new())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid default value: Value at `$` does not fit its type: expected `numberValueNode`, found `stringValueNode`"
+    );
+    Ok(())
+}
+
+#[test]
+fn it_checks_instruction_argument_nodes() -> CodamaResult<()> {
+    let item: syn::Field = syn::parse_quote! {
+        #[codama(default_value = "not a public key")]
+        pub authority: Pubkey
+    };
+    let mut korok = FieldKorok::parse(&item)?;
+    korok.node = Some(
+        InstructionArgumentNode {
+            default_value: Box::new(Some(StringValueNode::new("not a public key").into())),
+            ..InstructionArgumentNode::new("authority", PublicKeyTypeNode::new())
+        }
+        .into(),
+    );
+    assert!(korok.accept(&mut CheckDefaultValuesVisitor::new()).is_err());
+    Ok(())
+}
+
+#[test]
+fn it_ignores_default_values_not_set_by_attributes() -> CodamaResult<()> {
+    let item: syn::Field = syn::parse_quote! { pub amount: u8 };
+    let mut korok = FieldKorok::parse(&item)?;
+    korok.node = Some(
+        StructFieldTypeNode {
+            default_value: Box::new(Some(StringValueNode::new("hello").into())),
+            ..StructFieldTypeNode::new("amount", NumberTypeNode::le(U8))
+        }
+        .into(),
+    );
+    korok.accept(&mut CheckDefaultValuesVisitor::new())?;
+    Ok(())
+}
+
+#[test]
+fn it_resolves_linked_types_from_the_root_node() -> CodamaResult<()> {
+    let store = RootStore::hydrate(quote! {
+        pub struct Membership {
+            #[codama(default_value = 256)]
+            pub level: Level,
+        }
+    })?;
+    let mut korok = RootKorok::parse(&store)?;
+    korok.accept(&mut IdentifyFieldTypesVisitor::new())?;
+    korok.accept(&mut SetDefaultValuesVisitor::new())?;
+    let ItemKorok::Struct(item) = &korok.crates[0].items[0] else {
+        panic!("expected a struct korok");
+    };
+    assert!(item.fields[0].node.is_some());
+
+    // Without a root node, linked types cannot be checked.
+    korok.accept(&mut CheckDefaultValuesVisitor::new())?;
+
+    korok.node = Some(
+        RootNode::new(
+            ProgramNode::new("membership", "1111")
+                .add_defined_type(DefinedTypeNode::new("level", NumberTypeNode::le(U8))),
+        )
+        .into(),
+    );
+    let error = korok
+        .accept(&mut CheckDefaultValuesVisitor::new())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid default value: Value at `$` does not fit its type: number 256 is out of range [0, 255]"
+    );
+    Ok(())
+}
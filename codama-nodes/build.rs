@@ -0,0 +1,110 @@
+//! Lists every node and node union of the crate that gets its own `visit_*` and
+//! `transform_*` methods, so the `NodeVisitor` and `NodeTransformer` traits are
+//! generated from the same `#[node]` and `#[node_union]` items as their traversal.
+use std::path::Path;
+use syn::punctuated::Punctuated;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let mut items = vec![];
+    collect_visitable_items(Path::new("src"), &mut items);
+    items.sort();
+    items.dedup();
+
+    let entries = items
+        .iter()
+        .map(|(name, path)| {
+            let snake_case = to_snake_case(name);
+            format!("            visit_{snake_case}, transform_{snake_case}: {path};\n")
+        })
+        .collect::<String>();
+    let output = format!(
+        "/// Calls the given macro with the `visit_*` method, the `transform_*` method and the\n\
+         /// type of every node and node union that has methods of its own.\n\
+         macro_rules! for_each_visitable_node {{\n    ($callback:ident) => {{\n        $callback! {{\n{entries}        }}\n    }};\n}}\n"
+    );
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    std::fs::write(Path::new(&out_dir).join("visitable_nodes.rs"), output)
+        .expect("the list of visitable nodes can be written");
+}
+
+/// Collects the name and the type of the items whose `Visitable` implementation calls
+/// their own `visit_*` and `transform_*` methods. This mirrors the `Visitable` derive:
+/// - `#[node]` and `#[type_node]` structs, unless they are generic.
+/// - `#[nestable_type_node]` structs, through their `TypeNode` flavour.
+/// - `#[node_union]` enums, unless they are generic or registered unions, in which case
+///   the standalone union generated by `#[derive(RegisteredNodes)]` is used instead.
+/// - Items that derive `Visitable` directly, such as `Node`.
+fn collect_visitable_items(directory: &Path, items: &mut Vec<(String, String)>) {
+    let mut entries = std::fs::read_dir(directory)
+        .expect("the source directory can be read")
+        .map(|entry| entry.expect("the source entry can be read").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_visitable_items(&path, items);
+            continue;
+        }
+        if path.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path).expect("the source file can be read");
+        let file = syn::parse_file(&content)
+            .unwrap_or_else(|error| panic!("cannot parse {}: {error}", path.display()));
+        for item in file.items {
+            let (attrs, name, is_generic) = match &item {
+                syn::Item::Struct(item) => {
+                    (&item.attrs, &item.ident, !item.generics.params.is_empty())
+                }
+                syn::Item::Enum(item) => {
+                    (&item.attrs, &item.ident, !item.generics.params.is_empty())
+                }
+                _ => continue,
+            };
+            let name = name.to_string();
+            let has_attribute = |ident: &str| attrs.iter().any(|attr| attr.path().is_ident(ident));
+            let has_derive = |ident: &str| attrs.iter().any(|attr| derives(attr, ident));
+
+            if has_attribute("nestable_type_node") {
+                items.push((name.clone(), format!("crate::{name}<crate::TypeNode>")));
+            } else if is_generic {
+                continue;
+            } else if has_attribute("node") || has_attribute("type_node") || has_derive("Visitable")
+            {
+                items.push((name.clone(), format!("crate::{name}")));
+            } else if has_attribute("node_union") {
+                match name.strip_prefix("Registered") {
+                    Some(standalone) if has_derive("RegisteredNodes") => {
+                        items.push((standalone.to_string(), format!("crate::{standalone}")))
+                    }
+                    Some(_) => {}
+                    None => items.push((name.clone(), format!("crate::{name}"))),
+                }
+            }
+        }
+    }
+}
+
+fn derives(attr: &syn::Attribute, ident: &str) -> bool {
+    attr.path().is_ident("derive")
+        && attr
+            .parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+            .is_ok_and(|paths| {
+                paths
+                    .iter()
+                    .any(|path| path.segments.last().is_some_and(|s| s.ident == ident))
+            })
+}
+
+/// E.g. `StructTypeNode` becomes `struct_type_node`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            snake_case.push('_');
+        }
+        snake_case.push(c.to_ascii_lowercase());
+    }
+    snake_case
+}
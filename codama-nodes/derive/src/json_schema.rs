@@ -0,0 +1,180 @@
+use crate::{is_codama_nodes_crate, is_unknown_variant, lowercase_first_letter};
+use codama_errors::{CodamaResult, IteratorCombineErrors};
+use codama_syn_helpers::extensions::*;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The attribute to add to nodes and node unions so they describe their JSON format.
+///
+/// The `JsonSchema` trait is only implemented by the nodes of `codama-nodes`,
+/// so nodes defined anywhere else are left untouched.
+pub fn json_schema_derive_attribute() -> TokenStream {
+    match is_codama_nodes_crate() {
+        true => quote! { #[derive(codama_nodes_derive::JsonSchema)] },
+        false => quote! {},
+    }
+}
+
+pub fn expand_derive_json_schema(input: &syn::DeriveInput) -> CodamaResult<TokenStream> {
+    let item_name = &input.ident;
+    let base_name = lowercase_first_letter(&item_name.to_string());
+
+    // Generic items are defined once per set of type parameters, e.g. `nestedTypeNode:numberTypeNode`.
+    let mut generics = input.generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    for param in &type_params {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#param: crate::JsonSchema));
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let schema = match &input.data {
+        syn::Data::Struct(_) => expand_struct_schema(input, &base_name)?,
+        _ => expand_union_schema(input)?,
+    };
+
+    Ok(quote! {
+        impl #impl_generics crate::JsonSchema for #item_name #type_generics #where_clause {
+            fn schema_name() -> String {
+                let mut name = #base_name.to_string();
+                #(
+                    name.push(':');
+                    name.push_str(&<#type_params as crate::JsonSchema>::schema_name());
+                )*
+                name
+            }
+
+            fn json_schema(
+                definitions: &mut serde_json::Map<String, serde_json::Value>,
+            ) -> serde_json::Value {
+                <Self as crate::JsonSchema>::json_schema_reference(definitions, |definitions| {
+                    #schema
+                })
+            }
+        }
+    })
+}
+
+fn expand_struct_schema(input: &syn::DeriveInput, kind: &str) -> CodamaResult<TokenStream> {
+    let data = input.as_struct()?;
+    let description = get_description(&input.attrs);
+    let properties = data
+        .fields
+        .iter()
+        .filter(|field| !has_serde_flag(&field.attrs, "skip"))
+        .map(|field| -> CodamaResult<TokenStream> {
+            let Some(ident) = &field.ident else {
+                return Err(field.error("expected a named field").into());
+            };
+            let name = snake_to_camel_case(ident.to_string().trim_start_matches("r#"));
+            let ty = &field.ty;
+            let has_default = has_serde_flag(&field.attrs, "default");
+            let description = get_description(&field.attrs);
+            Ok(quote! {
+                let mut schema = <#ty as crate::JsonSchema>::json_schema(definitions);
+                if let (serde_json::Value::Object(schema), Some(description)) = (&mut schema, #description) {
+                    schema.insert("description".to_string(), description.into());
+                }
+                properties.insert(#name.to_string(), schema);
+                if !#has_default && !<#ty as crate::JsonSchema>::OPTIONAL {
+                    required.push(serde_json::Value::from(#name));
+                }
+            })
+        })
+        .collect_and_combine_errors()?;
+
+    Ok(quote! {
+        let mut properties = serde_json::Map::new();
+        let mut required = vec![serde_json::Value::from("kind")];
+        properties.insert("kind".to_string(), serde_json::json!({ "const": #kind }));
+        #(#properties)*
+
+        let mut schema = serde_json::Map::new();
+        if let Some(description) = #description {
+            schema.insert("description".to_string(), description.into());
+        }
+        schema.insert("type".to_string(), "object".into());
+        schema.insert("properties".to_string(), properties.into());
+        schema.insert("required".to_string(), required.into());
+        schema.insert("additionalProperties".to_string(), false.into());
+        serde_json::Value::Object(schema)
+    })
+}
+
+fn expand_union_schema(input: &syn::DeriveInput) -> CodamaResult<TokenStream> {
+    let data = input.as_enum()?;
+    // Unknown nodes are not part of the node format.
+    let variants = data
+        .variants
+        .iter()
+        .filter(|variant| !is_unknown_variant(variant))
+        .map(|variant| -> CodamaResult<TokenStream> {
+            let ty = &variant.fields.single_unnamed_field()?.ty;
+            Ok(quote! { <#ty as crate::JsonSchema>::json_schema(definitions) })
+        })
+        .collect_and_combine_errors()?;
+
+    Ok(quote! {
+        serde_json::json!({ "anyOf": [#(#variants),*] })
+    })
+}
+
+/// Whether a `#[serde(...)]` attribute contains the given flag, e.g. `default`.
+fn has_serde_flag(attrs: &[syn::Attribute], flag: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .any(|attr| {
+            let mut found = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta.path.is_ident(flag);
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+            found
+        })
+}
+
+/// The doc comments of an item as an `Option<&str>` expression.
+fn get_description(attrs: &[syn::Attribute]) -> TokenStream {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(doc),
+                    ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    match lines.is_empty() {
+        true => quote! { None::<&str> },
+        false => {
+            let description = lines.join("\n");
+            quote! { Some(#description) }
+        }
+    }
+}
+
+fn snake_to_camel_case(s: &str) -> String {
+    let mut words = s.split('_');
+    let first = words.next().unwrap_or_default().to_string();
+    words.fold(first, |mut result, word| {
+        let mut chars = word.chars();
+        if let Some(c) = chars.next() {
+            result.extend(c.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+        result
+    })
+}
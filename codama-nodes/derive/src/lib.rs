@@ -2,12 +2,14 @@ use codama_errors::CodamaError;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+mod json_schema;
 mod nestable_type_node;
 mod node;
 mod node_union;
 mod registered_nodes;
 mod type_node;
 mod utils;
+mod visitable;
 use utils::*;
 
 #[proc_macro_attribute]
@@ -26,6 +28,14 @@ pub fn derive_node(input: TokenStream) -> TokenStream {
         .into()
 }
 
+#[proc_macro_derive(JsonSchema)]
+pub fn derive_json_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    json_schema::expand_derive_json_schema(&input)
+        .unwrap_or_else(CodamaError::into_compile_error)
+        .into()
+}
+
 #[proc_macro_attribute]
 pub fn type_node(_attr: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -59,14 +69,14 @@ pub fn derive_nestable_type_node(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_attribute]
-pub fn node_union(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn node_union(attr: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    node_union::expand_attribute_node_union(&input)
+    node_union::expand_attribute_node_union(attr.into(), &input)
         .unwrap_or_else(CodamaError::into_compile_error)
         .into()
 }
 
-#[proc_macro_derive(NodeUnion, attributes(fallback))]
+#[proc_macro_derive(NodeUnion, attributes(fallback, unknown_suffix))]
 pub fn derive_node_union(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     node_union::expand_derive_node_union(&input)
@@ -81,3 +91,11 @@ pub fn derive_registered_nodes(input: TokenStream) -> TokenStream {
         .unwrap_or_else(CodamaError::into_compile_error)
         .into()
 }
+
+#[proc_macro_derive(Visitable)]
+pub fn derive_visitable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    visitable::expand_derive_visitable(&input)
+        .unwrap_or_else(CodamaError::into_compile_error)
+        .into()
+}
@@ -1,3 +1,5 @@
+use crate::is_codama_nodes_crate;
+use crate::visitable::expand_visitable;
 use codama_errors::CodamaResult;
 use codama_syn_helpers::extensions::*;
 use proc_macro2::TokenStream;
@@ -21,7 +23,16 @@ pub fn expand_derive_nestable_type_node(input: &syn::DeriveInput) -> CodamaResul
     let variant_name = item_name.to_string();
     let variant_name = syn::Ident::new(&variant_name[..variant_name.len() - 8], item_name.span());
 
+    // Only the `TypeNode` flavour has its own visitor method, nested flavours
+    // are visited through their `NestedTypeNode`.
+    let visitable = match is_codama_nodes_crate() {
+        true => expand_visitable(item_name, quote! { #item_name<crate::TypeNode> }),
+        false => quote! {},
+    };
+
     Ok(quote! {
+        #visitable
+
         impl crate::TypeNodeTrait for #item_name<TypeNode> {
             fn try_from_type_node(node: crate::TypeNode) -> codama_errors::CodamaResult<Self> {
                 use crate::{HasKind, NodeTrait};
@@ -1,4 +1,6 @@
+use crate::json_schema::json_schema_derive_attribute;
 use crate::lowercase_first_letter;
+use crate::visitable::visitable_derive_attribute;
 use codama_errors::CodamaResult;
 use codama_syn_helpers::extensions::*;
 use proc_macro2::TokenStream;
@@ -8,8 +10,12 @@ pub fn expand_attribute_node(input: &syn::DeriveInput) -> CodamaResult<TokenStre
     input.as_struct()?;
     let item_name = &input.ident;
     let kind = lowercase_first_letter(&item_name.to_string());
+    let visitable = visitable_derive_attribute();
+    let json_schema = json_schema_derive_attribute();
 
     Ok(quote! {
+        #visitable
+        #json_schema
         #[derive(codama_nodes_derive::Node, core::fmt::Debug, core::cmp::PartialEq, core::clone::Clone, serde::Serialize, serde::Deserialize)]
         #[serde(tag = "kind", rename = #kind)]
         #[serde(rename_all = "camelCase")]
@@ -1,14 +1,42 @@
-use crate::lowercase_first_letter;
+use crate::json_schema::json_schema_derive_attribute;
+use crate::visitable::visitable_derive_attribute;
+use crate::{is_unknown_variant, lowercase_first_letter};
 use codama_errors::{CodamaResult, IteratorCombineErrors};
 use codama_syn_helpers::extensions::*;
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn expand_attribute_node_union(input: &syn::DeriveInput) -> CodamaResult<TokenStream> {
+pub fn expand_attribute_node_union(
+    attr: TokenStream,
+    input: &syn::DeriveInput,
+) -> CodamaResult<TokenStream> {
     input.as_enum()?;
+    // E.g. `#[node_union(unknown_suffix = "TypeNode")]`.
+    let unknown_suffix = match attr.is_empty() {
+        true => quote! {},
+        false => {
+            let argument = syn::parse2::<syn::MetaNameValue>(attr)?;
+            if !argument.path.is_ident("unknown_suffix") {
+                return Err(
+                    syn::Error::new_spanned(argument.path, "expected `unknown_suffix`").into(),
+                );
+            }
+            let value = &argument.value;
+            quote! { #[unknown_suffix = #value] }
+        }
+    };
+    // Generic unions such as `NestedTypeNode` walk their children by hand.
+    let visitable = match input.generics.params.is_empty() {
+        true => visitable_derive_attribute(),
+        false => quote! {},
+    };
+    let json_schema = json_schema_derive_attribute();
 
     Ok(quote! {
+        #visitable
+        #json_schema
         #[derive(codama_nodes_derive::NodeUnion, derive_more::From, core::fmt::Debug, core::cmp::PartialEq, core::clone::Clone)]
+        #unknown_suffix
         #input
     })
 }
@@ -33,6 +61,9 @@ pub fn expand_derive_node_union(input: &syn::DeriveInput) -> CodamaResult<TokenS
         })
     });
 
+    // The variant keeping node kinds unknown to this version of the library, if any.
+    let unknown_variant = variants.iter().find(|variant| is_unknown_variant(variant));
+
     let kind_patterns = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
         quote! {
@@ -47,28 +78,57 @@ pub fn expand_derive_node_union(input: &syn::DeriveInput) -> CodamaResult<TokenS
         }
     });
 
-    let deserialize_patterns = variants
+    let known_variants = variants
         .iter()
-        .filter(|variant| match fallback_variant {
-            Some(fallback_variant) => *variant != fallback_variant,
-            None => true,
-        })
-        .map(|variant| -> CodamaResult<TokenStream> {
-            let variant_name = &variant.ident;
+        .filter(|variant| Some(*variant) != fallback_variant && Some(*variant) != unknown_variant)
+        .map(|variant| -> CodamaResult<(&syn::Ident, String)> {
             let variant_type = &variant.fields.single_unnamed_field()?.ty;
             let variant_type = variant_type
                 .single_generic_type_from_path("Box")
                 .unwrap_or(variant_type);
             let kind = lowercase_first_letter(&variant_type.as_path()?.last_str());
-
-            Ok(quote! {
-                #kind => Ok(#item_name::#variant_name(
-                    serde_json::from_value(value).map_err(to_serde_error)?,
-                )),
-            })
+            Ok((&variant.ident, kind))
         })
         .collect_and_combine_errors()?;
 
+    let deserialize_patterns = known_variants.iter().map(|(variant_name, kind)| {
+        quote! {
+            #kind => Ok(#item_name::#variant_name(
+                serde_json::from_value(value).map_err(to_serde_error)?,
+            )),
+        }
+    });
+
+    let unknown_deserialize_pattern = match unknown_variant {
+        Some(unknown_variant) => {
+            let unknown_variant_name = &unknown_variant.ident;
+            let suffix = input
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("unknown_suffix"))
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        unknown_variant,
+                        "unions with an `UnknownNode` variant must declare `#[node_union(unknown_suffix = \"...\")]`",
+                    )
+                })?
+                .meta
+                .require_name_value()?
+                .value
+                .clone();
+            quote! {
+                _ if kind == crate::UnknownNode::KIND => {
+                    let node = crate::UnknownNode::unwrap(value).ok_or_else(|| serde::de::Error::custom("missing kind"))?;
+                    match node.has_suffix(#suffix) {
+                        true => Ok(#item_name::#unknown_variant_name(node)),
+                        false => Err(serde::de::Error::custom(format!(concat!("unknown kind {} for ", stringify!(#item_name)), node.kind))),
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
     let fallback_deserialize_pattern = match fallback_variant {
         Some(fallback_variant) => {
             let fallback_variant_name = &fallback_variant.ident;
@@ -117,6 +177,7 @@ pub fn expand_derive_node_union(input: &syn::DeriveInput) -> CodamaResult<TokenS
                 };
                 match kind {
                     #(#deserialize_patterns)*
+                    #unknown_deserialize_pattern
                     #fallback_deserialize_pattern
                 }
             }
@@ -1,3 +1,5 @@
+use codama_syn_helpers::extensions::*;
+
 pub fn lowercase_first_letter(s: &str) -> String {
     let mut c = s.chars();
     match c.next() {
@@ -5,3 +7,18 @@ pub fn lowercase_first_letter(s: &str) -> String {
         Some(first) => first.to_lowercase().collect::<String>() + c.as_str(),
     }
 }
+
+/// Whether the variant of a node union wraps an `UnknownNode`.
+pub fn is_unknown_variant(variant: &syn::Variant) -> bool {
+    variant
+        .fields
+        .single_unnamed_field()
+        .ok()
+        .and_then(|field| field.ty.as_path().ok())
+        .is_some_and(|path| path.last_str() == "UnknownNode")
+}
+
+/// Whether the macro is expanded inside the `codama-nodes` crate itself.
+pub fn is_codama_nodes_crate() -> bool {
+    std::env::var("CARGO_CRATE_NAME").is_ok_and(|name| name == "codama_nodes")
+}
@@ -0,0 +1,164 @@
+use crate::{is_codama_nodes_crate, lowercase_first_letter};
+use codama_errors::CodamaResult;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The attribute to add to nodes and node unions so visitors and transformers can walk them.
+///
+/// The `NodeVisitor` and `NodeTransformer` traits only know about the nodes of
+/// `codama-nodes`, so nodes defined anywhere else are left untouched.
+pub fn visitable_derive_attribute() -> TokenStream {
+    match is_codama_nodes_crate() {
+        true => quote! { #[derive(codama_nodes_derive::Visitable)] },
+        false => quote! {},
+    }
+}
+
+pub fn expand_derive_visitable(input: &syn::DeriveInput) -> CodamaResult<TokenStream> {
+    match &input.data {
+        syn::Data::Struct(data) => expand_struct(input, data),
+        syn::Data::Enum(data) => expand_enum(input, data),
+        syn::Data::Union(_) => {
+            Err(syn::Error::new_spanned(input, "expected a struct or an enum").into())
+        }
+    }
+}
+
+fn expand_struct(input: &syn::DeriveInput, data: &syn::DataStruct) -> CodamaResult<TokenStream> {
+    let item_name = &input.ident;
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let index = syn::Index::from(index);
+                quote! { #index }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Generic nodes can only walk their children when all of their fields can be walked.
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for field in &data.fields {
+            let field_type = &field.ty;
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #field_type: crate::Visitable });
+        }
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let children = quote! {
+        impl #impl_generics crate::NodeChildren for #item_name #type_generics #where_clause {
+            fn visit_children<'__a, __V: crate::NodeVisitor<'__a> + ?Sized>(&'__a self, visitor: &mut __V) {
+                #(crate::Visitable::visit_with(&self.#fields, visitor);)*
+            }
+
+            fn transform_children<__T: crate::NodeTransformer + ?Sized>(self, transformer: &mut __T) -> Option<Self> {
+                Some(Self {
+                    #(#fields: crate::Visitable::transform_with(self.#fields, transformer)?,)*
+                })
+            }
+        }
+    };
+
+    // Generic nodes are visited through the union that holds them, e.g. `NestedTypeNode`.
+    if !input.generics.params.is_empty() {
+        return Ok(children);
+    }
+
+    let visitable = expand_visitable(item_name, quote! { #item_name });
+    Ok(quote! {
+        #children
+        #visitable
+    })
+}
+
+/// Implements `Visitable` by calling the `visit_*` and `transform_*` methods of the node,
+/// e.g. `visit_struct_type_node` for a `StructTypeNode`.
+pub fn expand_visitable(item_name: &syn::Ident, item_type: TokenStream) -> TokenStream {
+    let snake_case = lowercase_first_letter(&item_name.to_string())
+        .chars()
+        .flat_map(|c| match c.is_uppercase() {
+            true => vec!['_', c.to_ascii_lowercase()],
+            false => vec![c],
+        })
+        .collect::<String>();
+    let visit = format_ident!("visit_{snake_case}");
+    let transform = format_ident!("transform_{snake_case}");
+
+    quote! {
+        impl crate::Visitable for #item_type {
+            fn visit_with<'__a, __V: crate::NodeVisitor<'__a> + ?Sized>(&'__a self, visitor: &mut __V) {
+                visitor.#visit(self)
+            }
+
+            fn transform_with<__T: crate::NodeTransformer + ?Sized>(self, transformer: &mut __T) -> Option<Self> {
+                transformer.#transform(self)
+            }
+        }
+    }
+}
+
+fn expand_enum(input: &syn::DeriveInput, data: &syn::DataEnum) -> CodamaResult<TokenStream> {
+    // Generic unions such as `NestedTypeNode` hold nodes that cannot be visited
+    // on their own, so they walk their children by hand.
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "generic node unions cannot derive Visitable",
+        )
+        .into());
+    }
+
+    let item_name = &input.ident;
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+    let visit_patterns = variants.clone().map(|variant| {
+        quote! { Self::#variant(node) => crate::Visitable::visit_with(node, visitor), }
+    });
+    let transform_patterns = variants.map(|variant| {
+        quote! { Self::#variant(node) => crate::Visitable::transform_with(node, transformer).map(Self::#variant), }
+    });
+
+    let children = quote! {
+        impl crate::NodeChildren for #item_name {
+            fn visit_children<'__a, __V: crate::NodeVisitor<'__a> + ?Sized>(&'__a self, visitor: &mut __V) {
+                match self {
+                    #(#visit_patterns)*
+                }
+            }
+
+            fn transform_children<__T: crate::NodeTransformer + ?Sized>(self, transformer: &mut __T) -> Option<Self> {
+                match self {
+                    #(#transform_patterns)*
+                }
+            }
+        }
+    };
+
+    // Registered unions only list the nodes of a category and
+    // have no method of their own, e.g. `RegisteredTypeNode`.
+    let visitable = match item_name.to_string().starts_with("Registered") {
+        true => quote! {
+            impl crate::Visitable for #item_name {
+                fn visit_with<'__a, __V: crate::NodeVisitor<'__a> + ?Sized>(&'__a self, visitor: &mut __V) {
+                    crate::NodeChildren::visit_children(self, visitor)
+                }
+
+                fn transform_with<__T: crate::NodeTransformer + ?Sized>(self, transformer: &mut __T) -> Option<Self> {
+                    crate::NodeChildren::transform_children(self, transformer)
+                }
+            }
+        },
+        false => expand_visitable(item_name, quote! { #item_name }),
+    };
+
+    Ok(quote! {
+        #children
+        #visitable
+    })
+}
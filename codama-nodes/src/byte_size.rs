@@ -0,0 +1,561 @@
+use crate::{
+    AmountTypeNode, ArrayTypeNode, BooleanTypeNode, ConstantValueNode, CountNode, DateTimeTypeNode,
+    DefinedTypeLinkNode, DefinedTypeNode, EnumTypeNode, EnumVariantTypeNode, FixedSizeTypeNode,
+    HiddenPrefixTypeNode, HiddenSuffixTypeNode, LinkResolver, MapTypeNode, NestedTypeNode,
+    NumberFormat, NumberTypeNode, OptionTypeNode, PostOffsetStrategy, PostOffsetTypeNode,
+    PreOffsetStrategy, PreOffsetTypeNode, RemainderOptionTypeNode, SentinelTypeNode, SetTypeNode,
+    SizePrefixTypeNode, SolAmountTypeNode, StructTypeNode, TupleTypeNode, TypeNode, TypeNodeTrait,
+    TypeNodeUnionTrait, ValueNode, ZeroableOptionTypeNode,
+};
+
+/// Returns the number of bytes used to encode any value of the given type,
+/// or `None` if that number depends on the value itself.
+///
+/// Defined type links are followed using the provided resolver.
+pub fn fixed_size(node: &TypeNode, resolver: &LinkResolver) -> Option<usize> {
+    node.byte_size(&mut ByteSizeContext::new(*resolver, false))
+}
+
+/// Returns the maximum number of bytes used to encode any value of the given
+/// type, or `None` if that number is unbounded.
+///
+/// Defined type links are followed using the provided resolver.
+pub fn max_size(node: &TypeNode, resolver: &LinkResolver) -> Option<usize> {
+    node.byte_size(&mut ByteSizeContext::new(*resolver, true))
+}
+
+struct ByteSizeContext<'a> {
+    resolver: LinkResolver<'a>,
+    /// Whether we are computing the maximum size instead of the fixed size.
+    max: bool,
+    /// Defined types currently being visited, used to detect recursive types.
+    stack: Vec<&'a DefinedTypeNode>,
+}
+
+impl<'a> ByteSizeContext<'a> {
+    fn new(resolver: LinkResolver<'a>, max: bool) -> Self {
+        Self {
+            resolver,
+            max,
+            stack: Vec::new(),
+        }
+    }
+}
+
+trait ByteSize {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize>;
+}
+
+impl<T: ByteSize> ByteSize for Box<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.as_ref().byte_size(context)
+    }
+}
+
+impl ByteSize for TypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        match self {
+            TypeNode::Amount(node) => node.byte_size(context),
+            TypeNode::Array(node) => node.byte_size(context),
+            TypeNode::Boolean(node) => node.byte_size(context),
+            TypeNode::Bytes(_) => None,
+            TypeNode::DateTime(node) => node.byte_size(context),
+            TypeNode::Enum(node) => node.byte_size(context),
+            TypeNode::FixedSize(node) => node.byte_size(context),
+            TypeNode::HiddenPrefix(node) => node.byte_size(context),
+            TypeNode::HiddenSuffix(node) => node.byte_size(context),
+            TypeNode::Map(node) => node.byte_size(context),
+            TypeNode::Number(node) => node.byte_size(context),
+            TypeNode::Option(node) => node.byte_size(context),
+            TypeNode::PostOffset(node) => node.byte_size(context),
+            TypeNode::PreOffset(node) => node.byte_size(context),
+            TypeNode::PublicKey(_) => Some(32),
+            TypeNode::RemainderOption(node) => node.byte_size(context),
+            TypeNode::Sentinel(node) => node.byte_size(context),
+            TypeNode::Set(node) => node.byte_size(context),
+            TypeNode::SizePrefix(node) => node.byte_size(context),
+            TypeNode::SolAmount(node) => node.byte_size(context),
+            TypeNode::String(_) => None,
+            TypeNode::Struct(node) => node.byte_size(context),
+            TypeNode::Tuple(node) => node.byte_size(context),
+            TypeNode::ZeroableOption(node) => node.byte_size(context),
+            TypeNode::Unknown(_) => None,
+            TypeNode::Link(node) => node.byte_size(context),
+        }
+    }
+}
+
+impl<T: TypeNodeTrait + ByteSize> ByteSize for NestedTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        match self {
+            NestedTypeNode::FixedSize(node) => node.byte_size(context),
+            NestedTypeNode::HiddenPrefix(node) => node.byte_size(context),
+            NestedTypeNode::HiddenSuffix(node) => node.byte_size(context),
+            NestedTypeNode::PostOffset(node) => node.byte_size(context),
+            NestedTypeNode::PreOffset(node) => node.byte_size(context),
+            NestedTypeNode::Sentinel(node) => node.byte_size(context),
+            NestedTypeNode::SizePrefix(node) => node.byte_size(context),
+            NestedTypeNode::Value(node) => node.byte_size(context),
+        }
+    }
+}
+
+impl ByteSize for NumberTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        match self.format {
+            NumberFormat::U8 | NumberFormat::I8 => Some(1),
+            NumberFormat::U16 | NumberFormat::I16 => Some(2),
+            NumberFormat::U32 | NumberFormat::I32 | NumberFormat::F32 => Some(4),
+            NumberFormat::U64 | NumberFormat::I64 | NumberFormat::F64 => Some(8),
+            NumberFormat::U128 | NumberFormat::I128 => Some(16),
+            NumberFormat::ShortU16 => context.max.then_some(3),
+        }
+    }
+}
+
+impl ByteSize for AmountTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.number.byte_size(context)
+    }
+}
+
+impl ByteSize for BooleanTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.size.byte_size(context)
+    }
+}
+
+impl ByteSize for DateTimeTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.number.byte_size(context)
+    }
+}
+
+impl ByteSize for SolAmountTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.number.byte_size(context)
+    }
+}
+
+impl ByteSize for ArrayTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let count = fixed_count(&self.count)?;
+        self.item.byte_size(context)?.checked_mul(count)
+    }
+}
+
+impl ByteSize for SetTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let count = fixed_count(&self.count)?;
+        self.item.byte_size(context)?.checked_mul(count)
+    }
+}
+
+impl ByteSize for MapTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let count = fixed_count(&self.count)?;
+        let entry = self
+            .key
+            .byte_size(context)?
+            .checked_add(self.value.byte_size(context)?)?;
+        entry.checked_mul(count)
+    }
+}
+
+impl ByteSize for StructTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        sum(self.fields.iter().map(|field| &field.r#type), context)
+    }
+}
+
+impl ByteSize for TupleTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        sum(self.items.iter(), context)
+    }
+}
+
+impl ByteSize for EnumTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let prefix = self.size.byte_size(context)?;
+        let mut variant_sizes = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            variant_sizes.push(match variant {
+                EnumVariantTypeNode::Empty(_) => 0,
+                EnumVariantTypeNode::Struct(node) => node.r#struct.byte_size(context)?,
+                EnumVariantTypeNode::Tuple(node) => node.tuple.byte_size(context)?,
+            });
+        }
+        let variant_size = match context.max {
+            true => variant_sizes.into_iter().max().unwrap_or(0),
+            false => match variant_sizes.split_first() {
+                None => 0,
+                Some((first, rest)) if rest.iter().all(|size| size == first) => *first,
+                Some(_) => return None,
+            },
+        };
+        prefix.checked_add(variant_size)
+    }
+}
+
+impl ByteSize for OptionTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let prefix = self.prefix.byte_size(context)?;
+        let item = self.item.byte_size(context)?;
+        match context.max || self.fixed == Some(true) || item == 0 {
+            true => prefix.checked_add(item),
+            false => None,
+        }
+    }
+}
+
+impl ByteSize for ZeroableOptionTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        self.item.byte_size(context)
+    }
+}
+
+impl ByteSize for RemainderOptionTypeNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let item = self.item.byte_size(context)?;
+        match context.max || item == 0 {
+            true => Some(item),
+            false => None,
+        }
+    }
+}
+
+impl ByteSize for DefinedTypeLinkNode {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let resolver = match &self.program {
+            Some(program) => context
+                .resolver
+                .with_program(context.resolver.resolve_program(program).ok()?),
+            None => context.resolver,
+        };
+        let defined_type = resolver.resolve_defined_type(self).ok()?;
+        if context
+            .stack
+            .iter()
+            .any(|node| std::ptr::eq(*node, defined_type))
+        {
+            return None;
+        }
+
+        let mut link_context = ByteSizeContext {
+            resolver,
+            max: context.max,
+            stack: context.stack.clone(),
+        };
+        link_context.stack.push(defined_type);
+        defined_type.r#type.byte_size(&mut link_context)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for FixedSizeTypeNode<T> {
+    fn byte_size(&self, _context: &mut ByteSizeContext) -> Option<usize> {
+        Some(self.size)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for SizePrefixTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let prefix = self.prefix.byte_size(context)?;
+        prefix.checked_add(self.r#type.byte_size(context)?)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for HiddenPrefixTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let prefix = sum_constants(&self.prefix, context)?;
+        prefix.checked_add(self.r#type.byte_size(context)?)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for HiddenSuffixTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let suffix = sum_constants(&self.suffix, context)?;
+        suffix.checked_add(self.r#type.byte_size(context)?)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for SentinelTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        if !context.max {
+            return None;
+        }
+        let sentinel = constant_size(&self.sentinel, context)?;
+        sentinel.checked_add(self.r#type.byte_size(context)?)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for PreOffsetTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let size = self.r#type.byte_size(context)?;
+        match self.strategy {
+            PreOffsetStrategy::Padded => add_offset(size, self.offset),
+            _ => Some(size),
+        }
+    }
+}
+
+impl<T: TypeNodeUnionTrait + ByteSize> ByteSize for PostOffsetTypeNode<T> {
+    fn byte_size(&self, context: &mut ByteSizeContext) -> Option<usize> {
+        let size = self.r#type.byte_size(context)?;
+        match self.strategy {
+            PostOffsetStrategy::Padded => add_offset(size, self.offset),
+            _ => Some(size),
+        }
+    }
+}
+
+fn fixed_count(count: &CountNode) -> Option<usize> {
+    match count {
+        CountNode::Fixed(node) => usize::try_from(node.value).ok(),
+        _ => None,
+    }
+}
+
+fn sum<'b, T: ByteSize + 'b>(
+    mut nodes: impl Iterator<Item = &'b T>,
+    context: &mut ByteSizeContext,
+) -> Option<usize> {
+    nodes.try_fold(0usize, |total, node| {
+        total.checked_add(node.byte_size(context)?)
+    })
+}
+
+fn sum_constants(constants: &[ConstantValueNode], context: &mut ByteSizeContext) -> Option<usize> {
+    constants.iter().try_fold(0usize, |total, constant| {
+        total.checked_add(constant_size(constant, context)?)
+    })
+}
+
+/// Constants have a known value so we can also use
+/// the size of their data when their type is variable.
+fn constant_size(constant: &ConstantValueNode, context: &mut ByteSizeContext) -> Option<usize> {
+    let max = std::mem::replace(&mut context.max, false);
+    let size = constant.r#type.byte_size(context);
+    context.max = max;
+    if size.is_some() {
+        return size;
+    }
+    match (constant.r#type.as_ref(), constant.value.as_ref()) {
+        (TypeNode::Bytes(_), ValueNode::Bytes(value)) => value
+            .encoding
+            .decode(&value.data)
+            .ok()
+            .map(|bytes| bytes.len()),
+        (TypeNode::String(node), ValueNode::String(value)) => node
+            .encoding
+            .decode(&value.string)
+            .ok()
+            .map(|bytes| bytes.len()),
+        _ => None,
+    }
+}
+
+fn add_offset(size: usize, offset: i32) -> Option<usize> {
+    size.checked_add_signed(offset as isize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BooleanTypeNode, BytesTypeNode, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode,
+        EnumTupleVariantTypeNode, PrefixedCountNode, ProgramLinkNode, ProgramNode,
+        PublicKeyTypeNode, RootNode, ShortU16, StringTypeNode, StructFieldTypeNode, U128, U16, U32,
+        U64, U8,
+    };
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            defined_types: vec![
+                DefinedTypeNode::new("amount", NumberTypeNode::le(U64)),
+                DefinedTypeNode::new(
+                    "node",
+                    StructTypeNode::new(vec![StructFieldTypeNode::new(
+                        "next",
+                        OptionTypeNode::new(DefinedTypeLinkNode::new("node")),
+                    )]),
+                ),
+            ],
+            ..ProgramNode::new("myProgram", "1111")
+        })
+        .add_program(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new("amount", NumberTypeNode::le(U16))],
+            ..ProgramNode::new("otherProgram", "2222")
+        })
+    }
+
+    fn sizes(node: impl Into<TypeNode>) -> (Option<usize>, Option<usize>) {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root).with_program(&root.program);
+        let node = node.into();
+        (fixed_size(&node, &resolver), max_size(&node, &resolver))
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(sizes(NumberTypeNode::le(U8)), (Some(1), Some(1)));
+        assert_eq!(sizes(NumberTypeNode::le(U64)), (Some(8), Some(8)));
+        assert_eq!(sizes(NumberTypeNode::le(U128)), (Some(16), Some(16)));
+        assert_eq!(sizes(NumberTypeNode::le(ShortU16)), (None, Some(3)));
+    }
+
+    #[test]
+    fn variable_leaves() {
+        assert_eq!(sizes(BytesTypeNode::new()), (None, None));
+        assert_eq!(sizes(StringTypeNode::utf8()), (None, None));
+        assert_eq!(sizes(PublicKeyTypeNode::new()), (Some(32), Some(32)));
+        assert_eq!(sizes(BooleanTypeNode::default()), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn fixed_size_type() {
+        let node = FixedSizeTypeNode::<TypeNode>::new(StringTypeNode::utf8(), 42);
+        assert_eq!(sizes(node), (Some(42), Some(42)));
+    }
+
+    #[test]
+    fn size_prefix_type() {
+        let node = SizePrefixTypeNode::<TypeNode>::new(
+            FixedSizeTypeNode::<TypeNode>::new(BytesTypeNode::new(), 10),
+            NumberTypeNode::le(U32),
+        );
+        assert_eq!(sizes(node), (Some(14), Some(14)));
+        let node =
+            SizePrefixTypeNode::<TypeNode>::new(StringTypeNode::utf8(), NumberTypeNode::le(U32));
+        assert_eq!(sizes(node), (None, None));
+    }
+
+    #[test]
+    fn offset_types() {
+        let node = PreOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U32), 4);
+        assert_eq!(sizes(node), (Some(8), Some(8)));
+        let node = PreOffsetTypeNode::<TypeNode>::relative(NumberTypeNode::le(U32), 4);
+        assert_eq!(sizes(node), (Some(4), Some(4)));
+        let node = PostOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U32), -2);
+        assert_eq!(sizes(node), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn hidden_prefix_type() {
+        let node = HiddenPrefixTypeNode::<TypeNode>::new(
+            NumberTypeNode::le(U64),
+            vec![
+                ConstantValueNode::bytes(crate::BytesEncoding::Base16, "ffff"),
+                ConstantValueNode::new(NumberTypeNode::le(U32), crate::NumberValueNode::new(1u32)),
+            ],
+        );
+        assert_eq!(sizes(node), (Some(14), Some(14)));
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(
+            sizes(ArrayTypeNode::fixed(NumberTypeNode::le(U16), 5)),
+            (Some(10), Some(10))
+        );
+        assert_eq!(
+            sizes(ArrayTypeNode::new(
+                NumberTypeNode::le(U16),
+                PrefixedCountNode::new(NumberTypeNode::le(U32))
+            )),
+            (None, None)
+        );
+        assert_eq!(
+            sizes(ArrayTypeNode::remainder(NumberTypeNode::le(U16))),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn struct_and_tuple() {
+        let node = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("a", NumberTypeNode::le(U8)),
+            StructFieldTypeNode::new("b", PublicKeyTypeNode::new()),
+        ]);
+        assert_eq!(sizes(node), (Some(33), Some(33)));
+        let node = TupleTypeNode::new(vec![
+            NumberTypeNode::le(U8).into(),
+            StringTypeNode::utf8().into(),
+        ]);
+        assert_eq!(sizes(node), (None, None));
+    }
+
+    #[test]
+    fn enums() {
+        let scalar = EnumTypeNode::new(vec![
+            EnumEmptyVariantTypeNode::new("a").into(),
+            EnumEmptyVariantTypeNode::new("b").into(),
+        ]);
+        assert_eq!(sizes(scalar), (Some(1), Some(1)));
+
+        let data = EnumTypeNode::new(vec![
+            EnumEmptyVariantTypeNode::new("a").into(),
+            EnumTupleVariantTypeNode::new(
+                "b",
+                TupleTypeNode::new(vec![NumberTypeNode::le(U32).into()]),
+            )
+            .into(),
+            EnumStructVariantTypeNode::new(
+                "c",
+                StructTypeNode::new(vec![StructFieldTypeNode::new("x", NumberTypeNode::le(U64))]),
+            )
+            .into(),
+        ]);
+        assert_eq!(sizes(data), (None, Some(9)));
+
+        let same_size = EnumTypeNode::new(vec![
+            EnumTupleVariantTypeNode::new(
+                "a",
+                TupleTypeNode::new(vec![NumberTypeNode::le(U32).into()]),
+            )
+            .into(),
+            EnumTupleVariantTypeNode::new(
+                "b",
+                TupleTypeNode::new(vec![NumberTypeNode::le(U32).into()]),
+            )
+            .into(),
+        ]);
+        assert_eq!(sizes(same_size), (Some(5), Some(5)));
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(
+            sizes(OptionTypeNode::new(NumberTypeNode::le(U64))),
+            (None, Some(9))
+        );
+        assert_eq!(
+            sizes(OptionTypeNode::fixed(NumberTypeNode::le(U64))),
+            (Some(9), Some(9))
+        );
+        assert_eq!(
+            sizes(ZeroableOptionTypeNode::new(PublicKeyTypeNode::new())),
+            (Some(32), Some(32))
+        );
+        assert_eq!(
+            sizes(RemainderOptionTypeNode::new(PublicKeyTypeNode::new())),
+            (None, Some(32))
+        );
+    }
+
+    #[test]
+    fn defined_type_links() {
+        assert_eq!(
+            sizes(DefinedTypeLinkNode::new("amount")),
+            (Some(8), Some(8))
+        );
+        assert_eq!(
+            sizes(DefinedTypeLinkNode::new_from_program(
+                "amount",
+                ProgramLinkNode::new("otherProgram")
+            )),
+            (Some(2), Some(2))
+        );
+        assert_eq!(sizes(DefinedTypeLinkNode::new("missing")), (None, None));
+        assert_eq!(sizes(DefinedTypeLinkNode::new("node")), (None, None));
+    }
+}
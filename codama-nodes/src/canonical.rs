@@ -0,0 +1,228 @@
+use crate::RootNode;
+use codama_errors::CodamaResult;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalOptions {
+    /// Sort the accounts, instructions, defined types, PDAs, events and constants of every
+    /// program by name, errors by code and additional programs by name. Items whose order
+    /// carries meaning, such as struct fields or instruction accounts, are never sorted.
+    pub sort_program_items: bool,
+}
+
+/// Serializes a `RootNode` into a compact JSON string that only depends on its content:
+/// object keys are sorted, docs are normalized and, optionally, program items are sorted.
+pub fn to_canonical_json(root: &RootNode, options: CanonicalOptions) -> CodamaResult<String> {
+    let mut value = serde_json::to_value(root)?;
+    normalize(&mut value, options);
+    let mut json = String::new();
+    write_value(&value, &mut json)?;
+    Ok(json)
+}
+
+/// The hex-encoded SHA-256 digest of the canonical JSON of a `RootNode`.
+pub fn content_hash(root: &RootNode, options: CanonicalOptions) -> CodamaResult<String> {
+    let digest = Sha256::digest(to_canonical_json(root, options)?);
+    Ok(digest.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn normalize(value: &mut Value, options: CanonicalOptions) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Array(docs)) = map.get_mut("docs") {
+                normalize_docs(docs);
+                if docs.is_empty() {
+                    map.remove("docs");
+                }
+            }
+            if options.sort_program_items {
+                sort_items(map);
+            }
+            map.values_mut().for_each(|value| normalize(value, options));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| normalize(item, options)),
+        _ => {}
+    }
+}
+
+/// Uses `\n` line endings, removes trailing whitespace
+/// and drops empty lines at the start and end of the docs.
+fn normalize_docs(docs: &mut Vec<Value>) {
+    for doc in docs.iter_mut() {
+        if let Value::String(string) = doc {
+            *string = string
+                .replace("\r\n", "\n")
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+    let is_empty = |doc: &Value| doc.as_str().is_some_and(str::is_empty);
+    while docs.last().is_some_and(is_empty) {
+        docs.pop();
+    }
+    let leading = docs.iter().take_while(|doc| is_empty(doc)).count();
+    docs.drain(..leading);
+}
+
+fn sort_items(map: &mut serde_json::Map<String, Value>) {
+    let keys: &[(&str, &str)] = match map.get("kind").and_then(Value::as_str) {
+        Some("rootNode") => &[("additionalPrograms", "name")],
+        Some("programNode") => &[
+            ("accounts", "name"),
+            ("instructions", "name"),
+            ("definedTypes", "name"),
+            ("pdas", "name"),
+            ("events", "name"),
+            ("errors", "code"),
+            ("constants", "name"),
+        ],
+        _ => return,
+    };
+    for (key, sort_key) in keys {
+        if let Some(Value::Array(items)) = map.get_mut(*key) {
+            items.sort_by(|a, b| compare(&a[sort_key], &b[sort_key]));
+        }
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(std::cmp::Ordering::Equal),
+        _ => a.as_str().cmp(&b.as_str()),
+    }
+}
+
+/// Writes objects with sorted keys regardless of the
+/// key ordering of `serde_json::Map` for this build.
+fn write_value(value: &Value, json: &mut String) -> CodamaResult<()> {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            json.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                json.push_str(&serde_json::to_string(key)?);
+                json.push(':');
+                write_value(value, json)?;
+            }
+            json.push('}');
+        }
+        Value::Array(items) => {
+            json.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                write_value(item, json)?;
+            }
+            json.push(']');
+        }
+        value => json.push_str(&serde_json::to_string(value)?),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountNode, Docs, ErrorNode, NodeTrait, ProgramNode, StructTypeNode};
+    use pretty_assertions::assert_eq;
+
+    fn get_root(accounts: &[&str], errors: &[u32]) -> RootNode {
+        let mut program = ProgramNode::new("myProgram", "1111");
+        for name in accounts {
+            program = program.add_account(AccountNode::new(*name, StructTypeNode::new(vec![])));
+        }
+        for code in errors {
+            program = program.add_error(ErrorNode::new(format!("error{code}"), *code, ""));
+        }
+        RootNode::new(program)
+    }
+
+    #[test]
+    fn sorted_keys() {
+        let root = RootNode::new(ProgramNode::new("myProgram", "1111"));
+        assert_eq!(
+            to_canonical_json(&root, CanonicalOptions::default()).unwrap(),
+            format!(
+                r#"{{"kind":"rootNode","program":{{"kind":"programNode","name":"myProgram","publicKey":"1111","version":"0.0.0"}},"standard":"codama","version":"{}"}}"#,
+                root.version
+            )
+        );
+        assert_eq!(
+            RootNode::from_json(&to_canonical_json(&root, CanonicalOptions::default()).unwrap())
+                .unwrap(),
+            root
+        );
+    }
+
+    #[test]
+    fn normalized_docs() {
+        let get_program = |docs: Vec<&str>| {
+            let docs = docs.into_iter().map(String::from).collect::<Vec<_>>();
+            RootNode::new(ProgramNode {
+                docs: Docs::from(docs),
+                ..ProgramNode::new("myProgram", "1111")
+            })
+        };
+        let options = CanonicalOptions::default();
+        let hash = content_hash(&get_program(vec!["Hello.", "World."]), options).unwrap();
+        assert_eq!(
+            content_hash(
+                &get_program(vec!["", "Hello.  ", "World.\r\n", ""]),
+                options
+            )
+            .unwrap(),
+            hash
+        );
+        assert_ne!(
+            content_hash(&get_program(vec!["World.", "Hello."]), options).unwrap(),
+            hash
+        );
+        assert_eq!(
+            content_hash(&get_program(vec!["", ""]), options).unwrap(),
+            content_hash(&get_program(vec![]), options).unwrap()
+        );
+    }
+
+    #[test]
+    fn sorted_program_items() {
+        let a = get_root(&["mint", "token"], &[1, 0]);
+        let b = get_root(&["token", "mint"], &[0, 1]);
+        let default = CanonicalOptions::default();
+        assert_ne!(
+            content_hash(&a, default).unwrap(),
+            content_hash(&b, default).unwrap()
+        );
+
+        let sorted = CanonicalOptions {
+            sort_program_items: true,
+        };
+        assert_eq!(
+            content_hash(&a, sorted).unwrap(),
+            content_hash(&b, sorted).unwrap()
+        );
+        let json = to_canonical_json(&b, sorted).unwrap();
+        assert!(json.find(r#""name":"mint""#) < json.find(r#""name":"token""#));
+    }
+
+    #[test]
+    fn hash_format() {
+        let hash = content_hash(&get_root(&[], &[]), CanonicalOptions::default()).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(
+            hash,
+            content_hash(&get_root(&["mint"], &[]), CanonicalOptions::default()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,552 @@
+use super::json::MAX_SAFE_INTEGER;
+use crate::{
+    AccountNode, BytesEncoding, CountNode, DefinedTypeLinkNode, DefinedTypeNode, Docs,
+    EnumTypeNode, EnumVariantTypeNode, HasName, InstructionNode, JsonSchema, LinkResolver,
+    NestedTypeNodeTrait, NumberFormat, StructTypeNode, TupleTypeNode, TypeNode,
+};
+use codama_errors::CodamaResult;
+use serde_json::{json, Map, Value};
+
+const PUBLIC_KEY_PATTERN: &str = "^[1-9A-HJ-NP-Za-km-z]{32,44}$";
+
+/// A standalone JSON Schema describing values of the given type as produced by
+/// `value_to_json`. Linked defined types are added to the `$defs` of the document.
+pub fn type_json_schema(r#type: &TypeNode, resolver: &LinkResolver) -> CodamaResult<Value> {
+    let mut builder = DataSchemaBuilder::new(*resolver);
+    let schema = builder.r#type(r#type)?;
+    Ok(builder.document(schema, None, &Docs::default()))
+}
+
+/// A standalone JSON Schema describing values of the given defined type.
+pub fn defined_type_json_schema(
+    defined_type: &DefinedTypeNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<Value> {
+    let mut builder = DataSchemaBuilder::new(*resolver);
+    let schema = builder.r#type(&defined_type.r#type)?;
+    Ok(builder.document(schema, Some(&defined_type.name), &defined_type.docs))
+}
+
+/// A standalone JSON Schema describing the decoded data of the given account.
+pub fn account_json_schema(account: &AccountNode, resolver: &LinkResolver) -> CodamaResult<Value> {
+    let mut builder = DataSchemaBuilder::new(*resolver);
+    let schema = builder.r#struct(account.data.get_nested_type_node())?;
+    Ok(builder.document(schema, Some(&account.name), &account.docs))
+}
+
+/// A standalone JSON Schema describing the decoded arguments of the given instruction.
+pub fn instruction_json_schema(
+    instruction: &InstructionNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<Value> {
+    let mut builder = DataSchemaBuilder::new(resolver.with_instruction(instruction));
+    let fields = instruction.arguments.iter().map(|argument| {
+        (
+            argument.name.as_str(),
+            argument.r#type.as_ref(),
+            &argument.docs,
+        )
+    });
+    let schema = builder.object(fields)?;
+    Ok(builder.document(schema, Some(&instruction.name), &instruction.docs))
+}
+
+struct DataSchemaBuilder<'a> {
+    resolver: LinkResolver<'a>,
+    definitions: Map<String, Value>,
+}
+
+impl<'a> DataSchemaBuilder<'a> {
+    fn new(resolver: LinkResolver<'a>) -> Self {
+        Self {
+            resolver,
+            definitions: Map::new(),
+        }
+    }
+
+    fn document(self, schema: Value, title: Option<&str>, docs: &Docs) -> Value {
+        let mut document = Map::new();
+        document.insert(
+            "$schema".to_string(),
+            "https://json-schema.org/draft/2020-12/schema".into(),
+        );
+        if let Some(title) = title {
+            document.insert("title".to_string(), title.into());
+        }
+        if let Value::Object(schema) = with_description(schema, docs) {
+            document.extend(schema);
+        }
+        if !self.definitions.is_empty() {
+            document.insert("$defs".to_string(), self.definitions.into());
+        }
+        Value::Object(document)
+    }
+
+    fn r#type(&mut self, r#type: &TypeNode) -> CodamaResult<Value> {
+        Ok(match r#type {
+            TypeNode::Amount(node) => number(node.number.get_nested_type_node().format),
+            TypeNode::DateTime(node) => number(node.number.get_nested_type_node().format),
+            TypeNode::SolAmount(node) => number(node.number.get_nested_type_node().format),
+            TypeNode::Number(node) => number(node.format),
+            TypeNode::Boolean(_) => json!({ "type": "boolean" }),
+            TypeNode::String(_) => json!({ "type": "string" }),
+            TypeNode::Bytes(_) => json!({
+                "type": "object",
+                "properties": {
+                    "encoding": BytesEncoding::json_schema(&mut Map::new()),
+                    "data": { "type": "string" },
+                },
+                "required": ["encoding", "data"],
+                "additionalProperties": false,
+            }),
+            TypeNode::PublicKey(_) => json!({ "type": "string", "pattern": PUBLIC_KEY_PATTERN }),
+            TypeNode::Option(node) => self.option(&node.item)?,
+            TypeNode::ZeroableOption(node) => self.option(&node.item)?,
+            TypeNode::RemainderOption(node) => self.option(&node.item)?,
+            TypeNode::Array(node) => self.items(&node.item, &node.count, false)?,
+            TypeNode::Set(node) => self.items(&node.item, &node.count, true)?,
+            TypeNode::Map(node) => self.map(&node.key, &node.value)?,
+            TypeNode::Tuple(node) => self.tuple(node)?,
+            TypeNode::Struct(node) => self.r#struct(node)?,
+            TypeNode::Enum(node) => self.r#enum(node)?,
+            TypeNode::FixedSize(node) => self.r#type(&node.r#type)?,
+            TypeNode::HiddenPrefix(node) => self.r#type(&node.r#type)?,
+            TypeNode::HiddenSuffix(node) => self.r#type(&node.r#type)?,
+            TypeNode::PostOffset(node) => self.r#type(&node.r#type)?,
+            TypeNode::PreOffset(node) => self.r#type(&node.r#type)?,
+            TypeNode::Sentinel(node) => self.r#type(&node.r#type)?,
+            TypeNode::SizePrefix(node) => self.r#type(&node.r#type)?,
+            TypeNode::Unknown(_) => json!({}),
+            TypeNode::Link(link) => self.link(link)?,
+        })
+    }
+
+    /// Defines linked types once in `$defs` and references them, which also supports recursive types.
+    fn link(&mut self, link: &DefinedTypeLinkNode) -> CodamaResult<Value> {
+        let resolver = match &link.program {
+            Some(program) => self
+                .resolver
+                .with_program(self.resolver.resolve_program(program)?),
+            None => self.resolver,
+        };
+        let defined_type = resolver.resolve_defined_type(link)?;
+        let name = match &link.program {
+            Some(program) => format!("{}.{}", program.name.as_str(), link.name.as_str()),
+            None => link.name.to_string(),
+        };
+
+        if !self.definitions.contains_key(&name) {
+            self.definitions.insert(name.clone(), Value::Bool(true));
+            let parent_resolver = self.resolver;
+            self.resolver = resolver;
+            let schema = self.r#type(&defined_type.r#type);
+            self.resolver = parent_resolver;
+            let schema = with_description(schema?, &defined_type.docs);
+            self.definitions.insert(name.clone(), schema);
+        }
+        Ok(json!({ "$ref": format!("#/$defs/{name}") }))
+    }
+
+    fn option(&mut self, item: &TypeNode) -> CodamaResult<Value> {
+        Ok(json!({ "anyOf": [self.r#type(item)?, { "type": "null" }] }))
+    }
+
+    fn items(&mut self, item: &TypeNode, count: &CountNode, unique: bool) -> CodamaResult<Value> {
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), "array".into());
+        schema.insert("items".to_string(), self.r#type(item)?);
+        if let CountNode::Fixed(count) = count {
+            schema.insert("minItems".to_string(), count.value.into());
+            schema.insert("maxItems".to_string(), count.value.into());
+        }
+        if unique {
+            schema.insert("uniqueItems".to_string(), true.into());
+        }
+        Ok(Value::Object(schema))
+    }
+
+    /// Maps are objects when their keys are written as strings or numbers
+    /// and arrays of `[key, value]` pairs otherwise, except when empty.
+    fn map(&mut self, key: &TypeNode, value: &TypeNode) -> CodamaResult<Value> {
+        let value_schema = self.r#type(value)?;
+        if self.has_scalar_json(key)? {
+            return Ok(json!({ "type": "object", "additionalProperties": value_schema }));
+        }
+        let key_schema = self.r#type(key)?;
+        Ok(json!({
+            "anyOf": [
+                {
+                    "type": "array",
+                    "items": {
+                        "type": "array",
+                        "prefixItems": [key_schema, value_schema],
+                        "items": false,
+                        "minItems": 2,
+                    },
+                },
+                { "type": "object", "maxProperties": 0 },
+            ],
+        }))
+    }
+
+    fn tuple(&mut self, node: &TupleTypeNode) -> CodamaResult<Value> {
+        let items = node
+            .items
+            .iter()
+            .map(|item| self.r#type(item))
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(json!({
+            "type": "array",
+            "prefixItems": items,
+            "items": false,
+            "minItems": node.items.len(),
+            "maxItems": node.items.len(),
+        }))
+    }
+
+    fn r#struct(&mut self, node: &StructTypeNode) -> CodamaResult<Value> {
+        let fields = node
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.r#type.as_ref(), &field.docs));
+        self.object(fields)
+    }
+
+    /// All fields are required since decoded values always include every field.
+    fn object<'b>(
+        &mut self,
+        fields: impl Iterator<Item = (&'b str, &'b TypeNode, &'b Docs)>,
+    ) -> CodamaResult<Value> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for (name, r#type, docs) in fields {
+            let schema = self.r#type(r#type)?;
+            properties.insert(name.to_string(), with_description(schema, docs));
+            required.push(Value::from(name));
+        }
+        Ok(json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        }))
+    }
+
+    fn r#enum(&mut self, node: &EnumTypeNode) -> CodamaResult<Value> {
+        let variants = node
+            .variants
+            .iter()
+            .map(|variant| {
+                let payload = match variant {
+                    EnumVariantTypeNode::Empty(_) => {
+                        return Ok(json!({ "const": variant.name().as_str() }))
+                    }
+                    EnumVariantTypeNode::Struct(variant) => {
+                        self.r#struct(variant.r#struct.get_nested_type_node())?
+                    }
+                    EnumVariantTypeNode::Tuple(variant) => {
+                        self.tuple(variant.tuple.get_nested_type_node())?
+                    }
+                };
+                Ok(json!({
+                    "type": "object",
+                    "properties": { variant.name().as_str(): payload },
+                    "required": [variant.name().as_str()],
+                    "additionalProperties": false,
+                }))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(json!({ "oneOf": variants }))
+    }
+
+    /// Whether values of the type are written as JSON strings or numbers.
+    fn has_scalar_json(&self, r#type: &TypeNode) -> CodamaResult<bool> {
+        Ok(match r#type {
+            TypeNode::Amount(_)
+            | TypeNode::DateTime(_)
+            | TypeNode::SolAmount(_)
+            | TypeNode::Number(_)
+            | TypeNode::String(_)
+            | TypeNode::PublicKey(_) => true,
+            TypeNode::Enum(node) => node
+                .variants
+                .iter()
+                .all(|variant| matches!(variant, EnumVariantTypeNode::Empty(_))),
+            TypeNode::FixedSize(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::HiddenPrefix(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::HiddenSuffix(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::PostOffset(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::PreOffset(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::Sentinel(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::SizePrefix(node) => self.has_scalar_json(&node.r#type)?,
+            TypeNode::Link(link) => {
+                let resolver = match &link.program {
+                    Some(program) => self
+                        .resolver
+                        .with_program(self.resolver.resolve_program(program)?),
+                    None => self.resolver,
+                };
+                let defined_type = resolver.resolve_defined_type(link)?;
+                DataSchemaBuilder::new(resolver).has_scalar_json(&defined_type.r#type)?
+            }
+            _ => false,
+        })
+    }
+}
+
+fn number(format: NumberFormat) -> Value {
+    let (min, max): (i64, u64) = match format {
+        NumberFormat::F32 | NumberFormat::F64 => return json!({ "type": "number" }),
+        NumberFormat::U64 | NumberFormat::U128 => {
+            return large_integer(0, "^[0-9]+$");
+        }
+        NumberFormat::I64 | NumberFormat::I128 => {
+            return large_integer(-(MAX_SAFE_INTEGER as i64), "^-?[0-9]+$");
+        }
+        NumberFormat::U8 => (0, u8::MAX as u64),
+        NumberFormat::U16 | NumberFormat::ShortU16 => (0, u16::MAX as u64),
+        NumberFormat::U32 => (0, u32::MAX as u64),
+        NumberFormat::I8 => (i8::MIN as i64, i8::MAX as u64),
+        NumberFormat::I16 => (i16::MIN as i64, i16::MAX as u64),
+        NumberFormat::I32 => (i32::MIN as i64, i32::MAX as u64),
+    };
+    json!({ "type": "integer", "minimum": min, "maximum": max })
+}
+
+/// Integers beyond 2^53 are written as strings, see `value_to_json`.
+fn large_integer(min: i64, pattern: &str) -> Value {
+    json!({
+        "anyOf": [
+            { "type": "integer", "minimum": min, "maximum": MAX_SAFE_INTEGER },
+            { "type": "string", "pattern": pattern },
+        ]
+    })
+}
+
+fn with_description(mut schema: Value, docs: &Docs) -> Value {
+    if let (Value::Object(object), false) = (&mut schema, docs.is_empty()) {
+        object.insert("description".to_string(), docs.join("\n").into());
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArrayTypeNode, BooleanTypeNode, BytesTypeNode, EnumEmptyVariantTypeNode,
+        EnumStructVariantTypeNode, EnumTupleVariantTypeNode, InstructionArgumentNode, MapTypeNode,
+        NumberTypeNode, OptionTypeNode, ProgramNode, PublicKeyTypeNode, RootNode, SetTypeNode,
+        StringTypeNode, StructFieldTypeNode, I64, U16, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn get_root() -> RootNode {
+        let direction = EnumTypeNode::new(vec![
+            EnumEmptyVariantTypeNode::new("up").into(),
+            EnumStructVariantTypeNode::new(
+                "jump",
+                StructTypeNode::new(vec![StructFieldTypeNode::new(
+                    "height",
+                    NumberTypeNode::le(U8),
+                )]),
+            )
+            .into(),
+            EnumTupleVariantTypeNode::new(
+                "move",
+                TupleTypeNode::new(vec![NumberTypeNode::le(U8).into()]),
+            )
+            .into(),
+        ]);
+        let tree = StructTypeNode::new(vec![StructFieldTypeNode::new(
+            "children",
+            ArrayTypeNode::prefixed(DefinedTypeLinkNode::new("tree"), NumberTypeNode::le(U32)),
+        )]);
+        RootNode::new(
+            ProgramNode::new("game", "1111")
+                .add_defined_type(DefinedTypeNode {
+                    docs: vec!["A direction.".to_string()].into(),
+                    ..DefinedTypeNode::new("direction", direction)
+                })
+                .add_defined_type(DefinedTypeNode::new("tree", tree))
+                .add_account(AccountNode::new(
+                    "player",
+                    StructTypeNode::new(vec![
+                        StructFieldTypeNode::new("owner", PublicKeyTypeNode::new()),
+                        StructFieldTypeNode::new("score", NumberTypeNode::le(U64)),
+                        StructFieldTypeNode::new(
+                            "direction",
+                            OptionTypeNode::new(DefinedTypeLinkNode::new("direction")),
+                        ),
+                    ]),
+                ))
+                .add_instruction(InstructionNode {
+                    name: "play".into(),
+                    arguments: vec![
+                        InstructionArgumentNode::new("level", NumberTypeNode::le(U16)),
+                        InstructionArgumentNode::new("delta", NumberTypeNode::le(I64)),
+                    ],
+                    ..InstructionNode::default()
+                }),
+        )
+    }
+
+    #[test]
+    fn scalars() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let schema = |r#type: TypeNode| {
+            let mut schema = type_json_schema(&r#type, &resolver).unwrap();
+            schema.as_object_mut().unwrap().remove("$schema");
+            schema
+        };
+        assert_eq!(
+            schema(NumberTypeNode::le(U8).into()),
+            json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+        );
+        assert_eq!(
+            schema(NumberTypeNode::le(U64).into()),
+            json!({
+                "anyOf": [
+                    { "type": "integer", "minimum": 0, "maximum": 9007199254740991u64 },
+                    { "type": "string", "pattern": "^[0-9]+$" },
+                ]
+            })
+        );
+        assert_eq!(
+            schema(PublicKeyTypeNode::new().into()),
+            json!({ "type": "string", "pattern": PUBLIC_KEY_PATTERN })
+        );
+        assert_eq!(
+            schema(BooleanTypeNode::default().into()),
+            json!({ "type": "boolean" })
+        );
+        assert_eq!(
+            schema(BytesTypeNode::new().into())["properties"]["encoding"],
+            json!({ "enum": ["base16", "base58", "base64", "utf8"] })
+        );
+        assert_eq!(
+            schema(SetTypeNode::fixed(StringTypeNode::utf8(), 2).into()),
+            json!({
+                "type": "array",
+                "items": { "type": "string" },
+                "minItems": 2,
+                "maxItems": 2,
+                "uniqueItems": true,
+            })
+        );
+        assert_eq!(
+            schema(
+                MapTypeNode::prefixed(
+                    StringTypeNode::utf8(),
+                    NumberTypeNode::le(U8),
+                    NumberTypeNode::le(U32)
+                )
+                .into()
+            ),
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "integer", "minimum": 0, "maximum": 255 },
+            })
+        );
+    }
+
+    #[test]
+    fn accounts() {
+        let root = get_root();
+        let schema =
+            account_json_schema(&root.program.accounts[0], &LinkResolver::new(&root)).unwrap();
+        assert_eq!(schema["title"], "player");
+        assert_eq!(schema["required"], json!(["owner", "score", "direction"]));
+        assert_eq!(
+            schema["properties"]["direction"],
+            json!({ "anyOf": [{ "$ref": "#/$defs/direction" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            schema["$defs"]["direction"],
+            json!({
+                "description": "A direction.",
+                "oneOf": [
+                    { "const": "up" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "jump": {
+                                "type": "object",
+                                "properties": {
+                                    "height": { "type": "integer", "minimum": 0, "maximum": 255 },
+                                },
+                                "required": ["height"],
+                                "additionalProperties": false,
+                            },
+                        },
+                        "required": ["jump"],
+                        "additionalProperties": false,
+                    },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "move": {
+                                "type": "array",
+                                "prefixItems": [{ "type": "integer", "minimum": 0, "maximum": 255 }],
+                                "items": false,
+                                "minItems": 1,
+                                "maxItems": 1,
+                            },
+                        },
+                        "required": ["move"],
+                        "additionalProperties": false,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn instructions() {
+        let root = get_root();
+        let schema =
+            instruction_json_schema(&root.program.instructions[0], &LinkResolver::new(&root))
+                .unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "play",
+                "type": "object",
+                "properties": {
+                    "level": { "type": "integer", "minimum": 0, "maximum": 65535 },
+                    "delta": {
+                        "anyOf": [
+                            {
+                                "type": "integer",
+                                "minimum": -9007199254740991i64,
+                                "maximum": 9007199254740991u64,
+                            },
+                            { "type": "string", "pattern": "^-?[0-9]+$" },
+                        ]
+                    },
+                },
+                "required": ["level", "delta"],
+                "additionalProperties": false,
+            })
+        );
+    }
+
+    #[test]
+    fn recursive_defined_types() {
+        let root = get_root();
+        let schema =
+            defined_type_json_schema(&root.program.defined_types[1], &LinkResolver::new(&root))
+                .unwrap();
+        assert_eq!(schema["title"], "tree");
+        assert_eq!(
+            schema["properties"]["children"]["items"],
+            json!({ "$ref": "#/$defs/tree" })
+        );
+        assert_eq!(schema["$defs"]["tree"]["properties"], schema["properties"]);
+
+        let missing = TypeNode::from(DefinedTypeLinkNode::new("missing"));
+        assert!(type_json_schema(&missing, &LinkResolver::new(&root)).is_err());
+    }
+}
@@ -0,0 +1,925 @@
+use crate::{
+    encode, fixed_size, AmountTypeNode, ArrayTypeNode, ArrayValueNode, BooleanTypeNode,
+    BooleanValueNode, BytesEncoding, BytesValueNode, ConstantValueNode, CountNode,
+    DateTimeTypeNode, DefinedTypeLinkNode, Endianness, EnumTypeNode, EnumValueNode,
+    EnumValuePayload, EnumVariantTypeNode, FixedSizeTypeNode, HasKind, HasName,
+    HiddenPrefixTypeNode, HiddenSuffixTypeNode, LinkResolver, MapEntryValueNode, MapTypeNode,
+    MapValueNode, NestedTypeNode, NoneValueNode, Number, NumberFormat, NumberTypeNode,
+    NumberValueNode, OptionTypeNode, PostOffsetStrategy, PostOffsetTypeNode, PreOffsetStrategy,
+    PreOffsetTypeNode, PublicKeyValueNode, RemainderOptionTypeNode, SentinelTypeNode, SetTypeNode,
+    SetValueNode, SizePrefixTypeNode, SolAmountTypeNode, SomeValueNode, StringTypeNode,
+    StringValueNode, StructFieldValueNode, StructTypeNode, StructValueNode, TupleTypeNode,
+    TupleValueNode, TypeNode, TypeNodeTrait, TypeNodeUnionTrait, ValueNode, ZeroableOptionTypeNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+
+/// Decodes a value from the given bytes following the serialization rules of the given type.
+///
+/// Returns the decoded value along with the number of bytes read.
+/// Defined type links are followed using the provided resolver.
+///
+/// Enum value nodes reference the defined type of their enum, so enums must
+/// be reached through a `DefinedTypeLinkNode`. Decoding an inline `EnumTypeNode`,
+/// e.g. the type of a struct field, returns an error.
+pub fn decode(
+    r#type: &TypeNode,
+    bytes: &[u8],
+    resolver: &LinkResolver,
+) -> CodamaResult<(ValueNode, usize)> {
+    let mut decoder = Decoder::new(*resolver, bytes, 0, Vec::new());
+    let value = r#type.decode(&mut decoder)?;
+    Ok((value, decoder.cursor))
+}
+
+struct Decoder<'a, 'b> {
+    resolver: LinkResolver<'a>,
+    bytes: &'b [u8],
+    cursor: usize,
+    /// The offset of `bytes` within the original buffer, used for error reporting.
+    base: usize,
+    /// Segments of the path to the value being decoded, e.g. `.items` and `[2]`.
+    path: Vec<String>,
+}
+
+impl<'a, 'b> Decoder<'a, 'b> {
+    fn new(resolver: LinkResolver<'a>, bytes: &'b [u8], base: usize, path: Vec<String>) -> Self {
+        Self {
+            resolver,
+            bytes,
+            cursor: 0,
+            base,
+            path,
+        }
+    }
+
+    fn remaining(&self) -> &'b [u8] {
+        self.bytes.get(self.cursor..).unwrap_or_default()
+    }
+
+    fn read(&mut self, length: usize) -> CodamaResult<&'b [u8]> {
+        let remaining = self.remaining();
+        if remaining.len() < length {
+            return Err(self.error(format!(
+                "expected {length} bytes, found {}",
+                remaining.len()
+            )));
+        }
+        self.cursor += length;
+        Ok(&remaining[..length])
+    }
+
+    /// Decode a value from the next `length` bytes only.
+    fn decode_separately<T: Decode + ?Sized>(
+        &mut self,
+        r#type: &T,
+        length: usize,
+    ) -> CodamaResult<ValueNode> {
+        let base = self.base + self.cursor;
+        let bytes = self.read(length)?;
+        let mut decoder = Decoder::new(self.resolver, bytes, base, self.path.clone());
+        r#type.decode(&mut decoder)
+    }
+
+    fn seek(&mut self, position: i64) -> CodamaResult<()> {
+        self.cursor = usize::try_from(position)
+            .map_err(|_| self.error(format!("cannot move to negative offset {position}")))?;
+        Ok(())
+    }
+
+    fn with_segment<T>(
+        &mut self,
+        segment: String,
+        f: impl FnOnce(&mut Self) -> CodamaResult<T>,
+    ) -> CodamaResult<T> {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn error<T: Into<String>>(&self, message: T) -> CodamaError {
+        CodamaError::DecodingFailed {
+            offset: self.base + self.cursor,
+            path: format!("${}", self.path.concat()),
+            message: message.into(),
+        }
+    }
+}
+
+trait Decode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode>;
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        self.as_ref().decode(decoder)
+    }
+}
+
+impl Decode for TypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        match self {
+            TypeNode::Amount(node) => node.decode(decoder),
+            TypeNode::Array(node) => node.decode(decoder),
+            TypeNode::Boolean(node) => node.decode(decoder),
+            TypeNode::Bytes(_) => {
+                let bytes = decoder.read(decoder.remaining().len())?;
+                let data = encode_data(BytesEncoding::Base16, bytes, decoder)?;
+                Ok(BytesValueNode::base16(data).into())
+            }
+            TypeNode::DateTime(node) => node.decode(decoder),
+            TypeNode::Enum(node) => decode_enum(node, None, decoder),
+            TypeNode::FixedSize(node) => node.decode(decoder),
+            TypeNode::HiddenPrefix(node) => node.decode(decoder),
+            TypeNode::HiddenSuffix(node) => node.decode(decoder),
+            TypeNode::Map(node) => node.decode(decoder),
+            TypeNode::Number(node) => node.decode(decoder),
+            TypeNode::Option(node) => node.decode(decoder),
+            TypeNode::PostOffset(node) => node.decode(decoder),
+            TypeNode::PreOffset(node) => node.decode(decoder),
+            TypeNode::PublicKey(_) => {
+                let bytes = decoder.read(32)?;
+                let public_key = encode_data(BytesEncoding::Base58, bytes, decoder)?;
+                Ok(PublicKeyValueNode::new(public_key).into())
+            }
+            TypeNode::RemainderOption(node) => node.decode(decoder),
+            TypeNode::Sentinel(node) => node.decode(decoder),
+            TypeNode::Set(node) => node.decode(decoder),
+            TypeNode::SizePrefix(node) => node.decode(decoder),
+            TypeNode::SolAmount(node) => node.decode(decoder),
+            TypeNode::String(node) => node.decode(decoder),
+            TypeNode::Struct(node) => node.decode(decoder),
+            TypeNode::Tuple(node) => node.decode(decoder),
+            TypeNode::ZeroableOption(node) => node.decode(decoder),
+            TypeNode::Unknown(node) => {
+                Err(decoder.error(format!("cannot decode unknown type `{}`", node.kind)))
+            }
+            TypeNode::Link(node) => node.decode(decoder),
+        }
+    }
+}
+
+impl<T: TypeNodeTrait + Decode> Decode for NestedTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        match self {
+            NestedTypeNode::FixedSize(node) => node.decode(decoder),
+            NestedTypeNode::HiddenPrefix(node) => node.decode(decoder),
+            NestedTypeNode::HiddenSuffix(node) => node.decode(decoder),
+            NestedTypeNode::PostOffset(node) => node.decode(decoder),
+            NestedTypeNode::PreOffset(node) => node.decode(decoder),
+            NestedTypeNode::Sentinel(node) => node.decode(decoder),
+            NestedTypeNode::SizePrefix(node) => node.decode(decoder),
+            NestedTypeNode::Value(node) => node.decode(decoder),
+        }
+    }
+}
+
+impl Decode for NumberTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let number = decode_number(self.format, self.endian, decoder)?;
+        Ok(NumberValueNode::new(number).into())
+    }
+}
+
+impl Decode for AmountTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        self.number.decode(decoder)
+    }
+}
+
+impl Decode for DateTimeTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        self.number.decode(decoder)
+    }
+}
+
+impl Decode for SolAmountTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        self.number.decode(decoder)
+    }
+}
+
+impl Decode for BooleanTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let start = decoder.cursor;
+        match decode_integer(&self.size, decoder)? {
+            0 => Ok(BooleanValueNode::new(false).into()),
+            1 => Ok(BooleanValueNode::new(true).into()),
+            value => {
+                decoder.cursor = start;
+                Err(decoder.error(format!("invalid boolean value {value}")))
+            }
+        }
+    }
+}
+
+impl Decode for StringTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let bytes = decoder.read(decoder.remaining().len())?;
+        let string = encode_data(self.encoding, bytes, decoder)?;
+        Ok(StringValueNode::new(string).into())
+    }
+}
+
+impl Decode for ArrayTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let items = decode_items(self.item.as_ref(), &self.count, decoder, |decoder| {
+            self.item.decode(decoder)
+        })?;
+        Ok(ArrayValueNode::new(items).into())
+    }
+}
+
+impl Decode for SetTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let items = decode_items(self.item.as_ref(), &self.count, decoder, |decoder| {
+            self.item.decode(decoder)
+        })?;
+        Ok(SetValueNode::new(items).into())
+    }
+}
+
+impl Decode for MapTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let entries = decode_items(self.key.as_ref(), &self.count, decoder, |decoder| {
+            let key = self.key.decode(decoder)?;
+            let value = self.value.decode(decoder)?;
+            Ok(MapEntryValueNode::new(key, value))
+        })?;
+        Ok(MapValueNode::new(entries).into())
+    }
+}
+
+impl Decode for TupleTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let items = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                decoder.with_segment(format!("[{index}]"), |decoder| item.decode(decoder))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(TupleValueNode::new(items).into())
+    }
+}
+
+impl Decode for StructTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                decoder.with_segment(format!(".{}", field.name.as_str()), |decoder| {
+                    let value = field.r#type.decode(decoder)?;
+                    Ok(StructFieldValueNode::new(field.name.clone(), value))
+                })
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(StructValueNode::new(fields).into())
+    }
+}
+
+impl Decode for OptionTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let start = decoder.cursor;
+        match decode_integer(&self.prefix, decoder)? {
+            0 => {
+                if self.fixed == Some(true) {
+                    decoder.read(get_fixed_size(&self.item, decoder)?)?;
+                }
+                Ok(NoneValueNode::new().into())
+            }
+            1 => Ok(SomeValueNode::new(self.item.decode(decoder)?).into()),
+            value => {
+                decoder.cursor = start;
+                Err(decoder.error(format!("invalid option prefix {value}")))
+            }
+        }
+    }
+}
+
+impl Decode for ZeroableOptionTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let zero_value = match &self.zero_value {
+            Some(zero_value) => encode_constant(zero_value, decoder)?,
+            None => vec![0; get_fixed_size(&self.item, decoder)?],
+        };
+        if decoder.remaining().starts_with(&zero_value) {
+            decoder.read(zero_value.len())?;
+            return Ok(NoneValueNode::new().into());
+        }
+        Ok(SomeValueNode::new(self.item.decode(decoder)?).into())
+    }
+}
+
+impl Decode for RemainderOptionTypeNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        match decoder.remaining().is_empty() {
+            true => Ok(NoneValueNode::new().into()),
+            false => Ok(SomeValueNode::new(self.item.decode(decoder)?).into()),
+        }
+    }
+}
+
+impl Decode for DefinedTypeLinkNode {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let parent_resolver = decoder.resolver;
+        let resolver = match &self.program {
+            Some(program) => parent_resolver.with_program(
+                parent_resolver
+                    .resolve_program(program)
+                    .map_err(|error| decoder.error(error.to_string()))?,
+            ),
+            None => parent_resolver,
+        };
+        let defined_type = resolver
+            .resolve_defined_type(self)
+            .map_err(|error| decoder.error(error.to_string()))?;
+
+        decoder.resolver = resolver;
+        let result = match defined_type.r#type.as_ref() {
+            TypeNode::Enum(node) => decode_enum(node, Some(self), decoder),
+            node => node.decode(decoder),
+        };
+        decoder.resolver = parent_resolver;
+        result
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for FixedSizeTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        decoder.decode_separately(self.r#type.as_ref(), self.size)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for SizePrefixTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let size = decode_length(&self.prefix, decoder)?;
+        decoder.decode_separately(self.r#type.as_ref(), size)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for HiddenPrefixTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        for constant in &self.prefix {
+            skip_constant(constant, decoder)?;
+        }
+        self.r#type.decode(decoder)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for HiddenSuffixTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let value = self.r#type.decode(decoder)?;
+        for constant in &self.suffix {
+            skip_constant(constant, decoder)?;
+        }
+        Ok(value)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for SentinelTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let sentinel = encode_constant(&self.sentinel, decoder)?;
+        let remaining = decoder.remaining();
+        let Some(size) = (!sentinel.is_empty())
+            .then(|| {
+                remaining
+                    .windows(sentinel.len())
+                    .position(|w| w == sentinel)
+            })
+            .flatten()
+        else {
+            return Err(decoder.error("could not find the sentinel bytes"));
+        };
+        let value = decoder.decode_separately(self.r#type.as_ref(), size)?;
+        decoder.read(sentinel.len())?;
+        Ok(value)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for PreOffsetTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let offset = self.offset as i64;
+        match self.strategy {
+            PreOffsetStrategy::Absolute => decoder.seek(absolute_position(offset, decoder))?,
+            PreOffsetStrategy::Relative | PreOffsetStrategy::Padded => {
+                decoder.seek(decoder.cursor as i64 + offset)?
+            }
+        };
+        self.r#type.decode(decoder)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Decode> Decode for PostOffsetTypeNode<T> {
+    fn decode(&self, decoder: &mut Decoder) -> CodamaResult<ValueNode> {
+        let start = decoder.cursor;
+        let value = self.r#type.decode(decoder)?;
+        let offset = self.offset as i64;
+        match self.strategy {
+            PostOffsetStrategy::Absolute => decoder.seek(absolute_position(offset, decoder))?,
+            PostOffsetStrategy::Relative | PostOffsetStrategy::Padded => {
+                decoder.seek(decoder.cursor as i64 + offset)?
+            }
+            PostOffsetStrategy::PreOffset => decoder.seek(start as i64 + offset)?,
+        };
+        Ok(value)
+    }
+}
+
+/// Enum values reference their defined type so we forward the link that led
+/// to the enum. Inline enums have no such link and cannot be decoded as values.
+fn decode_enum(
+    node: &EnumTypeNode,
+    link: Option<&DefinedTypeLinkNode>,
+    decoder: &mut Decoder,
+) -> CodamaResult<ValueNode> {
+    let Some(link) = link else {
+        return Err(decoder.error("inline enums must be decoded through a defined type link"));
+    };
+    let start = decoder.cursor;
+    let discriminator = decode_integer(&node.size, decoder)?;
+    let Some(variant) = node
+        .variants
+        .iter()
+        .enumerate()
+        .find_map(|(index, variant)| {
+            let variant_discriminator = variant.discriminator().unwrap_or(index as u32);
+            (variant_discriminator as i128 == discriminator).then_some(variant)
+        })
+    else {
+        decoder.cursor = start;
+        return Err(decoder.error(format!("invalid enum discriminator {discriminator}")));
+    };
+
+    let payload = decoder.with_segment(format!(".{}", variant.name().as_str()), |decoder| {
+        Ok(match variant {
+            EnumVariantTypeNode::Empty(_) => None,
+            EnumVariantTypeNode::Struct(variant) => match variant.r#struct.decode(decoder)? {
+                ValueNode::Struct(value) => Some(EnumValuePayload::Struct(value)),
+                _ => None,
+            },
+            EnumVariantTypeNode::Tuple(variant) => match variant.tuple.decode(decoder)? {
+                ValueNode::Tuple(value) => Some(EnumValuePayload::Tuple(value)),
+                _ => None,
+            },
+        })
+    })?;
+
+    Ok(EnumValueNode::new(link.clone(), variant.name().clone(), payload).into())
+}
+
+fn decode_items<T>(
+    item: &TypeNode,
+    count: &CountNode,
+    decoder: &mut Decoder,
+    mut decode_item: impl FnMut(&mut Decoder) -> CodamaResult<T>,
+) -> CodamaResult<Vec<T>> {
+    let count = match count {
+        CountNode::Fixed(node) => Some(node.value as usize),
+        CountNode::Prefixed(node) => Some(decode_length(&node.prefix, decoder)?),
+        CountNode::Remainder(_) => None,
+    };
+
+    let mut items = Vec::new();
+    loop {
+        let index = items.len();
+        let done = match count {
+            Some(count) => index >= count,
+            None => decoder.remaining().is_empty(),
+        };
+        if done {
+            return Ok(items);
+        }
+        let start = decoder.cursor;
+        let value = decoder.with_segment(format!("[{index}]"), &mut decode_item)?;
+        if count.is_none() && decoder.cursor == start {
+            return Err(decoder.error(format!(
+                "cannot decode a remainder of `{}` items that use no bytes",
+                item.kind()
+            )));
+        }
+        items.push(value);
+    }
+}
+
+/// Negative absolute offsets are relative to the end of the buffer.
+fn absolute_position(offset: i64, decoder: &Decoder) -> i64 {
+    match offset < 0 {
+        true => decoder.bytes.len() as i64 + offset,
+        false => offset,
+    }
+}
+
+fn get_fixed_size(r#type: &TypeNode, decoder: &Decoder) -> CodamaResult<usize> {
+    fixed_size(r#type, &decoder.resolver)
+        .ok_or_else(|| decoder.error(format!("`{}` does not have a fixed size", r#type.kind())))
+}
+
+fn encode_data(encoding: BytesEncoding, bytes: &[u8], decoder: &Decoder) -> CodamaResult<String> {
+    encoding
+        .encode(bytes)
+        .map_err(|error| decoder.error(error.to_string()))
+}
+
+fn encode_constant(constant: &ConstantValueNode, decoder: &Decoder) -> CodamaResult<Vec<u8>> {
+    encode(&constant.value, &constant.r#type, &decoder.resolver)
+        .map_err(|error| decoder.error(error.to_string()))
+}
+
+fn skip_constant(constant: &ConstantValueNode, decoder: &mut Decoder) -> CodamaResult<()> {
+    let expected = encode_constant(constant, decoder)?;
+    if !decoder.remaining().starts_with(&expected) {
+        return Err(decoder.error("bytes do not match the expected constant"));
+    }
+    decoder.read(expected.len())?;
+    Ok(())
+}
+
+fn decode_integer(
+    r#type: &NestedTypeNode<NumberTypeNode>,
+    decoder: &mut Decoder,
+) -> CodamaResult<i128> {
+    let start = decoder.cursor;
+    match r#type.decode(decoder)? {
+        ValueNode::Number(NumberValueNode {
+            number: Number::UnsignedInteger(value),
+        }) => Ok(value as i128),
+        ValueNode::Number(NumberValueNode {
+            number: Number::SignedInteger(value),
+        }) => Ok(value as i128),
+        _ => {
+            decoder.cursor = start;
+            Err(decoder.error("expected an integer"))
+        }
+    }
+}
+
+fn decode_length(
+    r#type: &NestedTypeNode<NumberTypeNode>,
+    decoder: &mut Decoder,
+) -> CodamaResult<usize> {
+    let start = decoder.cursor;
+    let length = decode_integer(r#type, decoder)?;
+    usize::try_from(length).map_err(|_| {
+        decoder.cursor = start;
+        decoder.error(format!("invalid length {length}"))
+    })
+}
+
+fn decode_number(
+    format: NumberFormat,
+    endian: Endianness,
+    decoder: &mut Decoder,
+) -> CodamaResult<Number> {
+    macro_rules! from_bytes {
+        ($type:ty) => {{
+            let bytes = decoder.read(std::mem::size_of::<$type>())?;
+            let bytes = bytes.try_into().unwrap();
+            match endian {
+                Endianness::Le => <$type>::from_le_bytes(bytes),
+                Endianness::Be => <$type>::from_be_bytes(bytes),
+            }
+        }};
+    }
+
+    let start = decoder.cursor;
+    Ok(match format {
+        NumberFormat::U8 => Number::from(from_bytes!(u8)),
+        NumberFormat::U16 => Number::from(from_bytes!(u16)),
+        NumberFormat::U32 => Number::from(from_bytes!(u32)),
+        NumberFormat::U64 => Number::from(from_bytes!(u64)),
+        NumberFormat::I8 => Number::from(from_bytes!(i8)),
+        NumberFormat::I16 => Number::from(from_bytes!(i16)),
+        NumberFormat::I32 => Number::from(from_bytes!(i32)),
+        NumberFormat::I64 => Number::from(from_bytes!(i64)),
+        NumberFormat::F32 => Number::from(from_bytes!(f32) as f64),
+        NumberFormat::F64 => Number::from(from_bytes!(f64)),
+        NumberFormat::U128 => {
+            let value = from_bytes!(u128);
+            Number::from(u64::try_from(value).map_err(|_| {
+                decoder.cursor = start;
+                decoder.error(format!("number {value} does not fit in a number value"))
+            })?)
+        }
+        NumberFormat::I128 => {
+            let value = from_bytes!(i128);
+            Number::from(i64::try_from(value).map_err(|_| {
+                decoder.cursor = start;
+                decoder.error(format!("number {value} does not fit in a number value"))
+            })?)
+        }
+        NumberFormat::ShortU16 => {
+            let mut value = 0u32;
+            for index in 0..3 {
+                let byte = decoder.read(1)?[0];
+                value |= ((byte & 0x7f) as u32) << (7 * index);
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            Number::from(u16::try_from(value).map_err(|_| {
+                decoder.cursor = start;
+                decoder.error(format!("invalid short u16 value {value}"))
+            })?)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BytesTypeNode, DefinedTypeNode, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode,
+        EnumTupleVariantTypeNode, PrefixedCountNode, ProgramNode, PublicKeyTypeNode,
+        RemainderCountNode, RootNode, ShortU16, StructFieldTypeNode, I16, U16, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new(
+                "direction",
+                EnumTypeNode::new(vec![
+                    EnumEmptyVariantTypeNode::new("up").into(),
+                    EnumTupleVariantTypeNode::new(
+                        "move",
+                        TupleTypeNode::new(vec![NumberTypeNode::le(U16).into()]),
+                    )
+                    .into(),
+                    EnumStructVariantTypeNode::new(
+                        "jump",
+                        StructTypeNode::new(vec![StructFieldTypeNode::new(
+                            "height",
+                            NumberTypeNode::le(U8),
+                        )]),
+                    )
+                    .into(),
+                ]),
+            )],
+            ..ProgramNode::new("myProgram", "1111")
+        })
+    }
+
+    fn decode_with_root(
+        r#type: impl Into<TypeNode>,
+        bytes: &[u8],
+    ) -> CodamaResult<(ValueNode, usize)> {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        decode(&r#type.into(), bytes, &resolver)
+    }
+
+    /// Decode the given bytes and check that encoding the result gives the same bytes back.
+    fn round_trip(r#type: impl Into<TypeNode>, bytes: &[u8]) -> ValueNode {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let r#type = r#type.into();
+        let (value, size) = decode(&r#type, bytes, &resolver).unwrap();
+        assert_eq!(size, bytes.len());
+        assert_eq!(encode(&value, &r#type, &resolver).unwrap(), bytes);
+        value
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            round_trip(NumberTypeNode::le(U16), &[2, 1]),
+            NumberValueNode::new(258u16).into()
+        );
+        assert_eq!(
+            round_trip(NumberTypeNode::be(U32), &[0, 0, 1, 2]),
+            NumberValueNode::new(258u32).into()
+        );
+        assert_eq!(
+            round_trip(NumberTypeNode::le(I16), &[0xfe, 0xff]),
+            NumberValueNode::new(-2i16).into()
+        );
+        assert_eq!(
+            round_trip(NumberTypeNode::le(ShortU16), &[0xff, 0xff, 0x03]),
+            NumberValueNode::new(u16::MAX).into()
+        );
+    }
+
+    #[test]
+    fn bytes_read() {
+        let (value, size) = decode_with_root(NumberTypeNode::le(U8), &[7, 8, 9]).unwrap();
+        assert_eq!(value, NumberValueNode::new(7u8).into());
+        assert_eq!(size, 1);
+    }
+
+    #[test]
+    fn truncated_data() {
+        let node = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("a", NumberTypeNode::le(U32)),
+            StructFieldTypeNode::new("b", NumberTypeNode::le(U64)),
+        ]);
+        let error = decode_with_root(node, &[1, 0, 0, 0, 2, 0, 0]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not decode value at `$.b` (offset 4): expected 8 bytes, found 3"
+        );
+    }
+
+    #[test]
+    fn invalid_data() {
+        let error = decode_with_root(ArrayTypeNode::fixed(BooleanTypeNode::default(), 2), &[1, 2])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not decode value at `$[1]` (offset 1): invalid boolean value 2"
+        );
+    }
+
+    #[test]
+    fn strings_bytes_and_public_keys() {
+        let node =
+            SizePrefixTypeNode::<TypeNode>::new(StringTypeNode::utf8(), NumberTypeNode::le(U32));
+        assert_eq!(
+            round_trip(node, &[2, 0, 0, 0, b'h', b'i']),
+            StringValueNode::new("hi").into()
+        );
+        assert_eq!(
+            round_trip(BytesTypeNode::new(), &[0xde, 0xad]),
+            BytesValueNode::base16("dead").into()
+        );
+        assert_eq!(
+            round_trip(PublicKeyTypeNode::new(), &[0; 32]),
+            PublicKeyValueNode::new("11111111111111111111111111111111").into()
+        );
+    }
+
+    #[test]
+    fn fixed_size_and_sentinel() {
+        let node = TupleTypeNode::new(vec![
+            FixedSizeTypeNode::<TypeNode>::new(StringTypeNode::utf8(), 3).into(),
+            SentinelTypeNode::<TypeNode>::new(
+                StringTypeNode::utf8(),
+                ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+            )
+            .into(),
+            NumberTypeNode::le(U8).into(),
+        ]);
+        assert_eq!(
+            round_trip(node, &[b'a', b'b', b'c', b'x', 0xff, 7]),
+            TupleValueNode::new(vec![
+                StringValueNode::new("abc").into(),
+                StringValueNode::new("x").into(),
+                NumberValueNode::new(7u8).into(),
+            ])
+            .into()
+        );
+    }
+
+    #[test]
+    fn hidden_prefix() {
+        let node = HiddenPrefixTypeNode::<TypeNode>::new(
+            NumberTypeNode::le(U8),
+            vec![ConstantValueNode::bytes(BytesEncoding::Base16, "0102")],
+        );
+        assert_eq!(
+            round_trip(node.clone(), &[1, 2, 3]),
+            NumberValueNode::new(3u8).into()
+        );
+        assert_eq!(
+            decode_with_root(node, &[1, 3, 3]).unwrap_err().to_string(),
+            "Could not decode value at `$` (offset 0): bytes do not match the expected constant"
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        let node = PreOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U8), 2);
+        assert_eq!(
+            round_trip(node, &[0, 0, 7]),
+            NumberValueNode::new(7u8).into()
+        );
+        let node = PostOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U8), 2);
+        assert_eq!(
+            round_trip(node, &[7, 0, 0]),
+            NumberValueNode::new(7u8).into()
+        );
+
+        // Read the second item from the last byte of the first one.
+        let node = TupleTypeNode::new(vec![
+            PostOffsetTypeNode::<TypeNode>::pre_offset(NumberTypeNode::le(U16), 1).into(),
+            NumberTypeNode::le(U8).into(),
+        ]);
+        assert_eq!(
+            round_trip(node, &[1, 2]),
+            TupleValueNode::new(vec![
+                NumberValueNode::new(0x0201u16).into(),
+                NumberValueNode::new(2u8).into(),
+            ])
+            .into()
+        );
+    }
+
+    #[test]
+    fn options() {
+        let node = OptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(round_trip(node.clone(), &[0]), NoneValueNode::new().into());
+        assert_eq!(
+            round_trip(node, &[1, 5, 0]),
+            SomeValueNode::new(NumberValueNode::new(5u16)).into()
+        );
+        let node = OptionTypeNode::fixed(NumberTypeNode::le(U16));
+        assert_eq!(round_trip(node, &[0, 0, 0]), NoneValueNode::new().into());
+        let node = ZeroableOptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(
+            round_trip(node.clone(), &[0, 0]),
+            NoneValueNode::new().into()
+        );
+        assert_eq!(
+            round_trip(node, &[1, 0]),
+            SomeValueNode::new(NumberValueNode::new(1u16)).into()
+        );
+        let node = RemainderOptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(round_trip(node, &[]), NoneValueNode::new().into());
+        assert_eq!(
+            decode_with_root(OptionTypeNode::new(NumberTypeNode::le(U16)), &[2])
+                .unwrap_err()
+                .to_string(),
+            "Could not decode value at `$` (offset 0): invalid option prefix 2"
+        );
+    }
+
+    #[test]
+    fn arrays_and_maps() {
+        let node = ArrayTypeNode::new(
+            NumberTypeNode::le(U8),
+            PrefixedCountNode::new(NumberTypeNode::le(U32)),
+        );
+        assert_eq!(
+            round_trip(node, &[2, 0, 0, 0, 1, 2]),
+            ArrayValueNode::new(vec![
+                NumberValueNode::new(1u8).into(),
+                NumberValueNode::new(2u8).into(),
+            ])
+            .into()
+        );
+        let node = MapTypeNode::new(
+            NumberTypeNode::le(U8),
+            NumberTypeNode::le(U8),
+            RemainderCountNode::new(),
+        );
+        assert_eq!(
+            round_trip(node, &[1, 2, 3, 4]),
+            MapValueNode::new(vec![
+                MapEntryValueNode::new(NumberValueNode::new(1u8), NumberValueNode::new(2u8)),
+                MapEntryValueNode::new(NumberValueNode::new(3u8), NumberValueNode::new(4u8)),
+            ])
+            .into()
+        );
+    }
+
+    #[test]
+    fn enums_through_links() {
+        let link = || DefinedTypeLinkNode::new("direction");
+        assert_eq!(
+            round_trip(link(), &[0]),
+            EnumValueNode::empty(link(), "up").into()
+        );
+        assert_eq!(
+            round_trip(link(), &[1, 3, 0]),
+            EnumValueNode::tuple(
+                link(),
+                "move",
+                TupleValueNode::new(vec![NumberValueNode::new(3u16).into()])
+            )
+            .into()
+        );
+        assert_eq!(
+            round_trip(link(), &[2, 4]),
+            EnumValueNode::fields(
+                link(),
+                "jump",
+                StructValueNode::new(vec![StructFieldValueNode::new(
+                    "height",
+                    NumberValueNode::new(4u8)
+                )])
+            )
+            .into()
+        );
+        assert_eq!(
+            decode_with_root(link(), &[3]).unwrap_err().to_string(),
+            "Could not decode value at `$` (offset 0): invalid enum discriminator 3"
+        );
+    }
+
+    #[test]
+    fn inline_enums() {
+        let r#type = EnumTypeNode::new(vec![EnumEmptyVariantTypeNode::new("up").into()]);
+        assert_eq!(
+            decode_with_root(r#type.clone(), &[0]).unwrap_err().to_string(),
+            "Could not decode value at `$` (offset 0): inline enums must be decoded through a defined type link"
+        );
+
+        let r#type = StructTypeNode::new(vec![StructFieldTypeNode::new("direction", r#type)]);
+        assert_eq!(
+            decode_with_root(r#type, &[0]).unwrap_err().to_string(),
+            "Could not decode value at `$.direction` (offset 0): inline enums must be decoded through a defined type link"
+        );
+    }
+}
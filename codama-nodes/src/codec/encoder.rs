@@ -0,0 +1,948 @@
+use crate::{
+    decode_public_key, fixed_size, AmountTypeNode, ArrayTypeNode, BooleanTypeNode, BytesEncoding,
+    ConstantValueNode, CountNode, DateTimeTypeNode, DefinedTypeLinkNode, Endianness, EnumTypeNode,
+    EnumValuePayload, EnumVariantTypeNode, FixedSizeTypeNode, HasKind, HasName,
+    HiddenPrefixTypeNode, HiddenSuffixTypeNode, LinkResolver, MapTypeNode, NestedTypeNode, Number,
+    NumberFormat, NumberTypeNode, NumberValueNode, OptionTypeNode, PostOffsetStrategy,
+    PostOffsetTypeNode, PreOffsetStrategy, PreOffsetTypeNode, RemainderOptionTypeNode,
+    SentinelTypeNode, SetTypeNode, SizePrefixTypeNode, SolAmountTypeNode, StringTypeNode,
+    StructTypeNode, TupleTypeNode, TypeNode, TypeNodeTrait, TypeNodeUnionTrait, ValueNode,
+    ZeroableOptionTypeNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+
+/// Encodes a value into bytes following the serialization rules of the given type.
+///
+/// Defined type links are followed using the provided resolver.
+pub fn encode(
+    value: &ValueNode,
+    r#type: &TypeNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<Vec<u8>> {
+    let mut encoder = Encoder::new(*resolver, Vec::new());
+    r#type.encode(value, &mut encoder)?;
+    Ok(encoder.bytes)
+}
+
+struct Encoder<'a> {
+    resolver: LinkResolver<'a>,
+    bytes: Vec<u8>,
+    cursor: usize,
+    /// Segments of the path to the value being encoded, e.g. `.items` and `[2]`.
+    path: Vec<String>,
+}
+
+impl<'a> Encoder<'a> {
+    fn new(resolver: LinkResolver<'a>, path: Vec<String>) -> Self {
+        Self {
+            resolver,
+            bytes: Vec::new(),
+            cursor: 0,
+            path,
+        }
+    }
+
+    /// Encode a value into a separate buffer, e.g. to measure or wrap it.
+    fn encode_separately<T: Encode + ?Sized>(
+        &self,
+        r#type: &T,
+        value: &ValueNode,
+    ) -> CodamaResult<Vec<u8>> {
+        let mut encoder = Encoder::new(self.resolver, self.path.clone());
+        r#type.encode(value, &mut encoder)?;
+        Ok(encoder.bytes)
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        let end = self.cursor + data.len();
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+        self.bytes[self.cursor..end].copy_from_slice(data);
+        self.cursor = end;
+    }
+
+    fn seek(&mut self, position: i64, padded: bool) -> CodamaResult<()> {
+        self.cursor = usize::try_from(position)
+            .map_err(|_| self.error(format!("cannot move to negative offset {position}")))?;
+        if padded && self.bytes.len() < self.cursor {
+            self.bytes.resize(self.cursor, 0);
+        }
+        Ok(())
+    }
+
+    fn with_segment<T>(
+        &mut self,
+        segment: String,
+        f: impl FnOnce(&mut Self) -> CodamaResult<T>,
+    ) -> CodamaResult<T> {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn error<T: Into<String>>(&self, message: T) -> CodamaError {
+        CodamaError::EncodingFailed {
+            path: format!("${}", self.path.concat()),
+            message: message.into(),
+        }
+    }
+
+    fn unexpected_value(&self, expected: &str, value: &ValueNode) -> CodamaError {
+        self.error(format!("expected `{expected}`, found `{}`", value.kind()))
+    }
+}
+
+trait Encode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()>;
+}
+
+impl<T: Encode> Encode for Box<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        self.as_ref().encode(value, encoder)
+    }
+}
+
+impl Encode for TypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let value = get_injected_fallback(value);
+        match self {
+            TypeNode::Amount(node) => node.encode(value, encoder),
+            TypeNode::Array(node) => node.encode(value, encoder),
+            TypeNode::Boolean(node) => node.encode(value, encoder),
+            TypeNode::Bytes(_) => match value {
+                ValueNode::Bytes(value) => {
+                    let bytes = decode_data(value.encoding, &value.data, encoder)?;
+                    encoder.write(&bytes);
+                    Ok(())
+                }
+                _ => Err(encoder.unexpected_value("bytesValueNode", value)),
+            },
+            TypeNode::DateTime(node) => node.encode(value, encoder),
+            TypeNode::Enum(node) => node.encode(value, encoder),
+            TypeNode::FixedSize(node) => node.encode(value, encoder),
+            TypeNode::HiddenPrefix(node) => node.encode(value, encoder),
+            TypeNode::HiddenSuffix(node) => node.encode(value, encoder),
+            TypeNode::Map(node) => node.encode(value, encoder),
+            TypeNode::Number(node) => node.encode(value, encoder),
+            TypeNode::Option(node) => node.encode(value, encoder),
+            TypeNode::PostOffset(node) => node.encode(value, encoder),
+            TypeNode::PreOffset(node) => node.encode(value, encoder),
+            TypeNode::PublicKey(_) => match value {
+                ValueNode::PublicKey(value) => {
+                    let bytes = decode_public_key(&value.public_key)
+                        .map_err(|message| encoder.error(message))?;
+                    encoder.write(&bytes);
+                    Ok(())
+                }
+                _ => Err(encoder.unexpected_value("publicKeyValueNode", value)),
+            },
+            TypeNode::RemainderOption(node) => node.encode(value, encoder),
+            TypeNode::Sentinel(node) => node.encode(value, encoder),
+            TypeNode::Set(node) => node.encode(value, encoder),
+            TypeNode::SizePrefix(node) => node.encode(value, encoder),
+            TypeNode::SolAmount(node) => node.encode(value, encoder),
+            TypeNode::String(node) => node.encode(value, encoder),
+            TypeNode::Struct(node) => node.encode(value, encoder),
+            TypeNode::Tuple(node) => node.encode(value, encoder),
+            TypeNode::ZeroableOption(node) => node.encode(value, encoder),
+            TypeNode::Unknown(node) => {
+                Err(encoder.error(format!("cannot encode unknown type `{}`", node.kind)))
+            }
+            TypeNode::Link(node) => node.encode(value, encoder),
+        }
+    }
+}
+
+impl<T: TypeNodeTrait + Encode> Encode for NestedTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let value = get_injected_fallback(value);
+        match self {
+            NestedTypeNode::FixedSize(node) => node.encode(value, encoder),
+            NestedTypeNode::HiddenPrefix(node) => node.encode(value, encoder),
+            NestedTypeNode::HiddenSuffix(node) => node.encode(value, encoder),
+            NestedTypeNode::PostOffset(node) => node.encode(value, encoder),
+            NestedTypeNode::PreOffset(node) => node.encode(value, encoder),
+            NestedTypeNode::Sentinel(node) => node.encode(value, encoder),
+            NestedTypeNode::SizePrefix(node) => node.encode(value, encoder),
+            NestedTypeNode::Value(node) => node.encode(value, encoder),
+        }
+    }
+}
+
+impl Encode for NumberTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Number(value) = value else {
+            return Err(encoder.unexpected_value("numberValueNode", value));
+        };
+        let bytes = encode_number(value.number, self.format, self.endian)
+            .map_err(|message| encoder.error(message))?;
+        encoder.write(&bytes);
+        Ok(())
+    }
+}
+
+impl Encode for AmountTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        self.number.encode(value, encoder)
+    }
+}
+
+impl Encode for DateTimeTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        self.number.encode(value, encoder)
+    }
+}
+
+impl Encode for SolAmountTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        self.number.encode(value, encoder)
+    }
+}
+
+impl Encode for BooleanTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Boolean(value) = value else {
+            return Err(encoder.unexpected_value("booleanValueNode", value));
+        };
+        let number = NumberValueNode::new(value.boolean as u8);
+        self.size.encode(&number.into(), encoder)
+    }
+}
+
+impl Encode for StringTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::String(value) = value else {
+            return Err(encoder.unexpected_value("stringValueNode", value));
+        };
+        let bytes = decode_data(self.encoding, &value.string, encoder)?;
+        encoder.write(&bytes);
+        Ok(())
+    }
+}
+
+impl Encode for ArrayTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Array(value) = value else {
+            return Err(encoder.unexpected_value("arrayValueNode", value));
+        };
+        encode_count(&self.count, value.items.len(), encoder)?;
+        encode_items(self.item.as_ref(), &value.items, encoder)
+    }
+}
+
+impl Encode for SetTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Set(value) = value else {
+            return Err(encoder.unexpected_value("setValueNode", value));
+        };
+        encode_count(&self.count, value.items.len(), encoder)?;
+        encode_items(self.item.as_ref(), &value.items, encoder)
+    }
+}
+
+impl Encode for MapTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Map(value) = value else {
+            return Err(encoder.unexpected_value("mapValueNode", value));
+        };
+        encode_count(&self.count, value.entries.len(), encoder)?;
+        for (index, entry) in value.entries.iter().enumerate() {
+            encoder.with_segment(format!("[{index}]"), |encoder| {
+                self.key.encode(&entry.key, encoder)?;
+                self.value.encode(&entry.value, encoder)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Encode for TupleTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Tuple(value) = value else {
+            return Err(encoder.unexpected_value("tupleValueNode", value));
+        };
+        if value.items.len() != self.items.len() {
+            return Err(encoder.error(format!(
+                "expected {} tuple items, found {}",
+                self.items.len(),
+                value.items.len()
+            )));
+        }
+        for (index, (item_type, item)) in self.items.iter().zip(&value.items).enumerate() {
+            encoder.with_segment(format!("[{index}]"), |encoder| {
+                item_type.encode(item, encoder)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Encode for StructTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Struct(value) = value else {
+            return Err(encoder.unexpected_value("structValueNode", value));
+        };
+        if let Some(unknown) = value
+            .fields
+            .iter()
+            .find(|field| !self.fields.iter().any(|f| f.name == field.name))
+        {
+            return Err(encoder.error(format!("unknown field `{}`", unknown.name.as_str())));
+        }
+        for field in &self.fields {
+            let field_value = value
+                .fields
+                .iter()
+                .find(|f| f.name == field.name)
+                .map(|f| f.value.as_ref())
+                .or(field.default_value.as_ref().as_ref());
+            let Some(field_value) = field_value else {
+                return Err(encoder.error(format!("missing field `{}`", field.name.as_str())));
+            };
+            encoder.with_segment(format!(".{}", field.name.as_str()), |encoder| {
+                field.r#type.encode(field_value, encoder)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Encode for EnumTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let ValueNode::Enum(value) = value else {
+            return Err(encoder.unexpected_value("enumValueNode", value));
+        };
+        let Some((index, variant)) = self
+            .variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name() == &value.variant)
+        else {
+            return Err(encoder.error(format!("unknown variant `{}`", value.variant.as_str())));
+        };
+
+        let discriminator = variant.discriminator().unwrap_or(index as u32);
+        self.size
+            .encode(&NumberValueNode::new(discriminator).into(), encoder)?;
+
+        encoder.with_segment(format!(".{}", value.variant.as_str()), |encoder| {
+            match (variant, value.value.as_ref()) {
+                (EnumVariantTypeNode::Empty(_), None) => Ok(()),
+                (EnumVariantTypeNode::Struct(node), Some(EnumValuePayload::Struct(payload))) => {
+                    node.r#struct.encode(&payload.clone().into(), encoder)
+                }
+                (EnumVariantTypeNode::Tuple(node), Some(EnumValuePayload::Tuple(payload))) => {
+                    node.tuple.encode(&payload.clone().into(), encoder)
+                }
+                (variant, _) => {
+                    Err(encoder.error(format!("payload does not match `{}`", variant.kind())))
+                }
+            }
+        })
+    }
+}
+
+impl Encode for OptionTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        match value {
+            ValueNode::None(_) => {
+                self.prefix
+                    .encode(&NumberValueNode::new(0u8).into(), encoder)?;
+                if self.fixed == Some(true) {
+                    let size = get_fixed_size(&self.item, encoder)?;
+                    encoder.write(&vec![0; size]);
+                }
+                Ok(())
+            }
+            ValueNode::Some(value) => {
+                self.prefix
+                    .encode(&NumberValueNode::new(1u8).into(), encoder)?;
+                self.item.encode(&value.value, encoder)
+            }
+            _ => Err(encoder.unexpected_value("someValueNode", value)),
+        }
+    }
+}
+
+impl Encode for ZeroableOptionTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        match (value, &self.zero_value) {
+            (ValueNode::None(_), Some(zero_value)) => encode_constant(zero_value, encoder),
+            (ValueNode::None(_), None) => {
+                let size = get_fixed_size(&self.item, encoder)?;
+                encoder.write(&vec![0; size]);
+                Ok(())
+            }
+            (ValueNode::Some(value), _) => self.item.encode(&value.value, encoder),
+            _ => Err(encoder.unexpected_value("someValueNode", value)),
+        }
+    }
+}
+
+impl Encode for RemainderOptionTypeNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        match value {
+            ValueNode::None(_) => Ok(()),
+            ValueNode::Some(value) => self.item.encode(&value.value, encoder),
+            _ => Err(encoder.unexpected_value("someValueNode", value)),
+        }
+    }
+}
+
+impl Encode for DefinedTypeLinkNode {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let parent_resolver = encoder.resolver;
+        let resolver = match &self.program {
+            Some(program) => parent_resolver.with_program(
+                parent_resolver
+                    .resolve_program(program)
+                    .map_err(|error| encoder.error(error.to_string()))?,
+            ),
+            None => parent_resolver,
+        };
+        let defined_type = resolver
+            .resolve_defined_type(self)
+            .map_err(|error| encoder.error(error.to_string()))?;
+
+        encoder.resolver = resolver;
+        let result = defined_type.r#type.encode(value, encoder);
+        encoder.resolver = parent_resolver;
+        result
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for FixedSizeTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let mut bytes = encoder.encode_separately(self.r#type.as_ref(), value)?;
+        bytes.resize(self.size, 0);
+        encoder.write(&bytes);
+        Ok(())
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for SizePrefixTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let bytes = encoder.encode_separately(self.r#type.as_ref(), value)?;
+        self.prefix
+            .encode(&NumberValueNode::new(bytes.len() as u64).into(), encoder)?;
+        encoder.write(&bytes);
+        Ok(())
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for HiddenPrefixTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        for constant in &self.prefix {
+            encode_constant(constant, encoder)?;
+        }
+        self.r#type.encode(value, encoder)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for HiddenSuffixTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        self.r#type.encode(value, encoder)?;
+        for constant in &self.suffix {
+            encode_constant(constant, encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for SentinelTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let bytes = encoder.encode_separately(self.r#type.as_ref(), value)?;
+        let sentinel =
+            encoder.encode_separately(self.sentinel.r#type.as_ref(), &self.sentinel.value)?;
+        if !sentinel.is_empty() && bytes.windows(sentinel.len()).any(|w| w == sentinel) {
+            return Err(encoder.error("encoded value contains the sentinel bytes"));
+        }
+        encoder.write(&bytes);
+        encoder.write(&sentinel);
+        Ok(())
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for PreOffsetTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let offset = self.offset as i64;
+        match self.strategy {
+            PreOffsetStrategy::Absolute => {
+                encoder.seek(absolute_position(offset, encoder), false)?
+            }
+            PreOffsetStrategy::Relative => encoder.seek(encoder.cursor as i64 + offset, false)?,
+            PreOffsetStrategy::Padded => encoder.seek(encoder.cursor as i64 + offset, true)?,
+        };
+        self.r#type.encode(value, encoder)
+    }
+}
+
+impl<T: TypeNodeUnionTrait + Encode> Encode for PostOffsetTypeNode<T> {
+    fn encode(&self, value: &ValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+        let start = encoder.cursor;
+        self.r#type.encode(value, encoder)?;
+        let offset = self.offset as i64;
+        match self.strategy {
+            PostOffsetStrategy::Absolute => encoder.seek(absolute_position(offset, encoder), false),
+            PostOffsetStrategy::Relative => encoder.seek(encoder.cursor as i64 + offset, false),
+            PostOffsetStrategy::Padded => encoder.seek(encoder.cursor as i64 + offset, true),
+            PostOffsetStrategy::PreOffset => encoder.seek(start as i64 + offset, false),
+        }
+    }
+}
+
+/// Negative absolute offsets are relative to the end of the buffer.
+fn absolute_position(offset: i64, encoder: &Encoder) -> i64 {
+    match offset < 0 {
+        true => encoder.bytes.len() as i64 + offset,
+        false => offset,
+    }
+}
+
+fn get_injected_fallback(value: &ValueNode) -> &ValueNode {
+    match value {
+        ValueNode::Injected(node) => match node.fallback.as_ref() {
+            Some(fallback) => get_injected_fallback(fallback),
+            None => value,
+        },
+        _ => value,
+    }
+}
+
+fn get_fixed_size(r#type: &TypeNode, encoder: &Encoder) -> CodamaResult<usize> {
+    fixed_size(r#type, &encoder.resolver)
+        .ok_or_else(|| encoder.error(format!("`{}` does not have a fixed size", r#type.kind())))
+}
+
+fn decode_data(encoding: BytesEncoding, data: &str, encoder: &Encoder) -> CodamaResult<Vec<u8>> {
+    encoding
+        .decode(data)
+        .map_err(|error| encoder.error(error.to_string()))
+}
+
+fn encode_constant(constant: &ConstantValueNode, encoder: &mut Encoder) -> CodamaResult<()> {
+    constant.r#type.encode(&constant.value, encoder)
+}
+
+fn encode_count(count: &CountNode, length: usize, encoder: &mut Encoder) -> CodamaResult<()> {
+    match count {
+        CountNode::Fixed(node) if node.value != length as u64 => {
+            Err(encoder.error(format!("expected {} items, found {length}", node.value)))
+        }
+        CountNode::Fixed(_) | CountNode::Remainder(_) => Ok(()),
+        CountNode::Prefixed(node) => node
+            .prefix
+            .encode(&NumberValueNode::new(length as u64).into(), encoder),
+    }
+}
+
+fn encode_items(r#type: &TypeNode, items: &[ValueNode], encoder: &mut Encoder) -> CodamaResult<()> {
+    for (index, item) in items.iter().enumerate() {
+        encoder.with_segment(format!("[{index}]"), |encoder| r#type.encode(item, encoder))?;
+    }
+    Ok(())
+}
+
+fn encode_number(
+    number: Number,
+    format: NumberFormat,
+    endian: Endianness,
+) -> Result<Vec<u8>, String> {
+    macro_rules! to_bytes {
+        ($value:expr) => {
+            match endian {
+                Endianness::Le => $value.to_le_bytes().to_vec(),
+                Endianness::Be => $value.to_be_bytes().to_vec(),
+            }
+        };
+    }
+    macro_rules! integer {
+        ($type:ty) => {{
+            let integer = get_integer(number)?;
+            let integer = <$type>::try_from(integer)
+                .map_err(|_| format!("number {integer} does not fit in `{format:?}`"))?;
+            to_bytes!(integer)
+        }};
+    }
+
+    Ok(match format {
+        NumberFormat::U8 => integer!(u8),
+        NumberFormat::U16 => integer!(u16),
+        NumberFormat::U32 => integer!(u32),
+        NumberFormat::U64 => integer!(u64),
+        NumberFormat::U128 => integer!(u128),
+        NumberFormat::I8 => integer!(i8),
+        NumberFormat::I16 => integer!(i16),
+        NumberFormat::I32 => integer!(i32),
+        NumberFormat::I64 => integer!(i64),
+        NumberFormat::I128 => integer!(i128),
+        NumberFormat::F32 => to_bytes!(get_float(number) as f32),
+        NumberFormat::F64 => to_bytes!(get_float(number)),
+        NumberFormat::ShortU16 => {
+            let integer = get_integer(number)?;
+            let mut value = u16::try_from(integer)
+                .map_err(|_| format!("number {integer} does not fit in `{format:?}`"))?;
+            let mut bytes = Vec::with_capacity(3);
+            loop {
+                let byte = (value & 0x7f) as u8;
+                value >>= 7;
+                if value == 0 {
+                    bytes.push(byte);
+                    break;
+                }
+                bytes.push(byte | 0x80);
+            }
+            bytes
+        }
+    })
+}
+
+fn get_integer(number: Number) -> Result<i128, String> {
+    match number {
+        Number::UnsignedInteger(value) => Ok(value as i128),
+        Number::SignedInteger(value) => Ok(value as i128),
+        Number::Float(value) if value.is_finite() && value.fract() == 0.0 => Ok(value as i128),
+        Number::Float(value) => Err(format!("number {value} is not an integer")),
+    }
+}
+
+fn get_float(number: Number) -> f64 {
+    match number {
+        Number::UnsignedInteger(value) => value as f64,
+        Number::SignedInteger(value) => value as f64,
+        Number::Float(value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArrayValueNode, BooleanValueNode, BytesTypeNode, BytesValueNode, DefinedTypeNode,
+        EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode,
+        EnumValueNode, MapEntryValueNode, MapValueNode, NoneValueNode, PrefixedCountNode,
+        ProgramNode, PublicKeyTypeNode, PublicKeyValueNode, RootNode, ShortU16, SomeValueNode,
+        StringValueNode, StructFieldTypeNode, StructFieldValueNode, StructValueNode,
+        TupleValueNode, F64, I16, U16, U32, U8,
+    };
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new(
+                "direction",
+                EnumTypeNode::new(vec![
+                    EnumEmptyVariantTypeNode::new("up").into(),
+                    EnumTupleVariantTypeNode::new(
+                        "move",
+                        TupleTypeNode::new(vec![NumberTypeNode::le(U16).into()]),
+                    )
+                    .into(),
+                    EnumStructVariantTypeNode::new(
+                        "jump",
+                        StructTypeNode::new(vec![StructFieldTypeNode::new(
+                            "height",
+                            NumberTypeNode::le(U8),
+                        )]),
+                    )
+                    .into(),
+                ]),
+            )],
+            ..ProgramNode::new("myProgram", "1111")
+        })
+    }
+
+    fn encode_with_root(
+        value: impl Into<ValueNode>,
+        r#type: impl Into<TypeNode>,
+    ) -> CodamaResult<Vec<u8>> {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        encode(&value.into(), &r#type.into(), &resolver)
+    }
+
+    #[test]
+    fn numbers() {
+        let n = |number: Number| NumberValueNode::new(number);
+        assert_eq!(
+            encode_with_root(n(42u8.into()), NumberTypeNode::le(U8)).unwrap(),
+            vec![42]
+        );
+        assert_eq!(
+            encode_with_root(n(258u16.into()), NumberTypeNode::le(U16)).unwrap(),
+            vec![2, 1]
+        );
+        assert_eq!(
+            encode_with_root(n(258u16.into()), NumberTypeNode::be(U32)).unwrap(),
+            vec![0, 0, 1, 2]
+        );
+        assert_eq!(
+            encode_with_root(n((-2i16).into()), NumberTypeNode::le(I16)).unwrap(),
+            vec![0xfe, 0xff]
+        );
+        assert_eq!(
+            encode_with_root(n(1.5f64.into()), NumberTypeNode::le(F64)).unwrap(),
+            1.5f64.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn short_u16() {
+        let n = |number: u16| NumberValueNode::new(number);
+        let short_u16 = || NumberTypeNode::le(ShortU16);
+        assert_eq!(encode_with_root(n(0), short_u16()).unwrap(), vec![0]);
+        assert_eq!(encode_with_root(n(127), short_u16()).unwrap(), vec![0x7f]);
+        assert_eq!(
+            encode_with_root(n(128), short_u16()).unwrap(),
+            vec![0x80, 0x01]
+        );
+        assert_eq!(
+            encode_with_root(n(u16::MAX), short_u16()).unwrap(),
+            vec![0xff, 0xff, 0x03]
+        );
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        let error = encode_with_root(NumberValueNode::new(256u16), NumberTypeNode::le(U8));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Could not encode value at `$`: number 256 does not fit in `U8`"
+        );
+    }
+
+    #[test]
+    fn strings_bytes_and_public_keys() {
+        assert_eq!(
+            encode_with_root(StringValueNode::new("hi"), StringTypeNode::utf8()).unwrap(),
+            b"hi".to_vec()
+        );
+        assert_eq!(
+            encode_with_root(BytesValueNode::base16("dead"), BytesTypeNode::new()).unwrap(),
+            vec![0xde, 0xad]
+        );
+        assert_eq!(
+            encode_with_root(
+                PublicKeyValueNode::new("11111111111111111111111111111111"),
+                PublicKeyTypeNode::new()
+            )
+            .unwrap(),
+            vec![0; 32]
+        );
+        assert_eq!(
+            encode_with_root(BooleanValueNode::new(true), BooleanTypeNode::default()).unwrap(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn size_prefix_and_fixed_size() {
+        let node =
+            SizePrefixTypeNode::<TypeNode>::new(StringTypeNode::utf8(), NumberTypeNode::le(U32));
+        assert_eq!(
+            encode_with_root(StringValueNode::new("abc"), node).unwrap(),
+            vec![3, 0, 0, 0, b'a', b'b', b'c']
+        );
+        let node = FixedSizeTypeNode::<TypeNode>::new(StringTypeNode::utf8(), 5);
+        assert_eq!(
+            encode_with_root(StringValueNode::new("abc"), node).unwrap(),
+            vec![b'a', b'b', b'c', 0, 0]
+        );
+        let node = FixedSizeTypeNode::<TypeNode>::new(StringTypeNode::utf8(), 2);
+        assert_eq!(
+            encode_with_root(StringValueNode::new("abc"), node).unwrap(),
+            vec![b'a', b'b']
+        );
+    }
+
+    #[test]
+    fn sentinel_and_hidden_prefix() {
+        let node = SentinelTypeNode::<TypeNode>::new(
+            StringTypeNode::utf8(),
+            ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+        );
+        assert_eq!(
+            encode_with_root(StringValueNode::new("ab"), node.clone()).unwrap(),
+            vec![b'a', b'b', 0xff]
+        );
+        assert!(encode_with_root(
+            StringValueNode::new("ab"),
+            SentinelTypeNode::<TypeNode>::new(
+                StringTypeNode::utf8(),
+                ConstantValueNode::bytes(BytesEncoding::Utf8, "b"),
+            )
+        )
+        .is_err());
+
+        let node = HiddenPrefixTypeNode::<TypeNode>::new(
+            NumberTypeNode::le(U8),
+            vec![ConstantValueNode::bytes(BytesEncoding::Base16, "0102")],
+        );
+        assert_eq!(
+            encode_with_root(NumberValueNode::new(3u8), node).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        let node = PreOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U8), 2);
+        assert_eq!(
+            encode_with_root(NumberValueNode::new(7u8), node).unwrap(),
+            vec![0, 0, 7]
+        );
+        let node = PostOffsetTypeNode::<TypeNode>::padded(NumberTypeNode::le(U8), 2);
+        assert_eq!(
+            encode_with_root(NumberValueNode::new(7u8), node).unwrap(),
+            vec![7, 0, 0]
+        );
+
+        // Write the second item over the first one.
+        let node = TupleTypeNode::new(vec![
+            NumberTypeNode::le(U16).into(),
+            PreOffsetTypeNode::<TypeNode>::relative(NumberTypeNode::le(U8), -2).into(),
+        ]);
+        let value = TupleValueNode::new(vec![
+            NumberValueNode::new(0xffffu16).into(),
+            NumberValueNode::new(1u8).into(),
+        ]);
+        assert_eq!(encode_with_root(value, node).unwrap(), vec![1, 0xff]);
+
+        // Move one byte after the start of the first item.
+        let node = TupleTypeNode::new(vec![
+            PostOffsetTypeNode::<TypeNode>::pre_offset(NumberTypeNode::le(U16), 1).into(),
+            NumberTypeNode::le(U8).into(),
+        ]);
+        let value = TupleValueNode::new(vec![
+            NumberValueNode::new(0xffffu16).into(),
+            NumberValueNode::new(1u8).into(),
+        ]);
+        assert_eq!(encode_with_root(value, node).unwrap(), vec![0xff, 1]);
+    }
+
+    #[test]
+    fn options() {
+        let node = OptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(
+            encode_with_root(NoneValueNode::new(), node.clone()).unwrap(),
+            vec![0]
+        );
+        assert_eq!(
+            encode_with_root(SomeValueNode::new(NumberValueNode::new(5u16)), node).unwrap(),
+            vec![1, 5, 0]
+        );
+        let node = OptionTypeNode::fixed(NumberTypeNode::le(U16));
+        assert_eq!(
+            encode_with_root(NoneValueNode::new(), node).unwrap(),
+            vec![0, 0, 0]
+        );
+        let node = ZeroableOptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(
+            encode_with_root(NoneValueNode::new(), node).unwrap(),
+            vec![0, 0]
+        );
+        let node = RemainderOptionTypeNode::new(NumberTypeNode::le(U16));
+        assert_eq!(
+            encode_with_root(NoneValueNode::new(), node).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn arrays_and_maps() {
+        let value = ArrayValueNode::new(vec![
+            NumberValueNode::new(1u8).into(),
+            NumberValueNode::new(2u8).into(),
+        ]);
+        let node = ArrayTypeNode::new(
+            NumberTypeNode::le(U8),
+            PrefixedCountNode::new(NumberTypeNode::le(U32)),
+        );
+        assert_eq!(
+            encode_with_root(value.clone(), node).unwrap(),
+            vec![2, 0, 0, 0, 1, 2]
+        );
+        let node = ArrayTypeNode::fixed(NumberTypeNode::le(U8), 3);
+        assert_eq!(
+            encode_with_root(value, node).unwrap_err().to_string(),
+            "Could not encode value at `$`: expected 3 items, found 2"
+        );
+
+        let value = MapValueNode::new(vec![MapEntryValueNode::new(
+            StringValueNode::new("a"),
+            NumberValueNode::new(1u8),
+        )]);
+        let node = MapTypeNode::new(
+            StringTypeNode::utf8(),
+            NumberTypeNode::le(U8),
+            crate::RemainderCountNode::new(),
+        );
+        assert_eq!(encode_with_root(value, node).unwrap(), vec![b'a', 1]);
+    }
+
+    #[test]
+    fn structs() {
+        let node = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("a", NumberTypeNode::le(U8)),
+            StructFieldTypeNode {
+                default_value: Box::new(Some(NumberValueNode::new(9u16).into())),
+                ..StructFieldTypeNode::new("b", NumberTypeNode::le(U16))
+            },
+        ]);
+        let value = StructValueNode::new(vec![StructFieldValueNode::new(
+            "a",
+            NumberValueNode::new(1u8),
+        )]);
+        assert_eq!(
+            encode_with_root(value, node.clone()).unwrap(),
+            vec![1, 9, 0]
+        );
+
+        let value = StructValueNode::new(vec![StructFieldValueNode::new(
+            "a",
+            StringValueNode::new("nope"),
+        )]);
+        assert_eq!(
+            encode_with_root(value, node).unwrap_err().to_string(),
+            "Could not encode value at `$.a`: expected `numberValueNode`, found `stringValueNode`"
+        );
+    }
+
+    #[test]
+    fn enums_through_links() {
+        let link = || DefinedTypeLinkNode::new("direction");
+        assert_eq!(
+            encode_with_root(EnumValueNode::empty(link(), "up"), link()).unwrap(),
+            vec![0]
+        );
+        assert_eq!(
+            encode_with_root(
+                EnumValueNode::tuple(
+                    link(),
+                    "move",
+                    TupleValueNode::new(vec![NumberValueNode::new(3u16).into()])
+                ),
+                link()
+            )
+            .unwrap(),
+            vec![1, 3, 0]
+        );
+        assert_eq!(
+            encode_with_root(
+                EnumValueNode::fields(
+                    link(),
+                    "jump",
+                    StructValueNode::new(vec![StructFieldValueNode::new(
+                        "height",
+                        NumberValueNode::new(4u8)
+                    )])
+                ),
+                link()
+            )
+            .unwrap(),
+            vec![2, 4]
+        );
+        assert!(encode_with_root(EnumValueNode::empty(link(), "down"), link()).is_err());
+    }
+}
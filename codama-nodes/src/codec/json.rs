@@ -0,0 +1,903 @@
+use crate::{
+    check_number, decode_public_key, ArrayValueNode, BooleanValueNode, BytesEncoding,
+    BytesValueNode, CamelCaseString, CountNode, DefinedTypeLinkNode, EnumTypeNode, EnumValueNode,
+    EnumValuePayload, EnumVariantTypeNode, HasKind, HasName, LinkResolver, MapEntryValueNode,
+    MapValueNode, NestedTypeNodeTrait, NoneValueNode, Number, NumberFormat, NumberTypeNode,
+    NumberValueNode, PublicKeyValueNode, SetValueNode, SomeValueNode, StringValueNode,
+    StructFieldValueNode, StructTypeNode, StructValueNode, TupleTypeNode, TupleValueNode, TypeNode,
+    ValueNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+use serde_json::{Map, Value};
+
+/// Converts a plain JSON value into a `ValueNode` shaped after the given type.
+///
+/// Numbers may be given as JSON numbers or strings, public keys as base58
+/// strings, bytes as `{ "encoding": "base64", "data": "..." }` objects or
+/// arrays of bytes, options as `null` or their inner value, enums as the
+/// name of an empty variant or a `{ "variant": payload }` object and maps
+/// as objects or arrays of `[key, value]` pairs. Missing struct fields
+/// use the default value of the field, if any.
+///
+/// Number value nodes hold 64-bit integers so `u128` and `i128` values
+/// are limited to the ranges of `u64` and `i64` respectively.
+pub fn value_from_json(
+    json: &Value,
+    r#type: &TypeNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<ValueNode> {
+    JsonConverter::new(*resolver).read(json, r#type)
+}
+
+/// Converts a `ValueNode` into a plain JSON value following the
+/// conventions of `value_from_json`, so both functions round-trip.
+/// Integers beyond 2^53 are written as strings.
+pub fn value_to_json(
+    value: &ValueNode,
+    r#type: &TypeNode,
+    resolver: &LinkResolver,
+) -> CodamaResult<Value> {
+    JsonConverter::new(*resolver).write(value, r#type)
+}
+
+/// The largest integer JSON parsers can read without losing precision, i.e. 2^53 - 1.
+pub(crate) const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+struct JsonConverter<'a> {
+    resolver: LinkResolver<'a>,
+    /// Segments of the path to the value being converted, e.g. `.items` and `[2]`.
+    path: Vec<String>,
+}
+
+impl<'a> JsonConverter<'a> {
+    fn new(resolver: LinkResolver<'a>) -> Self {
+        Self {
+            resolver,
+            path: Vec::new(),
+        }
+    }
+
+    fn with_segment<T>(
+        &mut self,
+        segment: String,
+        f: impl FnOnce(&mut Self) -> CodamaResult<T>,
+    ) -> CodamaResult<T> {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    /// Run `f` with the defined type the link points to and the resolver of its program.
+    fn with_link<T>(
+        &mut self,
+        link: &DefinedTypeLinkNode,
+        f: impl FnOnce(&mut Self, &'a TypeNode) -> CodamaResult<T>,
+    ) -> CodamaResult<T> {
+        let parent_resolver = self.resolver;
+        let resolver = match &link.program {
+            Some(program) => parent_resolver.with_program(
+                parent_resolver
+                    .resolve_program(program)
+                    .map_err(|error| self.error(error.to_string()))?,
+            ),
+            None => parent_resolver,
+        };
+        let defined_type = resolver
+            .resolve_defined_type(link)
+            .map_err(|error| self.error(error.to_string()))?;
+
+        self.resolver = resolver;
+        let result = f(self, defined_type.r#type.as_ref());
+        self.resolver = parent_resolver;
+        result
+    }
+
+    fn error<T: Into<String>>(&self, message: T) -> CodamaError {
+        CodamaError::JsonConversionFailed {
+            path: format!("${}", self.path.concat()),
+            message: message.into(),
+        }
+    }
+
+    fn unexpected_json(&self, expected: &str, json: &Value) -> CodamaError {
+        let actual = match json {
+            Value::Null => "null",
+            Value::Bool(_) => "a boolean",
+            Value::Number(_) => "a number",
+            Value::String(_) => "a string",
+            Value::Array(_) => "an array",
+            Value::Object(_) => "an object",
+        };
+        self.error(format!("expected {expected}, found {actual}"))
+    }
+
+    fn unexpected_value(&self, expected: &str, value: &ValueNode) -> CodamaError {
+        self.error(format!("expected `{expected}`, found `{}`", value.kind()))
+    }
+
+    fn read(&mut self, json: &Value, r#type: &TypeNode) -> CodamaResult<ValueNode> {
+        match r#type {
+            TypeNode::Amount(node) => self.read_number(json, &node.number),
+            TypeNode::DateTime(node) => self.read_number(json, &node.number),
+            TypeNode::SolAmount(node) => self.read_number(json, &node.number),
+            TypeNode::Number(node) => {
+                let number = self.parse_number(json, node.format)?;
+                Ok(NumberValueNode::new(number).into())
+            }
+            TypeNode::Boolean(_) => match json {
+                Value::Bool(boolean) => Ok(BooleanValueNode::new(*boolean).into()),
+                _ => Err(self.unexpected_json("a boolean", json)),
+            },
+            TypeNode::String(_) => match json {
+                Value::String(string) => Ok(StringValueNode::new(string.as_str()).into()),
+                _ => Err(self.unexpected_json("a string", json)),
+            },
+            TypeNode::Bytes(_) => self.read_bytes(json),
+            TypeNode::PublicKey(_) => match json {
+                Value::String(public_key) => {
+                    decode_public_key(public_key).map_err(|message| self.error(message))?;
+                    Ok(PublicKeyValueNode::new(public_key.as_str()).into())
+                }
+                _ => Err(self.unexpected_json("a base58 public key", json)),
+            },
+            TypeNode::Option(node) => self.read_option(json, &node.item),
+            TypeNode::ZeroableOption(node) => self.read_option(json, &node.item),
+            TypeNode::RemainderOption(node) => self.read_option(json, &node.item),
+            TypeNode::Array(node) => {
+                let items = self.read_items(json, &node.item, &node.count)?;
+                Ok(ArrayValueNode::new(items).into())
+            }
+            TypeNode::Set(node) => {
+                let items = self.read_items(json, &node.item, &node.count)?;
+                Ok(SetValueNode::new(items).into())
+            }
+            TypeNode::Map(node) => {
+                let entries = match json {
+                    Value::Object(map) => map
+                        .iter()
+                        .map(|(key, value)| {
+                            self.with_segment(format!(".{key}"), |this| {
+                                let key = this.read(&Value::String(key.clone()), &node.key)?;
+                                let value = this.read(value, &node.value)?;
+                                Ok(MapEntryValueNode::new(key, value))
+                            })
+                        })
+                        .collect::<CodamaResult<Vec<_>>>()?,
+                    Value::Array(entries) => self.indexed(entries, |this, entry| match entry {
+                        Value::Array(pair) if pair.len() == 2 => {
+                            let key = this.read(&pair[0], &node.key)?;
+                            let value = this.read(&pair[1], &node.value)?;
+                            Ok(MapEntryValueNode::new(key, value))
+                        }
+                        _ => Err(this.unexpected_json("a `[key, value]` pair", entry)),
+                    })?,
+                    _ => Err(self.unexpected_json("an object or an array of entries", json))?,
+                };
+                Ok(MapValueNode::new(entries).into())
+            }
+            TypeNode::Tuple(node) => Ok(self.read_tuple(json, node)?.into()),
+            TypeNode::Struct(node) => Ok(self.read_struct(json, node)?.into()),
+            TypeNode::Enum(node) => self.read_enum(json, node, None),
+            TypeNode::FixedSize(node) => self.read(json, &node.r#type),
+            TypeNode::HiddenPrefix(node) => self.read(json, &node.r#type),
+            TypeNode::HiddenSuffix(node) => self.read(json, &node.r#type),
+            TypeNode::PostOffset(node) => self.read(json, &node.r#type),
+            TypeNode::PreOffset(node) => self.read(json, &node.r#type),
+            TypeNode::Sentinel(node) => self.read(json, &node.r#type),
+            TypeNode::SizePrefix(node) => self.read(json, &node.r#type),
+            TypeNode::Unknown(node) => {
+                Err(self.error(format!("cannot convert unknown type `{}`", node.kind)))
+            }
+            TypeNode::Link(link) => self.with_link(link, |this, r#type| match r#type {
+                TypeNode::Enum(node) => this.read_enum(json, node, Some(link)),
+                node => this.read(json, node),
+            }),
+        }
+    }
+
+    fn write(&mut self, value: &ValueNode, r#type: &TypeNode) -> CodamaResult<Value> {
+        match r#type {
+            TypeNode::Amount(_) => self.write_number(value),
+            TypeNode::DateTime(_) => self.write_number(value),
+            TypeNode::SolAmount(_) => self.write_number(value),
+            TypeNode::Number(_) => self.write_number(value),
+            TypeNode::Boolean(_) => match value {
+                ValueNode::Boolean(value) => Ok(Value::Bool(value.boolean)),
+                _ => Err(self.unexpected_value("booleanValueNode", value)),
+            },
+            TypeNode::String(_) => match value {
+                ValueNode::String(value) => Ok(Value::String(value.string.clone())),
+                _ => Err(self.unexpected_value("stringValueNode", value)),
+            },
+            TypeNode::Bytes(_) => match value {
+                ValueNode::Bytes(value) => Ok(serde_json::json!({
+                    "encoding": value.encoding.as_str(),
+                    "data": value.data,
+                })),
+                _ => Err(self.unexpected_value("bytesValueNode", value)),
+            },
+            TypeNode::PublicKey(_) => match value {
+                ValueNode::PublicKey(value) => Ok(Value::String(value.public_key.clone())),
+                _ => Err(self.unexpected_value("publicKeyValueNode", value)),
+            },
+            TypeNode::Option(node) => self.write_option(value, &node.item),
+            TypeNode::ZeroableOption(node) => self.write_option(value, &node.item),
+            TypeNode::RemainderOption(node) => self.write_option(value, &node.item),
+            TypeNode::Array(node) => match value {
+                ValueNode::Array(value) => self.write_items(&value.items, &node.item),
+                _ => Err(self.unexpected_value("arrayValueNode", value)),
+            },
+            TypeNode::Set(node) => match value {
+                ValueNode::Set(value) => self.write_items(&value.items, &node.item),
+                _ => Err(self.unexpected_value("setValueNode", value)),
+            },
+            TypeNode::Map(node) => {
+                let ValueNode::Map(value) = value else {
+                    return Err(self.unexpected_value("mapValueNode", value));
+                };
+                let entries = self.indexed(&value.entries, |this, entry| {
+                    let key = this.write(&entry.key, &node.key)?;
+                    let value = this.write(&entry.value, &node.value)?;
+                    Ok((key, value))
+                })?;
+                // Use an object when all keys can be represented as strings.
+                let keys = entries
+                    .iter()
+                    .map(|(key, _)| match key {
+                        Value::String(key) => Some(key.clone()),
+                        Value::Number(key) => Some(key.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                Ok(match keys {
+                    Some(keys) => Value::Object(
+                        keys.into_iter()
+                            .zip(entries)
+                            .map(|(key, (_, value))| (key, value))
+                            .collect(),
+                    ),
+                    None => Value::Array(
+                        entries
+                            .into_iter()
+                            .map(|(key, value)| Value::Array(vec![key, value]))
+                            .collect(),
+                    ),
+                })
+            }
+            TypeNode::Tuple(node) => match value {
+                ValueNode::Tuple(value) => self.write_tuple(value, node),
+                _ => Err(self.unexpected_value("tupleValueNode", value)),
+            },
+            TypeNode::Struct(node) => match value {
+                ValueNode::Struct(value) => self.write_struct(value, node),
+                _ => Err(self.unexpected_value("structValueNode", value)),
+            },
+            TypeNode::Enum(node) => self.write_enum(value, node),
+            TypeNode::FixedSize(node) => self.write(value, &node.r#type),
+            TypeNode::HiddenPrefix(node) => self.write(value, &node.r#type),
+            TypeNode::HiddenSuffix(node) => self.write(value, &node.r#type),
+            TypeNode::PostOffset(node) => self.write(value, &node.r#type),
+            TypeNode::PreOffset(node) => self.write(value, &node.r#type),
+            TypeNode::Sentinel(node) => self.write(value, &node.r#type),
+            TypeNode::SizePrefix(node) => self.write(value, &node.r#type),
+            TypeNode::Unknown(node) => {
+                Err(self.error(format!("cannot convert unknown type `{}`", node.kind)))
+            }
+            TypeNode::Link(link) => self.with_link(link, |this, r#type| this.write(value, r#type)),
+        }
+    }
+
+    /// Convert every item of a list, adding its index to the path.
+    fn indexed<T, U>(
+        &mut self,
+        items: &[T],
+        mut f: impl FnMut(&mut Self, &T) -> CodamaResult<U>,
+    ) -> CodamaResult<Vec<U>> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.with_segment(format!("[{index}]"), |this| f(this, item)))
+            .collect()
+    }
+
+    fn read_number<T: NestedTypeNodeTrait<NumberTypeNode>>(
+        &self,
+        json: &Value,
+        r#type: &T,
+    ) -> CodamaResult<ValueNode> {
+        let number = self.parse_number(json, r#type.get_nested_type_node().format)?;
+        Ok(NumberValueNode::new(number).into())
+    }
+
+    /// Integers beyond 2^53 are written as strings since
+    /// JSON parsers often lose precision on larger integers.
+    fn write_number(&self, value: &ValueNode) -> CodamaResult<Value> {
+        let ValueNode::Number(value) = value else {
+            return Err(self.unexpected_value("numberValueNode", value));
+        };
+        Ok(match value.number {
+            Number::UnsignedInteger(number) if number > MAX_SAFE_INTEGER => {
+                Value::String(number.to_string())
+            }
+            Number::SignedInteger(number) if number.unsigned_abs() > MAX_SAFE_INTEGER => {
+                Value::String(number.to_string())
+            }
+            number => Value::Number(number.into()),
+        })
+    }
+
+    /// Numbers can also be given as strings since JSON parsers
+    /// often lose precision on integers larger than 2^53.
+    fn parse_number(&self, json: &Value, format: NumberFormat) -> CodamaResult<Number> {
+        let number = match json {
+            Value::Number(number) => Number::from(number.clone()),
+            Value::String(string) => {
+                let string = string.trim();
+                if let Ok(number) = string.parse::<u64>() {
+                    Number::UnsignedInteger(number)
+                } else if let Ok(number) = string.parse::<i64>() {
+                    Number::SignedInteger(number)
+                } else if string.parse::<i128>().is_ok() || string.parse::<u128>().is_ok() {
+                    return Err(self.error(format!(
+                        "number `{string}` does not fit in 64 bits, which is the limit of number value nodes"
+                    )));
+                } else if let Ok(number) = string.parse::<f64>() {
+                    Number::Float(number)
+                } else {
+                    return Err(self.error(format!("invalid number `{string}`")));
+                }
+            }
+            _ => return Err(self.unexpected_json("a number", json)),
+        };
+
+        if let NumberFormat::F32 | NumberFormat::F64 = format {
+            return Ok(match number {
+                Number::UnsignedInteger(value) => Number::Float(value as f64),
+                Number::SignedInteger(value) => Number::Float(value as f64),
+                number => number,
+            });
+        }
+        check_number(number, format).map_err(|message| self.error(message))?;
+        Ok(number)
+    }
+
+    fn read_bytes(&self, json: &Value) -> CodamaResult<ValueNode> {
+        match json {
+            Value::Object(object) => {
+                let (Some(Value::String(encoding)), Some(Value::String(data)), 2) =
+                    (object.get("encoding"), object.get("data"), object.len())
+                else {
+                    return Err(self.error("expected an object with `encoding` and `data` strings"));
+                };
+                let encoding = BytesEncoding::try_from(encoding.as_str())
+                    .map_err(|error| self.error(error.to_string()))?;
+                encoding
+                    .decode(data)
+                    .map_err(|error| self.error(error.to_string()))?;
+                Ok(BytesValueNode::new(encoding, data.as_str()).into())
+            }
+            Value::Array(items) => {
+                let bytes = items
+                    .iter()
+                    .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| self.error("expected an array of bytes"))?;
+                let data = BytesEncoding::Base16
+                    .encode(&bytes)
+                    .map_err(|error| self.error(error.to_string()))?;
+                Ok(BytesValueNode::base16(data).into())
+            }
+            _ => Err(self.unexpected_json("an object or an array of bytes", json)),
+        }
+    }
+
+    fn read_option(&mut self, json: &Value, item: &TypeNode) -> CodamaResult<ValueNode> {
+        match json {
+            Value::Null => Ok(NoneValueNode::new().into()),
+            json => Ok(SomeValueNode::new(self.read(json, item)?).into()),
+        }
+    }
+
+    fn write_option(&mut self, value: &ValueNode, item: &TypeNode) -> CodamaResult<Value> {
+        match value {
+            ValueNode::None(_) => Ok(Value::Null),
+            ValueNode::Some(value) => self.write(&value.value, item),
+            _ => Err(self.unexpected_value("someValueNode", value)),
+        }
+    }
+
+    fn read_items(
+        &mut self,
+        json: &Value,
+        item: &TypeNode,
+        count: &CountNode,
+    ) -> CodamaResult<Vec<ValueNode>> {
+        let Value::Array(items) = json else {
+            return Err(self.unexpected_json("an array", json));
+        };
+        if let CountNode::Fixed(count) = count {
+            if items.len() as u64 != count.value {
+                return Err(self.error(format!(
+                    "expected {} items, found {}",
+                    count.value,
+                    items.len()
+                )));
+            }
+        }
+        self.indexed(items, |this, json| this.read(json, item))
+    }
+
+    fn write_items(&mut self, items: &[ValueNode], item: &TypeNode) -> CodamaResult<Value> {
+        let items = self.indexed(items, |this, value| this.write(value, item))?;
+        Ok(Value::Array(items))
+    }
+
+    fn read_tuple(&mut self, json: &Value, node: &TupleTypeNode) -> CodamaResult<TupleValueNode> {
+        let Value::Array(items) = json else {
+            return Err(self.unexpected_json("an array", json));
+        };
+        if items.len() != node.items.len() {
+            return Err(self.error(format!(
+                "expected {} items, found {}",
+                node.items.len(),
+                items.len()
+            )));
+        }
+        let items = items
+            .iter()
+            .zip(&node.items)
+            .enumerate()
+            .map(|(index, (json, r#type))| {
+                self.with_segment(format!("[{index}]"), |this| this.read(json, r#type))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(TupleValueNode::new(items))
+    }
+
+    fn write_tuple(&mut self, value: &TupleValueNode, node: &TupleTypeNode) -> CodamaResult<Value> {
+        if value.items.len() != node.items.len() {
+            return Err(self.error(format!(
+                "expected {} items, found {}",
+                node.items.len(),
+                value.items.len()
+            )));
+        }
+        let items = value
+            .items
+            .iter()
+            .zip(&node.items)
+            .enumerate()
+            .map(|(index, (value, r#type))| {
+                self.with_segment(format!("[{index}]"), |this| this.write(value, r#type))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(Value::Array(items))
+    }
+
+    fn read_struct(
+        &mut self,
+        json: &Value,
+        node: &StructTypeNode,
+    ) -> CodamaResult<StructValueNode> {
+        let Value::Object(object) = json else {
+            return Err(self.unexpected_json("an object", json));
+        };
+        if let Some(unknown) = object.keys().find(|key| {
+            !node
+                .fields
+                .iter()
+                .any(|f| f.name == CamelCaseString::new(*key))
+        }) {
+            return Err(self.error(format!("unknown field `{unknown}`")));
+        }
+        let fields = node
+            .fields
+            .iter()
+            .map(|field| {
+                let json = object
+                    .iter()
+                    .find(|(key, _)| CamelCaseString::new(*key) == field.name)
+                    .map(|(_, json)| json);
+                let value = match (json, field.default_value.as_ref()) {
+                    (Some(json), _) => self
+                        .with_segment(format!(".{}", field.name.as_str()), |this| {
+                            this.read(json, &field.r#type)
+                        })?,
+                    (None, Some(default_value)) => default_value.clone(),
+                    (None, None) => {
+                        return Err(self.error(format!("missing field `{}`", field.name.as_str())))
+                    }
+                };
+                Ok(StructFieldValueNode::new(field.name.clone(), value))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(StructValueNode::new(fields))
+    }
+
+    fn write_struct(
+        &mut self,
+        value: &StructValueNode,
+        node: &StructTypeNode,
+    ) -> CodamaResult<Value> {
+        if let Some(unknown) = value
+            .fields
+            .iter()
+            .find(|field| !node.fields.iter().any(|f| f.name == field.name))
+        {
+            return Err(self.error(format!("unknown field `{}`", unknown.name.as_str())));
+        }
+        let mut object = Map::new();
+        for field in &node.fields {
+            let field_value = value
+                .fields
+                .iter()
+                .find(|f| f.name == field.name)
+                .map(|f| f.value.as_ref())
+                .or(field.default_value.as_ref().as_ref());
+            let Some(field_value) = field_value else {
+                return Err(self.error(format!("missing field `{}`", field.name.as_str())));
+            };
+            let json = self.with_segment(format!(".{}", field.name.as_str()), |this| {
+                this.write(field_value, &field.r#type)
+            })?;
+            object.insert(field.name.to_string(), json);
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Enum values reference their defined type so we forward the
+    /// link that led to the enum, which inline enums do not have.
+    fn read_enum(
+        &mut self,
+        json: &Value,
+        node: &EnumTypeNode,
+        link: Option<&DefinedTypeLinkNode>,
+    ) -> CodamaResult<ValueNode> {
+        let Some(link) = link else {
+            return Err(self.error("inline enums must be converted through a defined type link"));
+        };
+        let (name, payload) = match json {
+            Value::String(name) => (name, None),
+            Value::Object(object) if object.len() == 1 => {
+                let (name, payload) = object.iter().next().unwrap();
+                (name, Some(payload))
+            }
+            _ => {
+                return Err(
+                    self.unexpected_json("a variant name or an object with a single variant", json)
+                )
+            }
+        };
+        let variant_name = CamelCaseString::new(name);
+        let Some(variant) = node.variants.iter().find(|v| *v.name() == variant_name) else {
+            return Err(self.error(format!("unknown variant `{name}`")));
+        };
+
+        let payload = self.with_segment(format!(".{}", variant_name.as_str()), |this| {
+            Ok(match (variant, payload) {
+                (EnumVariantTypeNode::Empty(_), None | Some(Value::Null)) => None,
+                (EnumVariantTypeNode::Struct(variant), Some(json)) => Some(
+                    EnumValuePayload::Struct(
+                        this.read_struct(json, variant.r#struct.get_nested_type_node())?,
+                    ),
+                ),
+                (EnumVariantTypeNode::Tuple(variant), Some(json)) => Some(EnumValuePayload::Tuple(
+                    this.read_tuple(json, variant.tuple.get_nested_type_node())?,
+                )),
+                (EnumVariantTypeNode::Empty(_), Some(_)) => {
+                    return Err(this.error("empty variants cannot have a payload"))
+                }
+                (_, None) => return Err(this.error("missing variant payload")),
+            })
+        })?;
+
+        Ok(EnumValueNode::new(link.clone(), variant_name, payload).into())
+    }
+
+    fn write_enum(&mut self, value: &ValueNode, node: &EnumTypeNode) -> CodamaResult<Value> {
+        let ValueNode::Enum(value) = value else {
+            return Err(self.unexpected_value("enumValueNode", value));
+        };
+        let Some(variant) = node.variants.iter().find(|v| *v.name() == value.variant) else {
+            return Err(self.error(format!("unknown variant `{}`", value.variant.as_str())));
+        };
+
+        let name = value.variant.to_string();
+        self.with_segment(format!(".{name}"), |this| {
+            let payload = match (variant, value.value.as_ref()) {
+                (EnumVariantTypeNode::Empty(_), None) => return Ok(Value::String(name)),
+                (EnumVariantTypeNode::Struct(variant), Some(EnumValuePayload::Struct(payload))) => {
+                    this.write_struct(payload, variant.r#struct.get_nested_type_node())?
+                }
+                (EnumVariantTypeNode::Tuple(variant), Some(EnumValuePayload::Tuple(payload))) => {
+                    this.write_tuple(payload, variant.tuple.get_nested_type_node())?
+                }
+                _ => return Err(this.error("variant payload does not match its type")),
+            };
+            Ok(Value::Object(Map::from_iter([(name, payload)])))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArrayTypeNode, BooleanTypeNode, BytesTypeNode, DefinedTypeNode, EnumEmptyVariantTypeNode,
+        EnumStructVariantTypeNode, EnumTupleVariantTypeNode, FixedSizeTypeNode, MapTypeNode,
+        OptionTypeNode, PrefixedCountNode, ProgramNode, PublicKeyTypeNode, RootNode,
+        StringTypeNode, StructFieldTypeNode, I128, I64, U128, U16, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new(
+                "direction",
+                EnumTypeNode::new(vec![
+                    EnumEmptyVariantTypeNode::new("up").into(),
+                    EnumTupleVariantTypeNode::new(
+                        "move",
+                        TupleTypeNode::new(vec![NumberTypeNode::le(U16).into()]),
+                    )
+                    .into(),
+                    EnumStructVariantTypeNode::new(
+                        "jump",
+                        StructTypeNode::new(vec![StructFieldTypeNode::new(
+                            "height",
+                            NumberTypeNode::le(U8),
+                        )]),
+                    )
+                    .into(),
+                ]),
+            )],
+            ..ProgramNode::new("myProgram", "1111")
+        })
+    }
+
+    fn from_json(json: Value, r#type: impl Into<TypeNode>) -> CodamaResult<ValueNode> {
+        let root = get_root();
+        value_from_json(&json, &r#type.into(), &LinkResolver::new(&root))
+    }
+
+    /// Convert the given JSON and check that converting the result back gives the same JSON.
+    fn round_trip(json: Value, r#type: impl Into<TypeNode>) -> ValueNode {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let r#type = r#type.into();
+        let value = value_from_json(&json, &r#type, &resolver).unwrap();
+        assert_eq!(value_to_json(&value, &r#type, &resolver).unwrap(), json);
+        value
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            round_trip(json!(42), NumberTypeNode::le(U16)),
+            NumberValueNode::new(42u16).into()
+        );
+        assert_eq!(
+            round_trip(json!("18446744073709551615"), NumberTypeNode::le(U64)),
+            NumberValueNode::new(u64::MAX).into()
+        );
+        assert_eq!(
+            round_trip(json!(9007199254740991u64), NumberTypeNode::le(U64)),
+            NumberValueNode::new(9007199254740991u64).into()
+        );
+        assert_eq!(
+            round_trip(json!("9007199254740992"), NumberTypeNode::le(U64)),
+            NumberValueNode::new(9007199254740992u64).into()
+        );
+        assert_eq!(
+            round_trip(json!("-9007199254740992"), NumberTypeNode::le(I64)),
+            NumberValueNode::new(-9007199254740992i64).into()
+        );
+        assert_eq!(
+            from_json(json!("42"), NumberTypeNode::le(U64)).unwrap(),
+            NumberValueNode::new(42u64).into()
+        );
+        assert_eq!(
+            from_json(json!(256), NumberTypeNode::le(U8))
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: number 256 is out of range [0, 255]"
+        );
+        assert_eq!(
+            from_json(json!(1.5), NumberTypeNode::le(U32))
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: expected an integer, found 1.5"
+        );
+    }
+
+    #[test]
+    fn large_numbers() {
+        assert_eq!(
+            round_trip(json!("18446744073709551615"), NumberTypeNode::le(U128)),
+            NumberValueNode::new(u64::MAX).into()
+        );
+        assert_eq!(
+            round_trip(json!("-9223372036854775808"), NumberTypeNode::le(I128)),
+            NumberValueNode::new(i64::MIN).into()
+        );
+        assert_eq!(
+            from_json(json!("18446744073709551616"), NumberTypeNode::le(U128))
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: number `18446744073709551616` does not fit in 64 bits, which is the limit of number value nodes"
+        );
+        assert_eq!(
+            from_json(json!("-9223372036854775809"), NumberTypeNode::le(I128))
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: number `-9223372036854775809` does not fit in 64 bits, which is the limit of number value nodes"
+        );
+    }
+
+    #[test]
+    fn strings_bytes_and_public_keys() {
+        assert_eq!(
+            round_trip(json!("hi"), StringTypeNode::utf8()),
+            StringValueNode::new("hi").into()
+        );
+        assert_eq!(
+            round_trip(json!(true), BooleanTypeNode::default()),
+            BooleanValueNode::new(true).into()
+        );
+        assert_eq!(
+            round_trip(
+                json!({ "encoding": "base64", "data": "aGk=" }),
+                BytesTypeNode::new()
+            ),
+            BytesValueNode::base64("aGk=").into()
+        );
+        assert_eq!(
+            from_json(json!([222, 173]), BytesTypeNode::new()).unwrap(),
+            BytesValueNode::base16("dead").into()
+        );
+        assert_eq!(
+            round_trip(
+                json!("So11111111111111111111111111111111111111112"),
+                PublicKeyTypeNode::new()
+            ),
+            PublicKeyValueNode::new("So11111111111111111111111111111111111111112").into()
+        );
+        assert_eq!(
+            from_json(json!("1111"), PublicKeyTypeNode::new())
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: public key `1111` must be 32 bytes, found 4"
+        );
+    }
+
+    #[test]
+    fn structs_and_options() {
+        let node = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+            StructFieldTypeNode::new("memo", OptionTypeNode::new(StringTypeNode::utf8())),
+            StructFieldTypeNode {
+                default_value: Box::new(Some(NumberValueNode::new(9u8).into())),
+                ..StructFieldTypeNode::new("decimals", NumberTypeNode::le(U8))
+            },
+        ]);
+        assert_eq!(
+            from_json(json!({ "amount": 10, "memo": "hi" }), node.clone()).unwrap(),
+            StructValueNode::new(vec![
+                StructFieldValueNode::new("amount", NumberValueNode::new(10u64)),
+                StructFieldValueNode::new("memo", SomeValueNode::new(StringValueNode::new("hi"))),
+                StructFieldValueNode::new("decimals", NumberValueNode::new(9u8)),
+            ])
+            .into()
+        );
+        round_trip(
+            json!({ "amount": 10, "memo": null, "decimals": 2 }),
+            node.clone(),
+        );
+        assert_eq!(
+            from_json(json!({ "memo": null }), node.clone())
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: missing field `amount`"
+        );
+        assert_eq!(
+            from_json(json!({ "amount": "ten" }), node)
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$.amount`: invalid number `ten`"
+        );
+    }
+
+    #[test]
+    fn arrays_tuples_and_maps() {
+        let node = ArrayTypeNode::new(
+            FixedSizeTypeNode::<TypeNode>::new(StringTypeNode::utf8(), 4),
+            PrefixedCountNode::new(NumberTypeNode::le(U32)),
+        );
+        round_trip(json!(["a", "b"]), node);
+        assert_eq!(
+            from_json(json!([1]), ArrayTypeNode::fixed(NumberTypeNode::le(U8), 2))
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$`: expected 2 items, found 1"
+        );
+        let node = TupleTypeNode::new(vec![
+            NumberTypeNode::le(U8).into(),
+            StringTypeNode::utf8().into(),
+        ]);
+        round_trip(json!([1, "a"]), node);
+
+        let node = MapTypeNode::new(
+            StringTypeNode::utf8(),
+            NumberTypeNode::le(U8),
+            PrefixedCountNode::new(NumberTypeNode::le(U32)),
+        );
+        assert_eq!(
+            round_trip(json!({ "a": 1 }), node),
+            MapValueNode::new(vec![MapEntryValueNode::new(
+                StringValueNode::new("a"),
+                NumberValueNode::new(1u8)
+            )])
+            .into()
+        );
+        let node = MapTypeNode::new(
+            NumberTypeNode::le(U8),
+            BooleanTypeNode::default(),
+            PrefixedCountNode::new(NumberTypeNode::le(U32)),
+        );
+        assert_eq!(
+            from_json(json!([[1, true]]), node.clone()).unwrap(),
+            from_json(json!({ "1": true }), node).unwrap()
+        );
+    }
+
+    #[test]
+    fn enums_through_links() {
+        let link = || DefinedTypeLinkNode::new("direction");
+        assert_eq!(
+            round_trip(json!("up"), link()),
+            EnumValueNode::empty(link(), "up").into()
+        );
+        assert_eq!(
+            round_trip(json!({ "move": [3] }), link()),
+            EnumValueNode::tuple(
+                link(),
+                "move",
+                TupleValueNode::new(vec![NumberValueNode::new(3u16).into()])
+            )
+            .into()
+        );
+        assert_eq!(
+            round_trip(json!({ "jump": { "height": 4 } }), link()),
+            EnumValueNode::fields(
+                link(),
+                "jump",
+                StructValueNode::new(vec![StructFieldValueNode::new(
+                    "height",
+                    NumberValueNode::new(4u8)
+                )])
+            )
+            .into()
+        );
+        assert_eq!(
+            from_json(json!("down"), link()).unwrap_err().to_string(),
+            "Could not convert JSON value at `$`: unknown variant `down`"
+        );
+        assert_eq!(
+            from_json(json!({ "jump": { "height": 256 } }), link())
+                .unwrap_err()
+                .to_string(),
+            "Could not convert JSON value at `$.jump.height`: number 256 is out of range [0, 255]"
+        );
+    }
+
+    #[test]
+    fn inline_enums() {
+        let r#type = EnumTypeNode::new(vec![EnumEmptyVariantTypeNode::new("up").into()]);
+        assert_eq!(
+            from_json(json!("up"), r#type).unwrap_err().to_string(),
+            "Could not convert JSON value at `$`: inline enums must be converted through a defined type link"
+        );
+    }
+}
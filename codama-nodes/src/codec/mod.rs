@@ -0,0 +1,9 @@
+mod data_schema;
+mod decoder;
+mod encoder;
+mod json;
+
+pub use data_schema::*;
+pub use decoder::*;
+pub use encoder::*;
+pub use json::*;
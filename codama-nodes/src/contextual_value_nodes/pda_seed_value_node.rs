@@ -1,4 +1,5 @@
-use crate::{CamelCaseString, PdaSeedValueNode, PdaSeedValueValue, ValueNode};
+use crate::{CamelCaseString, HasKind, PdaSeedValueNode, PdaSeedValueValue, ValueNode};
+use codama_errors::CodamaError;
 
 impl PdaSeedValueNode {
     pub fn new<T, U>(name: T, value: U) -> Self
@@ -35,6 +36,36 @@ impl From<ValueNode> for PdaSeedValueValue {
             ValueNode::String(value) => Self::String(value),
             ValueNode::Struct(value) => Self::Struct(value),
             ValueNode::Tuple(value) => Self::Tuple(value),
+            ValueNode::Unknown(value) => Self::Unknown(value),
+        }
+    }
+}
+
+impl TryFrom<PdaSeedValueValue> for ValueNode {
+    type Error = CodamaError;
+
+    fn try_from(value: PdaSeedValueValue) -> Result<Self, Self::Error> {
+        match value {
+            PdaSeedValueValue::Array(value) => Ok(Self::Array(value)),
+            PdaSeedValueValue::Boolean(value) => Ok(Self::Boolean(value)),
+            PdaSeedValueValue::Bytes(value) => Ok(Self::Bytes(value)),
+            PdaSeedValueValue::Constant(value) => Ok(Self::Constant(value)),
+            PdaSeedValueValue::Enum(value) => Ok(Self::Enum(value)),
+            PdaSeedValueValue::Injected(value) => Ok(Self::Injected(value)),
+            PdaSeedValueValue::Map(value) => Ok(Self::Map(value)),
+            PdaSeedValueValue::None(value) => Ok(Self::None(value)),
+            PdaSeedValueValue::Number(value) => Ok(Self::Number(value)),
+            PdaSeedValueValue::PublicKey(value) => Ok(Self::PublicKey(value)),
+            PdaSeedValueValue::Set(value) => Ok(Self::Set(value)),
+            PdaSeedValueValue::Some(value) => Ok(Self::Some(value)),
+            PdaSeedValueValue::String(value) => Ok(Self::String(value)),
+            PdaSeedValueValue::Struct(value) => Ok(Self::Struct(value)),
+            PdaSeedValueValue::Tuple(value) => Ok(Self::Tuple(value)),
+            PdaSeedValueValue::Unknown(value) => Ok(Self::Unknown(value)),
+            _ => Err(CodamaError::InvalidNodeConversion {
+                from: value.kind().to_string(),
+                into: "ValueNode".to_string(),
+            }),
         }
     }
 }
@@ -0,0 +1,776 @@
+use crate::{
+    fixed_size, AccountNode, CamelCaseString, DefinedTypeNode, DiscriminatorNode, Docs,
+    EnumTypeNode, ErrorNode, EventNode, HasKind, HasName, InstructionAccountNode,
+    InstructionArgumentNode, InstructionNode, LinkResolver, NestedTypeNode, NestedTypeNodeTrait,
+    NodePath, ProgramNode, RootNode, StructFieldTypeNode, TypeNode,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use ChangeImpact::{Breaking, Compatible};
+
+/// Compares two versions of a `RootNode`, pairing programs, accounts, instructions,
+/// defined types, errors and events by name and classifying every change found.
+pub fn diff(old: &RootNode, new: &RootNode) -> IdlDiff {
+    let mut differ = Differ {
+        old: LinkResolver::new(old),
+        new: LinkResolver::new(new),
+        path: NodePath::new(),
+        changes: vec![],
+    };
+    let old_programs = std::iter::once(&old.program).chain(&old.additional_programs);
+    let new_programs = std::iter::once(&new.program).chain(&new.additional_programs);
+    differ.diff_named(
+        &old_programs.collect::<Vec<_>>(),
+        &new_programs.collect::<Vec<_>>(),
+        ChangeImpact::Compatible,
+        Differ::diff_program,
+    );
+    IdlDiff {
+        changes: differ.changes,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdlDiff {
+    pub changes: Vec<Change>,
+}
+
+impl IdlDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether deploying the new version would break existing clients or data.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_changes().next().is_some()
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.impact == ChangeImpact::Breaking)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub impact: ChangeImpact,
+    /// The path of the added, removed or changed node.
+    pub path: NodePath,
+    pub message: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let impact = match self.impact {
+            ChangeImpact::Breaking => "breaking",
+            ChangeImpact::Compatible => "compatible",
+        };
+        match self.path.is_empty() {
+            true => write!(f, "[{impact}] {}", self.message),
+            false => write!(f, "[{impact}] {}: {}", self.path, self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeImpact {
+    /// Existing clients, accounts or transactions are no longer compatible.
+    Breaking,
+    Compatible,
+}
+
+struct Differ<'a> {
+    old: LinkResolver<'a>,
+    new: LinkResolver<'a>,
+    path: NodePath,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    fn report<T: Into<String>>(&mut self, kind: ChangeKind, impact: ChangeImpact, message: T) {
+        self.changes.push(Change {
+            kind,
+            impact,
+            path: self.path.clone(),
+            message: message.into(),
+        });
+    }
+
+    fn changed<T: Into<String>>(&mut self, impact: ChangeImpact, message: T) {
+        self.report(ChangeKind::Changed, impact, message);
+    }
+
+    fn enter<T: HasKind + HasName>(&mut self, node: &T) {
+        self.path.push(node.kind(), Some(node.name().clone()));
+    }
+
+    fn exit(&mut self) {
+        self.path.pop();
+    }
+
+    /// Pairs nodes by name. Removing a node is always breaking.
+    fn diff_named<T: HasKind + HasName>(
+        &mut self,
+        old: &[&'a T],
+        new: &[&'a T],
+        added_impact: ChangeImpact,
+        mut diff_pair: impl FnMut(&mut Self, &'a T, &'a T),
+    ) {
+        for old_node in old {
+            self.enter(*old_node);
+            match new.iter().find(|node| node.name() == old_node.name()) {
+                Some(new_node) => diff_pair(self, old_node, new_node),
+                None => self.report(ChangeKind::Removed, Breaking, removed(*old_node)),
+            }
+            self.exit();
+        }
+        for new_node in new {
+            if !old.iter().any(|node| node.name() == new_node.name()) {
+                self.enter(*new_node);
+                self.report(ChangeKind::Added, added_impact, added(*new_node));
+                self.exit();
+            }
+        }
+    }
+
+    fn diff_docs(&mut self, old: &Docs, new: &Docs) {
+        if old != new {
+            self.changed(Compatible, "docs changed");
+        }
+    }
+
+    fn diff_program(&mut self, old: &'a ProgramNode, new: &'a ProgramNode) {
+        self.old = self.old.with_program(old);
+        self.new = self.new.with_program(new);
+        if old.public_key != new.public_key {
+            self.changed(
+                Breaking,
+                format!(
+                    "public key changed from `{}` to `{}`",
+                    old.public_key, new.public_key
+                ),
+            );
+        }
+        self.diff_docs(&old.docs, &new.docs);
+        self.diff_named(
+            &refs(&old.accounts),
+            &refs(&new.accounts),
+            Compatible,
+            Self::diff_account,
+        );
+        self.diff_named(
+            &refs(&old.instructions),
+            &refs(&new.instructions),
+            Compatible,
+            Self::diff_instruction,
+        );
+        self.diff_named(
+            &refs(&old.defined_types),
+            &refs(&new.defined_types),
+            Compatible,
+            Self::diff_defined_type,
+        );
+        self.diff_errors(&old.errors, &new.errors);
+        self.diff_named(
+            &refs(&old.events),
+            &refs(&new.events),
+            Compatible,
+            Self::diff_event,
+        );
+    }
+
+    fn diff_account(&mut self, old: &'a AccountNode, new: &'a AccountNode) {
+        self.diff_docs(&old.docs, &new.docs);
+        let old_fields = &old.data.get_nested_type_node().fields;
+        let new_fields = &new.data.get_nested_type_node().fields;
+        if discriminator_values(&old.discriminators, old_fields)
+            != discriminator_values(&new.discriminators, new_fields)
+        {
+            self.changed(Breaking, "discriminator changed");
+        }
+        match (&old.data, &new.data) {
+            (NestedTypeNode::Value(_), NestedTypeNode::Value(_)) => {
+                self.diff_fields(&fields(old_fields), &fields(new_fields))
+            }
+            (old_data, new_data) => {
+                if !same_layout(old_data, new_data) {
+                    self.changed(Breaking, "type changed");
+                }
+            }
+        }
+    }
+
+    fn diff_instruction(&mut self, old: &'a InstructionNode, new: &'a InstructionNode) {
+        self.diff_docs(&old.docs, &new.docs);
+        if discriminator_values(&old.discriminators, &old.arguments)
+            != discriminator_values(&new.discriminators, &new.arguments)
+        {
+            self.changed(Breaking, "discriminator changed");
+        }
+        self.diff_instruction_accounts(&old.accounts, &new.accounts);
+        self.diff_fields(&arguments(&old.arguments), &arguments(&new.arguments));
+    }
+
+    /// Accounts are passed by position so moving, adding or removing one is breaking,
+    /// except for optional accounts appended after all existing ones.
+    fn diff_instruction_accounts(
+        &mut self,
+        old: &'a [InstructionAccountNode],
+        new: &'a [InstructionAccountNode],
+    ) {
+        for (index, old_account) in old.iter().enumerate() {
+            self.enter(old_account);
+            match new
+                .iter()
+                .position(|account| account.name == old_account.name)
+            {
+                None => self.report(ChangeKind::Removed, Breaking, removed(old_account)),
+                Some(new_index) => {
+                    let new_account = &new[new_index];
+                    if new_index != index {
+                        self.changed(
+                            Breaking,
+                            format!("moved from position {index} to {new_index}"),
+                        );
+                    }
+                    if old_account.is_writable != new_account.is_writable {
+                        self.changed(
+                            Breaking,
+                            match new_account.is_writable {
+                                true => "became writable",
+                                false => "is no longer writable",
+                            },
+                        );
+                    }
+                    if old_account.is_signer != new_account.is_signer {
+                        self.changed(
+                            Breaking,
+                            format!(
+                                "signer changed from `{}` to `{}`",
+                                signer(old_account),
+                                signer(new_account)
+                            ),
+                        );
+                    }
+                    match (is_optional(old_account), is_optional(new_account)) {
+                        (true, false) => self.changed(Breaking, "is no longer optional"),
+                        (false, true) => self.changed(Compatible, "became optional"),
+                        _ => {}
+                    }
+                    self.diff_docs(&old_account.docs, &new_account.docs);
+                }
+            }
+            self.exit();
+        }
+        for (index, new_account) in new.iter().enumerate() {
+            if !old.iter().any(|account| account.name == new_account.name) {
+                self.enter(new_account);
+                let impact = match index >= old.len() && is_optional(new_account) {
+                    true => Compatible,
+                    false => Breaking,
+                };
+                self.report(ChangeKind::Added, impact, added(new_account));
+                self.exit();
+            }
+        }
+    }
+
+    fn diff_defined_type(&mut self, old: &'a DefinedTypeNode, new: &'a DefinedTypeNode) {
+        self.diff_docs(&old.docs, &new.docs);
+        self.diff_type(&old.r#type, &new.r#type);
+    }
+
+    fn diff_event(&mut self, old: &'a EventNode, new: &'a EventNode) {
+        self.diff_docs(&old.docs, &new.docs);
+        let old_fields = match old.data.as_ref() {
+            TypeNode::Struct(node) => node.fields.as_slice(),
+            _ => &[],
+        };
+        let new_fields = match new.data.as_ref() {
+            TypeNode::Struct(node) => node.fields.as_slice(),
+            _ => &[],
+        };
+        if discriminator_values(&old.discriminators, old_fields)
+            != discriminator_values(&new.discriminators, new_fields)
+        {
+            self.changed(Breaking, "discriminator changed");
+        }
+        self.diff_type(&old.data, &new.data);
+    }
+
+    /// Errors are identified by their code on-chain, so giving an existing code
+    /// to another error or changing the code of an error is breaking.
+    fn diff_errors(&mut self, old: &'a [ErrorNode], new: &'a [ErrorNode]) {
+        self.diff_named(&refs(old), &refs(new), Compatible, |this, old, new| {
+            if old.code != new.code {
+                this.changed(
+                    Breaking,
+                    format!("code changed from {} to {}", old.code, new.code),
+                );
+            }
+            if old.message != new.message {
+                this.changed(Compatible, "message changed");
+            }
+            this.diff_docs(&old.docs, &new.docs);
+        });
+        for new_error in new {
+            let previous_owner = old
+                .iter()
+                .find(|error| error.code == new_error.code && error.name != new_error.name);
+            if let Some(previous_owner) = previous_owner {
+                self.enter(new_error);
+                self.changed(
+                    Breaking,
+                    format!(
+                        "code {} was previously used by `{}`",
+                        new_error.code,
+                        previous_owner.name.as_str()
+                    ),
+                );
+                self.exit();
+            }
+        }
+    }
+
+    fn diff_type(&mut self, old: &TypeNode, new: &TypeNode) {
+        match (old, new) {
+            (TypeNode::Struct(old), TypeNode::Struct(new)) => {
+                self.diff_fields(&fields(&old.fields), &fields(&new.fields))
+            }
+            (TypeNode::Enum(old), TypeNode::Enum(new)) => self.diff_enum(old, new),
+            _ => self.diff_layout(old, new),
+        }
+    }
+
+    /// Variants are identified by their position, so only appending variants is compatible.
+    fn diff_enum(&mut self, old: &EnumTypeNode, new: &EnumTypeNode) {
+        if !same_layout(&old.size, &new.size) {
+            self.changed(Breaking, "enum size changed");
+        }
+        for (index, old_variant) in old.variants.iter().enumerate() {
+            self.enter(old_variant);
+            match new.variants.get(index) {
+                None => self.report(ChangeKind::Removed, Breaking, removed(old_variant)),
+                Some(new_variant) if !same_layout(old_variant, new_variant) => {
+                    let message = match old_variant.name() == new_variant.name() {
+                        true => "variant changed".to_string(),
+                        false => format!("replaced by `{}`", new_variant.name().as_str()),
+                    };
+                    self.changed(Breaking, message);
+                }
+                Some(new_variant) if old_variant.name() != new_variant.name() => {
+                    let message = format!("renamed to `{}`", new_variant.name().as_str());
+                    self.changed(Compatible, message);
+                }
+                Some(_) => {}
+            }
+            self.exit();
+        }
+        for new_variant in new.variants.iter().skip(old.variants.len()) {
+            self.enter(new_variant);
+            self.report(ChangeKind::Added, Compatible, added(new_variant));
+            self.exit();
+        }
+    }
+
+    /// Fields are encoded in order so moving, resizing or removing one is breaking
+    /// whereas appending fields or renaming them in place is compatible.
+    fn diff_fields(&mut self, old: &[Field], new: &[Field]) {
+        for (index, old_field) in old.iter().enumerate() {
+            self.path.push(old_field.kind, Some(old_field.name.clone()));
+            let new_index = new.iter().position(|field| field.name == old_field.name);
+            match new_index.map(|new_index| (new_index, &new[new_index])) {
+                Some((new_index, new_field)) => {
+                    if new_index != index {
+                        self.changed(
+                            Breaking,
+                            format!("moved from position {index} to {new_index}"),
+                        );
+                    }
+                    self.diff_layout(old_field.r#type, new_field.r#type);
+                    self.diff_docs(old_field.docs, new_field.docs);
+                }
+                None => match new.get(index) {
+                    Some(new_field)
+                        if !old.iter().any(|field| field.name == new_field.name)
+                            && same_layout(old_field.r#type, new_field.r#type) =>
+                    {
+                        let message = format!("renamed to `{}`", new_field.name.as_str());
+                        self.changed(Compatible, message);
+                    }
+                    _ => self.report(
+                        ChangeKind::Removed,
+                        Breaking,
+                        format!("`{}` removed", old_field.kind),
+                    ),
+                },
+            }
+            self.path.pop();
+        }
+        for (index, new_field) in new.iter().enumerate() {
+            let is_new = !old.iter().any(|field| field.name == new_field.name);
+            let is_renamed = old.get(index).is_some_and(|old_field| {
+                !new.iter().any(|field| field.name == old_field.name)
+                    && same_layout(old_field.r#type, new_field.r#type)
+            });
+            if is_new && !is_renamed {
+                self.path.push(new_field.kind, Some(new_field.name.clone()));
+                let (impact, message) = match index >= old.len() {
+                    true => (Compatible, format!("`{}` appended", new_field.kind)),
+                    false => (Breaking, format!("`{}` inserted", new_field.kind)),
+                };
+                self.report(ChangeKind::Added, impact, message);
+                self.path.pop();
+            }
+        }
+    }
+
+    fn diff_layout(&mut self, old: &TypeNode, new: &TypeNode) {
+        if same_layout(old, new) {
+            return;
+        }
+        match (fixed_size(old, &self.old), fixed_size(new, &self.new)) {
+            (Some(old_size), Some(new_size)) if old_size != new_size => self.changed(
+                Breaking,
+                format!("resized from {old_size} to {new_size} bytes"),
+            ),
+            _ => self.changed(Breaking, "type changed"),
+        }
+    }
+}
+
+/// A struct field or an instruction argument.
+struct Field<'a> {
+    kind: &'static str,
+    name: &'a CamelCaseString,
+    r#type: &'a TypeNode,
+    docs: &'a Docs,
+}
+
+fn fields(fields: &[StructFieldTypeNode]) -> Vec<Field<'_>> {
+    fields
+        .iter()
+        .map(|field| Field {
+            kind: field.kind(),
+            name: &field.name,
+            r#type: &field.r#type,
+            docs: &field.docs,
+        })
+        .collect()
+}
+
+fn arguments(arguments: &[InstructionArgumentNode]) -> Vec<Field<'_>> {
+    arguments
+        .iter()
+        .map(|argument| Field {
+            kind: argument.kind(),
+            name: &argument.name,
+            r#type: &argument.r#type,
+            docs: &argument.docs,
+        })
+        .collect()
+}
+
+fn refs<T>(nodes: &[T]) -> Vec<&T> {
+    nodes.iter().collect()
+}
+
+fn added<T: HasKind>(node: &T) -> String {
+    format!("`{}` added", node.kind())
+}
+
+fn removed<T: HasKind>(node: &T) -> String {
+    format!("`{}` removed", node.kind())
+}
+
+fn is_optional(account: &InstructionAccountNode) -> bool {
+    account.is_optional == Some(true)
+}
+
+fn signer(account: &InstructionAccountNode) -> Value {
+    serde_json::to_value(account.is_signer).unwrap_or_default()
+}
+
+/// Compares the serialized form of two nodes, ignoring docs and display hints.
+fn same_layout<T: Serialize>(old: &T, new: &T) -> bool {
+    let strip = |node: &T| {
+        let mut value = serde_json::to_value(node).unwrap_or_default();
+        strip_presentation(&mut value);
+        value
+    };
+    strip(old) == strip(new)
+}
+
+fn strip_presentation(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("docs");
+            map.remove("display");
+            map.values_mut().for_each(strip_presentation);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_presentation),
+        _ => {}
+    }
+}
+
+/// The discriminators of a node, alongside the default values
+/// of the fields or arguments they point to.
+fn discriminator_values<T: HasName + Serialize>(
+    discriminators: &[DiscriminatorNode],
+    fields: &[T],
+) -> Vec<Value> {
+    discriminators
+        .iter()
+        .map(|discriminator| {
+            let field = match discriminator {
+                DiscriminatorNode::Field(node) => {
+                    fields.iter().find(|field| field.name() == &node.name)
+                }
+                _ => None,
+            };
+            let default_value = field
+                .and_then(|field| serde_json::to_value(field).ok())
+                .and_then(|field| field.get("defaultValue").cloned());
+            serde_json::json!([discriminator, default_value])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BytesTypeNode, BytesValueNode, DefaultValueStrategy, EnumEmptyVariantTypeNode,
+        FieldDiscriminatorNode, FixedSizeTypeNode, NumberTypeNode, PublicKeyTypeNode,
+        StructTypeNode, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    const PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn get_root(program: ProgramNode) -> RootNode {
+        RootNode::new(ProgramNode {
+            public_key: PROGRAM.into(),
+            ..program
+        })
+    }
+
+    fn get_changes(old: ProgramNode, new: ProgramNode) -> Vec<String> {
+        diff(&get_root(old), &get_root(new))
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn get_account(fields: Vec<StructFieldTypeNode>) -> ProgramNode {
+        ProgramNode::new("token", PROGRAM)
+            .add_account(AccountNode::new("mint", StructTypeNode::new(fields)))
+    }
+
+    fn get_instruction(discriminator: &str, accounts: Vec<InstructionAccountNode>) -> ProgramNode {
+        let discriminator = InstructionArgumentNode {
+            default_value_strategy: Some(DefaultValueStrategy::Omitted),
+            default_value: Box::new(Some(BytesValueNode::base16(discriminator).into())),
+            ..InstructionArgumentNode::new(
+                "discriminator",
+                FixedSizeTypeNode::new(BytesTypeNode::new(), 1),
+            )
+        };
+        ProgramNode::new("token", PROGRAM).add_instruction(InstructionNode {
+            name: "transfer".into(),
+            accounts,
+            arguments: vec![discriminator],
+            discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+            ..InstructionNode::default()
+        })
+    }
+
+    #[test]
+    fn identical() {
+        let program = get_account(vec![StructFieldTypeNode::new(
+            "supply",
+            NumberTypeNode::le(U64),
+        )]);
+        let diff = diff(&get_root(program.clone()), &get_root(program));
+        assert!(diff.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn account_fields() {
+        let supply = StructFieldTypeNode::new("supply", NumberTypeNode::le(U64));
+        let authority = StructFieldTypeNode::new("authority", PublicKeyTypeNode::new());
+        let decimals = StructFieldTypeNode::new("decimals", NumberTypeNode::le(U8));
+
+        // Appending a field is compatible.
+        let old = get_account(vec![supply.clone()]);
+        let new = get_account(vec![supply.clone(), decimals.clone()]);
+        assert_eq!(
+            get_changes(old, new),
+            ["[compatible] [programNode]token > [accountNode]mint > [structFieldTypeNode]decimals: `structFieldTypeNode` appended"]
+        );
+
+        // Reordering fields is breaking.
+        let old = get_account(vec![supply.clone(), authority.clone()]);
+        let new = get_account(vec![authority.clone(), supply.clone()]);
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[breaking] [programNode]token > [accountNode]mint > [structFieldTypeNode]supply: moved from position 0 to 1",
+                "[breaking] [programNode]token > [accountNode]mint > [structFieldTypeNode]authority: moved from position 1 to 0",
+            ]
+        );
+
+        // Resizing a field is breaking but changing its docs is not.
+        let old = get_account(vec![supply.clone()]);
+        let new = get_account(vec![StructFieldTypeNode {
+            docs: vec!["The total supply.".to_string()].into(),
+            ..StructFieldTypeNode::new("supply", NumberTypeNode::le(U32))
+        }]);
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[breaking] [programNode]token > [accountNode]mint > [structFieldTypeNode]supply: resized from 8 to 4 bytes",
+                "[compatible] [programNode]token > [accountNode]mint > [structFieldTypeNode]supply: docs changed",
+            ]
+        );
+
+        // Renaming a field in place is compatible, removing one is not.
+        let old = get_account(vec![supply.clone(), decimals.clone()]);
+        let new = get_account(vec![StructFieldTypeNode::new(
+            "totalSupply",
+            NumberTypeNode::le(U64),
+        )]);
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[compatible] [programNode]token > [accountNode]mint > [structFieldTypeNode]supply: renamed to `totalSupply`",
+                "[breaking] [programNode]token > [accountNode]mint > [structFieldTypeNode]decimals: `structFieldTypeNode` removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn accounts() {
+        let old = get_account(vec![]);
+        let new = ProgramNode::new("token", PROGRAM);
+        let diff = diff(&get_root(old.clone()), &get_root(new.clone()));
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.changes,
+            vec![Change {
+                kind: ChangeKind::Removed,
+                impact: ChangeImpact::Breaking,
+                path: {
+                    let mut path = NodePath::new();
+                    path.push("programNode", Some("token"));
+                    path.push("accountNode", Some("mint"));
+                    path
+                },
+                message: "`accountNode` removed".to_string(),
+            }]
+        );
+        assert_eq!(
+            get_changes(new, old),
+            ["[compatible] [programNode]token > [accountNode]mint: `accountNode` added"]
+        );
+    }
+
+    #[test]
+    fn instructions() {
+        let source = InstructionAccountNode::new("source", true, false);
+        let owner = InstructionAccountNode::new("owner", false, true);
+        let memo = InstructionAccountNode {
+            is_optional: Some(true),
+            ..InstructionAccountNode::new("memo", false, false)
+        };
+
+        let old = get_instruction("01", vec![source.clone(), owner.clone()]);
+        let new = get_instruction("02", vec![source.clone()]);
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[breaking] [programNode]token > [instructionNode]transfer: discriminator changed",
+                "[breaking] [programNode]token > [instructionNode]transfer > [instructionAccountNode]owner: `instructionAccountNode` removed",
+            ]
+        );
+
+        let old = get_instruction("01", vec![source.clone()]);
+        let new = get_instruction("01", vec![source.clone(), memo.clone()]);
+        assert_eq!(
+            get_changes(old, new),
+            ["[compatible] [programNode]token > [instructionNode]transfer > [instructionAccountNode]memo: `instructionAccountNode` added"]
+        );
+
+        let old = get_instruction("01", vec![source.clone()]);
+        let new = get_instruction(
+            "01",
+            vec![owner, InstructionAccountNode::new("source", false, false)],
+        );
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[breaking] [programNode]token > [instructionNode]transfer > [instructionAccountNode]source: moved from position 0 to 1",
+                "[breaking] [programNode]token > [instructionNode]transfer > [instructionAccountNode]source: is no longer writable",
+                "[breaking] [programNode]token > [instructionNode]transfer > [instructionAccountNode]owner: `instructionAccountNode` added",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let get_program = |errors: Vec<ErrorNode>| ProgramNode {
+            errors,
+            ..ProgramNode::new("token", PROGRAM)
+        };
+        let old = get_program(vec![ErrorNode::new(
+            "notRentExempt",
+            0,
+            "Lamport balance below rent-exempt threshold",
+        )]);
+        let new = get_program(vec![
+            ErrorNode::new("insufficientFunds", 0, "Insufficient funds"),
+            ErrorNode::new("notRentExempt", 1, "Not rent exempt"),
+        ]);
+        assert_eq!(
+            get_changes(old, new),
+            [
+                "[breaking] [programNode]token > [errorNode]notRentExempt: code changed from 0 to 1",
+                "[compatible] [programNode]token > [errorNode]notRentExempt: message changed",
+                "[compatible] [programNode]token > [errorNode]insufficientFunds: `errorNode` added",
+                "[breaking] [programNode]token > [errorNode]insufficientFunds: code 0 was previously used by `notRentExempt`",
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_variants() {
+        let get_program = |variants: &[&str]| {
+            let variants = variants
+                .iter()
+                .map(|name| EnumEmptyVariantTypeNode::new(*name).into())
+                .collect();
+            ProgramNode::new("token", PROGRAM)
+                .add_defined_type(DefinedTypeNode::new("state", EnumTypeNode::new(variants)))
+        };
+        assert_eq!(
+            get_changes(get_program(&["active"]), get_program(&["active", "frozen"])),
+            ["[compatible] [programNode]token > [definedTypeNode]state > [enumEmptyVariantTypeNode]frozen: `enumEmptyVariantTypeNode` added"]
+        );
+        assert_eq!(
+            get_changes(get_program(&["active", "frozen"]), get_program(&["active"])),
+            ["[breaking] [programNode]token > [definedTypeNode]state > [enumEmptyVariantTypeNode]frozen: `enumEmptyVariantTypeNode` removed"]
+        );
+    }
+}
@@ -4,11 +4,11 @@ use crate::{
     EnumValueNode, IdentityValueNode, InjectedValueNode, MapValueNode, NoneValueNode,
     NumberValueNode, PayerValueNode, PdaValueNode, ProgramIdValueNode, ProgramLinkNode,
     PublicKeyValueNode, ResolverValueNode, SetValueNode, SomeValueNode, StringValueNode,
-    StructValueNode, TupleValueNode,
+    StructValueNode, TupleValueNode, UnknownNode,
 };
 use codama_nodes_derive::node_union;
 
-#[node_union]
+#[node_union(unknown_suffix = "ValueNode")]
 pub enum InstructionInputValueNode {
     AccountBumpValue(AccountBumpValueNode),
     AccountFieldValue(AccountFieldValueNode),
@@ -36,4 +36,5 @@ pub enum InstructionInputValueNode {
     StringValue(StringValueNode),
     StructValue(StructValueNode),
     TupleValue(TupleValueNode),
+    Unknown(UnknownNode),
 }
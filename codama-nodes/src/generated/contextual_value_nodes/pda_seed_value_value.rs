@@ -2,11 +2,11 @@ use crate::{
     AccountValueNode, ArgumentValueNode, ArrayValueNode, BooleanValueNode, BytesValueNode,
     ConstantValueNode, EnumValueNode, InjectedValueNode, MapValueNode, NoneValueNode,
     NumberValueNode, PublicKeyValueNode, SetValueNode, SomeValueNode, StringValueNode,
-    StructValueNode, TupleValueNode,
+    StructValueNode, TupleValueNode, UnknownNode,
 };
 use codama_nodes_derive::node_union;
 
-#[node_union]
+#[node_union(unknown_suffix = "ValueNode")]
 pub enum PdaSeedValueValue {
     Account(AccountValueNode),
     Argument(ArgumentValueNode),
@@ -25,4 +25,5 @@ pub enum PdaSeedValueValue {
     String(StringValueNode),
     Struct(StructValueNode),
     Tuple(TupleValueNode),
+    Unknown(UnknownNode),
 }
@@ -1,9 +1,12 @@
-use crate::{ConstantDiscriminatorNode, FieldDiscriminatorNode, SizeDiscriminatorNode};
+use crate::{
+    ConstantDiscriminatorNode, FieldDiscriminatorNode, SizeDiscriminatorNode, UnknownNode,
+};
 use codama_nodes_derive::node_union;
 
-#[node_union]
+#[node_union(unknown_suffix = "DiscriminatorNode")]
 pub enum DiscriminatorNode {
     Constant(ConstantDiscriminatorNode),
     Field(FieldDiscriminatorNode),
     Size(SizeDiscriminatorNode),
+    Unknown(UnknownNode),
 }
@@ -2,11 +2,11 @@ use crate::{
     ArrayValueNode, BooleanValueNode, BytesValueNode, ConstantValueNode, EnumValueNode,
     InjectedValueNode, MapValueNode, NoneValueNode, NumberValueNode, ProgramIdValueNode,
     PublicKeyValueNode, SetValueNode, SomeValueNode, StringValueNode, StructValueNode,
-    TupleValueNode,
+    TupleValueNode, UnknownNode,
 };
 use codama_nodes_derive::node_union;
 
-#[node_union]
+#[node_union(unknown_suffix = "ValueNode")]
 pub enum ConstantPdaSeedValue {
     Array(ArrayValueNode),
     Boolean(BooleanValueNode),
@@ -24,4 +24,5 @@ pub enum ConstantPdaSeedValue {
     String(StringValueNode),
     Struct(StructValueNode),
     Tuple(TupleValueNode),
+    Unknown(UnknownNode),
 }
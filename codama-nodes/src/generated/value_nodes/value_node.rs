@@ -2,12 +2,12 @@ use crate::{
     ArrayValueNode, BooleanValueNode, BytesValueNode, ConstantValueNode, EnumValueNode, HasKind,
     InjectedValueNode, MapEntryValueNode, MapValueNode, NoneValueNode, NumberValueNode,
     PublicKeyValueNode, SetValueNode, SomeValueNode, StringValueNode, StructFieldValueNode,
-    StructValueNode, TupleValueNode,
+    StructValueNode, TupleValueNode, UnknownNode,
 };
 use codama_nodes_derive::{node_union, RegisteredNodes};
 
 #[derive(RegisteredNodes)]
-#[node_union]
+#[node_union(unknown_suffix = "ValueNode")]
 pub enum RegisteredValueNode {
     Array(ArrayValueNode),
     Boolean(BooleanValueNode),
@@ -24,6 +24,7 @@ pub enum RegisteredValueNode {
     String(StringValueNode),
     Struct(StructValueNode),
     Tuple(TupleValueNode),
+    Unknown(UnknownNode),
 
     #[registered]
     MapEntry(MapEntryValueNode),
@@ -0,0 +1,243 @@
+use crate::{
+    encode, link_resolver::get_all_instructions, AccountNode, CamelCaseString, DiscriminatorNode,
+    EventNode, InstructionNode, LinkResolver, NestedTypeNode, NestedTypeNodeTrait, ProgramNode,
+    StructTypeNode, TypeNode, ValueNode,
+};
+
+/// Returns every account of the program whose discriminators match the given data.
+///
+/// Accounts without discriminators are never matched. An empty result
+/// means the data could not be identified whereas more than one result
+/// means the discriminators of these accounts are ambiguous.
+pub fn identify_accounts<'a>(program: &'a ProgramNode, data: &[u8]) -> Vec<&'a AccountNode> {
+    let resolver = LinkResolver::from_program(program);
+    program
+        .accounts
+        .iter()
+        .filter(|account| {
+            let fields = &account.data.get_nested_type_node().fields;
+            matches_discriminators(&account.discriminators, data, &resolver, |name| {
+                let field = fields.iter().find(|field| &field.name == name)?;
+                let default_value = field.default_value.as_ref().as_ref()?;
+                Some((&field.r#type, default_value.clone()))
+            })
+        })
+        .collect()
+}
+
+/// Returns every instruction of the program, including sub-instructions,
+/// whose discriminators match the given instruction data.
+///
+/// Instructions without discriminators are never matched. An empty result
+/// means the data could not be identified whereas more than one result
+/// means the discriminators of these instructions are ambiguous.
+pub fn identify_instructions<'a>(
+    program: &'a ProgramNode,
+    data: &[u8],
+) -> Vec<&'a InstructionNode> {
+    let resolver = LinkResolver::from_program(program);
+    get_all_instructions(&program.instructions)
+        .into_iter()
+        .filter(|instruction| {
+            matches_discriminators(&instruction.discriminators, data, &resolver, |name| {
+                let argument = instruction
+                    .arguments
+                    .iter()
+                    .find(|argument| &argument.name == name)?;
+                let default_value = argument.default_value.as_ref().clone()?;
+                Some((&argument.r#type, ValueNode::try_from(default_value).ok()?))
+            })
+        })
+        .collect()
+}
+
+/// Returns every event of the program whose discriminators match the given data.
+///
+/// Events without discriminators are never matched. An empty result
+/// means the data could not be identified whereas more than one result
+/// means the discriminators of these events are ambiguous.
+pub fn identify_events<'a>(program: &'a ProgramNode, data: &[u8]) -> Vec<&'a EventNode> {
+    let resolver = LinkResolver::from_program(program);
+    program
+        .events
+        .iter()
+        .filter(|event| {
+            let data_struct = NestedTypeNode::<StructTypeNode>::try_from(*event.data.clone()).ok();
+            matches_discriminators(&event.discriminators, data, &resolver, |name| {
+                let fields = &data_struct.as_ref()?.get_nested_type_node().fields;
+                let field = fields.iter().find(|field| &field.name == name)?;
+                let default_value = field.default_value.as_ref().as_ref()?;
+                Some((&field.r#type, default_value.clone()))
+            })
+        })
+        .collect()
+}
+
+/// Field discriminators are matched against the encoded default value of
+/// the field, which `get_field` returns alongside the type of the field.
+fn matches_discriminators<'b>(
+    discriminators: &[DiscriminatorNode],
+    data: &[u8],
+    resolver: &LinkResolver,
+    get_field: impl Fn(&CamelCaseString) -> Option<(&'b TypeNode, ValueNode)>,
+) -> bool {
+    if discriminators.is_empty() {
+        return false;
+    }
+    discriminators
+        .iter()
+        .all(|discriminator| match discriminator {
+            DiscriminatorNode::Constant(node) => {
+                encode(&node.constant.value, &node.constant.r#type, resolver)
+                    .is_ok_and(|bytes| matches_bytes_at(data, &bytes, node.offset))
+            }
+            DiscriminatorNode::Field(node) => {
+                get_field(&node.name).is_some_and(|(r#type, value)| {
+                    encode(&value, r#type, resolver)
+                        .is_ok_and(|bytes| matches_bytes_at(data, &bytes, node.offset))
+                })
+            }
+            DiscriminatorNode::Size(node) => data.len() as u64 == node.size,
+            DiscriminatorNode::Unknown(_) => false,
+        })
+}
+
+fn matches_bytes_at(data: &[u8], bytes: &[u8], offset: u64) -> bool {
+    usize::try_from(offset)
+        .ok()
+        .and_then(|offset| data.get(offset..offset.checked_add(bytes.len())?))
+        .is_some_and(|slice| slice == bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BytesEncoding, ConstantDiscriminatorNode, ConstantValueNode, FieldDiscriminatorNode,
+        InstructionArgumentNode, NumberTypeNode, NumberValueNode, SizeDiscriminatorNode,
+        StructFieldTypeNode, U64, U8,
+    };
+
+    fn get_program() -> ProgramNode {
+        let discriminator_field = |value: u8| StructFieldTypeNode {
+            default_value: Box::new(Some(NumberValueNode::new(value).into())),
+            ..StructFieldTypeNode::new("discriminator", NumberTypeNode::le(U8))
+        };
+        ProgramNode {
+            accounts: vec![
+                AccountNode {
+                    discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+                    ..AccountNode::new(
+                        "mint",
+                        StructTypeNode::new(vec![
+                            discriminator_field(1),
+                            StructFieldTypeNode::new("supply", NumberTypeNode::le(U64)),
+                        ]),
+                    )
+                },
+                AccountNode {
+                    discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+                    ..AccountNode::new(
+                        "token",
+                        StructTypeNode::new(vec![
+                            discriminator_field(2),
+                            StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+                        ]),
+                    )
+                },
+                AccountNode {
+                    discriminators: vec![SizeDiscriminatorNode::new(9).into()],
+                    ..AccountNode::new(
+                        "legacyToken",
+                        StructTypeNode::new(vec![
+                            StructFieldTypeNode::new("kind", NumberTypeNode::le(U8)),
+                            StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+                        ]),
+                    )
+                },
+                AccountNode::new("unknown", StructTypeNode::new(vec![])),
+            ],
+            instructions: vec![
+                InstructionNode {
+                    name: "initialize".into(),
+                    arguments: vec![InstructionArgumentNode {
+                        default_value: Box::new(Some(NumberValueNode::new(0u8).into())),
+                        ..InstructionArgumentNode::new("discriminator", NumberTypeNode::le(U8))
+                    }],
+                    discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+                    ..InstructionNode::default()
+                },
+                InstructionNode {
+                    name: "transfer".into(),
+                    discriminators: vec![ConstantDiscriminatorNode::new(
+                        ConstantValueNode::bytes(BytesEncoding::Base16, "0307"),
+                        0,
+                    )
+                    .into()],
+                    ..InstructionNode::default()
+                },
+            ],
+            events: vec![EventNode {
+                discriminators: vec![ConstantDiscriminatorNode::new(
+                    ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+                    0,
+                )
+                .into()],
+                ..EventNode::new("transferred", StructTypeNode::new(vec![]))
+            }],
+            ..ProgramNode::new("token", "1111")
+        }
+    }
+
+    fn names<T: crate::HasName>(nodes: Vec<&T>) -> Vec<String> {
+        nodes.iter().map(|node| node.name().to_string()).collect()
+    }
+
+    #[test]
+    fn identify_accounts_by_field() {
+        let program = get_program();
+        let data = [2, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(names(identify_accounts(&program, &data)), vec!["token"]);
+    }
+
+    #[test]
+    fn identify_accounts_ambiguously() {
+        let program = get_program();
+        let data = [1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            names(identify_accounts(&program, &data)),
+            vec!["mint", "legacyToken"]
+        );
+    }
+
+    #[test]
+    fn identify_accounts_without_match() {
+        let program = get_program();
+        assert!(identify_accounts(&program, &[9, 9]).is_empty());
+        assert!(identify_accounts(&program, &[]).is_empty());
+    }
+
+    #[test]
+    fn identify_instructions_by_field_and_constant() {
+        let program = get_program();
+        assert_eq!(
+            names(identify_instructions(&program, &[0, 42])),
+            vec!["initialize"]
+        );
+        assert_eq!(
+            names(identify_instructions(&program, &[3, 7, 42])),
+            vec!["transfer"]
+        );
+        assert!(identify_instructions(&program, &[3]).is_empty());
+    }
+
+    #[test]
+    fn identify_events_by_constant() {
+        let program = get_program();
+        assert_eq!(
+            names(identify_events(&program, &[0xff])),
+            vec!["transferred"]
+        );
+        assert!(identify_events(&program, &[0xfe]).is_empty());
+    }
+}
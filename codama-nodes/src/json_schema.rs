@@ -0,0 +1,328 @@
+use crate::{
+    BytesEncoding, CamelCaseString, DefaultValueStrategy, DisplaySkip, Docs, Endianness,
+    InstructionLifecycle, IsSigner, Number, NumberFormat, OptionalAccountStrategy,
+    PostOffsetStrategy, PreOffsetStrategy, ProgramOrigin, RootNode,
+};
+use serde_json::{json, Map, Value};
+
+/// Describes how a type is represented in the JSON format of Codama.
+///
+/// Nodes and node unions implement this trait through the `#[node]` and `#[node_union]`
+/// macros so their schemas always match the fields that are serialized.
+pub trait JsonSchema {
+    /// Whether the value may be omitted from its parent object.
+    const OPTIONAL: bool = false;
+
+    /// The key of the schema in the `$defs` object, when it has one.
+    fn schema_name() -> String;
+
+    /// The schema of the type, adding any definition it relies on to `definitions`.
+    fn json_schema(definitions: &mut Map<String, Value>) -> Value;
+
+    /// Defines the schema returned by `build` once and returns a reference to it.
+    /// The definition is reserved before `build` is called to support recursive types.
+    fn json_schema_reference(
+        definitions: &mut Map<String, Value>,
+        build: impl FnOnce(&mut Map<String, Value>) -> Value,
+    ) -> Value
+    where
+        Self: Sized,
+    {
+        let name = Self::schema_name();
+        if !definitions.contains_key(&name) {
+            definitions.insert(name.clone(), Value::Bool(true));
+            let schema = build(definitions);
+            definitions.insert(name.clone(), schema);
+        }
+        json!({ "$ref": format!("#/$defs/{name}") })
+    }
+}
+
+/// A standalone JSON Schema document describing the given type and every node it contains.
+pub fn json_schema<T: JsonSchema>() -> Value {
+    let mut definitions = Map::new();
+    let reference = T::json_schema(&mut definitions);
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_string(),
+        "https://json-schema.org/draft/2020-12/schema".into(),
+    );
+    schema.insert("title".to_string(), T::schema_name().into());
+    if let Value::Object(reference) = reference {
+        schema.extend(reference);
+    }
+    schema.insert("$defs".to_string(), definitions.into());
+    Value::Object(schema)
+}
+
+/// The JSON Schema of Codama IDL files, i.e. serialized `RootNode`s.
+pub fn root_node_json_schema() -> Value {
+    json_schema::<RootNode>()
+}
+
+macro_rules! primitive_schema {
+    ($($type:ty => $name:literal $schema:tt),* $(,)?) => {
+        $(
+            impl JsonSchema for $type {
+                fn schema_name() -> String {
+                    $name.to_string()
+                }
+
+                fn json_schema(_: &mut Map<String, Value>) -> Value {
+                    json!($schema)
+                }
+            }
+        )*
+    };
+}
+
+primitive_schema! {
+    bool => "boolean" { "type": "boolean" },
+    String => "string" { "type": "string" },
+    u8 => "u8" { "type": "integer", "minimum": 0, "maximum": u8::MAX },
+    u16 => "u16" { "type": "integer", "minimum": 0, "maximum": u16::MAX },
+    u32 => "u32" { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+    u64 => "u64" { "type": "integer", "minimum": 0 },
+    usize => "usize" { "type": "integer", "minimum": 0 },
+    i32 => "i32" { "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX },
+    i64 => "i64" { "type": "integer" },
+    f64 => "f64" { "type": "number" },
+    Value => "any" true,
+    CamelCaseString => "camelCaseString" { "type": "string" },
+    Docs => "docs" { "type": "array", "items": { "type": "string" } },
+    Number => "number" { "type": "number" },
+    IsSigner => "isSigner" { "anyOf": [{ "type": "boolean" }, { "const": "either" }] },
+}
+
+/// Lists the serialized values of unit enums. The `match` ensures
+/// a compilation error whenever a variant is added or removed.
+macro_rules! string_enum_schema {
+    ($($type:ident { $($variant:ident),* }),* $(,)?) => {
+        $(
+            impl JsonSchema for $type {
+                fn schema_name() -> String {
+                    crate::CamelCaseString::new(stringify!($type)).to_string()
+                }
+
+                fn json_schema(_: &mut Map<String, Value>) -> Value {
+                    let values = [$($type::$variant),*].map(|variant| {
+                        match variant {
+                            $($type::$variant)|* => serde_json::to_value(variant).unwrap(),
+                        }
+                    });
+                    json!({ "enum": values })
+                }
+            }
+        )*
+    };
+}
+
+string_enum_schema! {
+    BytesEncoding { Base16, Base58, Base64, Utf8 },
+    DefaultValueStrategy { Omitted, Optional },
+    DisplaySkip { Always, Never, WhenInjected },
+    Endianness { Be, Le },
+    InstructionLifecycle { Archived, Deprecated, Draft, Live },
+    NumberFormat { F32, F64, I8, I16, I32, I64, I128, ShortU16, U8, U16, U32, U64, U128 },
+    OptionalAccountStrategy { Omitted, ProgramId },
+    PostOffsetStrategy { Absolute, Padded, PreOffset, Relative },
+    PreOffsetStrategy { Absolute, Padded, Relative },
+    ProgramOrigin { Anchor, Shank },
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn schema_name() -> String {
+        format!("option:{}", T::schema_name())
+    }
+
+    fn json_schema(definitions: &mut Map<String, Value>) -> Value {
+        json!({ "anyOf": [T::json_schema(definitions), { "type": "null" }] })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Box<T> {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+
+    fn json_schema(definitions: &mut Map<String, Value>) -> Value {
+        T::json_schema(definitions)
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn schema_name() -> String {
+        format!("array:{}", T::schema_name())
+    }
+
+    fn json_schema(definitions: &mut Map<String, Value>) -> Value {
+        json!({ "type": "array", "items": T::json_schema(definitions) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountNode, DefinedTypeNode, EnumEmptyVariantTypeNode, EnumTypeNode, FixedSizeTypeNode,
+        InstructionAccountNode, InstructionArgumentNode, InstructionNode, NestedTypeNode, Node,
+        NumberTypeNode, NumberValueNode, OptionTypeNode, PayerValueNode, ProgramNode,
+        SizePrefixTypeNode, StringTypeNode, StructFieldTypeNode, StructTypeNode, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn definition<'a>(schema: &'a Value, name: &str) -> &'a Value {
+        &schema["$defs"][name]
+    }
+
+    /// A light validator checking that every object with a `kind` only uses
+    /// properties declared by its definition and provides all required ones.
+    fn assert_matches_schema(schema: &Value, json: &Value) {
+        match json {
+            Value::Object(object) => {
+                if let Some(Value::String(kind)) = object.get("kind") {
+                    // Generic nodes are defined per type parameter, e.g. `fixedSizeTypeNode:typeNode`.
+                    let definition = schema["$defs"]
+                        .as_object()
+                        .unwrap()
+                        .iter()
+                        .find(|(name, _)| *name == kind || name.starts_with(&format!("{kind}:")))
+                        .map(|(_, definition)| definition)
+                        .unwrap_or_else(|| panic!("missing definition for `{kind}`"));
+                    let properties = definition["properties"].as_object().unwrap();
+                    for key in object.keys() {
+                        assert!(properties.contains_key(key), "unknown `{key}` in `{kind}`");
+                    }
+                    for key in definition["required"].as_array().unwrap() {
+                        let key = key.as_str().unwrap();
+                        assert!(object.contains_key(key), "missing `{key}` in `{kind}`");
+                    }
+                }
+                object
+                    .values()
+                    .for_each(|value| assert_matches_schema(schema, value));
+            }
+            Value::Array(items) => items
+                .iter()
+                .for_each(|item| assert_matches_schema(schema, item)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn root_node_document() {
+        let schema = root_node_json_schema();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(schema["$ref"], "#/$defs/rootNode");
+        assert_eq!(
+            definition(&schema, "rootNode")["required"],
+            json!(["kind", "standard", "version", "program"])
+        );
+        for kind in [
+            "programNode",
+            "accountNode",
+            "instructionNode",
+            "pdaNode",
+            "errorNode",
+            "typeNode",
+            "structFieldTypeNode",
+            "enumStructVariantTypeNode",
+            "conditionalValueNode",
+            "pdaValueNode",
+            "fieldDiscriminatorNode",
+            "instructionDisplayNode",
+        ] {
+            assert!(schema["$defs"].get(kind).is_some(), "missing `{kind}`");
+        }
+    }
+
+    #[test]
+    fn node_properties() {
+        let schema = root_node_json_schema();
+        let field = definition(&schema, "structFieldTypeNode");
+        assert_eq!(field["type"], "object");
+        assert_eq!(field["additionalProperties"], false);
+        assert_eq!(
+            field["properties"]["kind"],
+            json!({ "const": "structFieldTypeNode" })
+        );
+        assert_eq!(field["required"], json!(["kind", "name", "type"]));
+        assert_eq!(
+            field["properties"]["type"]["$ref"],
+            json!("#/$defs/typeNode")
+        );
+        assert_eq!(
+            field["properties"]["defaultValueStrategy"]["anyOf"][0],
+            json!({ "enum": ["omitted", "optional"] })
+        );
+    }
+
+    #[test]
+    fn generic_nodes() {
+        let schema = json_schema::<Node>();
+        assert!(schema["$defs"]
+            .get("nestedTypeNode:numberTypeNode")
+            .is_some());
+        assert_eq!(
+            definition(&schema, "fixedSizeTypeNode:typeNode")["properties"]["type"]["$ref"],
+            "#/$defs/typeNode"
+        );
+        assert_eq!(
+            definition(&schema, "fixedSizeTypeNode:nestedTypeNode:numberTypeNode")["properties"]
+                ["type"]["$ref"],
+            "#/$defs/nestedTypeNode:numberTypeNode"
+        );
+    }
+
+    #[test]
+    fn serialized_nodes_match_the_schema() {
+        let root = RootNode::new(
+            ProgramNode::new("counter", "1111")
+                .add_account(AccountNode::new(
+                    "counter",
+                    StructTypeNode::new(vec![
+                        StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+                        StructFieldTypeNode::new(
+                            "label",
+                            SizePrefixTypeNode::new(
+                                StringTypeNode::utf8(),
+                                NumberTypeNode::le(U32),
+                            ),
+                        ),
+                        StructFieldTypeNode::new(
+                            "tag",
+                            OptionTypeNode::new(FixedSizeTypeNode::new(StringTypeNode::utf8(), 8)),
+                        ),
+                    ]),
+                ))
+                .add_defined_type(DefinedTypeNode::new(
+                    "mode",
+                    EnumTypeNode::new(vec![EnumEmptyVariantTypeNode::new("on").into()]),
+                ))
+                .add_instruction(InstructionNode {
+                    name: "increment".into(),
+                    accounts: vec![InstructionAccountNode {
+                        default_value: Box::new(Some(PayerValueNode::new().into())),
+                        ..InstructionAccountNode::new("payer", true, true)
+                    }],
+                    arguments: vec![InstructionArgumentNode {
+                        default_value: Box::new(Some(NumberValueNode::new(1u8).into())),
+                        ..InstructionArgumentNode::new("amount", NumberTypeNode::le(U8))
+                    }],
+                    ..InstructionNode::default()
+                }),
+        );
+        let schema = root_node_json_schema();
+        assert_matches_schema(&schema, &serde_json::to_value(&root).unwrap());
+
+        let nested: NestedTypeNode<NumberTypeNode> = NumberTypeNode::le(U8).into();
+        assert_matches_schema(&schema, &serde_json::to_value(&nested).unwrap());
+    }
+}
@@ -1,40 +1,72 @@
 #![warn(missing_copy_implementations)]
 
 mod account_node;
+mod byte_size;
+mod canonical;
+mod codec;
 mod constant_node;
 mod contextual_value_nodes;
 mod count_nodes;
 mod defined_type_node;
+mod diff;
 mod discriminator_nodes;
 mod display_nodes;
 mod error_node;
 mod event_node;
 mod generated;
+mod identify;
 mod instruction_account_node;
 mod instruction_argument_node;
 mod instruction_byte_delta_node;
 mod instruction_node;
 mod instruction_remaining_accounts_node;
 mod instruction_status_node;
+mod json_schema;
 mod link_nodes;
+mod link_resolver;
+mod merge;
+mod migration;
 mod node;
+mod pda_derivation;
 mod pda_node;
 mod pda_seed_nodes;
 mod plugin_node;
 mod program_node;
 mod provided_node;
+mod render_display;
 mod root_node;
+mod selector;
 mod shared;
 mod traits;
+mod type_check;
 mod type_nodes;
+mod unknown_node;
+mod validate;
 mod value_nodes;
+mod visitors;
 
+pub use byte_size::*;
+pub use canonical::*;
+pub use codec::*;
+pub use diff::*;
 pub use generated::*;
+pub use identify::*;
+pub use json_schema::*;
+pub use link_resolver::*;
+pub use merge::*;
+pub use migration::*;
 pub use node::*;
+pub use pda_derivation::*;
+pub use render_display::*;
+pub use selector::*;
 pub use shared::*;
 pub use traits::*;
+pub use type_check::*;
 pub use type_nodes::*;
+pub use unknown_node::*;
+pub use validate::*;
 pub use value_nodes::*;
+pub use visitors::*;
 
 // Serde helper function to use with `#[serde(some_thing = "crate::is_default")]`.
 fn is_default<T: Default + PartialEq>(t: &T) -> bool {
@@ -0,0 +1,647 @@
+use crate::{
+    AccountLinkNode, AccountNode, ConstantNode, DefinedTypeLinkNode, DefinedTypeNode,
+    EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EventNode,
+    HasKind, HasName, InstructionAccountLinkNode, InstructionAccountNode,
+    InstructionArgumentLinkNode, InstructionArgumentNode, InstructionLinkNode, InstructionNode,
+    LinkNode, NodeChildren, NodePath, NodeVisitor, PdaLinkNode, PdaNode, PdaSeedValueNode,
+    ProgramLinkNode, ProgramNode, RootNode, StructFieldTypeNode, StructFieldValueNode,
+    VariablePdaSeedNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+
+/// Resolves link nodes against the programs of a `RootNode`.
+///
+/// Links that do not specify a program are resolved within the current
+/// program, if any, or across all programs of the root otherwise. Similarly,
+/// instruction account and argument links that do not specify an instruction
+/// are resolved within the current instruction, if any.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkResolver<'a> {
+    root: Option<&'a RootNode>,
+    program: Option<&'a ProgramNode>,
+    instruction: Option<&'a InstructionNode>,
+}
+
+impl<'a> LinkResolver<'a> {
+    pub fn new(root: &'a RootNode) -> Self {
+        Self {
+            root: Some(root),
+            program: None,
+            instruction: None,
+        }
+    }
+
+    /// Create a resolver that only knows about the given program.
+    pub fn from_program(program: &'a ProgramNode) -> Self {
+        Self {
+            root: None,
+            program: Some(program),
+            instruction: None,
+        }
+    }
+
+    /// Scope the resolver to the given program.
+    pub fn with_program(self, program: &'a ProgramNode) -> Self {
+        Self {
+            program: Some(program),
+            instruction: None,
+            ..self
+        }
+    }
+
+    /// Scope the resolver to the given instruction of the current program.
+    pub fn with_instruction(self, instruction: &'a InstructionNode) -> Self {
+        Self {
+            instruction: Some(instruction),
+            ..self
+        }
+    }
+
+    /// The root of the resolver, if it was not created from a program.
+    pub fn root(&self) -> Option<&'a RootNode> {
+        self.root
+    }
+
+    pub fn program(&self) -> Option<&'a ProgramNode> {
+        self.program
+    }
+
+    pub fn instruction(&self) -> Option<&'a InstructionNode> {
+        self.instruction
+    }
+
+    /// All programs of the root, starting with the main program,
+    /// or the only known program when the resolver was created from a program.
+    pub fn programs(&self) -> impl Iterator<Item = &'a ProgramNode> {
+        let (program, additional_programs) = match self.root {
+            Some(root) => (Some(&root.program), root.additional_programs.as_slice()),
+            None => (self.program, [].as_slice()),
+        };
+        program.into_iter().chain(additional_programs)
+    }
+
+    pub fn resolve_program(&self, link: &ProgramLinkNode) -> CodamaResult<&'a ProgramNode> {
+        find_unique(
+            link,
+            self.programs().filter(|program| program.name == link.name),
+        )
+    }
+
+    pub fn resolve_account(&self, link: &AccountLinkNode) -> CodamaResult<&'a AccountNode> {
+        let programs = self.scoped_programs(link.program.as_ref())?;
+        find_unique(
+            link,
+            programs
+                .into_iter()
+                .flat_map(|program| program.accounts.iter())
+                .filter(|account| account.name == link.name),
+        )
+    }
+
+    pub fn resolve_defined_type(
+        &self,
+        link: &DefinedTypeLinkNode,
+    ) -> CodamaResult<&'a DefinedTypeNode> {
+        let programs = self.scoped_programs(link.program.as_ref())?;
+        find_unique(
+            link,
+            programs
+                .into_iter()
+                .flat_map(|program| program.defined_types.iter())
+                .filter(|defined_type| defined_type.name == link.name),
+        )
+    }
+
+    pub fn resolve_pda(&self, link: &PdaLinkNode) -> CodamaResult<&'a PdaNode> {
+        let programs = self.scoped_programs(link.program.as_ref())?;
+        find_unique(
+            link,
+            programs
+                .into_iter()
+                .flat_map(|program| program.pdas.iter())
+                .filter(|pda| pda.name == link.name),
+        )
+    }
+
+    /// Resolve an instruction link, including sub-instructions.
+    pub fn resolve_instruction(
+        &self,
+        link: &InstructionLinkNode,
+    ) -> CodamaResult<&'a InstructionNode> {
+        let programs = self.scoped_programs(link.program.as_ref())?;
+        find_unique(
+            link,
+            programs
+                .into_iter()
+                .flat_map(|program| get_all_instructions(&program.instructions))
+                .filter(|instruction| instruction.name == link.name),
+        )
+    }
+
+    pub fn resolve_instruction_account(
+        &self,
+        link: &InstructionAccountLinkNode,
+    ) -> CodamaResult<&'a InstructionAccountNode> {
+        let instructions = self.scoped_instructions(link.instruction.as_ref())?;
+        find_unique(
+            link,
+            instructions
+                .into_iter()
+                .flat_map(|instruction| instruction.accounts.iter())
+                .filter(|account| account.name == link.name),
+        )
+    }
+
+    /// Resolve an instruction argument link, including extra arguments.
+    pub fn resolve_instruction_argument(
+        &self,
+        link: &InstructionArgumentLinkNode,
+    ) -> CodamaResult<&'a InstructionArgumentNode> {
+        let instructions = self.scoped_instructions(link.instruction.as_ref())?;
+        find_unique(
+            link,
+            instructions
+                .into_iter()
+                .flat_map(|instruction| {
+                    instruction
+                        .arguments
+                        .iter()
+                        .chain(instruction.extra_arguments.iter())
+                })
+                .filter(|argument| argument.name == link.name),
+        )
+    }
+
+    /// Walk the whole root, or the only known program, and report every link that is dangling or ambiguous.
+    pub fn get_link_issues(&self) -> Vec<LinkIssue> {
+        let resolver = match self.root {
+            Some(root) => LinkResolver::new(root),
+            None => Self {
+                instruction: None,
+                ..*self
+            },
+        };
+        let mut collector = LinkIssueCollector {
+            resolver,
+            path: NodePath::new(),
+            issues: Vec::new(),
+        };
+        match (self.root, self.program) {
+            (Some(root), _) => collector.visit_root_node(root),
+            (None, Some(program)) => collector.visit_program_node(program),
+            (None, None) => {}
+        }
+        collector.issues
+    }
+
+    fn scoped_programs(
+        &self,
+        link: Option<&ProgramLinkNode>,
+    ) -> CodamaResult<Vec<&'a ProgramNode>> {
+        match (link, self.program) {
+            (Some(link), _) => Ok(vec![self.resolve_program(link)?]),
+            (None, Some(program)) => Ok(vec![program]),
+            (None, None) => Ok(self.programs().collect()),
+        }
+    }
+
+    fn scoped_instructions(
+        &self,
+        link: Option<&InstructionLinkNode>,
+    ) -> CodamaResult<Vec<&'a InstructionNode>> {
+        match (link, self.instruction) {
+            (Some(link), _) => Ok(vec![self.resolve_instruction(link)?]),
+            (None, Some(instruction)) => Ok(vec![instruction]),
+            (None, None) => Ok(self
+                .scoped_programs(None)?
+                .into_iter()
+                .flat_map(|program| get_all_instructions(&program.instructions))
+                .collect()),
+        }
+    }
+}
+
+/// A link that could not be resolved, along with the path of the node that contains it.
+#[derive(Debug)]
+pub struct LinkIssue {
+    pub path: NodePath,
+    pub link: LinkNode,
+    pub error: CodamaError,
+}
+
+fn find_unique<'a, T, L>(link: &L, candidates: impl Iterator<Item = &'a T>) -> CodamaResult<&'a T>
+where
+    L: HasKind + HasName,
+{
+    let candidates = candidates.collect::<Vec<_>>();
+    match candidates.as_slice() {
+        [node] => Ok(node),
+        [] => Err(CodamaError::UnresolvedLink {
+            kind: link.kind().to_string(),
+            name: link.name().to_string(),
+        }),
+        _ => Err(CodamaError::AmbiguousLink {
+            kind: link.kind().to_string(),
+            name: link.name().to_string(),
+            count: candidates.len(),
+        }),
+    }
+}
+
+pub(crate) fn get_all_instructions(instructions: &[InstructionNode]) -> Vec<&InstructionNode> {
+    instructions
+        .iter()
+        .flat_map(|instruction| {
+            std::iter::once(instruction).chain(get_all_instructions(&instruction.sub_instructions))
+        })
+        .collect()
+}
+
+struct LinkIssueCollector<'a> {
+    resolver: LinkResolver<'a>,
+    path: NodePath,
+    issues: Vec<LinkIssue>,
+}
+
+impl LinkIssueCollector<'_> {
+    fn check<T>(&mut self, result: CodamaResult<T>, link: impl Into<LinkNode>) {
+        if let Err(error) = result {
+            self.issues.push(LinkIssue {
+                path: self.path.clone(),
+                link: link.into(),
+                error,
+            });
+        }
+    }
+
+    fn enter<T: HasKind + HasName>(&mut self, node: &T) {
+        self.path.push(node.kind(), Some(node.name().clone()));
+    }
+
+    fn exit(&mut self) {
+        self.path.pop();
+    }
+}
+
+impl<'a> NodeVisitor<'a> for LinkIssueCollector<'a> {
+    fn visit_program_node(&mut self, node: &'a ProgramNode) {
+        let resolver = self.resolver;
+        self.resolver = resolver.with_program(node);
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+        self.resolver = resolver;
+    }
+
+    fn visit_instruction_node(&mut self, node: &'a InstructionNode) {
+        let resolver = self.resolver;
+        self.resolver = resolver.with_instruction(node);
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+        self.resolver = resolver;
+    }
+
+    fn visit_account_node(&mut self, node: &'a AccountNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_constant_node(&mut self, node: &'a ConstantNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_defined_type_node(&mut self, node: &'a DefinedTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_enum_empty_variant_type_node(&mut self, node: &'a EnumEmptyVariantTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_enum_struct_variant_type_node(&mut self, node: &'a EnumStructVariantTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_enum_tuple_variant_type_node(&mut self, node: &'a EnumTupleVariantTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_event_node(&mut self, node: &'a EventNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_instruction_account_node(&mut self, node: &'a InstructionAccountNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_instruction_argument_node(&mut self, node: &'a InstructionArgumentNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_pda_node(&mut self, node: &'a PdaNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_pda_seed_value_node(&mut self, node: &'a PdaSeedValueNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_struct_field_type_node(&mut self, node: &'a StructFieldTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_struct_field_value_node(&mut self, node: &'a StructFieldValueNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_variable_pda_seed_node(&mut self, node: &'a VariablePdaSeedNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_account_link_node(&mut self, node: &'a AccountLinkNode) {
+        let result = self.resolver.resolve_account(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_defined_type_link_node(&mut self, node: &'a DefinedTypeLinkNode) {
+        let result = self.resolver.resolve_defined_type(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_instruction_account_link_node(&mut self, node: &'a InstructionAccountLinkNode) {
+        let result = self.resolver.resolve_instruction_account(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_instruction_argument_link_node(&mut self, node: &'a InstructionArgumentLinkNode) {
+        let result = self.resolver.resolve_instruction_argument(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_instruction_link_node(&mut self, node: &'a InstructionLinkNode) {
+        let result = self.resolver.resolve_instruction(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_pda_link_node(&mut self, node: &'a PdaLinkNode) {
+        let result = self.resolver.resolve_pda(node);
+        self.check(result, node.clone());
+    }
+
+    fn visit_program_link_node(&mut self, node: &'a ProgramLinkNode) {
+        let result = self.resolver.resolve_program(node);
+        self.check(result, node.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InstructionAccountNode, IsSigner, NumberTypeNode, StructFieldTypeNode, StructTypeNode, U64,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            accounts: vec![AccountNode {
+                pda: Some(PdaLinkNode::new("counter")),
+                ..AccountNode::new(
+                    "counter",
+                    StructTypeNode::new(vec![StructFieldTypeNode::new(
+                        "value",
+                        DefinedTypeLinkNode::new("amount"),
+                    )]),
+                )
+            }],
+            instructions: vec![InstructionNode {
+                name: "increment".into(),
+                accounts: vec![InstructionAccountNode::new(
+                    "counter",
+                    true,
+                    IsSigner::False,
+                )],
+                arguments: vec![InstructionArgumentNode::new(
+                    "amount",
+                    NumberTypeNode::le(U64),
+                )],
+                extra_arguments: vec![InstructionArgumentNode::new(
+                    "bump",
+                    NumberTypeNode::le(U64),
+                )],
+                ..InstructionNode::default()
+            }],
+            defined_types: vec![DefinedTypeNode::new("amount", NumberTypeNode::le(U64))],
+            pdas: vec![PdaNode::new("counter", vec![])],
+            ..ProgramNode::new("counterProgram", "1111")
+        })
+        .add_program(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new("amount", NumberTypeNode::le(U64))],
+            ..ProgramNode::new("tokenProgram", "2222")
+        })
+    }
+
+    #[test]
+    fn resolve_program() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let program = resolver.resolve_program(&ProgramLinkNode::new("tokenProgram"));
+        assert_eq!(program.unwrap().public_key, "2222");
+    }
+
+    #[test]
+    fn resolve_in_current_program() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root).with_program(&root.additional_programs[0]);
+        let defined_type = resolver
+            .resolve_defined_type(&DefinedTypeLinkNode::new("amount"))
+            .unwrap();
+        assert!(std::ptr::eq(
+            defined_type,
+            &root.additional_programs[0].defined_types[0]
+        ));
+    }
+
+    #[test]
+    fn resolve_with_program_link() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root).with_program(&root.program);
+        let defined_type = resolver
+            .resolve_defined_type(&DefinedTypeLinkNode::new_from_program(
+                "amount",
+                ProgramLinkNode::new("tokenProgram"),
+            ))
+            .unwrap();
+        assert!(std::ptr::eq(
+            defined_type,
+            &root.additional_programs[0].defined_types[0]
+        ));
+    }
+
+    #[test]
+    fn resolve_across_programs() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let pda = resolver.resolve_pda(&PdaLinkNode::new("counter")).unwrap();
+        assert_eq!(pda.name, "counter".into());
+    }
+
+    #[test]
+    fn resolve_ambiguous_link() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let result = resolver.resolve_defined_type(&DefinedTypeLinkNode::new("amount"));
+        assert!(matches!(
+            result,
+            Err(CodamaError::AmbiguousLink { kind, name, count: 2 })
+            if kind == "definedTypeLinkNode" && name == "amount"
+        ));
+    }
+
+    #[test]
+    fn resolve_dangling_link() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let result = resolver.resolve_account(&AccountLinkNode::new("mint"));
+        assert!(matches!(
+            result,
+            Err(CodamaError::UnresolvedLink { kind, name })
+            if kind == "accountLinkNode" && name == "mint"
+        ));
+    }
+
+    #[test]
+    fn resolve_dangling_program_link() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let result = resolver.resolve_account(&AccountLinkNode::new_from_program(
+            "counter",
+            ProgramLinkNode::new("missingProgram"),
+        ));
+        assert!(matches!(
+            result,
+            Err(CodamaError::UnresolvedLink { kind, name })
+            if kind == "programLinkNode" && name == "missingProgram"
+        ));
+    }
+
+    #[test]
+    fn resolve_instruction_account_and_argument() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        let instruction = InstructionLinkNode::new("increment");
+        let account = resolver
+            .resolve_instruction_account(&InstructionAccountLinkNode::new_from_instruction(
+                "counter",
+                instruction.clone(),
+            ))
+            .unwrap();
+        assert!(account.is_writable);
+        let argument = resolver
+            .resolve_instruction_argument(&InstructionArgumentLinkNode::new_from_instruction(
+                "bump",
+                instruction,
+            ))
+            .unwrap();
+        assert_eq!(argument.name, "bump".into());
+    }
+
+    #[test]
+    fn resolve_in_current_instruction() {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root)
+            .with_program(&root.program)
+            .with_instruction(&root.program.instructions[0]);
+        let argument = resolver
+            .resolve_instruction_argument(&InstructionArgumentLinkNode::new("amount"))
+            .unwrap();
+        assert_eq!(argument.name, "amount".into());
+    }
+
+    #[test]
+    fn resolve_from_program() {
+        let root = get_root();
+        let resolver = LinkResolver::from_program(&root.additional_programs[0]);
+        assert!(resolver.root().is_none());
+        assert_eq!(resolver.programs().count(), 1);
+        assert!(resolver
+            .resolve_defined_type(&DefinedTypeLinkNode::new("amount"))
+            .is_ok());
+        assert!(resolver
+            .resolve_program(&ProgramLinkNode::new("counterProgram"))
+            .is_err());
+    }
+
+    #[test]
+    fn get_link_issues_with_valid_links() {
+        let root = get_root();
+        let issues = LinkResolver::new(&root).get_link_issues();
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn get_link_issues_with_invalid_links() {
+        let mut root = get_root();
+        root.program.accounts[0].pda = Some(PdaLinkNode::new("missingPda"));
+        root.additional_programs[0]
+            .defined_types
+            .push(DefinedTypeNode::new("amount", NumberTypeNode::le(U64)));
+        root.additional_programs[0]
+            .defined_types
+            .push(DefinedTypeNode::new(
+                "wrapper",
+                DefinedTypeLinkNode::new("amount"),
+            ));
+
+        let issues = LinkResolver::new(&root).get_link_issues();
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(
+            issues[0].path.to_string(),
+            "[programNode]counterProgram > [accountNode]counter"
+        );
+        assert_eq!(issues[0].link, PdaLinkNode::new("missingPda").into());
+        assert!(matches!(
+            issues[0].error,
+            CodamaError::UnresolvedLink { .. }
+        ));
+
+        assert_eq!(
+            issues[1].path.to_string(),
+            "[programNode]tokenProgram > [definedTypeNode]wrapper"
+        );
+        assert_eq!(issues[1].link, DefinedTypeLinkNode::new("amount").into());
+        assert!(matches!(
+            issues[1].error,
+            CodamaError::AmbiguousLink { count: 2, .. }
+        ));
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    AccountNode, CamelCaseString, ConstantNode, DefinedTypeNode, Docs, ErrorNode, EventNode,
+    HasName, InstructionNode, NodeTrait, PdaNode, ProgramNode, RootNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+
+/// How to find the program of `this` that a program of `that` should be merged into.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramMergeStrategy {
+    /// Merge programs sharing the same public key.
+    #[default]
+    PublicKey,
+    /// Merge programs sharing the same name.
+    Name,
+    /// Never merge programs, they are all added as additional programs.
+    Never,
+}
+
+/// What to do when both programs define an item with the same name.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateStrategy {
+    /// Fail with a `MergeConflict` error.
+    Error,
+    /// Keep the item from `this` and ignore the one from `that`.
+    KeepFirst,
+    /// Replace the item from `this` with the one from `that`.
+    #[default]
+    KeepLast,
+    /// Keep both items by adding a numeric suffix to the name of the one from `that`.
+    Rename,
+}
+
+/// What to do when both programs define different metadata (name, public key, version, origin or docs).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataStrategy {
+    /// Keep the metadata of `this` and only fill in its missing values.
+    #[default]
+    KeepFirst,
+    /// Use any metadata provided by `that`.
+    KeepLast,
+    /// Fail with a `MergeConflict` error.
+    Error,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MergeOptions {
+    pub programs: ProgramMergeStrategy,
+    pub duplicates: DuplicateStrategy,
+    pub metadata: MetadataStrategy,
+    /// Merge programs without a public key into the first program of `this`.
+    pub merge_programs_without_public_key: bool,
+}
+
+/// Merge all programs of `that` into `this`. Programs that cannot be
+/// paired with an existing program are added as additional programs.
+pub fn merge(mut this: RootNode, that: RootNode, options: MergeOptions) -> CodamaResult<RootNode> {
+    let those_programs = std::iter::once(that.program).chain(that.additional_programs);
+    for that_program in those_programs {
+        if should_merge_programs(&this.program, &that_program, options) {
+            merge_programs(&mut this.program, that_program, options)?;
+            continue;
+        }
+
+        let found = this
+            .additional_programs
+            .iter_mut()
+            .find(|program| should_merge_programs(program, &that_program, options));
+
+        match found {
+            Some(program) => merge_programs(program, that_program, options)?,
+            None => this.additional_programs.push(that_program),
+        }
+    }
+    Ok(this)
+}
+
+fn should_merge_programs(this: &ProgramNode, that: &ProgramNode, options: MergeOptions) -> bool {
+    if options.merge_programs_without_public_key && that.public_key.is_empty() {
+        return true;
+    }
+    match options.programs {
+        ProgramMergeStrategy::PublicKey => this.public_key == that.public_key,
+        ProgramMergeStrategy::Name => this.name == that.name,
+        ProgramMergeStrategy::Never => false,
+    }
+}
+
+fn merge_programs(
+    this: &mut ProgramNode,
+    that: ProgramNode,
+    options: MergeOptions,
+) -> CodamaResult<()> {
+    let name = this.name.clone();
+    let metadata = |field: &'static str| Metadata {
+        name: &name,
+        field,
+        strategy: options.metadata,
+    };
+    metadata("name").merge(&mut this.name, that.name, |name| name.is_empty())?;
+    metadata("publicKey").merge(&mut this.public_key, that.public_key, String::is_empty)?;
+    metadata("version").merge(&mut this.version, that.version, String::is_empty)?;
+    metadata("origin").merge(&mut this.origin, that.origin, Option::is_none)?;
+    metadata("docs").merge(&mut this.docs, that.docs, Docs::is_empty)?;
+
+    merge_items(&mut this.accounts, that.accounts, options)?;
+    merge_items(&mut this.instructions, that.instructions, options)?;
+    merge_items(&mut this.defined_types, that.defined_types, options)?;
+    merge_items(&mut this.pdas, that.pdas, options)?;
+    merge_items(&mut this.events, that.events, options)?;
+    merge_items(&mut this.errors, that.errors, options)?;
+    merge_items(&mut this.constants, that.constants, options)?;
+    Ok(())
+}
+
+struct Metadata<'a> {
+    name: &'a CamelCaseString,
+    field: &'static str,
+    strategy: MetadataStrategy,
+}
+
+impl Metadata<'_> {
+    fn merge<T: PartialEq>(
+        &self,
+        this: &mut T,
+        that: T,
+        is_empty: fn(&T) -> bool,
+    ) -> CodamaResult<()> {
+        if is_empty(&that) || *this == that {
+            return Ok(());
+        }
+        if is_empty(this) {
+            *this = that;
+            return Ok(());
+        }
+        match self.strategy {
+            MetadataStrategy::KeepFirst => {}
+            MetadataStrategy::KeepLast => *this = that,
+            MetadataStrategy::Error => {
+                return Err(CodamaError::MergeConflict {
+                    kind: ProgramNode::KIND.to_string(),
+                    name: self.name.to_string(),
+                    message: format!("conflicting `{}`", self.field),
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+trait MergeItem: HasName + NodeTrait {
+    fn set_name(&mut self, name: CamelCaseString);
+}
+
+macro_rules! merge_item {
+    ($($node:ident),*) => {
+        $(
+            impl MergeItem for $node {
+                fn set_name(&mut self, name: CamelCaseString) {
+                    self.name = name;
+                }
+            }
+        )*
+    };
+}
+
+merge_item!(
+    AccountNode,
+    InstructionNode,
+    DefinedTypeNode,
+    PdaNode,
+    EventNode,
+    ErrorNode,
+    ConstantNode
+);
+
+fn merge_items<T: MergeItem>(
+    items: &mut Vec<T>,
+    new_items: Vec<T>,
+    options: MergeOptions,
+) -> CodamaResult<()> {
+    for mut new_item in new_items {
+        let Some(index) = items.iter().position(|item| item.name() == new_item.name()) else {
+            items.push(new_item);
+            continue;
+        };
+        match options.duplicates {
+            DuplicateStrategy::Error => {
+                return Err(CodamaError::MergeConflict {
+                    kind: T::KIND.to_string(),
+                    name: new_item.name().to_string(),
+                    message: "duplicate name".to_string(),
+                })
+            }
+            DuplicateStrategy::KeepFirst => {}
+            DuplicateStrategy::KeepLast => items[index] = new_item,
+            DuplicateStrategy::Rename => {
+                let name = (2..)
+                    .map(|suffix| {
+                        CamelCaseString::new(format!("{}{suffix}", new_item.name().as_str()))
+                    })
+                    .find(|name| items.iter().all(|item| item.name() != name))
+                    .unwrap();
+                new_item.set_name(name);
+                items.push(new_item);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProgramOrigin, StructTypeNode};
+    use pretty_assertions::assert_eq;
+
+    fn program(name: &str, public_key: &str, accounts: &[&str]) -> ProgramNode {
+        accounts
+            .iter()
+            .fold(ProgramNode::new(name, public_key), |program, account| {
+                program.add_account(AccountNode::new(*account, StructTypeNode::new(vec![])))
+            })
+    }
+
+    fn account_names(program: &ProgramNode) -> Vec<String> {
+        program
+            .accounts
+            .iter()
+            .map(|a| a.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn merge_by_public_key() {
+        let this = RootNode::new(program("token", "1111", &["mint"]));
+        let that = RootNode::new(program("token", "1111", &["token"])).add_program(program(
+            "system",
+            "2222",
+            &[],
+        ));
+        let root = merge(this, that, MergeOptions::default()).unwrap();
+        assert_eq!(account_names(&root.program), vec!["mint", "token"]);
+        assert_eq!(
+            root.additional_programs,
+            vec![program("system", "2222", &[])]
+        );
+    }
+
+    #[test]
+    fn merge_by_name() {
+        let this = RootNode::new(program("token", "1111", &["mint"]));
+        let that = RootNode::new(program("token", "2222", &["token"]));
+
+        let root = merge(this.clone(), that.clone(), MergeOptions::default()).unwrap();
+        assert_eq!(root.additional_programs.len(), 1);
+
+        let options = MergeOptions {
+            programs: ProgramMergeStrategy::Name,
+            ..MergeOptions::default()
+        };
+        let root = merge(this.clone(), that.clone(), options).unwrap();
+        assert_eq!(root.additional_programs, vec![]);
+        assert_eq!(root.program.public_key, "1111");
+        assert_eq!(account_names(&root.program), vec!["mint", "token"]);
+
+        let options = MergeOptions {
+            programs: ProgramMergeStrategy::Never,
+            ..MergeOptions::default()
+        };
+        let root = merge(this, that, options).unwrap();
+        assert_eq!(root.additional_programs.len(), 1);
+    }
+
+    #[test]
+    fn merge_programs_without_public_key() {
+        let this = RootNode::new(program("token", "1111", &["mint"]));
+        let that = RootNode::new(program("", "", &["token"]));
+        let options = MergeOptions {
+            merge_programs_without_public_key: true,
+            ..MergeOptions::default()
+        };
+        let root = merge(this, that, options).unwrap();
+        assert_eq!(root.program.name, CamelCaseString::new("token"));
+        assert_eq!(account_names(&root.program), vec!["mint", "token"]);
+    }
+
+    #[test]
+    fn duplicate_strategies() {
+        let mut this = program("token", "1111", &["mint"]);
+        this.accounts[0].docs = vec!["First.".to_string()].into();
+        let mut that = program("token", "1111", &["mint"]);
+        that.accounts[0].docs = vec!["Last.".to_string()].into();
+        let get_root = |duplicates| {
+            let options = MergeOptions {
+                duplicates,
+                ..MergeOptions::default()
+            };
+            merge(
+                RootNode::new(this.clone()),
+                RootNode::new(that.clone()),
+                options,
+            )
+        };
+
+        let root = get_root(DuplicateStrategy::KeepFirst).unwrap();
+        assert_eq!(root.program.accounts, this.accounts);
+
+        let root = get_root(DuplicateStrategy::KeepLast).unwrap();
+        assert_eq!(root.program.accounts, that.accounts);
+
+        let root = get_root(DuplicateStrategy::Rename).unwrap();
+        assert_eq!(account_names(&root.program), vec!["mint", "mint2"]);
+
+        assert_eq!(
+            get_root(DuplicateStrategy::Error).unwrap_err().to_string(),
+            "Could not merge `accountNode` with name `mint`: duplicate name"
+        );
+    }
+
+    #[test]
+    fn metadata_strategies() {
+        let this = ProgramNode {
+            origin: Some(ProgramOrigin::Anchor),
+            ..program("token", "1111", &[])
+        };
+        let that = ProgramNode {
+            origin: Some(ProgramOrigin::Shank),
+            docs: vec!["My program.".to_string()].into(),
+            ..program("token", "1111", &[])
+        }
+        .set_version("1.2.3");
+        let get_root = |metadata| {
+            let options = MergeOptions {
+                metadata,
+                ..MergeOptions::default()
+            };
+            merge(
+                RootNode::new(this.clone()),
+                RootNode::new(that.clone()),
+                options,
+            )
+        };
+
+        let root = get_root(MetadataStrategy::KeepFirst).unwrap();
+        assert_eq!(root.program.origin, Some(ProgramOrigin::Anchor));
+        assert_eq!(root.program.version, "0.0.0");
+        assert_eq!(root.program.docs, that.docs);
+
+        let root = get_root(MetadataStrategy::KeepLast).unwrap();
+        assert_eq!(root.program.origin, Some(ProgramOrigin::Shank));
+        assert_eq!(root.program.version, "1.2.3");
+
+        assert_eq!(
+            get_root(MetadataStrategy::Error).unwrap_err().to_string(),
+            "Could not merge `programNode` with name `token`: conflicting `version`"
+        );
+    }
+}
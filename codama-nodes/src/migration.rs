@@ -0,0 +1,135 @@
+use crate::{RootNode, CODAMA_VERSION};
+use codama_errors::{CodamaError, CodamaResult};
+use serde_json::Value;
+
+const MIN_VERSION: Version = Version(1, 0, 0);
+
+/// Rewrites applied, in order, to every document older than their version.
+/// All spec changes from 1.0 to the current version only added optional fields
+/// and new node kinds, which deserialize with their defaults, so no rewrite
+/// is needed yet. Breaking spec changes must register their migration here.
+const MIGRATIONS: &[Migration] = &[];
+
+struct Migration {
+    version: Version,
+    migrate: fn(&mut Value),
+}
+
+/// Parses a `RootNode` produced by any supported version of the Codama
+/// standard and upgrades it to the current version.
+pub fn migrate_json(json: &str) -> CodamaResult<RootNode> {
+    migrate_value(serde_json::from_str(json)?)
+}
+
+/// Same as `migrate_json` but from an already parsed JSON value.
+pub fn migrate_value(mut value: Value) -> CodamaResult<RootNode> {
+    let current = Version::parse(CODAMA_VERSION).expect("valid current version");
+    let raw_version = value
+        .get("version")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let version = Version::parse(&raw_version)
+        .filter(|version| (MIN_VERSION..=current).contains(version))
+        .ok_or_else(|| CodamaError::UnsupportedVersion {
+            version: raw_version,
+            expected: format!("{MIN_VERSION} to {current}"),
+        })?;
+
+    for migration in MIGRATIONS.iter().filter(|m| version < m.version) {
+        (migration.migrate)(&mut value);
+    }
+    value["version"] = CODAMA_VERSION.into();
+    Ok(serde_json::from_value(value)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version(u32, u32, u32);
+
+impl Version {
+    /// Parses `major.minor.patch`, ignoring any pre-release or build suffix.
+    fn parse(version: &str) -> Option<Self> {
+        let core = version.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+        let version = Self(parts.next()??, parts.next()??, parts.next()??);
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeTrait, ProgramNode};
+    use pretty_assertions::assert_eq;
+
+    fn get_json(version: &str) -> String {
+        format!(
+            r#"{{"kind":"rootNode","standard":"codama","version":"{version}","program":{{"kind":"programNode","name":"myProgram","publicKey":"1111","version":"1.2.3"}}}}"#
+        )
+    }
+
+    #[test]
+    fn migrate_older_versions() {
+        for version in ["1.0.0", "1.3.2", "1.7.0", "1.2.0-beta.1"] {
+            let node = migrate_json(&get_json(version)).unwrap();
+            assert_eq!(node.version, CODAMA_VERSION);
+            assert_eq!(
+                node.program,
+                ProgramNode {
+                    version: "1.2.3".into(),
+                    ..ProgramNode::new("myProgram", "1111")
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn do_not_repair_older_documents() {
+        // Attributes left out by a document are not filled in by its version.
+        let mut json: Value = serde_json::from_str(&get_json("1.0.0")).unwrap();
+        json["program"]["definedTypes"] = serde_json::json!([{
+            "kind": "definedTypeNode",
+            "name": "counter",
+            "type": { "kind": "numberTypeNode", "format": "u64" },
+        }]);
+        assert!(migrate_value(json).is_err());
+    }
+
+    #[test]
+    fn migrate_current_version() {
+        let json = get_json(CODAMA_VERSION);
+        assert_eq!(
+            migrate_json(&json).unwrap(),
+            RootNode::from_json(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn migrate_unsupported_versions() {
+        for version in ["0.22.0", "1.99.0", "2.0.0", "1.0", "latest", ""] {
+            assert!(matches!(
+                migrate_json(&get_json(version)),
+                Err(CodamaError::UnsupportedVersion { version: v, .. }) if v == version
+            ));
+        }
+        assert!(matches!(
+            migrate_value(serde_json::json!({ "kind": "rootNode" })),
+            Err(CodamaError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn version_parse() {
+        assert_eq!(Version::parse("1.8.0"), Some(Version(1, 8, 0)));
+        assert_eq!(Version::parse("1.2.0-rc.1+abc"), Some(Version(1, 2, 0)));
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+        assert!(Version(1, 7, 9) < Version(1, 8, 0));
+    }
+}
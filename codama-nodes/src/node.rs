@@ -6,10 +6,11 @@ use crate::{
     ProvidedNode, RegisteredContextualValueNode, RegisteredTypeNode, RegisteredValueNode, RootNode,
     TypeNode, ValueNode,
 };
+use codama_nodes_derive::{JsonSchema, Visitable};
 use derive_more::derive::From;
 use serde::{Deserialize, Serialize};
 
-#[derive(From, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(From, Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema, Visitable)]
 #[serde(untagged)]
 pub enum Node {
     // Node unions.
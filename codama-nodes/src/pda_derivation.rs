@@ -0,0 +1,266 @@
+use crate::{
+    decode_public_key, encode, BytesEncoding, ConstantPdaSeedValue, LinkResolver, PdaNode,
+    PdaSeedNode, PdaSeedValueNode, ValueNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+const MAX_SEEDS: usize = 16;
+const MAX_SEED_LENGTH: usize = 32;
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Derives the address and bump of the given PDA, entirely offline.
+///
+/// Variable seeds are read by name from the provided seed values and encoded using
+/// their seed type whereas constant seeds are encoded from the PDA itself. The program
+/// address is only used when the PDA does not define its own `program_id`.
+pub fn find_program_address(
+    pda: &PdaNode,
+    program_address: &str,
+    seeds: &[PdaSeedValueNode],
+    resolver: &LinkResolver,
+) -> CodamaResult<(String, u8)> {
+    let error = |message: String| CodamaError::PdaDerivationFailed {
+        name: pda.name.to_string(),
+        message,
+    };
+
+    let program_address = pda.program_id.as_deref().unwrap_or(program_address);
+    let program_id = decode_public_key(program_address)
+        .map_err(|_| error(format!("invalid program address `{program_address}`")))?;
+
+    if let Some(seed) = seeds.iter().find(|seed| {
+        !pda.seeds.iter().any(|pda_seed| match pda_seed {
+            PdaSeedNode::Variable(pda_seed) => pda_seed.name == seed.name,
+            PdaSeedNode::Constant(_) => false,
+        })
+    }) {
+        return Err(error(format!("unknown seed `{}`", seed.name.as_str())));
+    }
+
+    let seed_bytes = pda
+        .seeds
+        .iter()
+        .map(|seed| get_seed_bytes(seed, &program_id, seeds, resolver))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(error)?;
+    if seed_bytes.len() >= MAX_SEEDS {
+        return Err(error(format!(
+            "found {} seeds, the maximum is {}",
+            seed_bytes.len(),
+            MAX_SEEDS - 1
+        )));
+    }
+    if let Some(seed) = seed_bytes.iter().find(|seed| seed.len() > MAX_SEED_LENGTH) {
+        return Err(error(format!(
+            "found a seed of {} bytes, the maximum is {MAX_SEED_LENGTH}",
+            seed.len()
+        )));
+    }
+
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut seeds = seed_bytes.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        seeds.push(&bump_seed);
+        if let Some(address) = create_program_address(&seeds, &program_id) {
+            return Ok((BytesEncoding::Base58.encode(&address)?, bump));
+        }
+    }
+    Err(error("could not find a viable bump seed".to_string()))
+}
+
+fn get_seed_bytes(
+    seed: &PdaSeedNode,
+    program_id: &[u8; 32],
+    seeds: &[PdaSeedValueNode],
+    resolver: &LinkResolver,
+) -> Result<Vec<u8>, String> {
+    let (value, r#type) = match seed {
+        PdaSeedNode::Constant(seed) => match seed.value.as_ref() {
+            ConstantPdaSeedValue::ProgramId(_) => return Ok(program_id.to_vec()),
+            value => (
+                ValueNode::try_from(value.clone()).map_err(|error| error.to_string())?,
+                &seed.r#type,
+            ),
+        },
+        PdaSeedNode::Variable(seed) => {
+            let name = seed.name.as_str();
+            let value = seeds
+                .iter()
+                .find(|value| value.name == seed.name)
+                .ok_or_else(|| format!("missing value for seed `{name}`"))?;
+            (
+                ValueNode::try_from(value.value.as_ref().clone())
+                    .map_err(|_| format!("seed `{name}` must be a concrete value"))?,
+                &seed.r#type,
+            )
+        }
+    };
+    encode(&value, r#type, resolver).map_err(|error| error.to_string())
+}
+
+/// Hashes the seeds with the program ID and only returns the result if it does not
+/// lie on the ed25519 curve, meaning no private key can sign for this address.
+fn create_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+    let address: [u8; 32] = hasher.finalize().into();
+    match CompressedEdwardsY(address).decompress() {
+        Some(_) => None,
+        None => Some(address),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountValueNode, ConstantPdaSeedNode, NumberTypeNode, NumberValueNode, ProgramIdValueNode,
+        ProgramNode, PublicKeyTypeNode, PublicKeyValueNode, StringTypeNode, StringValueNode,
+        VariablePdaSeedNode, U8,
+    };
+
+    const PROGRAM: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+    fn decode_public_key(address: &str) -> [u8; 32] {
+        BytesEncoding::Base58
+            .decode(address)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn encode_public_key(address: &[u8; 32]) -> String {
+        BytesEncoding::Base58.encode(address).unwrap()
+    }
+
+    #[test]
+    fn create_program_address_vectors() {
+        let program_id = decode_public_key(PROGRAM);
+        let seed_public_key = decode_public_key("SeedPubey1111111111111111111111111111111111");
+        let create = |seeds: &[&[u8]]| {
+            create_program_address(seeds, &program_id).map(|address| encode_public_key(&address))
+        };
+        assert_eq!(
+            create(&[b"", &[1]]),
+            Some("BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe".to_string())
+        );
+        assert_eq!(
+            create(&["☉".as_bytes(), &[0]]),
+            Some("13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19".to_string())
+        );
+        assert_eq!(
+            create(&[b"Talking", b"Squirrels"]),
+            Some("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk".to_string())
+        );
+        assert_eq!(
+            create(&[&seed_public_key, &[1]]),
+            Some("976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL".to_string())
+        );
+    }
+
+    #[test]
+    fn find_program_address_with_highest_viable_bump() {
+        let pda = PdaNode::new(
+            "counter",
+            vec![
+                ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new("counter"))
+                    .into(),
+                VariablePdaSeedNode::new("authority", PublicKeyTypeNode::new()).into(),
+                VariablePdaSeedNode::new("index", NumberTypeNode::le(U8)).into(),
+                ConstantPdaSeedNode::new(PublicKeyTypeNode::new(), ProgramIdValueNode::new())
+                    .into(),
+            ],
+        );
+        let authority = "SeedPubey1111111111111111111111111111111111";
+        let seeds = vec![
+            PdaSeedValueNode::new("authority", PublicKeyValueNode::new(authority)),
+            PdaSeedValueNode::new("index", NumberValueNode::new(7u8)),
+        ];
+        let program = ProgramNode::new("test", PROGRAM);
+        let (address, bump) =
+            find_program_address(&pda, PROGRAM, &seeds, &LinkResolver::from_program(&program))
+                .unwrap();
+
+        let program_id = decode_public_key(PROGRAM);
+        let authority = decode_public_key(authority);
+        let seeds: [&[u8]; 4] = [b"counter", &authority, &[7], &program_id];
+        let create = |bump: u8| {
+            let mut seeds = seeds.to_vec();
+            let bump_seed = [bump];
+            seeds.push(&bump_seed);
+            create_program_address(&seeds, &program_id)
+        };
+        assert_eq!(create(bump).map(|a| encode_public_key(&a)), Some(address));
+        assert!((bump + 1..=u8::MAX).all(|bump| create(bump).is_none()));
+    }
+
+    #[test]
+    fn find_program_address_with_pda_program_id() {
+        let pda = PdaNode {
+            program_id: Some(PROGRAM.into()),
+            ..PdaNode::new("empty", vec![])
+        };
+        let program = ProgramNode::new("test", PROGRAM);
+        let resolver = LinkResolver::from_program(&program);
+        let other_program = "11111111111111111111111111111111";
+        assert_eq!(
+            find_program_address(&pda, other_program, &[], &resolver).unwrap(),
+            find_program_address(&PdaNode::new("empty", vec![]), PROGRAM, &[], &resolver).unwrap(),
+        );
+    }
+
+    #[test]
+    fn find_program_address_with_invalid_seeds() {
+        let pda = PdaNode::new(
+            "counter",
+            vec![VariablePdaSeedNode::new("authority", PublicKeyTypeNode::new()).into()],
+        );
+        let program = ProgramNode::new("test", PROGRAM);
+        let resolver = LinkResolver::from_program(&program);
+        let find = |seeds: &[PdaSeedValueNode]| {
+            find_program_address(&pda, PROGRAM, seeds, &resolver)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            find(&[]),
+            "Could not derive PDA `counter`: missing value for seed `authority`"
+        );
+        assert_eq!(
+            find(&[PdaSeedValueNode::new(
+                "authority",
+                AccountValueNode::new("payer")
+            )]),
+            "Could not derive PDA `counter`: seed `authority` must be a concrete value"
+        );
+        assert_eq!(
+            find(&[PdaSeedValueNode::new("owner", NumberValueNode::new(1u8))]),
+            "Could not derive PDA `counter`: unknown seed `owner`"
+        );
+    }
+
+    #[test]
+    fn find_program_address_with_too_long_seed() {
+        let pda = PdaNode::new(
+            "counter",
+            vec![VariablePdaSeedNode::new("name", StringTypeNode::utf8()).into()],
+        );
+        let seeds = vec![PdaSeedValueNode::new(
+            "name",
+            StringValueNode::new("a".repeat(33)),
+        )];
+        let program = ProgramNode::new("test", PROGRAM);
+        assert_eq!(
+            find_program_address(&pda, PROGRAM, &seeds, &LinkResolver::from_program(&program))
+                .unwrap_err()
+                .to_string(),
+            "Could not derive PDA `counter`: found a seed of 33 bytes, the maximum is 32"
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{ConstantPdaSeedNode, ConstantPdaSeedValue, TypeNode, ValueNode};
+use crate::{ConstantPdaSeedNode, ConstantPdaSeedValue, HasKind, TypeNode, ValueNode};
+use codama_errors::CodamaError;
 
 impl ConstantPdaSeedNode {
     pub fn new<T, U>(r#type: T, value: U) -> Self
@@ -35,6 +36,36 @@ impl From<ValueNode> for ConstantPdaSeedValue {
             ValueNode::String(n) => Self::String(n),
             ValueNode::Struct(n) => Self::Struct(n),
             ValueNode::Tuple(n) => Self::Tuple(n),
+            ValueNode::Unknown(n) => Self::Unknown(n),
+        }
+    }
+}
+
+impl TryFrom<ConstantPdaSeedValue> for ValueNode {
+    type Error = CodamaError;
+
+    fn try_from(value: ConstantPdaSeedValue) -> Result<Self, Self::Error> {
+        match value {
+            ConstantPdaSeedValue::Array(value) => Ok(Self::Array(value)),
+            ConstantPdaSeedValue::Boolean(value) => Ok(Self::Boolean(value)),
+            ConstantPdaSeedValue::Bytes(value) => Ok(Self::Bytes(value)),
+            ConstantPdaSeedValue::Constant(value) => Ok(Self::Constant(value)),
+            ConstantPdaSeedValue::Enum(value) => Ok(Self::Enum(value)),
+            ConstantPdaSeedValue::Injected(value) => Ok(Self::Injected(value)),
+            ConstantPdaSeedValue::Map(value) => Ok(Self::Map(value)),
+            ConstantPdaSeedValue::None(value) => Ok(Self::None(value)),
+            ConstantPdaSeedValue::Number(value) => Ok(Self::Number(value)),
+            ConstantPdaSeedValue::PublicKey(value) => Ok(Self::PublicKey(value)),
+            ConstantPdaSeedValue::Set(value) => Ok(Self::Set(value)),
+            ConstantPdaSeedValue::Some(value) => Ok(Self::Some(value)),
+            ConstantPdaSeedValue::String(value) => Ok(Self::String(value)),
+            ConstantPdaSeedValue::Struct(value) => Ok(Self::Struct(value)),
+            ConstantPdaSeedValue::Tuple(value) => Ok(Self::Tuple(value)),
+            ConstantPdaSeedValue::Unknown(value) => Ok(Self::Unknown(value)),
+            _ => Err(CodamaError::InvalidNodeConversion {
+                from: value.kind().to_string(),
+                into: "ValueNode".to_string(),
+            }),
         }
     }
 }
@@ -0,0 +1,915 @@
+use crate::{
+    CamelCaseString, DisplaySkip, EnumValuePayload, EnumVariantTypeNode, HasKind, HasName,
+    InjectableNumberValueNode, InjectableStringValueNode, InjectedValueNode, InstructionNode,
+    LinkResolver, NestedTypeNodeTrait, Node, Number, NumberDisplayNode, NumberTypeNode,
+    PublicKeyValueNode, RegisteredContextualValueNode, StringTypeNode, StructFieldDisplayNode,
+    StructFieldValueNode, StructTypeNode, TypeNode, ValueNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+use serde_json::Number as JsonNumber;
+
+/// The decoded values of an instruction, used to render its display.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InstructionDisplayValues {
+    /// The decoded arguments of the instruction, by argument name.
+    pub arguments: Vec<StructFieldValueNode>,
+    /// The addresses of the instruction accounts, by account name.
+    pub accounts: Vec<(CamelCaseString, String)>,
+    /// The values of `injectedValueNode`s, by key. They take precedence
+    /// over the values provided by the instruction itself.
+    pub injected: Vec<(CamelCaseString, ValueNode)>,
+}
+
+/// The human-readable display of an instruction.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RenderedInstructionDisplay {
+    /// The intent of the instruction, with its placeholders replaced by their values.
+    pub intent: Option<String>,
+    /// The labelled values of the accounts and arguments that are not skipped.
+    pub fields: Vec<RenderedDisplayField>,
+}
+
+/// A labelled value of a rendered instruction display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedDisplayField {
+    pub label: String,
+    pub value: String,
+}
+
+/// Renders the display of an instruction from its decoded arguments and account addresses.
+///
+/// Placeholders of the interpolated intent refer to arguments as `${data.amount}` —
+/// optionally followed by the fields of a struct argument — and to accounts
+/// as `${accounts.destination}`. Missing arguments fall back to their default
+/// value whereas missing accounts are omitted from the rendered fields.
+pub fn render_instruction_display(
+    instruction: &InstructionNode,
+    values: &InstructionDisplayValues,
+    resolver: &LinkResolver,
+) -> CodamaResult<RenderedInstructionDisplay> {
+    let mut renderer = DisplayRenderer {
+        instruction,
+        values,
+        resolver: resolver.with_instruction(instruction),
+        surfaced: vec![],
+    };
+    renderer
+        .render()
+        .map_err(|message| CodamaError::DisplayRenderingFailed {
+            name: instruction.name.to_string(),
+            message,
+        })
+}
+
+/// An instruction member whose value can be surfaced through injected values.
+#[derive(Debug, Clone, PartialEq)]
+enum Member {
+    Account(CamelCaseString),
+    Argument(CamelCaseString),
+}
+
+struct PendingField {
+    label: String,
+    value: String,
+    skip: Option<DisplaySkip>,
+    member: Option<Member>,
+}
+
+struct DisplayRenderer<'a, 'b> {
+    instruction: &'b InstructionNode,
+    values: &'b InstructionDisplayValues,
+    resolver: LinkResolver<'a>,
+    /// The members whose values were pulled through injected values.
+    surfaced: Vec<Member>,
+}
+
+impl DisplayRenderer<'_, '_> {
+    fn render(&mut self) -> Result<RenderedInstructionDisplay, String> {
+        let display = self.instruction.display.as_ref();
+        let intent = match display.and_then(|display| display.interpolated_intent.as_deref()) {
+            Some(intent) => Some(self.interpolate(intent)?),
+            None => display.and_then(|display| display.intent.clone()),
+        };
+
+        // Members surfaced anywhere through injection are only known once every field
+        // is rendered, which is why skip rules are applied on pending fields.
+        let mut fields = vec![];
+        for account in &self.instruction.accounts {
+            let Some(address) = self.account_address(&account.name) else {
+                continue;
+            };
+            let display = account.display.as_ref();
+            fields.push(PendingField {
+                label: label(display.and_then(|d| d.label.as_ref()), &account.name),
+                value: address.to_string(),
+                skip: display.and_then(|display| display.skip),
+                member: Some(Member::Account(account.name.clone())),
+            });
+        }
+        for argument in &self.instruction.arguments {
+            let value = self.argument_value(&argument.name)?;
+            let member = Member::Argument(argument.name.clone());
+            self.push_fields(
+                &mut fields,
+                &argument.name,
+                argument.display.as_ref(),
+                &value,
+                &argument.r#type,
+                "",
+                Some(member),
+            )?;
+        }
+
+        let fields = fields
+            .into_iter()
+            .filter(|field| match field.skip {
+                Some(DisplaySkip::Always) => false,
+                Some(DisplaySkip::WhenInjected) => field
+                    .member
+                    .as_ref()
+                    .is_none_or(|member| !self.surfaced.contains(member)),
+                Some(DisplaySkip::Never) | None => true,
+            })
+            .map(|field| RenderedDisplayField {
+                label: field.label,
+                value: field.value,
+            })
+            .collect();
+        Ok(RenderedInstructionDisplay { intent, fields })
+    }
+
+    /// Pushes the field of a member, or the fields of its struct value when it is flattened.
+    #[allow(clippy::too_many_arguments)]
+    fn push_fields(
+        &mut self,
+        fields: &mut Vec<PendingField>,
+        name: &CamelCaseString,
+        display: Option<&StructFieldDisplayNode>,
+        value: &ValueNode,
+        r#type: &TypeNode,
+        prefix: &str,
+        member: Option<Member>,
+    ) -> Result<(), String> {
+        let flatten = display.and_then(|display| display.flatten) == Some(true);
+        if let (true, Some(struct_type), ValueNode::Struct(struct_value)) =
+            (flatten, self.struct_type(r#type)?, value)
+        {
+            let prefix = match display.and_then(|display| display.flatten_prefix.as_deref()) {
+                Some(flatten_prefix) => format!("{prefix}{flatten_prefix}"),
+                None => prefix.to_string(),
+            };
+            for field in &struct_type.fields {
+                let value = find_field(&struct_value.fields, &field.name)?;
+                self.push_fields(
+                    fields,
+                    &field.name,
+                    field.display.as_ref(),
+                    value,
+                    &field.r#type,
+                    &prefix,
+                    None,
+                )?;
+            }
+            return Ok(());
+        }
+
+        fields.push(PendingField {
+            label: format!(
+                "{prefix}{}",
+                label(display.and_then(|d| d.label.as_ref()), name)
+            ),
+            value: self.format(value, r#type)?,
+            skip: display.and_then(|display| display.skip),
+            member,
+        });
+        Ok(())
+    }
+
+    fn interpolate(&mut self, intent: &str) -> Result<String, String> {
+        let mut result = String::new();
+        let mut rest = intent;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| format!("unclosed placeholder in `{intent}`"))?;
+            let placeholder = &rest[start + 2..start + end];
+            result.push_str(&self.placeholder(placeholder)?);
+            rest = &rest[start + end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    fn placeholder(&mut self, placeholder: &str) -> Result<String, String> {
+        let segments = placeholder.split('.').map(str::trim).collect::<Vec<_>>();
+        match segments.as_slice() {
+            ["accounts", name] => {
+                let name = CamelCaseString::new(name);
+                if !self.instruction.accounts.iter().any(|a| a.name == name) {
+                    return Err(format!("unknown account `{}`", name.as_str()));
+                }
+                self.account_address(&name)
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("missing address for account `{}`", name.as_str()))
+            }
+            ["data", name, path @ ..] => {
+                let name = CamelCaseString::new(name);
+                let argument = self
+                    .instruction
+                    .arguments
+                    .iter()
+                    .find(|argument| argument.name == name)
+                    .ok_or_else(|| format!("unknown argument `{}`", name.as_str()))?;
+                let mut value = self.argument_value(&name)?;
+                let mut r#type = argument.r#type.as_ref().clone();
+                for segment in path {
+                    let field_name = CamelCaseString::new(segment);
+                    let struct_type = self
+                        .struct_type(&r#type)?
+                        .ok_or_else(|| format!("cannot read `{segment}` in `{placeholder}`"))?;
+                    let field = struct_type
+                        .fields
+                        .iter()
+                        .find(|field| field.name == field_name)
+                        .ok_or_else(|| format!("unknown field `{segment}` in `{placeholder}`"))?;
+                    let ValueNode::Struct(struct_value) = self.resolve(&value)? else {
+                        return Err(format!("cannot read `{segment}` in `{placeholder}`"));
+                    };
+                    value = find_field(&struct_value.fields, &field_name)?.clone();
+                    r#type = field.r#type.as_ref().clone();
+                }
+                self.format(&value, &r#type)
+            }
+            _ => Err(format!("invalid placeholder `{placeholder}`")),
+        }
+    }
+
+    fn account_address(&self, name: &CamelCaseString) -> Option<&str> {
+        self.values
+            .accounts
+            .iter()
+            .find(|(account, _)| account == name)
+            .map(|(_, address)| address.as_str())
+    }
+
+    fn argument_value(&self, name: &CamelCaseString) -> Result<ValueNode, String> {
+        if let Some(field) = self.values.arguments.iter().find(|f| &f.name == name) {
+            return Ok(field.value.as_ref().clone());
+        }
+        self.instruction
+            .arguments
+            .iter()
+            .find(|argument| &argument.name == name)
+            .and_then(|argument| (*argument.default_value).clone())
+            .and_then(|value| ValueNode::try_from(value).ok())
+            .ok_or_else(|| format!("missing value for argument `{}`", name.as_str()))
+    }
+
+    /// Resolves injected values from the given values first, then from the
+    /// values provided by the instruction and finally from their fallback.
+    fn injected(&mut self, node: &InjectedValueNode) -> Result<ValueNode, String> {
+        if let Some((_, value)) = self.values.injected.iter().find(|(k, _)| k == &node.key) {
+            return Ok(value.clone());
+        }
+        let provided = self
+            .instruction
+            .provides
+            .iter()
+            .find(|provided| provided.name == node.key);
+        if let Some(provided) = provided {
+            match provided.node.as_ref() {
+                Node::Value(value) => {
+                    if let Ok(value) = ValueNode::try_from(value.clone()) {
+                        return Ok(value);
+                    }
+                }
+                Node::ContextualValue(RegisteredContextualValueNode::Argument(argument)) => {
+                    let value = self.argument_value(&argument.name)?;
+                    self.surfaced.push(Member::Argument(argument.name.clone()));
+                    return Ok(value);
+                }
+                Node::ContextualValue(RegisteredContextualValueNode::Account(account)) => {
+                    if let Some(address) = self.account_address(&account.name) {
+                        let value = PublicKeyValueNode::new(address).into();
+                        self.surfaced.push(Member::Account(account.name.clone()));
+                        return Ok(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        (*node.fallback)
+            .clone()
+            .ok_or_else(|| format!("could not resolve injected value `{}`", node.key.as_str()))
+    }
+
+    /// Resolves injected and constant values into the values they hold.
+    fn resolve(&mut self, value: &ValueNode) -> Result<ValueNode, String> {
+        match value {
+            ValueNode::Injected(node) => {
+                let value = self.injected(node)?;
+                self.resolve(&value)
+            }
+            ValueNode::Constant(node) => self.resolve(&node.value),
+            value => Ok(value.clone()),
+        }
+    }
+
+    /// The struct type behind wrappers and links, if any.
+    fn struct_type(&self, r#type: &TypeNode) -> Result<Option<StructTypeNode>, String> {
+        Ok(match self.unwrap_type(r#type)? {
+            TypeNode::Struct(node) => Some(node),
+            _ => None,
+        })
+    }
+
+    /// Follows links and type wrappers that do not affect how values are displayed.
+    fn unwrap_type(&self, r#type: &TypeNode) -> Result<TypeNode, String> {
+        match r#type {
+            TypeNode::FixedSize(node) => self.unwrap_type(&node.r#type),
+            TypeNode::HiddenPrefix(node) => self.unwrap_type(&node.r#type),
+            TypeNode::HiddenSuffix(node) => self.unwrap_type(&node.r#type),
+            TypeNode::PostOffset(node) => self.unwrap_type(&node.r#type),
+            TypeNode::PreOffset(node) => self.unwrap_type(&node.r#type),
+            TypeNode::Sentinel(node) => self.unwrap_type(&node.r#type),
+            TypeNode::SizePrefix(node) => self.unwrap_type(&node.r#type),
+            TypeNode::Link(link) => {
+                let resolver = match &link.program {
+                    Some(program) => self.resolver.with_program(
+                        self.resolver
+                            .resolve_program(program)
+                            .map_err(|error| error.to_string())?,
+                    ),
+                    None => self.resolver,
+                };
+                let defined_type = resolver
+                    .resolve_defined_type(link)
+                    .map_err(|error| error.to_string())?;
+                self.unwrap_type(&defined_type.r#type)
+            }
+            r#type => Ok(r#type.clone()),
+        }
+    }
+
+    fn format(&mut self, value: &ValueNode, r#type: &TypeNode) -> Result<String, String> {
+        let value = self.resolve(value)?;
+        let r#type = self.unwrap_type(r#type)?;
+        let mismatch = || format!("cannot display `{}` as `{}`", value.kind(), r#type.kind());
+        Ok(match (&r#type, &value) {
+            (TypeNode::Number(node), ValueNode::Number(value)) => {
+                self.format_number(value.number, node)?
+            }
+            (TypeNode::Amount(node), ValueNode::Number(value)) => {
+                format_amount(value.number, node.decimals, node.unit.as_deref())
+            }
+            (TypeNode::SolAmount(_), ValueNode::Number(value)) => {
+                format_amount(value.number, 9, Some("SOL"))
+            }
+            (TypeNode::DateTime(_), ValueNode::Number(value)) => format_date_time(value.number, 1),
+            (TypeNode::Boolean(_), ValueNode::Boolean(value)) => value.boolean.to_string(),
+            (TypeNode::String(node), ValueNode::String(value)) => {
+                format_string(&value.string, node)
+            }
+            (TypeNode::Bytes(_), ValueNode::Bytes(value)) => value.data.clone(),
+            (TypeNode::PublicKey(_), ValueNode::PublicKey(value)) => value.public_key.clone(),
+            (
+                TypeNode::Option(_) | TypeNode::ZeroableOption(_) | TypeNode::RemainderOption(_),
+                ValueNode::None(_),
+            ) => "none".to_string(),
+            (TypeNode::Option(node), ValueNode::Some(value)) => {
+                self.format(&value.value, &node.item)?
+            }
+            (TypeNode::ZeroableOption(node), ValueNode::Some(value)) => {
+                self.format(&value.value, &node.item)?
+            }
+            (TypeNode::RemainderOption(node), ValueNode::Some(value)) => {
+                self.format(&value.value, &node.item)?
+            }
+            (TypeNode::Array(node), ValueNode::Array(value)) => {
+                format!("[{}]", self.format_items(&value.items, &node.item)?)
+            }
+            (TypeNode::Set(node), ValueNode::Set(value)) => {
+                format!("[{}]", self.format_items(&value.items, &node.item)?)
+            }
+            (TypeNode::Map(node), ValueNode::Map(value)) => {
+                let entries = value
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let key = self.format(&entry.key, &node.key)?;
+                        let value = self.format(&entry.value, &node.value)?;
+                        Ok(format!("{key}: {value}"))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                format!("{{{}}}", entries.join(", "))
+            }
+            (TypeNode::Tuple(node), ValueNode::Tuple(value)) => {
+                format!("({})", self.format_tuple(&value.items, &node.items)?)
+            }
+            (TypeNode::Struct(node), ValueNode::Struct(value)) => {
+                format!("{{{}}}", self.format_struct(&value.fields, node)?)
+            }
+            (TypeNode::Enum(node), ValueNode::Enum(value)) => {
+                let variant = node
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name() == &value.variant)
+                    .ok_or_else(|| format!("unknown variant `{}`", value.variant.as_str()))?;
+                let display = match variant {
+                    EnumVariantTypeNode::Empty(variant) => variant.display.as_ref(),
+                    EnumVariantTypeNode::Struct(variant) => variant.display.as_ref(),
+                    EnumVariantTypeNode::Tuple(variant) => variant.display.as_ref(),
+                };
+                let label = label(display.and_then(|d| d.label.as_ref()), variant.name());
+                if display.and_then(|display| display.skip_inner_data) == Some(true) {
+                    return Ok(label);
+                }
+                match (variant, value.value.as_ref()) {
+                    (EnumVariantTypeNode::Struct(variant), Some(EnumValuePayload::Struct(v))) => {
+                        let fields = variant.r#struct.get_nested_type_node();
+                        format!("{label} {{{}}}", self.format_struct(&v.fields, fields)?)
+                    }
+                    (EnumVariantTypeNode::Tuple(variant), Some(EnumValuePayload::Tuple(v))) => {
+                        let items = &variant.tuple.get_nested_type_node().items;
+                        format!("{label}({})", self.format_tuple(&v.items, items)?)
+                    }
+                    (EnumVariantTypeNode::Empty(_), None) => label,
+                    _ => return Err(mismatch()),
+                }
+            }
+            _ => return Err(mismatch()),
+        })
+    }
+
+    fn format_items(&mut self, items: &[ValueNode], r#type: &TypeNode) -> Result<String, String> {
+        let items = items
+            .iter()
+            .map(|item| self.format(item, r#type))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(items.join(", "))
+    }
+
+    fn format_tuple(&mut self, items: &[ValueNode], types: &[TypeNode]) -> Result<String, String> {
+        if items.len() != types.len() {
+            return Err(format!(
+                "expected {} tuple items, found {}",
+                types.len(),
+                items.len()
+            ));
+        }
+        let items = items
+            .iter()
+            .zip(types)
+            .map(|(item, r#type)| self.format(item, r#type))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(items.join(", "))
+    }
+
+    fn format_struct(
+        &mut self,
+        values: &[StructFieldValueNode],
+        node: &StructTypeNode,
+    ) -> Result<String, String> {
+        let fields = node
+            .fields
+            .iter()
+            .map(|field| {
+                let value = find_field(values, &field.name)?;
+                let label = label(
+                    field.display.as_ref().and_then(|d| d.label.as_ref()),
+                    &field.name,
+                );
+                Ok(format!("{label}: {}", self.format(value, &field.r#type)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(fields.join(", "))
+    }
+
+    fn format_number(&mut self, number: Number, node: &NumberTypeNode) -> Result<String, String> {
+        Ok(match node.display.as_ref() {
+            Some(NumberDisplayNode::Amount(display)) => {
+                let decimals = match display.decimals.as_ref() {
+                    Some(decimals) => self.injectable_number(decimals)?,
+                    None => 0,
+                };
+                let unit = match display.unit.as_ref() {
+                    Some(unit) => Some(self.injectable_string(unit)?),
+                    None => None,
+                };
+                format_amount(number, decimals, unit.as_deref())
+            }
+            Some(NumberDisplayNode::DateTime(display)) => {
+                format_date_time(number, display.ticks_per_second.unwrap_or(1))
+            }
+            Some(NumberDisplayNode::Duration(display)) => {
+                format_duration(number, display.ticks_per_second.unwrap_or(1))
+            }
+            None => JsonNumber::from(number).to_string(),
+        })
+    }
+
+    fn injectable_number(&mut self, node: &InjectableNumberValueNode) -> Result<u32, String> {
+        let number = match node {
+            InjectableNumberValueNode::Number(node) => node.number,
+            InjectableNumberValueNode::Injected(node) => match self.injected(node)? {
+                ValueNode::Number(node) => node.number,
+                value => return Err(format!("expected a number, found `{}`", value.kind())),
+            },
+        };
+        match number {
+            Number::UnsignedInteger(number) => u32::try_from(number).ok(),
+            Number::SignedInteger(number) => u32::try_from(number).ok(),
+            Number::Float(_) => None,
+        }
+        .ok_or_else(|| format!("invalid decimals `{}`", JsonNumber::from(number)))
+    }
+
+    fn injectable_string(&mut self, node: &InjectableStringValueNode) -> Result<String, String> {
+        match node {
+            InjectableStringValueNode::String(node) => Ok(node.string.clone()),
+            InjectableStringValueNode::Injected(node) => match self.injected(node)? {
+                ValueNode::String(node) => Ok(node.string),
+                value => Err(format!("expected a string, found `{}`", value.kind())),
+            },
+        }
+    }
+}
+
+fn label(label: Option<&String>, name: &CamelCaseString) -> String {
+    label.cloned().unwrap_or_else(|| name.to_string())
+}
+
+fn find_field<'a>(
+    fields: &'a [StructFieldValueNode],
+    name: &CamelCaseString,
+) -> Result<&'a ValueNode, String> {
+    fields
+        .iter()
+        .find(|field| &field.name == name)
+        .map(|field| field.value.as_ref())
+        .ok_or_else(|| format!("missing value for field `{}`", name.as_str()))
+}
+
+fn format_string(string: &str, node: &StringTypeNode) -> String {
+    let Some(display) = &node.display else {
+        return string.to_string();
+    };
+    let start = display.slice_start.unwrap_or(0) as usize;
+    let end = display.slice_end.map(|end| end as usize);
+    let chars = string.chars().skip(start);
+    match end {
+        Some(end) => chars.take(end.saturating_sub(start)).collect(),
+        None => chars.collect(),
+    }
+}
+
+/// Shifts integers by the given decimals, e.g. `1500000` with 6 decimals becomes `1.5`.
+fn format_amount(number: Number, decimals: u32, unit: Option<&str>) -> String {
+    let (negative, digits) = match number {
+        Number::UnsignedInteger(number) => (false, number.to_string()),
+        Number::SignedInteger(number) => (number < 0, number.unsigned_abs().to_string()),
+        Number::Float(number) => {
+            let amount = number / 10f64.powi(decimals as i32);
+            return with_unit(amount.to_string(), unit);
+        }
+    };
+    let decimals = decimals as usize;
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    let sign = if negative { "-" } else { "" };
+    let amount = match fraction.is_empty() {
+        true => format!("{sign}{integer}"),
+        false => format!("{sign}{integer}.{fraction}"),
+    };
+    with_unit(amount, unit)
+}
+
+fn with_unit(amount: String, unit: Option<&str>) -> String {
+    match unit {
+        Some(unit) => format!("{amount} {unit}"),
+        None => amount,
+    }
+}
+
+fn to_seconds(number: Number, ticks_per_second: u64) -> i128 {
+    let ticks = match number {
+        Number::UnsignedInteger(number) => number as i128,
+        Number::SignedInteger(number) => number as i128,
+        Number::Float(number) => number as i128,
+    };
+    ticks.div_euclid(ticks_per_second.max(1) as i128)
+}
+
+/// Formats a Unix timestamp as an ISO 8601 UTC date, e.g. `2024-01-01T00:00:00Z`.
+fn format_date_time(number: Number, ticks_per_second: u64) -> String {
+    let seconds = to_seconds(number, ticks_per_second);
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+    // Converts days since the Unix epoch into a civil date.
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+/// Formats a duration using days, hours, minutes and seconds, e.g. `1d 2h 30m`.
+fn format_duration(number: Number, ticks_per_second: u64) -> String {
+    let seconds = to_seconds(number, ticks_per_second);
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.unsigned_abs();
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3_600, "h"),
+        (seconds % 3_600 / 60, "m"),
+        (seconds % 60, "s"),
+    ]
+    .into_iter()
+    .filter(|(value, _)| *value > 0)
+    .map(|(value, unit)| format!("{value}{unit}"))
+    .collect::<Vec<_>>();
+    match parts.is_empty() {
+        true => "0s".to_string(),
+        false => format!("{sign}{}", parts.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountValueNode, AmountNumberDisplayNode, ArgumentValueNode, DateTimeNumberDisplayNode,
+        DefinedTypeLinkNode, DefinedTypeNode, DurationNumberDisplayNode, EnumEmptyVariantTypeNode,
+        EnumTypeNode, EnumValueNode, EnumVariantDisplayNode, InstructionAccountDisplayNode,
+        InstructionAccountNode, InstructionArgumentNode, InstructionDisplayNode, NumberValueNode,
+        ProgramNode, ProvidedNode, RootNode, StringValueNode, StructFieldTypeNode, StructValueNode,
+        U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    const SOURCE: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const DESTINATION: &str = "7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV";
+
+    fn get_root() -> RootNode {
+        let amount_display = AmountNumberDisplayNode {
+            decimals: Box::new(Some(InjectedValueNode::new("decimals").into())),
+            unit: Box::new(Some(StringValueNode::new("USDC").into())),
+        };
+        let schedule = StructTypeNode::new(vec![
+            StructFieldTypeNode::new(
+                "startsAt",
+                NumberTypeNode {
+                    display: Box::new(Some(DateTimeNumberDisplayNode::new(None).into())),
+                    ..NumberTypeNode::le(U64)
+                },
+            ),
+            StructFieldTypeNode::new(
+                "period",
+                NumberTypeNode {
+                    display: Box::new(Some(DurationNumberDisplayNode::new(Some(1000)).into())),
+                    ..NumberTypeNode::le(U64)
+                },
+            ),
+        ]);
+        let speed = EnumTypeNode::new(vec![
+            EnumEmptyVariantTypeNode {
+                display: Some(EnumVariantDisplayNode::new("Fast")),
+                ..EnumEmptyVariantTypeNode::new("fast")
+            }
+            .into(),
+            EnumEmptyVariantTypeNode::new("slow").into(),
+        ]);
+        let instruction = InstructionNode {
+            name: "transfer".into(),
+            accounts: vec![
+                InstructionAccountNode {
+                    display: Some(InstructionAccountDisplayNode {
+                        label: Some("From".to_string()),
+                        skip: None,
+                    }),
+                    ..InstructionAccountNode::new("source", true, false)
+                },
+                InstructionAccountNode {
+                    display: Some(InstructionAccountDisplayNode {
+                        label: Some("To".to_string()),
+                        skip: Some(DisplaySkip::WhenInjected),
+                    }),
+                    ..InstructionAccountNode::new("destination", true, false)
+                },
+            ],
+            arguments: vec![
+                InstructionArgumentNode {
+                    display: Some(StructFieldDisplayNode {
+                        skip: Some(DisplaySkip::Always),
+                        ..StructFieldDisplayNode::default()
+                    }),
+                    ..InstructionArgumentNode::new("discriminator", NumberTypeNode::le(U8))
+                },
+                InstructionArgumentNode::new(
+                    "amount",
+                    NumberTypeNode {
+                        display: Box::new(Some(amount_display.into())),
+                        ..NumberTypeNode::le(U64)
+                    },
+                ),
+                InstructionArgumentNode {
+                    display: Some(StructFieldDisplayNode {
+                        skip: Some(DisplaySkip::WhenInjected),
+                        ..StructFieldDisplayNode::default()
+                    }),
+                    ..InstructionArgumentNode::new("decimals", NumberTypeNode::le(U8))
+                },
+                InstructionArgumentNode {
+                    display: Some(StructFieldDisplayNode {
+                        flatten: Some(true),
+                        flatten_prefix: Some("Schedule ".to_string()),
+                        ..StructFieldDisplayNode::default()
+                    }),
+                    ..InstructionArgumentNode::new("schedule", schedule)
+                },
+                InstructionArgumentNode::new("speed", DefinedTypeLinkNode::new("speed")),
+            ],
+            provides: vec![
+                ProvidedNode::new("decimals", ArgumentValueNode::new("decimals")),
+                ProvidedNode::new("recipient", AccountValueNode::new("destination")),
+            ],
+            display: Some(InstructionDisplayNode::interpolated(
+                "Send ${data.amount} to ${accounts.destination} from ${data.schedule.starts_at}",
+            )),
+            ..InstructionNode::default()
+        };
+        RootNode::new(
+            ProgramNode::new("token", "1111")
+                .add_defined_type(DefinedTypeNode::new("speed", speed))
+                .add_instruction(instruction),
+        )
+    }
+
+    fn get_values() -> InstructionDisplayValues {
+        InstructionDisplayValues {
+            arguments: vec![
+                StructFieldValueNode::new("discriminator", NumberValueNode::new(3u8)),
+                StructFieldValueNode::new("amount", NumberValueNode::new(1_500_000u64)),
+                StructFieldValueNode::new("decimals", NumberValueNode::new(6u8)),
+                StructFieldValueNode::new(
+                    "schedule",
+                    StructValueNode::new(vec![
+                        StructFieldValueNode::new(
+                            "startsAt",
+                            NumberValueNode::new(1_704_067_200u64),
+                        ),
+                        StructFieldValueNode::new("period", NumberValueNode::new(93_784_000u64)),
+                    ]),
+                ),
+                StructFieldValueNode::new(
+                    "speed",
+                    EnumValueNode::new(DefinedTypeLinkNode::new("speed"), "fast", None),
+                ),
+            ],
+            accounts: vec![
+                ("source".into(), SOURCE.to_string()),
+                ("destination".into(), DESTINATION.to_string()),
+            ],
+            injected: vec![],
+        }
+    }
+
+    fn render(values: &InstructionDisplayValues) -> CodamaResult<RenderedInstructionDisplay> {
+        let root = get_root();
+        let resolver = LinkResolver::new(&root);
+        render_instruction_display(&root.program.instructions[0], values, &resolver)
+    }
+
+    fn field_value(label: &str, value: &str) -> RenderedDisplayField {
+        RenderedDisplayField {
+            label: label.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn interpolated_intent() {
+        let display = render(&get_values()).unwrap();
+        assert_eq!(
+            display.intent,
+            Some(format!(
+                "Send 1.5 USDC to {DESTINATION} from 2024-01-01T00:00:00Z"
+            ))
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let display = render(&get_values()).unwrap();
+        assert_eq!(
+            display.fields,
+            vec![
+                field_value("From", SOURCE),
+                field_value("To", DESTINATION),
+                field_value("amount", "1.5 USDC"),
+                field_value("Schedule startsAt", "2024-01-01T00:00:00Z"),
+                field_value("Schedule period", "1d 2h 3m 4s"),
+                field_value("speed", "Fast"),
+            ]
+        );
+    }
+
+    #[test]
+    fn injected_values_take_precedence() {
+        let mut values = get_values();
+        values.injected = vec![("decimals".into(), NumberValueNode::new(2u8).into())];
+        let display = render(&values).unwrap();
+        assert_eq!(display.fields[2], field_value("amount", "15000 USDC"));
+
+        // The decimals argument is no longer surfaced through injection.
+        assert!(display.fields.contains(&field_value("decimals", "6")));
+    }
+
+    #[test]
+    fn plain_intent() {
+        let mut root = get_root();
+        root.program.instructions[0].display = Some(InstructionDisplayNode::new("Transfer"));
+        let display = render_instruction_display(
+            &root.program.instructions[0],
+            &get_values(),
+            &LinkResolver::new(&root),
+        )
+        .unwrap();
+        assert_eq!(display.intent, Some("Transfer".to_string()));
+    }
+
+    #[test]
+    fn rendering_errors() {
+        let mut values = get_values();
+        values.arguments.remove(1);
+        assert_eq!(
+            render(&values).unwrap_err().to_string(),
+            "Could not render the display of instruction `transfer`: missing value for argument `amount`"
+        );
+
+        let mut root = get_root();
+        root.program.instructions[0].display =
+            Some(InstructionDisplayNode::interpolated("Send ${data.unknown}"));
+        let error = render_instruction_display(
+            &root.program.instructions[0],
+            &get_values(),
+            &LinkResolver::new(&root),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not render the display of instruction `transfer`: unknown argument `unknown`"
+        );
+    }
+
+    #[test]
+    fn amounts() {
+        let amount = |number: Number, decimals| format_amount(number, decimals, None);
+        assert_eq!(amount(Number::UnsignedInteger(1_500_000), 6), "1.5");
+        assert_eq!(amount(Number::UnsignedInteger(42), 6), "0.000042");
+        assert_eq!(amount(Number::UnsignedInteger(1_000), 3), "1");
+        assert_eq!(amount(Number::SignedInteger(-250), 2), "-2.5");
+        assert_eq!(amount(Number::UnsignedInteger(7), 0), "7");
+        assert_eq!(
+            format_amount(Number::UnsignedInteger(1), 9, Some("SOL")),
+            "0.000000001 SOL"
+        );
+    }
+
+    #[test]
+    fn dates_and_durations() {
+        assert_eq!(
+            format_date_time(Number::UnsignedInteger(0), 1),
+            "1970-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            format_date_time(Number::UnsignedInteger(951_782_400_000), 1000),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            format_date_time(Number::SignedInteger(-1), 1),
+            "1969-12-31T23:59:59Z"
+        );
+        assert_eq!(format_duration(Number::UnsignedInteger(0), 1), "0s");
+        assert_eq!(format_duration(Number::UnsignedInteger(3_600), 1), "1h");
+        assert_eq!(format_duration(Number::SignedInteger(-90), 1), "-1m 30s");
+    }
+}
@@ -0,0 +1,617 @@
+use crate::{
+    AccountNode, CamelCaseString, ConstantNode, DefinedTypeNode, EnumVariantTypeNode, ErrorNode,
+    EventNode, HasKind, HasName, InstructionAccountNode, InstructionArgumentNode, InstructionNode,
+    NestedTypeNode, NodeTrait, PdaNode, PdaSeedNode, ProgramNode, RootNode, StructFieldTypeNode,
+    StructTypeNode, TupleTypeNode, TypeNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+use std::str::FromStr;
+
+/// Returns every node of the root matching the given selector.
+/// See `NodeSelector` for the selector syntax.
+pub fn select<'a>(root: &'a RootNode, selector: &str) -> CodamaResult<Vec<NodeRef<'a>>> {
+    Ok(selector.parse::<NodeSelector>()?.select(root))
+}
+
+/// Returns a mutable reference to every node of the root matching the given selector.
+/// See `NodeSelector` for the selector syntax.
+pub fn select_mut<'a>(root: &'a mut RootNode, selector: &str) -> CodamaResult<Vec<NodeMut<'a>>> {
+    Ok(selector.parse::<NodeSelector>()?.select_mut(root))
+}
+
+/// A path through the nodes of a `RootNode`, starting from the root itself.
+///
+/// Segments are separated by dots and are either a field name such as
+/// `instructions`, `*` for any field or `**` for any descendant. Each segment
+/// can be followed by `[...]` filters that keep nodes whose name or kind
+/// match, e.g. `program.instructions[transfer].arguments[amount]` or
+/// `program.*[accountNode]`. `**` must be followed by at least one filter
+/// and stops at the first matching node of each branch, e.g. `**[amount]`.
+/// Type nodes wrapping other types, such as a `fixedSizeTypeNode` around
+/// the data of an account, expose the wrapped type as their `type` field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSelector {
+    segments: Vec<SelectorSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SelectorSegment {
+    step: SelectorStep,
+    filters: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SelectorStep {
+    Field(CamelCaseString),
+    AnyField,
+    Descendants,
+}
+
+impl NodeSelector {
+    pub fn select<'a>(&self, root: &'a RootNode) -> Vec<NodeRef<'a>> {
+        self.evaluate(NodeRef::Root(root))
+    }
+
+    pub fn select_mut<'a>(&self, root: &'a mut RootNode) -> Vec<NodeMut<'a>> {
+        self.evaluate(NodeMut::Root(root))
+    }
+
+    fn evaluate<N: Selectable>(&self, root: N) -> Vec<N> {
+        self.segments.iter().fold(vec![root], |nodes, segment| {
+            let mut matches = Vec::new();
+            nodes
+                .into_iter()
+                .for_each(|node| segment.apply(node, &mut matches));
+            matches
+        })
+    }
+}
+
+impl FromStr for NodeSelector {
+    type Err = CodamaError;
+
+    fn from_str(selector: &str) -> CodamaResult<Self> {
+        let segments = selector
+            .split('.')
+            .map(SelectorSegment::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|message| CodamaError::InvalidSelector {
+                selector: selector.to_string(),
+                message: message.to_string(),
+            })?;
+        Ok(Self { segments })
+    }
+}
+
+impl SelectorSegment {
+    fn parse(segment: &str) -> Result<Self, &'static str> {
+        let (head, mut rest) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+        let step = match head.trim() {
+            "" => return Err("empty segment"),
+            "*" => SelectorStep::AnyField,
+            "**" => SelectorStep::Descendants,
+            name => SelectorStep::Field(CamelCaseString::new(name)),
+        };
+
+        let mut filters = Vec::new();
+        while !rest.is_empty() {
+            let inner = rest.strip_prefix('[').ok_or("expected `[` after `]`")?;
+            let end = inner.find(']').ok_or("unclosed `[`")?;
+            match inner[..end].trim() {
+                "" => return Err("empty filter"),
+                "*" => {}
+                filter => filters.push(filter.to_string()),
+            }
+            rest = &inner[end + 1..];
+        }
+
+        if step == SelectorStep::Descendants && filters.is_empty() {
+            return Err("`**` must be followed by a filter");
+        }
+        Ok(Self { step, filters })
+    }
+
+    fn apply<N: Selectable>(&self, node: N, matches: &mut Vec<N>) {
+        for (field, child) in node.into_children() {
+            match &self.step {
+                SelectorStep::Field(name) if name.as_str() != field => {}
+                SelectorStep::Descendants if !self.matches(&child) => self.apply(child, matches),
+                _ if self.matches(&child) => matches.push(child),
+                _ => {}
+            }
+        }
+    }
+
+    fn matches<N: Selectable>(&self, node: &N) -> bool {
+        self.filters.iter().all(|filter| {
+            node.kind() == filter
+                || node
+                    .name()
+                    .is_some_and(|name| *name == CamelCaseString::new(filter))
+        })
+    }
+}
+
+trait Selectable: Sized {
+    fn kind(&self) -> &'static str;
+    fn name(&self) -> Option<&CamelCaseString>;
+    fn into_children(self) -> Vec<(&'static str, Self)>;
+}
+
+struct Children<N>(Vec<(&'static str, N)>);
+
+impl<N> Children<N> {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn one(mut self, field: &'static str, node: N) -> Self {
+        self.0.push((field, node));
+        self
+    }
+
+    fn all<T>(
+        mut self,
+        field: &'static str,
+        items: impl IntoIterator<Item = T>,
+        f: fn(T) -> N,
+    ) -> Self {
+        self.0
+            .extend(items.into_iter().map(|item| (field, f(item))));
+        self
+    }
+}
+
+/// Defines an enum of references to the selectable nodes, either shared
+/// or mutable, along with the way to walk through their children.
+macro_rules! node_references {
+    ($(#[$attr:meta])* $Node:ident $(, $mut:ident)?) => {
+        $(#[$attr])*
+        pub enum $Node<'a> {
+            Root(&'a $($mut)? RootNode),
+            Program(&'a $($mut)? ProgramNode),
+            Account(&'a $($mut)? AccountNode),
+            Instruction(&'a $($mut)? InstructionNode),
+            InstructionAccount(&'a $($mut)? InstructionAccountNode),
+            InstructionArgument(&'a $($mut)? InstructionArgumentNode),
+            DefinedType(&'a $($mut)? DefinedTypeNode),
+            Pda(&'a $($mut)? PdaNode),
+            PdaSeed(&'a $($mut)? PdaSeedNode),
+            Event(&'a $($mut)? EventNode),
+            Error(&'a $($mut)? ErrorNode),
+            Constant(&'a $($mut)? ConstantNode),
+            Type(&'a $($mut)? TypeNode),
+            StructType(&'a $($mut)? StructTypeNode),
+            /// A type node wrapping a struct, e.g. the `fixedSizeTypeNode` of an account.
+            NestedStructType(&'a $($mut)? NestedTypeNode<StructTypeNode>),
+            StructField(&'a $($mut)? StructFieldTypeNode),
+            TupleType(&'a $($mut)? TupleTypeNode),
+            /// A type node wrapping a tuple, e.g. the `sizePrefixTypeNode` of an enum variant.
+            NestedTupleType(&'a $($mut)? NestedTypeNode<TupleTypeNode>),
+            EnumVariant(&'a $($mut)? EnumVariantTypeNode),
+        }
+
+        impl $Node<'_> {
+            pub fn kind(&self) -> &'static str {
+                Selectable::kind(self)
+            }
+
+            pub fn name(&self) -> Option<&CamelCaseString> {
+                Selectable::name(self)
+            }
+        }
+
+        impl<'a> $Node<'a> {
+            fn struct_type(node: &'a $($mut)? NestedTypeNode<StructTypeNode>) -> Self {
+                match node {
+                    NestedTypeNode::Value(node) => $Node::StructType(node),
+                    node => $Node::NestedStructType(node),
+                }
+            }
+
+            fn tuple_type(node: &'a $($mut)? NestedTypeNode<TupleTypeNode>) -> Self {
+                match node {
+                    NestedTypeNode::Value(node) => $Node::TupleType(node),
+                    node => $Node::NestedTupleType(node),
+                }
+            }
+        }
+
+        impl<'a> Selectable for $Node<'a> {
+            fn kind(&self) -> &'static str {
+                match self {
+                    $Node::Root(_) => RootNode::KIND,
+                    $Node::Program(_) => ProgramNode::KIND,
+                    $Node::Account(_) => AccountNode::KIND,
+                    $Node::Instruction(_) => InstructionNode::KIND,
+                    $Node::InstructionAccount(_) => InstructionAccountNode::KIND,
+                    $Node::InstructionArgument(_) => InstructionArgumentNode::KIND,
+                    $Node::DefinedType(_) => DefinedTypeNode::KIND,
+                    $Node::Pda(_) => PdaNode::KIND,
+                    $Node::PdaSeed(node) => node.kind(),
+                    $Node::Event(_) => EventNode::KIND,
+                    $Node::Error(_) => ErrorNode::KIND,
+                    $Node::Constant(_) => ConstantNode::KIND,
+                    $Node::Type(node) => node.kind(),
+                    $Node::StructType(_) => StructTypeNode::KIND,
+                    $Node::NestedStructType(node) => node.kind(),
+                    $Node::StructField(_) => StructFieldTypeNode::KIND,
+                    $Node::TupleType(_) => TupleTypeNode::KIND,
+                    $Node::NestedTupleType(node) => node.kind(),
+                    $Node::EnumVariant(node) => node.kind(),
+                }
+            }
+
+            fn name(&self) -> Option<&CamelCaseString> {
+                match self {
+                    $Node::Program(node) => Some(node.name()),
+                    $Node::Account(node) => Some(node.name()),
+                    $Node::Instruction(node) => Some(node.name()),
+                    $Node::InstructionAccount(node) => Some(node.name()),
+                    $Node::InstructionArgument(node) => Some(&node.name),
+                    $Node::DefinedType(node) => Some(node.name()),
+                    $Node::Pda(node) => Some(node.name()),
+                    $Node::PdaSeed(PdaSeedNode::Variable(node)) => Some(node.name()),
+                    $Node::Event(node) => Some(node.name()),
+                    $Node::Error(node) => Some(node.name()),
+                    $Node::Constant(node) => Some(node.name()),
+                    $Node::StructField(node) => Some(&node.name),
+                    $Node::EnumVariant(node) => Some(node.name()),
+                    _ => None,
+                }
+            }
+
+            fn into_children(self) -> Vec<(&'static str, Self)> {
+                let children = Children::new();
+                let children = match self {
+                    $Node::Root(node) => children
+                        .one("program", $Node::Program(&$($mut)? node.program))
+                        .all(
+                            "additionalPrograms",
+                            &$($mut)? node.additional_programs,
+                            $Node::Program,
+                        ),
+                    $Node::Program(node) => children
+                        .all("accounts", &$($mut)? node.accounts, $Node::Account)
+                        .all("instructions", &$($mut)? node.instructions, $Node::Instruction)
+                        .all("definedTypes", &$($mut)? node.defined_types, $Node::DefinedType)
+                        .all("pdas", &$($mut)? node.pdas, $Node::Pda)
+                        .all("events", &$($mut)? node.events, $Node::Event)
+                        .all("errors", &$($mut)? node.errors, $Node::Error)
+                        .all("constants", &$($mut)? node.constants, $Node::Constant),
+                    $Node::Account(node) => {
+                        children.one("data", $Node::struct_type(&$($mut)? node.data))
+                    }
+                    $Node::Instruction(node) => children
+                        .all("accounts", &$($mut)? node.accounts, $Node::InstructionAccount)
+                        .all("arguments", &$($mut)? node.arguments, $Node::InstructionArgument)
+                        .all(
+                            "extraArguments",
+                            &$($mut)? node.extra_arguments,
+                            $Node::InstructionArgument,
+                        )
+                        .all(
+                            "subInstructions",
+                            &$($mut)? node.sub_instructions,
+                            $Node::Instruction,
+                        ),
+                    $Node::InstructionArgument(node) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::DefinedType(node) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::Pda(node) => children.all("seeds", &$($mut)? node.seeds, $Node::PdaSeed),
+                    $Node::PdaSeed(PdaSeedNode::Constant(node)) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::PdaSeed(PdaSeedNode::Variable(node)) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::Event(node) => children.one("data", $Node::Type(&$($mut)? node.data)),
+                    $Node::Constant(node) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::Type(node) => match node {
+                        TypeNode::Struct(node) => {
+                            children.all("fields", &$($mut)? node.fields, $Node::StructField)
+                        }
+                        TypeNode::Tuple(node) => {
+                            children.all("items", &$($mut)? node.items, $Node::Type)
+                        }
+                        TypeNode::Enum(node) => {
+                            children.all("variants", &$($mut)? node.variants, $Node::EnumVariant)
+                        }
+                        TypeNode::Array(node) => {
+                            children.one("item", $Node::Type(&$($mut)? node.item))
+                        }
+                        TypeNode::Set(node) => children.one("item", $Node::Type(&$($mut)? node.item)),
+                        TypeNode::Option(node) => {
+                            children.one("item", $Node::Type(&$($mut)? node.item))
+                        }
+                        TypeNode::ZeroableOption(node) => {
+                            children.one("item", $Node::Type(&$($mut)? node.item))
+                        }
+                        TypeNode::RemainderOption(node) => {
+                            children.one("item", $Node::Type(&$($mut)? node.item))
+                        }
+                        TypeNode::Map(node) => children
+                            .one("key", $Node::Type(&$($mut)? node.key))
+                            .one("value", $Node::Type(&$($mut)? node.value)),
+                        TypeNode::FixedSize(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::HiddenPrefix(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::HiddenSuffix(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::PostOffset(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::PreOffset(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::Sentinel(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        TypeNode::SizePrefix(node) => {
+                            children.one("type", $Node::Type(&$($mut)? node.r#type))
+                        }
+                        _ => children,
+                    },
+                    $Node::StructType(node) => {
+                        children.all("fields", &$($mut)? node.fields, $Node::StructField)
+                    }
+                    $Node::NestedStructType(node) => {
+                        nested_child!(children, node, $Node::struct_type $(, $mut)?)
+                    }
+                    $Node::StructField(node) => {
+                        children.one("type", $Node::Type(&$($mut)? node.r#type))
+                    }
+                    $Node::TupleType(node) => {
+                        children.all("items", &$($mut)? node.items, $Node::Type)
+                    }
+                    $Node::NestedTupleType(node) => {
+                        nested_child!(children, node, $Node::tuple_type $(, $mut)?)
+                    }
+                    $Node::EnumVariant(EnumVariantTypeNode::Struct(node)) => {
+                        children.one("struct", $Node::struct_type(&$($mut)? node.r#struct))
+                    }
+                    $Node::EnumVariant(EnumVariantTypeNode::Tuple(node)) => {
+                        children.one("tuple", $Node::tuple_type(&$($mut)? node.tuple))
+                    }
+                    _ => children,
+                };
+                children.0
+            }
+        }
+    };
+}
+
+/// Adds the type wrapped by a nested type node as its `type` child.
+macro_rules! nested_child {
+    ($children:expr, $node:expr, $wrap:path $(, $mut:ident)?) => {
+        match $node {
+            NestedTypeNode::FixedSize(node) => $children.one("type", $wrap(&$($mut)? node.r#type)),
+            NestedTypeNode::HiddenPrefix(node) => {
+                $children.one("type", $wrap(&$($mut)? node.r#type))
+            }
+            NestedTypeNode::HiddenSuffix(node) => {
+                $children.one("type", $wrap(&$($mut)? node.r#type))
+            }
+            NestedTypeNode::PostOffset(node) => $children.one("type", $wrap(&$($mut)? node.r#type)),
+            NestedTypeNode::PreOffset(node) => $children.one("type", $wrap(&$($mut)? node.r#type)),
+            NestedTypeNode::Sentinel(node) => $children.one("type", $wrap(&$($mut)? node.r#type)),
+            NestedTypeNode::SizePrefix(node) => $children.one("type", $wrap(&$($mut)? node.r#type)),
+            NestedTypeNode::Value(_) => $children,
+        }
+    };
+}
+
+node_references!(
+    /// A reference to a node returned by a `NodeSelector`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    NodeRef
+);
+
+node_references!(
+    /// A mutable reference to a node returned by a `NodeSelector`.
+    #[derive(Debug, PartialEq)]
+    NodeMut,
+    mut
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EnumStructVariantTypeNode, EnumTypeNode, FixedSizeTypeNode, NestedTypeNodeTrait,
+        NumberFormat::*, NumberTypeNode, PublicKeyTypeNode,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn get_root() -> RootNode {
+        let instruction = |name: &str, arguments: Vec<InstructionArgumentNode>| InstructionNode {
+            name: name.into(),
+            arguments,
+            ..InstructionNode::default()
+        };
+        RootNode::new(
+            ProgramNode::new("token", "1111")
+                .add_account(AccountNode::new(
+                    "mint",
+                    StructTypeNode::new(vec![
+                        StructFieldTypeNode::new("supply", NumberTypeNode::le(U64)),
+                        StructFieldTypeNode::new("authority", PublicKeyTypeNode::new()),
+                    ]),
+                ))
+                .add_instruction(instruction(
+                    "transfer",
+                    vec![InstructionArgumentNode::new(
+                        "amount",
+                        NumberTypeNode::le(U64),
+                    )],
+                ))
+                .add_instruction(instruction(
+                    "burn",
+                    vec![
+                        InstructionArgumentNode::new("amount", NumberTypeNode::le(U64)),
+                        InstructionArgumentNode::new("decimals", NumberTypeNode::le(U8)),
+                    ],
+                ))
+                .add_defined_type(DefinedTypeNode::new(
+                    "authorityType",
+                    EnumTypeNode::new(vec![EnumStructVariantTypeNode::new(
+                        "custom",
+                        StructTypeNode::new(vec![StructFieldTypeNode::new(
+                            "amount",
+                            NumberTypeNode::le(U32),
+                        )]),
+                    )
+                    .into()]),
+                )),
+        )
+    }
+
+    fn names(nodes: &[NodeRef]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "[{}]{}",
+                    node.kind(),
+                    node.name().map_or("", |n| n.as_str())
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn select_path() {
+        let root = get_root();
+        let nodes = select(&root, "program.instructions[transfer].arguments[amount]").unwrap();
+        assert_eq!(names(&nodes), vec!["[instructionArgumentNode]amount"]);
+        assert_eq!(
+            nodes[0],
+            NodeRef::InstructionArgument(&root.program.instructions[0].arguments[0])
+        );
+
+        let nodes = select(&root, "program.defined_types[authority_type].type").unwrap();
+        assert_eq!(names(&nodes), vec!["[enumTypeNode]"]);
+        assert_eq!(select(&root, "program.accounts[token]").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn select_wildcards() {
+        let root = get_root();
+        let nodes = select(&root, "program.instructions[*].arguments").unwrap();
+        assert_eq!(
+            names(&nodes),
+            vec![
+                "[instructionArgumentNode]amount",
+                "[instructionArgumentNode]amount",
+                "[instructionArgumentNode]decimals"
+            ]
+        );
+        let nodes = select(&root, "program.*[mint]").unwrap();
+        assert_eq!(names(&nodes), vec!["[accountNode]mint"]);
+        let nodes = select(&root, "**[amount]").unwrap();
+        assert_eq!(
+            names(&nodes),
+            vec![
+                "[instructionArgumentNode]amount",
+                "[instructionArgumentNode]amount",
+                "[structFieldTypeNode]amount"
+            ]
+        );
+    }
+
+    #[test]
+    fn select_kinds() {
+        let root = get_root();
+        let nodes = select(&root, "program.*[instructionNode]").unwrap();
+        assert_eq!(
+            names(&nodes),
+            vec!["[instructionNode]transfer", "[instructionNode]burn"]
+        );
+        let nodes = select(&root, "**[structFieldTypeNode].type[publicKeyTypeNode]").unwrap();
+        assert_eq!(names(&nodes), vec!["[publicKeyTypeNode]"]);
+        let nodes = select(&root, "**[instructionArgumentNode][decimals]").unwrap();
+        assert_eq!(names(&nodes), vec!["[instructionArgumentNode]decimals"]);
+    }
+
+    #[test]
+    fn select_mutably() {
+        let mut root = get_root();
+        for node in
+            select_mut(&mut root, "program.accounts[mint].data.fields[supply].type").unwrap()
+        {
+            if let NodeMut::Type(r#type) = node {
+                *r#type = NumberTypeNode::le(U128).into();
+            }
+        }
+        for node in select_mut(&mut root, "**[instructionArgumentNode][amount]").unwrap() {
+            if let NodeMut::InstructionArgument(argument) = node {
+                argument.name = "lamports".into();
+            }
+        }
+
+        let mint = &root.program.accounts[0].data.get_nested_type_node().fields[0];
+        assert_eq!(*mint.r#type, NumberTypeNode::le(U128).into());
+        let nodes = select(&root, "program.instructions.arguments[lamports]").unwrap();
+        assert_eq!(nodes.len(), 2);
+    }
+
+    #[test]
+    fn select_through_nested_type_nodes() {
+        let mut root = get_root();
+        let data = StructTypeNode::new(vec![StructFieldTypeNode::new(
+            "supply",
+            NumberTypeNode::le(U64),
+        )]);
+        root.program.accounts[0].data = FixedSizeTypeNode::new(data, 42).into();
+
+        let nodes = select(&root, "program.accounts[mint].data").unwrap();
+        assert_eq!(names(&nodes), vec!["[fixedSizeTypeNode]"]);
+        let nodes = select(&root, "program.accounts[mint].data.type.fields").unwrap();
+        assert_eq!(names(&nodes), vec!["[structFieldTypeNode]supply"]);
+        let nodes = select(&root, "program.accounts.**[supply]").unwrap();
+        assert_eq!(names(&nodes), vec!["[structFieldTypeNode]supply"]);
+
+        for node in select_mut(&mut root, "**[supply]").unwrap() {
+            if let NodeMut::StructField(field) = node {
+                field.name = "maxSupply".into();
+            }
+        }
+        let data = root.program.accounts[0].data.get_nested_type_node();
+        assert_eq!(data.fields[0].name, CamelCaseString::new("maxSupply"));
+    }
+
+    #[test]
+    fn invalid_selectors() {
+        let error = |selector: &str| select(&get_root(), selector).unwrap_err().to_string();
+        assert_eq!(error(""), "Invalid selector ``: empty segment");
+        assert_eq!(
+            error("program..accounts"),
+            "Invalid selector `program..accounts`: empty segment"
+        );
+        assert_eq!(
+            error("program.accounts[mint"),
+            "Invalid selector `program.accounts[mint`: unclosed `[`"
+        );
+        assert_eq!(
+            error("program.accounts[]"),
+            "Invalid selector `program.accounts[]`: empty filter"
+        );
+        assert_eq!(
+            error("program.accounts[a]b"),
+            "Invalid selector `program.accounts[a]b`: expected `[` after `]`"
+        );
+        assert_eq!(
+            error("**"),
+            "Invalid selector `**`: `**` must be followed by a filter"
+        );
+    }
+}
@@ -23,3 +23,207 @@ impl TryFrom<&str> for BytesEncoding {
         }
     }
 }
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl BytesEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Base16 => "base16",
+            Base58 => "base58",
+            Base64 => "base64",
+            Utf8 => "utf8",
+        }
+    }
+
+    /// Decode the given string into bytes using this encoding.
+    pub fn decode(&self, data: &str) -> CodamaResult<Vec<u8>> {
+        let bytes = match self {
+            Base16 => decode_base16(data),
+            Base58 => decode_base58(data),
+            Base64 => decode_base64(data),
+            Utf8 => Some(data.as_bytes().to_vec()),
+        };
+        bytes.ok_or_else(|| CodamaError::InvalidBytesData {
+            encoding: self.as_str().to_string(),
+            data: data.to_string(),
+        })
+    }
+
+    /// Encode the given bytes into a string using this encoding.
+    pub fn encode(&self, bytes: &[u8]) -> CodamaResult<String> {
+        match self {
+            Base16 => Ok(encode_base16(bytes)),
+            Base58 => Ok(encode_base58(bytes)),
+            Base64 => Ok(encode_base64(bytes)),
+            Utf8 => String::from_utf8(bytes.to_vec()).map_err(|_| CodamaError::InvalidBytesData {
+                encoding: self.as_str().to_string(),
+                data: encode_base16(bytes),
+            }),
+        }
+    }
+}
+
+fn decode_base16(data: &str) -> Option<Vec<u8>> {
+    if !data.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(data.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn decode_base58(data: &str) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in data.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let leading_zeros = data.bytes().take_while(|&c| c == b'1').count();
+    bytes.extend(std::iter::repeat_n(0, leading_zeros));
+    bytes.reverse();
+    Some(bytes)
+}
+
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let data = data.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in data.bytes() {
+        buffer = (buffer << 6) | BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+fn encode_base16(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn encode_base58(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let leading_zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n(b'1', leading_zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&digit| BASE58_ALPHABET[digit as usize]),
+        )
+        .map(char::from)
+        .collect()
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |buffer, (i, &byte)| {
+            buffer | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => {
+                    result.push(BASE64_ALPHABET[(buffer >> (18 - 6 * i) & 0x3f) as usize] as char)
+                }
+                false => result.push('='),
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_base16() {
+        assert_eq!(Base16.decode("01ff").unwrap(), vec![1, 255]);
+        assert!(Base16.decode("0").is_err());
+        assert!(Base16.decode("zz").is_err());
+    }
+
+    #[test]
+    fn decode_base58() {
+        assert_eq!(Base58.decode("").unwrap(), Vec::<u8>::new());
+        assert_eq!(Base58.decode("11").unwrap(), vec![0, 0]);
+        assert_eq!(Base58.decode("5R").unwrap(), vec![1, 0]);
+        assert_eq!(
+            Base58.decode("11111111111111111111111111111111").unwrap(),
+            vec![0; 32]
+        );
+        assert!(Base58.decode("0OIl").is_err());
+    }
+
+    #[test]
+    fn decode_base64() {
+        assert_eq!(Base64.decode("aGVsbG8=").unwrap(), b"hello".to_vec());
+        assert_eq!(Base64.decode("aGVsbG8").unwrap(), b"hello".to_vec());
+        assert!(Base64.decode("a$").is_err());
+    }
+
+    #[test]
+    fn encode_base16() {
+        assert_eq!(Base16.encode(&[1, 255]).unwrap(), "01ff");
+    }
+
+    #[test]
+    fn encode_base58() {
+        assert_eq!(Base58.encode(&[]).unwrap(), "");
+        assert_eq!(Base58.encode(&[0, 0]).unwrap(), "11");
+        assert_eq!(Base58.encode(&[1, 0]).unwrap(), "5R");
+        let bytes = Base58
+            .decode("SysvarRent111111111111111111111111111111111")
+            .unwrap();
+        assert_eq!(
+            Base58.encode(&bytes).unwrap(),
+            "SysvarRent111111111111111111111111111111111"
+        );
+    }
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(Base64.encode(b"hello").unwrap(), "aGVsbG8=");
+        assert_eq!(Base64.encode(b"hell").unwrap(), "aGVsbA==");
+        assert_eq!(Base64.encode(b"hel").unwrap(), "aGVs");
+    }
+
+    #[test]
+    fn encode_utf8() {
+        assert_eq!(Utf8.encode("hé".as_bytes()).unwrap(), "hé");
+        assert!(Utf8.encode(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn decode_utf8() {
+        assert_eq!(Utf8.decode("hé").unwrap(), "hé".as_bytes().to_vec());
+    }
+}
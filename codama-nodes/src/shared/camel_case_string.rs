@@ -11,6 +11,36 @@ impl CamelCaseString {
     {
         Self(to_camel_case(string.as_ref()))
     }
+
+    /// The name in PascalCase, e.g. `MyAccount` for `myAccount`.
+    pub fn to_pascal_case(&self) -> String {
+        let mut chars = self.0.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// The name in snake_case, e.g. `my_account` for `myAccount`.
+    pub fn to_snake_case(&self) -> String {
+        let mut result = String::new();
+        for (index, char) in self.0.chars().enumerate() {
+            if char.is_uppercase() {
+                if index > 0 {
+                    result.push('_');
+                }
+                result.extend(char.to_lowercase());
+            } else {
+                result.push(char);
+            }
+        }
+        result
+    }
+
+    /// The name in kebab-case, e.g. `my-account` for `myAccount`.
+    pub fn to_kebab_case(&self) -> String {
+        self.to_snake_case().replace('_', "-")
+    }
 }
 
 impl From<CamelCaseString> for String {
@@ -185,4 +215,26 @@ mod tests {
         let value: CamelCaseString = serde_json::from_str(json).unwrap();
         assert_eq!(value, CamelCaseString::new("helloWorld"));
     }
+
+    #[test]
+    fn to_pascal_case() {
+        assert_eq!(
+            CamelCaseString::new("myAccount").to_pascal_case(),
+            "MyAccount"
+        );
+        assert_eq!(CamelCaseString::new("").to_pascal_case(), "");
+    }
+
+    #[test]
+    fn to_snake_case() {
+        let value = CamelCaseString::new("initializeV2Account");
+        assert_eq!(value.to_snake_case(), "initialize_v2_account");
+        assert_eq!(CamelCaseString::new("transfer").to_snake_case(), "transfer");
+    }
+
+    #[test]
+    fn to_kebab_case() {
+        let value = CamelCaseString::new("initializeV2Account");
+        assert_eq!(value.to_kebab_case(), "initialize-v2-account");
+    }
 }
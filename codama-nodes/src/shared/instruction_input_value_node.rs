@@ -24,6 +24,7 @@ impl From<ValueNode> for InstructionInputValueNode {
             ValueNode::String(value) => Self::StringValue(value),
             ValueNode::Struct(value) => Self::StructValue(value),
             ValueNode::Tuple(value) => Self::TupleValue(value),
+            ValueNode::Unknown(value) => Self::Unknown(value),
         }
     }
 }
@@ -51,6 +52,7 @@ impl TryFrom<InstructionInputValueNode> for ValueNode {
             InstructionInputValueNode::StringValue(value) => Ok(Self::String(value)),
             InstructionInputValueNode::StructValue(value) => Ok(Self::Struct(value)),
             InstructionInputValueNode::TupleValue(value) => Ok(Self::Tuple(value)),
+            InstructionInputValueNode::Unknown(value) => Ok(Self::Unknown(value)),
             _ => Err(CodamaError::InvalidNodeConversion {
                 from: value.kind().to_string(),
                 into: "ValueNode".to_string(),
@@ -4,8 +4,10 @@ mod docs;
 mod instruction_input_value_node;
 mod instruction_lifecycle;
 mod is_signer;
+mod node_path;
 mod optional_account_strategy;
 
 pub use bytes_encoding::*;
 pub use camel_case_string::*;
 pub use docs::*;
+pub use node_path::*;
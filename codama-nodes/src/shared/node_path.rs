@@ -0,0 +1,99 @@
+use crate::CamelCaseString;
+use std::fmt::{Display, Formatter};
+
+/// The location of a node within a tree of nodes, from the outermost to the innermost node.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NodePath(Vec<NodePathSegment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePathSegment {
+    pub kind: &'static str,
+    pub name: Option<CamelCaseString>,
+}
+
+impl NodePath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<T>(&mut self, kind: &'static str, name: Option<T>)
+    where
+        T: Into<CamelCaseString>,
+    {
+        self.0.push(NodePathSegment {
+            kind,
+            name: name.map(Into::into),
+        });
+    }
+
+    pub fn pop(&mut self) -> Option<NodePathSegment> {
+        self.0.pop()
+    }
+
+    pub fn segments(&self) -> &[NodePathSegment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for NodePathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "[{}]{}", self.kind, name.as_str()),
+            None => write!(f, "[{}]", self.kind),
+        }
+    }
+}
+
+impl Display for NodePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop() {
+        let mut path = NodePath::new();
+        path.push("programNode", Some("myProgram"));
+        path.push::<&str>("structTypeNode", None);
+        assert_eq!(path.segments().len(), 2);
+        assert_eq!(
+            path.pop(),
+            Some(NodePathSegment {
+                kind: "structTypeNode",
+                name: None
+            })
+        );
+        assert_eq!(path.segments().len(), 1);
+    }
+
+    #[test]
+    fn display() {
+        let mut path = NodePath::new();
+        path.push("programNode", Some("myProgram"));
+        path.push("accountNode", Some("token"));
+        path.push::<&str>("structTypeNode", None);
+        assert_eq!(
+            path.to_string(),
+            "[programNode]myProgram > [accountNode]token > [structTypeNode]"
+        );
+    }
+
+    #[test]
+    fn display_empty() {
+        assert_eq!(NodePath::new().to_string(), "");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::CamelCaseString;
+use crate::{CamelCaseString, UnknownNode};
 use codama_errors::CodamaResult;
 use std::fmt::Debug;
 
@@ -18,6 +18,15 @@ pub trait NodeTrait:
     fn from_json(json: &str) -> CodamaResult<Self> {
         serde_json::from_str(json).map_err(Into::into)
     }
+
+    /// Same as `from_json` but node kinds unknown to this version of the library
+    /// are kept as `UnknownNode`s inside the unions of their family, e.g. an unknown
+    /// `fooTypeNode` inside a `TypeNode`.
+    fn from_json_lenient(json: &str) -> CodamaResult<Self> {
+        let mut value = serde_json::from_str(json)?;
+        UnknownNode::wrap_unknown_nodes(&mut value);
+        serde_json::from_value(value).map_err(Into::into)
+    }
 }
 
 pub trait NodeUnionTrait:
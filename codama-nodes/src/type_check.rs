@@ -0,0 +1,562 @@
+use crate::{
+    BytesEncoding, CountNode, DefinedTypeLinkNode, EnumTypeNode, EnumValuePayload,
+    EnumVariantTypeNode, HasKind, HasName, LinkResolver, NestedTypeNodeTrait, Number, NumberFormat,
+    StructTypeNode, StructValueNode, TupleTypeNode, TupleValueNode, TypeNode, ValueNode,
+};
+use codama_errors::{CodamaError, CodamaResult};
+
+/// Checks that a value fits the given type: number ranges per `NumberFormat`,
+/// strings vs bytes, public keys, item counts, enum variants and struct fields.
+///
+/// Links are only followed when a resolver is given, otherwise values of
+/// linked types are accepted as is. Injected values without a fallback
+/// cannot be checked and are accepted as well.
+pub fn check_value_type(
+    value: &ValueNode,
+    r#type: &TypeNode,
+    resolver: Option<&LinkResolver>,
+) -> CodamaResult<()> {
+    TypeChecker::new(resolver.copied()).check(value, r#type)
+}
+
+/// Ensures a number can be represented by the given format.
+/// Since `Number` holds 64-bit integers, `u128` and `i128` numbers
+/// are limited to the ranges of `u64` and `i64` respectively.
+pub(crate) fn check_number(number: Number, format: NumberFormat) -> Result<(), String> {
+    let (min, max) = match format {
+        NumberFormat::F32 | NumberFormat::F64 => return Ok(()),
+        NumberFormat::U8 => (0, u8::MAX as i128),
+        NumberFormat::U16 | NumberFormat::ShortU16 => (0, u16::MAX as i128),
+        NumberFormat::U32 => (0, u32::MAX as i128),
+        NumberFormat::U64 | NumberFormat::U128 => (0, u64::MAX as i128),
+        NumberFormat::I8 => (i8::MIN as i128, i8::MAX as i128),
+        NumberFormat::I16 => (i16::MIN as i128, i16::MAX as i128),
+        NumberFormat::I32 => (i32::MIN as i128, i32::MAX as i128),
+        NumberFormat::I64 | NumberFormat::I128 => (i64::MIN as i128, i64::MAX as i128),
+    };
+    let value = match number {
+        Number::UnsignedInteger(value) => value as i128,
+        Number::SignedInteger(value) => value as i128,
+        Number::Float(value) => return Err(format!("expected an integer, found {value}")),
+    };
+    if value < min || value > max {
+        return Err(format!("number {value} is out of range [{min}, {max}]"));
+    }
+    Ok(())
+}
+
+/// Decodes a base58 public key, ensuring it is 32 bytes long.
+pub(crate) fn decode_public_key(public_key: &str) -> Result<[u8; 32], String> {
+    let bytes = BytesEncoding::Base58
+        .decode(public_key)
+        .map_err(|_| format!("public key `{public_key}` is not a valid base58 string"))?;
+    let length = bytes.len();
+    <[u8; 32]>::try_from(bytes)
+        .map_err(|_| format!("public key `{public_key}` must be 32 bytes, found {length}"))
+}
+
+struct TypeChecker<'a> {
+    resolver: Option<LinkResolver<'a>>,
+    /// Segments of the path to the value being checked, e.g. `.items` and `[2]`.
+    path: Vec<String>,
+}
+
+impl<'a> TypeChecker<'a> {
+    fn new(resolver: Option<LinkResolver<'a>>) -> Self {
+        Self {
+            resolver,
+            path: Vec::new(),
+        }
+    }
+
+    fn with_segment(
+        &mut self,
+        segment: String,
+        f: impl FnOnce(&mut Self) -> CodamaResult<()>,
+    ) -> CodamaResult<()> {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    /// Run `f` with the defined type the link points to and the resolver of its program.
+    fn with_link(
+        &mut self,
+        link: &DefinedTypeLinkNode,
+        f: impl FnOnce(&mut Self, &'a TypeNode) -> CodamaResult<()>,
+    ) -> CodamaResult<()> {
+        let Some(parent_resolver) = self.resolver else {
+            return Ok(());
+        };
+        let resolver = match &link.program {
+            Some(program) => parent_resolver.with_program(
+                parent_resolver
+                    .resolve_program(program)
+                    .map_err(|error| self.error(error.to_string()))?,
+            ),
+            None => parent_resolver,
+        };
+        let defined_type = resolver
+            .resolve_defined_type(link)
+            .map_err(|error| self.error(error.to_string()))?;
+
+        self.resolver = Some(resolver);
+        let result = f(self, defined_type.r#type.as_ref());
+        self.resolver = Some(parent_resolver);
+        result
+    }
+
+    fn error<T: Into<String>>(&self, message: T) -> CodamaError {
+        CodamaError::TypeMismatch {
+            path: format!("${}", self.path.concat()),
+            message: message.into(),
+        }
+    }
+
+    fn unexpected_value(&self, expected: &str, value: &ValueNode) -> CodamaError {
+        self.error(format!("expected `{expected}`, found `{}`", value.kind()))
+    }
+
+    fn check(&mut self, value: &ValueNode, r#type: &TypeNode) -> CodamaResult<()> {
+        let value = match value {
+            ValueNode::Injected(node) => match node.fallback.as_ref() {
+                Some(fallback) => fallback,
+                None => return Ok(()),
+            },
+            ValueNode::Constant(node) => return self.check(&node.value, &node.r#type),
+            ValueNode::Unknown(_) => return Ok(()),
+            value => value,
+        };
+
+        match r#type {
+            TypeNode::Amount(node) => {
+                self.check_number(value, node.number.get_nested_type_node().format)
+            }
+            TypeNode::DateTime(node) => {
+                self.check_number(value, node.number.get_nested_type_node().format)
+            }
+            TypeNode::SolAmount(node) => {
+                self.check_number(value, node.number.get_nested_type_node().format)
+            }
+            TypeNode::Number(node) => self.check_number(value, node.format),
+            TypeNode::Boolean(_) => match value {
+                ValueNode::Boolean(_) => Ok(()),
+                _ => Err(self.unexpected_value("booleanValueNode", value)),
+            },
+            TypeNode::String(_) => match value {
+                ValueNode::String(_) => Ok(()),
+                _ => Err(self.unexpected_value("stringValueNode", value)),
+            },
+            TypeNode::Bytes(_) => match value {
+                ValueNode::Bytes(value) => value
+                    .encoding
+                    .decode(&value.data)
+                    .map(|_| ())
+                    .map_err(|error| self.error(error.to_string())),
+                _ => Err(self.unexpected_value("bytesValueNode", value)),
+            },
+            TypeNode::PublicKey(_) => match value {
+                ValueNode::PublicKey(value) => self.check_public_key(&value.public_key),
+                _ => Err(self.unexpected_value("publicKeyValueNode", value)),
+            },
+            TypeNode::Option(node) => self.check_option(value, &node.item),
+            TypeNode::RemainderOption(node) => self.check_option(value, &node.item),
+            TypeNode::ZeroableOption(node) => self.check_option(value, &node.item),
+            TypeNode::Array(node) => match value {
+                ValueNode::Array(value) => self.check_items(&value.items, &node.item, &node.count),
+                _ => Err(self.unexpected_value("arrayValueNode", value)),
+            },
+            TypeNode::Set(node) => match value {
+                ValueNode::Set(value) => self.check_items(&value.items, &node.item, &node.count),
+                _ => Err(self.unexpected_value("setValueNode", value)),
+            },
+            TypeNode::Map(node) => {
+                let ValueNode::Map(value) = value else {
+                    return Err(self.unexpected_value("mapValueNode", value));
+                };
+                self.check_count(value.entries.len(), &node.count)?;
+                value
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(index, entry)| {
+                        self.with_segment(format!("[{index}]"), |this| {
+                            this.check(&entry.key, &node.key)?;
+                            this.check(&entry.value, &node.value)
+                        })
+                    })
+            }
+            TypeNode::Tuple(node) => match value {
+                ValueNode::Tuple(value) => self.check_tuple(value, node),
+                _ => Err(self.unexpected_value("tupleValueNode", value)),
+            },
+            TypeNode::Struct(node) => match value {
+                ValueNode::Struct(value) => self.check_struct(value, node),
+                _ => Err(self.unexpected_value("structValueNode", value)),
+            },
+            TypeNode::Enum(node) => self.check_enum(value, node),
+            TypeNode::FixedSize(node) => self.check(value, &node.r#type),
+            TypeNode::HiddenPrefix(node) => self.check(value, &node.r#type),
+            TypeNode::HiddenSuffix(node) => self.check(value, &node.r#type),
+            TypeNode::PostOffset(node) => self.check(value, &node.r#type),
+            TypeNode::PreOffset(node) => self.check(value, &node.r#type),
+            TypeNode::Sentinel(node) => self.check(value, &node.r#type),
+            TypeNode::SizePrefix(node) => self.check(value, &node.r#type),
+            // Types unknown to this version of the library cannot be checked.
+            TypeNode::Unknown(_) => Ok(()),
+            TypeNode::Link(link) => self.with_link(link, |this, r#type| this.check(value, r#type)),
+        }
+    }
+
+    fn check_number(&self, value: &ValueNode, format: NumberFormat) -> CodamaResult<()> {
+        let ValueNode::Number(value) = value else {
+            return Err(self.unexpected_value("numberValueNode", value));
+        };
+        check_number(value.number, format).map_err(|message| self.error(message))
+    }
+
+    fn check_public_key(&self, public_key: &str) -> CodamaResult<()> {
+        decode_public_key(public_key).map_err(|message| self.error(message))?;
+        Ok(())
+    }
+
+    fn check_option(&mut self, value: &ValueNode, item: &TypeNode) -> CodamaResult<()> {
+        match value {
+            ValueNode::None(_) => Ok(()),
+            ValueNode::Some(value) => self.check(&value.value, item),
+            _ => Err(self.unexpected_value("noneValueNode` or `someValueNode", value)),
+        }
+    }
+
+    fn check_count(&self, length: usize, count: &CountNode) -> CodamaResult<()> {
+        match count {
+            CountNode::Fixed(count) if count.value != length as u64 => {
+                Err(self.error(format!("expected {} items, found {length}", count.value)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_items(
+        &mut self,
+        items: &[ValueNode],
+        item: &TypeNode,
+        count: &CountNode,
+    ) -> CodamaResult<()> {
+        self.check_count(items.len(), count)?;
+        items.iter().enumerate().try_for_each(|(index, value)| {
+            self.with_segment(format!("[{index}]"), |this| this.check(value, item))
+        })
+    }
+
+    fn check_tuple(&mut self, value: &TupleValueNode, node: &TupleTypeNode) -> CodamaResult<()> {
+        if value.items.len() != node.items.len() {
+            return Err(self.error(format!(
+                "expected {} items, found {}",
+                node.items.len(),
+                value.items.len()
+            )));
+        }
+        value
+            .items
+            .iter()
+            .zip(&node.items)
+            .enumerate()
+            .try_for_each(|(index, (value, r#type))| {
+                self.with_segment(format!("[{index}]"), |this| this.check(value, r#type))
+            })
+    }
+
+    fn check_struct(&mut self, value: &StructValueNode, node: &StructTypeNode) -> CodamaResult<()> {
+        if let Some(unknown) = value
+            .fields
+            .iter()
+            .find(|field| !node.fields.iter().any(|f| f.name == field.name))
+        {
+            return Err(self.error(format!("unknown field `{}`", unknown.name.as_str())));
+        }
+        for field in &node.fields {
+            match value.fields.iter().find(|f| f.name == field.name) {
+                Some(field_value) => self
+                    .with_segment(format!(".{}", field.name.as_str()), |this| {
+                        this.check(&field_value.value, &field.r#type)
+                    })?,
+                None if field.default_value.is_some() => {}
+                None => return Err(self.error(format!("missing field `{}`", field.name.as_str()))),
+            }
+        }
+        Ok(())
+    }
+
+    fn check_enum(&mut self, value: &ValueNode, node: &EnumTypeNode) -> CodamaResult<()> {
+        let ValueNode::Enum(value) = value else {
+            return Err(self.unexpected_value("enumValueNode", value));
+        };
+        let Some(variant) = node.variants.iter().find(|v| *v.name() == value.variant) else {
+            return Err(self.error(format!("unknown variant `{}`", value.variant.as_str())));
+        };
+
+        self.with_segment(format!(".{}", value.variant.as_str()), |this| {
+            match (variant, value.value.as_ref()) {
+                (EnumVariantTypeNode::Empty(_), None) => Ok(()),
+                (EnumVariantTypeNode::Struct(variant), Some(EnumValuePayload::Struct(payload))) => {
+                    this.check_struct(payload, variant.r#struct.get_nested_type_node())
+                }
+                (EnumVariantTypeNode::Tuple(variant), Some(EnumValuePayload::Tuple(payload))) => {
+                    this.check_tuple(payload, variant.tuple.get_nested_type_node())
+                }
+                _ => Err(this.error("variant payload does not match its type")),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArrayTypeNode, ArrayValueNode, BooleanValueNode, BytesTypeNode, BytesValueNode,
+        DefinedTypeNode, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumValueNode,
+        FixedCountNode, InjectedValueNode, NoneValueNode, NumberTypeNode, NumberValueNode,
+        OptionTypeNode, ProgramNode, PublicKeyTypeNode, PublicKeyValueNode, RootNode,
+        SomeValueNode, StringTypeNode, StringValueNode, StructFieldTypeNode, StructFieldValueNode,
+        TupleValueNode, I8, U16, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn check(value: impl Into<ValueNode>, r#type: impl Into<TypeNode>) -> Result<(), String> {
+        check_value_type(&value.into(), &r#type.into(), None).map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            check(NumberValueNode::new(255u8), NumberTypeNode::le(U8)),
+            Ok(())
+        );
+        assert_eq!(
+            check(NumberValueNode::new(-128i8), NumberTypeNode::le(I8)),
+            Ok(())
+        );
+        assert_eq!(
+            check(NumberValueNode::new(256u16), NumberTypeNode::le(U8)),
+            Err("Value at `$` does not fit its type: number 256 is out of range [0, 255]".into())
+        );
+        assert_eq!(
+            check(NumberValueNode::new(-1i8), NumberTypeNode::le(U64)),
+            Err(
+                "Value at `$` does not fit its type: number -1 is out of range [0, 18446744073709551615]"
+                    .into()
+            )
+        );
+        assert_eq!(
+            check(NumberValueNode::new(1.5), NumberTypeNode::le(U16)),
+            Err("Value at `$` does not fit its type: expected an integer, found 1.5".into())
+        );
+        assert_eq!(
+            check(StringValueNode::new("1"), NumberTypeNode::le(U8)),
+            Err(
+                "Value at `$` does not fit its type: expected `numberValueNode`, found `stringValueNode`"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn strings_bytes_and_public_keys() {
+        assert_eq!(
+            check(StringValueNode::new("hi"), StringTypeNode::utf8()),
+            Ok(())
+        );
+        assert_eq!(
+            check(BytesValueNode::base16("ff00"), BytesTypeNode::new()),
+            Ok(())
+        );
+        assert_eq!(
+            check(StringValueNode::new("ff00"), BytesTypeNode::new()),
+            Err(
+                "Value at `$` does not fit its type: expected `bytesValueNode`, found `stringValueNode`"
+                    .into()
+            )
+        );
+        assert_eq!(
+            check(BytesValueNode::base16("ff00"), StringTypeNode::utf8()),
+            Err(
+                "Value at `$` does not fit its type: expected `stringValueNode`, found `bytesValueNode`"
+                    .into()
+            )
+        );
+        assert!(check(BytesValueNode::base16("xyz"), BytesTypeNode::new()).is_err());
+        assert_eq!(
+            check(
+                PublicKeyValueNode::new("11111111111111111111111111111111"),
+                PublicKeyTypeNode::new()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check(PublicKeyValueNode::new("1111"), PublicKeyTypeNode::new()),
+            Err(
+                "Value at `$` does not fit its type: public key `1111` must be 32 bytes, found 4"
+                    .into()
+            )
+        );
+        assert_eq!(
+            check(PublicKeyValueNode::new("0OIl"), PublicKeyTypeNode::new()),
+            Err(
+                "Value at `$` does not fit its type: public key `0OIl` is not a valid base58 string"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn options_and_items() {
+        let option = OptionTypeNode::new(NumberTypeNode::le(U8));
+        assert_eq!(check(NoneValueNode::new(), option.clone()), Ok(()));
+        assert_eq!(
+            check(
+                SomeValueNode::new(NumberValueNode::new(1u8)),
+                option.clone()
+            ),
+            Ok(())
+        );
+        assert!(check(NumberValueNode::new(1u8), option).is_err());
+
+        let array = ArrayTypeNode::new(NumberTypeNode::le(U8), FixedCountNode::new(2));
+        let items = |items: Vec<u16>| {
+            ArrayValueNode::new(
+                items
+                    .into_iter()
+                    .map(|i| NumberValueNode::new(i).into())
+                    .collect(),
+            )
+        };
+        assert_eq!(check(items(vec![1, 2]), array.clone()), Ok(()));
+        assert_eq!(
+            check(items(vec![1]), array.clone()),
+            Err("Value at `$` does not fit its type: expected 2 items, found 1".into())
+        );
+        assert_eq!(
+            check(items(vec![1, 300]), array),
+            Err(
+                "Value at `$[1]` does not fit its type: number 300 is out of range [0, 255]".into()
+            )
+        );
+        assert!(check(
+            TupleValueNode::new(vec![BooleanValueNode::new(true).into()]),
+            crate::TupleTypeNode::new(vec![])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn structs() {
+        let r#type = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+            StructFieldTypeNode {
+                default_value: Box::new(Some(StringValueNode::new("hi").into())),
+                ..StructFieldTypeNode::new("label", StringTypeNode::utf8())
+            },
+        ]);
+        let value = |fields: Vec<StructFieldValueNode>| StructValueNode::new(fields);
+        assert_eq!(
+            check(
+                value(vec![StructFieldValueNode::new(
+                    "amount",
+                    NumberValueNode::new(1u8)
+                )]),
+                r#type.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check(value(vec![]), r#type.clone()),
+            Err("Value at `$` does not fit its type: missing field `amount`".into())
+        );
+        assert_eq!(
+            check(
+                value(vec![
+                    StructFieldValueNode::new("amount", NumberValueNode::new(1u8)),
+                    StructFieldValueNode::new("extra", NumberValueNode::new(1u8)),
+                ]),
+                r#type.clone()
+            ),
+            Err("Value at `$` does not fit its type: unknown field `extra`".into())
+        );
+        assert_eq!(
+            check(
+                value(vec![StructFieldValueNode::new("amount", StringValueNode::new("1"))]),
+                r#type
+            ),
+            Err(
+                "Value at `$.amount` does not fit its type: expected `numberValueNode`, found `stringValueNode`"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn enums_through_links() {
+        let direction = EnumTypeNode::new(vec![
+            EnumEmptyVariantTypeNode::new("up").into(),
+            EnumStructVariantTypeNode::new(
+                "jump",
+                StructTypeNode::new(vec![StructFieldTypeNode::new(
+                    "height",
+                    NumberTypeNode::le(U8),
+                )]),
+            )
+            .into(),
+        ]);
+        let root = RootNode::new(
+            ProgramNode::new("game", "1111")
+                .add_defined_type(DefinedTypeNode::new("direction", direction)),
+        );
+        let resolver = LinkResolver::new(&root);
+        let link = TypeNode::from(DefinedTypeLinkNode::new("direction"));
+        let check_link = |value: EnumValueNode| {
+            check_value_type(&value.into(), &link, Some(&resolver)).map_err(|e| e.to_string())
+        };
+
+        assert_eq!(
+            check_link(EnumValueNode::new("direction", "up", None)),
+            Ok(())
+        );
+        assert_eq!(
+            check_link(EnumValueNode::new("direction", "down", None)),
+            Err("Value at `$` does not fit its type: unknown variant `down`".into())
+        );
+        assert_eq!(
+            check_link(EnumValueNode::new(
+                "direction",
+                "jump",
+                Some(EnumValuePayload::Struct(StructValueNode::new(vec![
+                    StructFieldValueNode::new("height", NumberValueNode::new(256u16))
+                ])))
+            )),
+            Err(
+                "Value at `$.jump.height` does not fit its type: number 256 is out of range [0, 255]"
+                    .into()
+            )
+        );
+        assert_eq!(
+            check_link(EnumValueNode::new("direction", "jump", None)),
+            Err(
+                "Value at `$.jump` does not fit its type: variant payload does not match its type"
+                    .into()
+            )
+        );
+
+        // Without a resolver, links and injected values are not checked.
+        assert_eq!(
+            check(EnumValueNode::new("direction", "down", None), link),
+            Ok(())
+        );
+        assert_eq!(
+            check(InjectedValueNode::new("amount"), NumberTypeNode::le(U8)),
+            Ok(())
+        );
+    }
+}
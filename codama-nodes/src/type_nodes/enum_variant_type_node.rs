@@ -1,6 +1,6 @@
 use crate::{
-    EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode, HasKind, Node,
-    RegisteredTypeNode,
+    CamelCaseString, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode, EnumTupleVariantTypeNode,
+    HasKind, HasName, Node, RegisteredTypeNode,
 };
 use codama_errors::CodamaError;
 use codama_nodes_derive::node_union;
@@ -12,6 +12,26 @@ pub enum EnumVariantTypeNode {
     Tuple(EnumTupleVariantTypeNode),
 }
 
+impl EnumVariantTypeNode {
+    pub fn discriminator(&self) -> Option<u32> {
+        match self {
+            EnumVariantTypeNode::Empty(node) => node.discriminator,
+            EnumVariantTypeNode::Struct(node) => node.discriminator,
+            EnumVariantTypeNode::Tuple(node) => node.discriminator,
+        }
+    }
+}
+
+impl HasName for EnumVariantTypeNode {
+    fn name(&self) -> &CamelCaseString {
+        match self {
+            EnumVariantTypeNode::Empty(node) => node.name(),
+            EnumVariantTypeNode::Struct(node) => node.name(),
+            EnumVariantTypeNode::Tuple(node) => node.name(),
+        }
+    }
+}
+
 impl TryFrom<Node> for EnumVariantTypeNode {
     type Error = CodamaError;
 
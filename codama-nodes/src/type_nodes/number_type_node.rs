@@ -19,6 +19,11 @@ impl NumberTypeNode {
     pub fn be(format: NumberFormat) -> Self {
         Self::new(format, Endianness::Be)
     }
+
+    /// Whether the number is a little-endian number of the given format.
+    pub fn is_le(&self, format: NumberFormat) -> bool {
+        self.format == format && self.endian == Endianness::Le
+    }
 }
 
 impl TryFrom<String> for NumberFormat {
@@ -97,6 +102,13 @@ mod tests {
         assert_eq!(node.endian, Endianness::Be);
     }
 
+    #[test]
+    fn is_le() {
+        assert!(NumberTypeNode::le(U32).is_le(U32));
+        assert!(!NumberTypeNode::le(U32).is_le(U64));
+        assert!(!NumberTypeNode::be(U32).is_le(U32));
+    }
+
     #[test]
     fn to_json() {
         let node = NumberTypeNode::be(U8);
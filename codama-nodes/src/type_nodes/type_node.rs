@@ -2,15 +2,16 @@ use crate::{
     AmountTypeNode, ArrayTypeNode, BooleanTypeNode, BytesTypeNode, DateTimeTypeNode,
     DefinedTypeLinkNode, EnumEmptyVariantTypeNode, EnumStructVariantTypeNode,
     EnumTupleVariantTypeNode, EnumTypeNode, FixedSizeTypeNode, HasKind, HiddenPrefixTypeNode,
-    HiddenSuffixTypeNode, LinkNode, MapTypeNode, Node, NumberTypeNode, OptionTypeNode,
-    PostOffsetTypeNode, PreOffsetTypeNode, PublicKeyTypeNode, RemainderOptionTypeNode,
-    SentinelTypeNode, SetTypeNode, SizePrefixTypeNode, SolAmountTypeNode, StringTypeNode,
-    StructFieldTypeNode, StructTypeNode, TupleTypeNode, TypeNodeUnionTrait, ZeroableOptionTypeNode,
+    HiddenSuffixTypeNode, LinkNode, MapTypeNode, NestedTypeNodeTrait, Node, NumberFormat,
+    NumberTypeNode, OptionTypeNode, PostOffsetTypeNode, PreOffsetTypeNode, PublicKeyTypeNode,
+    RemainderOptionTypeNode, SentinelTypeNode, SetTypeNode, SizePrefixTypeNode, SolAmountTypeNode,
+    StringTypeNode, StructFieldTypeNode, StructTypeNode, TupleTypeNode, TypeNodeUnionTrait,
+    UnknownNode, ZeroableOptionTypeNode,
 };
 use codama_errors::CodamaError;
 use codama_nodes_derive::node_union;
 
-#[node_union]
+#[node_union(unknown_suffix = "TypeNode")]
 pub enum RegisteredTypeNode {
     Amount(AmountTypeNode),
     Array(ArrayTypeNode),
@@ -36,6 +37,7 @@ pub enum RegisteredTypeNode {
     Struct(StructTypeNode),
     Tuple(TupleTypeNode),
     ZeroableOption(ZeroableOptionTypeNode),
+    Unknown(UnknownNode),
 
     // Registered only.
     EnumEmptyVariant(EnumEmptyVariantTypeNode),
@@ -44,7 +46,7 @@ pub enum RegisteredTypeNode {
     StructField(StructFieldTypeNode),
 }
 
-#[node_union]
+#[node_union(unknown_suffix = "TypeNode")]
 pub enum TypeNode {
     Amount(AmountTypeNode),
     Array(ArrayTypeNode),
@@ -70,6 +72,7 @@ pub enum TypeNode {
     Struct(StructTypeNode),
     Tuple(TupleTypeNode),
     ZeroableOption(ZeroableOptionTypeNode),
+    Unknown(UnknownNode),
 
     // Standalone only.
     Link(DefinedTypeLinkNode),
@@ -77,6 +80,20 @@ pub enum TypeNode {
 
 impl TypeNodeUnionTrait for TypeNode {}
 
+impl TypeNode {
+    /// The format of the number held by this type, if it is a number or wraps one,
+    /// e.g. the `u64` of an `AmountTypeNode`.
+    pub fn number_format(&self) -> Option<NumberFormat> {
+        match self {
+            TypeNode::Number(node) => Some(node.format),
+            TypeNode::Amount(node) => Some(node.number.get_nested_type_node().format),
+            TypeNode::DateTime(node) => Some(node.number.get_nested_type_node().format),
+            TypeNode::SolAmount(node) => Some(node.number.get_nested_type_node().format),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<Node> for TypeNode {
     type Error = CodamaError;
 
@@ -138,6 +155,7 @@ impl TryFrom<RegisteredTypeNode> for TypeNode {
             RegisteredTypeNode::Struct(node) => Ok(Self::Struct(node)),
             RegisteredTypeNode::Tuple(node) => Ok(Self::Tuple(node)),
             RegisteredTypeNode::ZeroableOption(node) => Ok(Self::ZeroableOption(node)),
+            RegisteredTypeNode::Unknown(node) => Ok(Self::Unknown(node)),
             _ => Err(CodamaError::InvalidNodeConversion {
                 from: node.kind().to_string(),
                 into: "TypeNode".to_string(),
@@ -175,6 +193,7 @@ impl TryFrom<TypeNode> for RegisteredTypeNode {
             TypeNode::Struct(node) => Ok(Self::Struct(node)),
             TypeNode::Tuple(node) => Ok(Self::Tuple(node)),
             TypeNode::ZeroableOption(node) => Ok(Self::ZeroableOption(node)),
+            TypeNode::Unknown(node) => Ok(Self::Unknown(node)),
             _ => Err(CodamaError::InvalidNodeConversion {
                 from: node.kind().to_string(),
                 into: "RegisteredTypeNode".to_string(),
@@ -187,6 +206,22 @@ impl TryFrom<TypeNode> for RegisteredTypeNode {
 mod tests {
     use super::*;
 
+    #[test]
+    fn number_format() {
+        let amount = AmountTypeNode::new(NumberTypeNode::le(crate::U64), 2, None);
+        assert_eq!(
+            TypeNode::from(amount).number_format(),
+            Some(NumberFormat::U64)
+        );
+        let number = FixedSizeTypeNode::new(NumberTypeNode::le(crate::U8), 4);
+        let date_time = DateTimeTypeNode::new(number);
+        assert_eq!(
+            TypeNode::from(date_time).number_format(),
+            Some(NumberFormat::U8)
+        );
+        assert_eq!(TypeNode::from(StringTypeNode::utf8()).number_format(), None);
+    }
+
     #[test]
     fn kind_from_standalone() {
         let node: TypeNode = StringTypeNode::utf8().into();
@@ -0,0 +1,290 @@
+use crate::{json_schema, HasKind, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// A node whose kind is not known by this version of the library.
+///
+/// Unknown nodes are only created when deserializing leniently — see
+/// [`NodeTrait::from_json_lenient`](crate::NodeTrait::from_json_lenient) —
+/// and serialize back to their raw JSON unchanged.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnknownNode {
+    pub kind: String,
+    pub raw: Value,
+}
+
+impl UnknownNode {
+    pub const KIND: &'static str = "unknownNode";
+
+    pub fn new(raw: Value) -> Option<Self> {
+        let kind = raw.get("kind")?.as_str()?.to_string();
+        Some(Self { kind, raw })
+    }
+
+    /// Whether the node may be kept inside the node union of the given suffix,
+    /// e.g. `"fooTypeNode"` inside a `TypeNode`. Restricting unknown kinds to their
+    /// family keeps untagged unions such as `Node` unambiguous.
+    pub(crate) fn has_suffix(&self, suffix: &str) -> bool {
+        self.kind.len() > suffix.len() && self.kind.ends_with(suffix)
+    }
+
+    /// Wraps every node of the given JSON whose kind is unknown to this version of
+    /// the library as `{ "kind": "unknownNode", "node": <node> }`. Node unions that
+    /// declare an `unknown_suffix` deserialize these wrappers into `UnknownNode`s
+    /// whereas any other node rejects them.
+    ///
+    /// Only the attributes that may hold nodes are visited, so free-form values such
+    /// as the payload of a `PluginNode` are kept as they are.
+    pub(crate) fn wrap_unknown_nodes(json: &mut Value) {
+        match json {
+            Value::Array(items) => items.iter_mut().for_each(Self::wrap_unknown_nodes),
+            Value::Object(object) => {
+                let Some(kind) = object.get("kind").and_then(Value::as_str) else {
+                    return;
+                };
+                match node_attributes().get(kind) {
+                    Some(attributes) => object
+                        .iter_mut()
+                        .filter(|(name, _)| attributes.contains(name.as_str()))
+                        .for_each(|(_, value)| Self::wrap_unknown_nodes(value)),
+                    None => *json = json!({ "kind": Self::KIND, "node": json.take() }),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Reads a node wrapped by [`UnknownNode::wrap_unknown_nodes`].
+    pub(crate) fn unwrap(mut wrapper: Value) -> Option<Self> {
+        Self::new(wrapper.get_mut("node")?.take())
+    }
+}
+
+/// The attributes that may hold nodes, indexed by the kinds of the nodes defined
+/// by this version of the library. These are the attributes whose JSON schema
+/// references another definition.
+fn node_attributes() -> &'static HashMap<String, HashSet<String>> {
+    static ATTRIBUTES: OnceLock<HashMap<String, HashSet<String>>> = OnceLock::new();
+    ATTRIBUTES.get_or_init(|| {
+        let schema = json_schema::<Node>();
+        let mut attributes = HashMap::<String, HashSet<String>>::new();
+        for definition in schema["$defs"]
+            .as_object()
+            .into_iter()
+            .flat_map(|d| d.values())
+        {
+            let Some(kind) = definition["properties"]["kind"]["const"].as_str() else {
+                continue;
+            };
+            let properties = definition["properties"].as_object().into_iter().flatten();
+            attributes.entry(kind.to_string()).or_default().extend(
+                properties
+                    .filter(|(_, schema)| has_reference(schema))
+                    .map(|(name, _)| name.clone()),
+            );
+        }
+        attributes
+    })
+}
+
+fn has_reference(schema: &Value) -> bool {
+    match schema {
+        Value::Object(object) => object.contains_key("$ref") || object.values().any(has_reference),
+        Value::Array(items) => items.iter().any(has_reference),
+        _ => false,
+    }
+}
+
+impl HasKind for UnknownNode {
+    fn kind(&self) -> &'static str {
+        Self::KIND
+    }
+}
+
+impl Serialize for UnknownNode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnknownNode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = Value::deserialize(deserializer)?;
+        Self::new(raw).ok_or_else(|| serde::de::Error::custom("missing kind"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DiscriminatorNode, InstructionNode, NodeTrait, NumberTypeNode, PluginNode, ProgramNode,
+        RootNode, StructFieldTypeNode, StructTypeNode, TypeNode, ValueNode, U8,
+    };
+    use serde::de::DeserializeOwned;
+
+    fn from_value_lenient<T: DeserializeOwned>(mut json: Value) -> serde_json::Result<T> {
+        UnknownNode::wrap_unknown_nodes(&mut json);
+        serde_json::from_value(json)
+    }
+
+    fn root_node_json(r#type: Value) -> String {
+        json!({
+            "kind": "rootNode",
+            "standard": "codama",
+            "version": "1.0.0",
+            "program": {
+                "kind": "programNode",
+                "name": "myProgram",
+                "publicKey": "1111",
+                "version": "1.2.3",
+                "definedTypes": [{ "kind": "definedTypeNode", "name": "myType", "type": r#type }],
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn new() {
+        let node = UnknownNode::new(json!({ "kind": "fooTypeNode", "size": 42 })).unwrap();
+        assert_eq!(node.kind, "fooTypeNode");
+        assert_eq!(node.raw, json!({ "kind": "fooTypeNode", "size": 42 }));
+        assert_eq!(UnknownNode::new(json!({ "size": 42 })), None);
+    }
+
+    #[test]
+    fn wrap_unknown_nodes() {
+        let mut json = json!({
+            "kind": "structTypeNode",
+            "fields": [{ "kind": "fooTypeNode" }, { "kind": "numberTypeNode" }],
+        });
+        UnknownNode::wrap_unknown_nodes(&mut json);
+        assert_eq!(
+            json,
+            json!({
+                "kind": "structTypeNode",
+                "fields": [
+                    { "kind": "unknownNode", "node": { "kind": "fooTypeNode" } },
+                    { "kind": "numberTypeNode" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn wrap_unknown_nodes_skips_raw_values() {
+        let payload = json!({ "kind": "fooTypeNode", "items": [{ "kind": "barTypeNode" }] });
+        let mut json = serde_json::to_value(PluginNode::with_payload("foo", payload)).unwrap();
+        let expected = json.clone();
+        UnknownNode::wrap_unknown_nodes(&mut json);
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn strict_by_default() {
+        let json = json!({ "kind": "fooTypeNode" });
+        assert!(serde_json::from_value::<TypeNode>(json).is_err());
+    }
+
+    #[test]
+    fn lenient_unions() {
+        let unknown = |kind: &str| UnknownNode::new(json!({ "kind": kind })).unwrap();
+        let from_value = |kind: &str| {
+            (
+                from_value_lenient::<TypeNode>(json!({ "kind": kind })).ok(),
+                from_value_lenient::<ValueNode>(json!({ "kind": kind })).ok(),
+                from_value_lenient::<DiscriminatorNode>(json!({ "kind": kind })).ok(),
+            )
+        };
+
+        let (r#type, value, discriminator) = from_value("fooTypeNode");
+        assert_eq!(r#type, Some(TypeNode::Unknown(unknown("fooTypeNode"))));
+        assert_eq!((value, discriminator), (None, None));
+
+        let (_, value, discriminator) = from_value("fooValueNode");
+        assert_eq!(value, Some(ValueNode::Unknown(unknown("fooValueNode"))));
+        assert_eq!(discriminator, None);
+
+        let (_, value, discriminator) = from_value("fooDiscriminatorNode");
+        let expected = DiscriminatorNode::Unknown(unknown("fooDiscriminatorNode"));
+        assert_eq!((value, discriminator), (None, Some(expected)));
+    }
+
+    #[test]
+    fn lenient_wrong_categories() {
+        let error = |r#type: Value| {
+            RootNode::from_json_lenient(&root_node_json(r#type))
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(json!({ "kind": "structTypNode" }))
+            .contains("unknown kind structTypNode for TypeNode"));
+        assert!(error(json!({ "kind": "fooLinkNode" }))
+            .contains("unknown kind fooLinkNode for TypeNode"));
+        assert!(error(json!({ "kind": "instructionNode", "name": "foo" }))
+            .contains("unknown kind instructionNode for TypeNode"));
+    }
+
+    #[test]
+    fn lenient_known_kinds() {
+        let node = from_value_lenient::<TypeNode>(json!({
+            "kind": "numberTypeNode",
+            "format": "u8",
+            "endian": "le",
+        }));
+        assert_eq!(node.unwrap(), NumberTypeNode::le(U8).into());
+    }
+
+    #[test]
+    fn nested_unknown_nodes() {
+        let node = from_value_lenient::<TypeNode>(json!({
+            "kind": "structTypeNode",
+            "fields": [{
+                "kind": "structFieldTypeNode",
+                "name": "amount",
+                "type": { "kind": "bigDecimalTypeNode", "scale": 6 },
+            }],
+        }));
+        assert_eq!(
+            node.unwrap(),
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "amount",
+                UnknownNode::new(json!({ "kind": "bigDecimalTypeNode", "scale": 6 })).unwrap(),
+            )])
+            .into()
+        );
+    }
+
+    #[test]
+    fn lenient_plugin_payloads() {
+        let payload = json!({ "kind": "fooTypeNode", "size": 42 });
+        let instruction = InstructionNode {
+            name: "myInstruction".into(),
+            plugins: vec![PluginNode::with_payload("foo", payload)],
+            ..InstructionNode::default()
+        };
+        let root = RootNode::new(ProgramNode {
+            instructions: vec![instruction],
+            ..ProgramNode::new("myProgram", "1111")
+        });
+        let json = root.to_json().unwrap();
+        assert_eq!(RootNode::from_json_lenient(&json).unwrap(), root);
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = root_node_json(json!({ "kind": "fooTypeNode", "bar": [1, 2, 3] }));
+        assert!(RootNode::from_json(&json).is_err());
+        let node = RootNode::from_json_lenient(&json).unwrap();
+        let round_trip = serde_json::from_str::<Value>(&node.to_json().unwrap()).unwrap();
+        assert_eq!(round_trip, serde_json::from_str::<Value>(&json).unwrap());
+    }
+}
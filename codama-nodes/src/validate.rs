@@ -0,0 +1,597 @@
+use crate::{
+    check_value_type, decode_public_key, fixed_size, AccountNode, CamelCaseString, ConstantNode,
+    DefinedTypeNode, DiscriminatorNode, EventNode, HasKind, HasName, InstructionAccountNode,
+    InstructionArgumentNode, InstructionNode, LinkResolver, NestedTypeNode, NestedTypeNodeTrait,
+    NodeChildren, NodePath, NodeVisitor, PdaNode, PdaSeedNode, PdaSeedValueValue, PdaValueNode,
+    PdaValuePda, ProgramNode, PublicKeyValueNode, RootNode, StructFieldTypeNode, StructTypeNode,
+    TypeNode, ValueNode,
+};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Checks the given root for semantic issues that cannot be caught when deserializing it,
+/// such as duplicate names, dangling links, invalid public keys or invalid default values.
+pub fn validate(root: &RootNode) -> Vec<Diagnostic> {
+    let resolver = LinkResolver::new(root);
+    let mut validator = Validator {
+        resolver,
+        path: NodePath::new(),
+        diagnostics: resolver
+            .get_link_issues()
+            .into_iter()
+            .map(|issue| Diagnostic {
+                kind: DiagnosticKind::UnresolvedLink,
+                path: issue.path,
+                message: issue.error.to_string(),
+            })
+            .collect(),
+    };
+    validator.visit_root_node(root);
+    validator.diagnostics
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// The path of the node that holds the issue.
+    pub path: NodePath,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    DuplicateName,
+    DuplicateErrorCode,
+    UnresolvedLink,
+    InvalidPublicKey,
+    MissingDiscriminatorField,
+    InvalidDiscriminatorOffset,
+    InvalidDefaultValue,
+    UnknownPdaSeed,
+}
+
+struct Validator<'a> {
+    resolver: LinkResolver<'a>,
+    path: NodePath,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report<T: Into<String>>(&mut self, kind: DiagnosticKind, message: T) {
+        self.diagnostics.push(Diagnostic {
+            kind,
+            path: self.path.clone(),
+            message: message.into(),
+        });
+    }
+
+    fn enter<T: HasKind + HasName>(&mut self, node: &T) {
+        self.path.push(node.kind(), Some(node.name().clone()));
+    }
+
+    fn exit(&mut self) {
+        self.path.pop();
+    }
+
+    fn check_duplicates<'b, T: HasName + 'b>(
+        &mut self,
+        label: &str,
+        nodes: impl IntoIterator<Item = &'b T>,
+    ) {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for node in nodes {
+            let name = node.name();
+            if !seen.insert(name) && reported.insert(name) {
+                self.report(
+                    DiagnosticKind::DuplicateName,
+                    format!("duplicate {label} name `{}`", name.as_str()),
+                );
+            }
+        }
+    }
+
+    fn check_public_key(&mut self, public_key: &str) {
+        if let Err(message) = decode_public_key(public_key) {
+            self.report(DiagnosticKind::InvalidPublicKey, message);
+        }
+    }
+
+    fn check_value(&mut self, label: &str, value: &ValueNode, r#type: &TypeNode) {
+        if let Err(error) = check_value_type(value, r#type, Some(&self.resolver)) {
+            self.report(
+                DiagnosticKind::InvalidDefaultValue,
+                format!("invalid {label}: {error}"),
+            );
+        }
+    }
+
+    /// Check that field discriminators point to existing fields at the right offsets.
+    /// Offsets are only checked when all preceding fields have a fixed size.
+    fn check_discriminators(
+        &mut self,
+        discriminators: &[DiscriminatorNode],
+        fields: &[(&CamelCaseString, &TypeNode)],
+    ) {
+        for discriminator in discriminators {
+            let DiscriminatorNode::Field(discriminator) = discriminator else {
+                continue;
+            };
+            let Some(index) = fields
+                .iter()
+                .position(|(name, _)| *name == &discriminator.name)
+            else {
+                self.report(
+                    DiagnosticKind::MissingDiscriminatorField,
+                    format!(
+                        "discriminator field `{}` does not exist",
+                        discriminator.name.as_str()
+                    ),
+                );
+                continue;
+            };
+            let offset = fields[..index]
+                .iter()
+                .try_fold(0usize, |offset, (_, r#type)| {
+                    offset.checked_add(fixed_size(r#type, &self.resolver)?)
+                });
+            if let Some(offset) = offset {
+                if offset as u64 != discriminator.offset {
+                    self.report(
+                        DiagnosticKind::InvalidDiscriminatorOffset,
+                        format!(
+                            "discriminator field `{}` is at offset {offset}, not {}",
+                            discriminator.name.as_str(),
+                            discriminator.offset
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a> NodeVisitor<'a> for Validator<'a> {
+    fn visit_root_node(&mut self, node: &'a RootNode) {
+        let programs = std::iter::once(&node.program).chain(node.additional_programs.iter());
+        self.check_duplicates("program", programs);
+        node.visit_children(self);
+    }
+
+    fn visit_program_node(&mut self, node: &'a ProgramNode) {
+        let resolver = self.resolver;
+        self.resolver = resolver.with_program(node);
+        self.enter(node);
+        self.check_public_key(&node.public_key);
+        self.check_duplicates("account", &node.accounts);
+        self.check_duplicates("instruction", &node.instructions);
+        self.check_duplicates("defined type", &node.defined_types);
+        self.check_duplicates("PDA", &node.pdas);
+        self.check_duplicates("event", &node.events);
+        self.check_duplicates("error", &node.errors);
+        self.check_duplicates("constant", &node.constants);
+        let mut codes = HashSet::new();
+        for error in &node.errors {
+            if !codes.insert(error.code) {
+                self.report(
+                    DiagnosticKind::DuplicateErrorCode,
+                    format!("duplicate error code {}", error.code),
+                );
+            }
+        }
+        node.visit_children(self);
+        self.exit();
+        self.resolver = resolver;
+    }
+
+    fn visit_account_node(&mut self, node: &'a AccountNode) {
+        self.enter(node);
+        let fields = get_struct_fields(node.data.get_nested_type_node());
+        self.check_discriminators(&node.discriminators, &fields);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_instruction_node(&mut self, node: &'a InstructionNode) {
+        let resolver = self.resolver;
+        self.resolver = resolver.with_instruction(node);
+        self.enter(node);
+        self.check_duplicates("instruction account", &node.accounts);
+        self.check_duplicates(
+            "instruction argument",
+            node.arguments.iter().chain(node.extra_arguments.iter()),
+        );
+        self.check_duplicates("sub-instruction", &node.sub_instructions);
+        let fields = node
+            .arguments
+            .iter()
+            .map(|argument| (&argument.name, argument.r#type.as_ref()))
+            .collect::<Vec<_>>();
+        self.check_discriminators(&node.discriminators, &fields);
+        node.visit_children(self);
+        self.exit();
+        self.resolver = resolver;
+    }
+
+    fn visit_event_node(&mut self, node: &'a EventNode) {
+        self.enter(node);
+        let data = NestedTypeNode::<StructTypeNode>::try_from(*node.data.clone()).ok();
+        let fields = data
+            .as_ref()
+            .map(|data| get_struct_fields(data.get_nested_type_node()))
+            .unwrap_or_default();
+        self.check_discriminators(&node.discriminators, &fields);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_defined_type_node(&mut self, node: &'a DefinedTypeNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_constant_node(&mut self, node: &'a ConstantNode) {
+        self.enter(node);
+        self.check_value("constant value", &node.value, &node.r#type);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_pda_node(&mut self, node: &'a PdaNode) {
+        self.enter(node);
+        if let Some(program_id) = &node.program_id {
+            self.check_public_key(program_id);
+        }
+        let variable_seeds = node.seeds.iter().filter_map(|seed| match seed {
+            PdaSeedNode::Variable(seed) => Some(seed),
+            PdaSeedNode::Constant(_) => None,
+        });
+        self.check_duplicates("seed", variable_seeds);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_struct_field_type_node(&mut self, node: &'a StructFieldTypeNode) {
+        self.enter(node);
+        if let Some(default_value) = node.default_value.as_ref() {
+            self.check_value("default value", default_value, &node.r#type);
+        }
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_instruction_account_node(&mut self, node: &'a InstructionAccountNode) {
+        self.enter(node);
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_instruction_argument_node(&mut self, node: &'a InstructionArgumentNode) {
+        self.enter(node);
+        let default_value = node.default_value.as_ref().clone();
+        if let Some(Ok(default_value)) = default_value.map(ValueNode::try_from) {
+            self.check_value("default value", &default_value, &node.r#type);
+        }
+        node.visit_children(self);
+        self.exit();
+    }
+
+    fn visit_pda_value_node(&mut self, node: &'a PdaValueNode) {
+        let pda = match node.pda.as_ref() {
+            PdaValuePda::Pda(pda) => Some(pda),
+            PdaValuePda::PdaLink(link) => self.resolver.resolve_pda(link).ok(),
+        };
+        let instruction = self.resolver.instruction();
+        for seed in &node.seeds {
+            if let Some(pda) = pda {
+                let exists = pda.seeds.iter().any(|pda_seed| match pda_seed {
+                    PdaSeedNode::Variable(pda_seed) => pda_seed.name == seed.name,
+                    PdaSeedNode::Constant(_) => false,
+                });
+                if !exists {
+                    self.report(
+                        DiagnosticKind::UnknownPdaSeed,
+                        format!(
+                            "seed `{}` does not exist on PDA `{}`",
+                            seed.name.as_str(),
+                            pda.name.as_str()
+                        ),
+                    );
+                }
+            }
+            let Some(instruction) = instruction else {
+                continue;
+            };
+            match seed.value.as_ref() {
+                PdaSeedValueValue::Argument(argument) => {
+                    let exists = instruction
+                        .arguments
+                        .iter()
+                        .chain(instruction.extra_arguments.iter())
+                        .any(|a| a.name == argument.name);
+                    if !exists {
+                        self.report(
+                            DiagnosticKind::UnknownPdaSeed,
+                            format!(
+                                "seed `{}` references missing argument `{}`",
+                                seed.name.as_str(),
+                                argument.name.as_str()
+                            ),
+                        );
+                    }
+                }
+                PdaSeedValueValue::Account(account) => {
+                    if !instruction.accounts.iter().any(|a| a.name == account.name) {
+                        self.report(
+                            DiagnosticKind::UnknownPdaSeed,
+                            format!(
+                                "seed `{}` references missing account `{}`",
+                                seed.name.as_str(),
+                                account.name.as_str()
+                            ),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        node.visit_children(self);
+    }
+
+    fn visit_public_key_value_node(&mut self, node: &'a PublicKeyValueNode) {
+        self.check_public_key(&node.public_key);
+    }
+}
+
+fn get_struct_fields(node: &StructTypeNode) -> Vec<(&CamelCaseString, &TypeNode)> {
+    node.fields
+        .iter()
+        .map(|field| (&field.name, field.r#type.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArgumentValueNode, ErrorNode, FieldDiscriminatorNode, IsSigner, NumberTypeNode,
+        NumberValueNode, PdaLinkNode, PdaSeedValueNode, StringTypeNode, StringValueNode,
+        VariablePdaSeedNode, U32, U64, U8,
+    };
+    use pretty_assertions::assert_eq;
+
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+    fn kinds(diagnostics: &[Diagnostic]) -> Vec<DiagnosticKind> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.kind)
+            .collect()
+    }
+
+    #[test]
+    fn valid_root() {
+        let root = RootNode::new(ProgramNode {
+            accounts: vec![AccountNode::new(
+                "counter",
+                StructTypeNode::new(vec![StructFieldTypeNode::new(
+                    "value",
+                    NumberTypeNode::le(U64),
+                )]),
+            )],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        assert_eq!(validate(&root), vec![]);
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let root = RootNode::new(ProgramNode {
+            errors: vec![
+                ErrorNode::new("invalidOwner", 1, "Invalid owner"),
+                ErrorNode::new("invalidOwner", 2, "Invalid owner"),
+                ErrorNode::new("invalidAmount", 2, "Invalid amount"),
+            ],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        let diagnostics = validate(&root);
+        assert_eq!(
+            kinds(&diagnostics),
+            vec![
+                DiagnosticKind::DuplicateName,
+                DiagnosticKind::DuplicateErrorCode
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "[programNode]counter: duplicate error name `invalidOwner`"
+        );
+        assert_eq!(diagnostics[1].message, "duplicate error code 2");
+    }
+
+    #[test]
+    fn dangling_links() {
+        let root = RootNode::new(ProgramNode {
+            accounts: vec![AccountNode {
+                pda: Some(PdaLinkNode::new("missing")),
+                ..AccountNode::new("counter", StructTypeNode::new(vec![]))
+            }],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        let diagnostics = validate(&root);
+        assert_eq!(kinds(&diagnostics), vec![DiagnosticKind::UnresolvedLink]);
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "[programNode]counter > [accountNode]counter"
+        );
+    }
+
+    #[test]
+    fn invalid_public_keys() {
+        let root = RootNode::new(ProgramNode {
+            pdas: vec![PdaNode {
+                program_id: Some("1111".into()),
+                ..PdaNode::new("counter", vec![])
+            }],
+            ..ProgramNode::new("counter", "not-base58!")
+        });
+        let diagnostics = validate(&root);
+        assert_eq!(
+            kinds(&diagnostics),
+            vec![
+                DiagnosticKind::InvalidPublicKey,
+                DiagnosticKind::InvalidPublicKey
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "public key `not-base58!` is not a valid base58 string"
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "public key `1111` must be 32 bytes, found 4"
+        );
+    }
+
+    #[test]
+    fn discriminators() {
+        let discriminator = |value: u8| StructFieldTypeNode {
+            default_value: Box::new(Some(NumberValueNode::new(value).into())),
+            ..StructFieldTypeNode::new("discriminator", NumberTypeNode::le(U8))
+        };
+        let root = RootNode::new(ProgramNode {
+            accounts: vec![
+                AccountNode {
+                    discriminators: vec![FieldDiscriminatorNode::new("kind", 0).into()],
+                    ..AccountNode::new("missing", StructTypeNode::new(vec![discriminator(1)]))
+                },
+                AccountNode {
+                    discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+                    ..AccountNode::new(
+                        "misplaced",
+                        StructTypeNode::new(vec![
+                            StructFieldTypeNode::new("owner", NumberTypeNode::le(U32)),
+                            discriminator(2),
+                        ]),
+                    )
+                },
+                AccountNode {
+                    discriminators: vec![FieldDiscriminatorNode::new("discriminator", 4).into()],
+                    ..AccountNode::new(
+                        "valid",
+                        StructTypeNode::new(vec![
+                            StructFieldTypeNode::new("owner", NumberTypeNode::le(U32)),
+                            discriminator(3),
+                        ]),
+                    )
+                },
+            ],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        let diagnostics = validate(&root);
+        assert_eq!(
+            kinds(&diagnostics),
+            vec![
+                DiagnosticKind::MissingDiscriminatorField,
+                DiagnosticKind::InvalidDiscriminatorOffset
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "[programNode]counter > [accountNode]misplaced: discriminator field `discriminator` is at offset 4, not 0"
+        );
+    }
+
+    #[test]
+    fn invalid_default_values() {
+        let root = RootNode::new(ProgramNode {
+            accounts: vec![AccountNode::new(
+                "counter",
+                StructTypeNode::new(vec![
+                    StructFieldTypeNode {
+                        default_value: Box::new(Some(NumberValueNode::new(300u16).into())),
+                        ..StructFieldTypeNode::new("small", NumberTypeNode::le(U8))
+                    },
+                    StructFieldTypeNode {
+                        default_value: Box::new(Some(StringValueNode::new("hello").into())),
+                        ..StructFieldTypeNode::new("label", StringTypeNode::utf8())
+                    },
+                ]),
+            )],
+            instructions: vec![InstructionNode {
+                name: "increment".into(),
+                arguments: vec![InstructionArgumentNode {
+                    default_value: Box::new(Some(StringValueNode::new("one").into())),
+                    ..InstructionArgumentNode::new("amount", NumberTypeNode::le(U64))
+                }],
+                ..InstructionNode::default()
+            }],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        let diagnostics = validate(&root);
+        assert_eq!(
+            kinds(&diagnostics),
+            vec![
+                DiagnosticKind::InvalidDefaultValue,
+                DiagnosticKind::InvalidDefaultValue
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "[programNode]counter > [accountNode]counter > [structFieldTypeNode]small"
+        );
+        assert_eq!(
+            diagnostics[1].path.to_string(),
+            "[programNode]counter > [instructionNode]increment > [instructionArgumentNode]amount"
+        );
+    }
+
+    #[test]
+    fn pda_seeds() {
+        let root = RootNode::new(ProgramNode {
+            pdas: vec![PdaNode::new(
+                "counter",
+                vec![VariablePdaSeedNode::new("authority", NumberTypeNode::le(U64)).into()],
+            )],
+            instructions: vec![InstructionNode {
+                name: "increment".into(),
+                accounts: vec![InstructionAccountNode {
+                    default_value: Box::new(Some(
+                        PdaValueNode::new(
+                            PdaLinkNode::new("counter"),
+                            vec![
+                                PdaSeedValueNode::new("authority", ArgumentValueNode::new("owner")),
+                                PdaSeedValueNode::new("unknown", NumberValueNode::new(1u8)),
+                            ],
+                        )
+                        .into(),
+                    )),
+                    ..InstructionAccountNode::new("counter", true, IsSigner::False)
+                }],
+                ..InstructionNode::default()
+            }],
+            ..ProgramNode::new("counter", SYSTEM_PROGRAM)
+        });
+        let messages = validate(&root)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "seed `authority` references missing argument `owner`",
+                "seed `unknown` does not exist on PDA `counter`",
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+include!(concat!(env!("OUT_DIR"), "/visitable_nodes.rs"));
+
+mod nested_type_leaf_node;
+mod node_transformer;
+mod node_visitor;
+mod visitable;
+
+pub use nested_type_leaf_node::*;
+pub use node_transformer::*;
+pub use node_visitor::*;
+pub use visitable::*;
@@ -0,0 +1,78 @@
+use crate::{
+    NestedTypeLeaf, NestedTypeNode, NodeChildren, NodeTransformer, NodeVisitor, TypeNodeTrait,
+    Visitable,
+};
+
+/// A type node that can sit at the bottom of a `NestedTypeNode`.
+///
+/// This lets visitors and transformers walk through nested type nodes
+/// whilst still dispatching to the right method for the underlying type node.
+pub trait NestedTypeLeafNode: TypeNodeTrait + Visitable {}
+
+impl<T: TypeNodeTrait + Visitable> NestedTypeLeafNode for T {}
+
+impl Visitable for NestedTypeLeaf {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_type_node(&self.0)
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        transformer.transform_type_node(self.0).map(Self)
+    }
+}
+
+impl<U: NestedTypeLeafNode> Visitable for NestedTypeNode<U> {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_nested_type_node(self)
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        transformer.transform_nested_type_node(self)
+    }
+}
+
+/// Nested wrappers have no method of their own, so their children
+/// are walked directly until the type node at the bottom is reached.
+impl<U: NestedTypeLeafNode> NodeChildren for NestedTypeNode<U> {
+    fn visit_children<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        match self {
+            NestedTypeNode::FixedSize(node) => node.visit_children(visitor),
+            NestedTypeNode::HiddenPrefix(node) => node.visit_children(visitor),
+            NestedTypeNode::HiddenSuffix(node) => node.visit_children(visitor),
+            NestedTypeNode::PostOffset(node) => node.visit_children(visitor),
+            NestedTypeNode::PreOffset(node) => node.visit_children(visitor),
+            NestedTypeNode::Sentinel(node) => node.visit_children(visitor),
+            NestedTypeNode::SizePrefix(node) => node.visit_children(visitor),
+            NestedTypeNode::Value(node) => node.visit_with(visitor),
+        }
+    }
+
+    fn transform_children<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        match self {
+            NestedTypeNode::FixedSize(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::FixedSize),
+            NestedTypeNode::HiddenPrefix(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::HiddenPrefix),
+            NestedTypeNode::HiddenSuffix(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::HiddenSuffix),
+            NestedTypeNode::PostOffset(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::PostOffset),
+            NestedTypeNode::PreOffset(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::PreOffset),
+            NestedTypeNode::Sentinel(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::Sentinel),
+            NestedTypeNode::SizePrefix(node) => node
+                .transform_children(transformer)
+                .map(NestedTypeNode::SizePrefix),
+            NestedTypeNode::Value(node) => {
+                node.transform_with(transformer).map(NestedTypeNode::Value)
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{NestedTypeLeafNode, NestedTypeNode, NodeChildren, UnknownNode};
+
+macro_rules! transform_methods {
+    ($($visit:ident, $transform:ident: $node:ty;)*) => {
+        $(
+            fn $transform(&mut self, node: $node) -> Option<$node> {
+                node.transform_children(self)
+            }
+        )*
+    };
+}
+
+/// A bottom-up transformer over Codama nodes.
+///
+/// Every node kind has its own `transform_*` method that takes ownership of the
+/// node and returns the node to keep in its place, or `None` to delete it.
+/// The default implementations transform the node's children first through
+/// `NodeChildren::transform_children`. Deleting a node stored in a `Vec`
+/// or an `Option` simply removes it from its parent, whereas deleting a required
+/// child also deletes its parent.
+///
+/// Replacing a node with a node of another kind is done by overriding the
+/// method of the union that contains it, e.g. `transform_type_node` to
+/// replace a `DefinedTypeLinkNode` with the type it points to.
+///
+/// The methods of the nodes and node unions are generated from their `#[node]`
+/// and `#[node_union]` items, see the `build.rs` script of this crate.
+pub trait NodeTransformer {
+    fn transform_nested_type_node<T: NestedTypeLeafNode>(
+        &mut self,
+        node: NestedTypeNode<T>,
+    ) -> Option<NestedTypeNode<T>> {
+        node.transform_children(self)
+    }
+
+    fn transform_unknown_node(&mut self, node: UnknownNode) -> Option<UnknownNode> {
+        node.transform_children(self)
+    }
+
+    for_each_visitable_node!(transform_methods);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountNode, DefinedTypeLinkNode, DefinedTypeNode, Docs, FixedSizeTypeNode, Node,
+        NumberTypeNode, ProgramNode, StringTypeNode, StructFieldTypeNode, StructTypeNode,
+        TupleTypeNode, TypeNode, U32, U64,
+    };
+    use pretty_assertions::assert_eq;
+
+    struct RenameLinks;
+    impl NodeTransformer for RenameLinks {
+        fn transform_defined_type_link_node(
+            &mut self,
+            mut node: DefinedTypeLinkNode,
+        ) -> Option<DefinedTypeLinkNode> {
+            node.name = format!("{}Renamed", node.name.as_str()).into();
+            Some(node)
+        }
+    }
+
+    struct StripDocs;
+    impl NodeTransformer for StripDocs {
+        fn transform_defined_type_node(
+            &mut self,
+            node: DefinedTypeNode,
+        ) -> Option<DefinedTypeNode> {
+            let mut node = node.transform_children(self)?;
+            node.docs = Docs::default();
+            Some(node)
+        }
+        fn transform_struct_field_type_node(
+            &mut self,
+            node: StructFieldTypeNode,
+        ) -> Option<StructFieldTypeNode> {
+            let mut node = node.transform_children(self)?;
+            node.docs = Docs::default();
+            Some(node)
+        }
+    }
+
+    struct DeleteStrings;
+    impl NodeTransformer for DeleteStrings {
+        fn transform_string_type_node(&mut self, _node: StringTypeNode) -> Option<StringTypeNode> {
+            None
+        }
+    }
+
+    struct InlineLinks(Vec<DefinedTypeNode>);
+    impl NodeTransformer for InlineLinks {
+        fn transform_type_node(&mut self, node: TypeNode) -> Option<TypeNode> {
+            match node {
+                TypeNode::Link(link) => self
+                    .0
+                    .iter()
+                    .find(|defined_type| defined_type.name == link.name)
+                    .map(|defined_type| *defined_type.r#type.clone()),
+                _ => node.transform_children(self),
+            }
+        }
+    }
+
+    #[test]
+    fn replace_nodes_of_the_same_kind() {
+        let node = StructTypeNode::new(vec![
+            StructFieldTypeNode::new("mint", DefinedTypeLinkNode::new("mint")),
+            StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+        ]);
+        assert_eq!(
+            RenameLinks.transform_struct_type_node(node),
+            Some(StructTypeNode::new(vec![
+                StructFieldTypeNode::new("mint", DefinedTypeLinkNode::new("mintRenamed")),
+                StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn transform_children_before_parents() {
+        let node = DefinedTypeNode {
+            docs: vec!["Some docs.".to_string()].into(),
+            ..DefinedTypeNode::new(
+                "myType",
+                StructTypeNode::new(vec![StructFieldTypeNode {
+                    docs: vec!["Field docs.".to_string()].into(),
+                    ..StructFieldTypeNode::new("amount", NumberTypeNode::le(U64))
+                }]),
+            )
+        };
+        assert_eq!(
+            StripDocs.transform_defined_type_node(node),
+            Some(DefinedTypeNode::new(
+                "myType",
+                StructTypeNode::new(vec![StructFieldTypeNode::new(
+                    "amount",
+                    NumberTypeNode::le(U64)
+                )]),
+            ))
+        );
+    }
+
+    #[test]
+    fn delete_nodes_in_lists() {
+        let node = TupleTypeNode::new(vec![
+            StringTypeNode::utf8().into(),
+            NumberTypeNode::le(U32).into(),
+        ]);
+        assert_eq!(
+            DeleteStrings.transform_tuple_type_node(node),
+            Some(TupleTypeNode::new(vec![NumberTypeNode::le(U32).into()]))
+        );
+    }
+
+    #[test]
+    fn delete_parents_of_deleted_required_children() {
+        let node = ProgramNode {
+            defined_types: vec![
+                DefinedTypeNode::new("name", StringTypeNode::utf8()),
+                DefinedTypeNode::new("age", NumberTypeNode::le(U32)),
+            ],
+            ..ProgramNode::default()
+        };
+        assert_eq!(
+            DeleteStrings.transform_program_node(node),
+            Some(ProgramNode {
+                defined_types: vec![DefinedTypeNode::new("age", NumberTypeNode::le(U32))],
+                ..ProgramNode::default()
+            })
+        );
+    }
+
+    #[test]
+    fn delete_inside_nested_type_nodes() {
+        let node = AccountNode::new(
+            "myAccount",
+            FixedSizeTypeNode::new(
+                StructTypeNode::new(vec![
+                    StructFieldTypeNode::new("name", StringTypeNode::utf8()),
+                    StructFieldTypeNode::new("age", NumberTypeNode::le(U32)),
+                ]),
+                32,
+            ),
+        );
+        assert_eq!(
+            DeleteStrings.transform_account_node(node),
+            Some(AccountNode::new(
+                "myAccount",
+                FixedSizeTypeNode::new(
+                    StructTypeNode::new(vec![StructFieldTypeNode::new(
+                        "age",
+                        NumberTypeNode::le(U32)
+                    )]),
+                    32,
+                ),
+            ))
+        );
+    }
+
+    #[test]
+    fn replace_nodes_with_another_kind() {
+        let mut transformer = InlineLinks(vec![DefinedTypeNode::new(
+            "amount",
+            NumberTypeNode::le(U64),
+        )]);
+        let node: Node =
+            StructFieldTypeNode::new("amount", DefinedTypeLinkNode::new("amount")).into();
+        assert_eq!(
+            transformer.transform_node(node),
+            Some(StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)).into())
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{NestedTypeLeafNode, NestedTypeNode, NodeChildren, UnknownNode};
+
+macro_rules! visit_methods {
+    ($($visit:ident, $transform:ident: $node:ty;)*) => {
+        $(
+            fn $visit(&mut self, node: &'a $node) {
+                node.visit_children(self)
+            }
+        )*
+    };
+}
+
+/// A read-only visitor over Codama nodes.
+///
+/// Every node kind has its own `visit_*` method whose default implementation
+/// recurses into the node's children through `NodeChildren::visit_children`.
+/// Override the methods you care about and call `node.visit_children(self)`
+/// from within them to keep walking down the tree.
+///
+/// Node unions have their own method too, e.g. `visit_type_node`, which is the
+/// place to handle all the nodes of a union at once. A `Node` goes straight to the
+/// method of the node it holds, unless it holds a union such as a `CountNode`.
+///
+/// The `'a` lifetime is the lifetime of the visited tree, which allows
+/// visitors to hold on to references of the nodes they visit.
+///
+/// The methods of the nodes and node unions are generated from their `#[node]`
+/// and `#[node_union]` items, see the `build.rs` script of this crate.
+pub trait NodeVisitor<'a> {
+    fn visit_nested_type_node<T: NestedTypeLeafNode>(&mut self, node: &'a NestedTypeNode<T>) {
+        node.visit_children(self)
+    }
+
+    fn visit_unknown_node(&mut self, node: &'a UnknownNode) {
+        node.visit_children(self)
+    }
+
+    for_each_visitable_node!(visit_methods);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountNode, CamelCaseString, DefinedTypeLinkNode, DefinedTypeNode, EnumValueNode,
+        FixedSizeTypeNode, HasName, InstructionArgumentNode, InstructionNode, Node, NumberTypeNode,
+        ProgramNode, RootNode, StringTypeNode, StructFieldTypeNode, StructFieldValueNode,
+        StructTypeNode, StructValueNode, U32, U64,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[derive(Default)]
+    struct FieldNameCollector {
+        names: Vec<CamelCaseString>,
+    }
+
+    impl<'a> NodeVisitor<'a> for FieldNameCollector {
+        fn visit_struct_field_type_node(&mut self, node: &'a StructFieldTypeNode) {
+            self.names.push(node.name().clone());
+            node.visit_children(self)
+        }
+    }
+
+    #[derive(Default)]
+    struct LinkCollector {
+        names: Vec<CamelCaseString>,
+    }
+
+    impl<'a> NodeVisitor<'a> for LinkCollector {
+        fn visit_defined_type_link_node(&mut self, node: &'a DefinedTypeLinkNode) {
+            self.names.push(node.name().clone());
+        }
+    }
+
+    fn get_root() -> RootNode {
+        RootNode::new(ProgramNode {
+            accounts: vec![AccountNode::new(
+                "token",
+                FixedSizeTypeNode::new(
+                    StructTypeNode::new(vec![
+                        StructFieldTypeNode::new("mint", DefinedTypeLinkNode::new("mint")),
+                        StructFieldTypeNode::new("amount", NumberTypeNode::le(U64)),
+                    ]),
+                    40,
+                ),
+            )],
+            instructions: vec![InstructionNode {
+                name: "transfer".into(),
+                arguments: vec![InstructionArgumentNode::new(
+                    "amount",
+                    NumberTypeNode::le(U64),
+                )],
+                ..InstructionNode::default()
+            }],
+            defined_types: vec![
+                DefinedTypeNode::new(
+                    "mint",
+                    StructTypeNode::new(vec![StructFieldTypeNode::new(
+                        "supply",
+                        NumberTypeNode::le(U64),
+                    )]),
+                ),
+                DefinedTypeNode::new(
+                    "config",
+                    StructTypeNode::new(vec![StructFieldTypeNode {
+                        default_value: Box::new(Some(
+                            StructValueNode::new(vec![StructFieldValueNode::new(
+                                "kind",
+                                EnumValueNode::empty("kind", "first"),
+                            )])
+                            .into(),
+                        )),
+                        ..StructFieldTypeNode::new("settings", StringTypeNode::utf8())
+                    }]),
+                ),
+            ],
+            ..ProgramNode::new("myProgram", "1111")
+        })
+        .add_program(ProgramNode {
+            defined_types: vec![DefinedTypeNode::new(
+                "counter",
+                StructTypeNode::new(vec![StructFieldTypeNode::new(
+                    "count",
+                    NumberTypeNode::le(U32),
+                )]),
+            )],
+            ..ProgramNode::new("myDependency", "2222")
+        })
+    }
+
+    #[test]
+    fn visit_struct_fields() {
+        let mut visitor = FieldNameCollector::default();
+        visitor.visit_root_node(&get_root());
+        assert_eq!(
+            visitor.names,
+            vec![
+                CamelCaseString::new("mint"),
+                CamelCaseString::new("amount"),
+                CamelCaseString::new("supply"),
+                CamelCaseString::new("settings"),
+                CamelCaseString::new("count"),
+            ]
+        );
+    }
+
+    #[test]
+    fn visit_links_in_types_and_values() {
+        let mut visitor = LinkCollector::default();
+        visitor.visit_root_node(&get_root());
+        assert_eq!(
+            visitor.names,
+            vec![CamelCaseString::new("mint"), CamelCaseString::new("kind")]
+        );
+    }
+
+    #[test]
+    fn visit_from_node() {
+        let node: Node = get_root().into();
+        let mut visitor = FieldNameCollector::default();
+        visitor.visit_node(&node);
+        assert_eq!(visitor.names.len(), 5);
+    }
+
+    #[test]
+    fn stop_recursion() {
+        struct ProgramOnly(usize);
+        impl<'a> NodeVisitor<'a> for ProgramOnly {
+            fn visit_program_node(&mut self, _node: &'a ProgramNode) {
+                self.0 += 1;
+            }
+            fn visit_struct_field_type_node(&mut self, _node: &'a StructFieldTypeNode) {
+                panic!("should not be visited");
+            }
+        }
+
+        let mut visitor = ProgramOnly(0);
+        visitor.visit_root_node(&get_root());
+        assert_eq!(visitor.0, 2);
+    }
+}
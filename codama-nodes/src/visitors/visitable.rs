@@ -0,0 +1,690 @@
+use crate::{
+    BytesEncoding, CamelCaseString, DefaultValueStrategy, DisplaySkip, Docs, Endianness,
+    InstructionLifecycle, IsSigner, NodeTransformer, NodeVisitor, Number, NumberFormat,
+    OptionalAccountStrategy, PostOffsetStrategy, PreOffsetStrategy, ProgramOrigin, UnknownNode,
+};
+
+/// A node, a node union or a field of a node that visitors and transformers can walk through.
+///
+/// Nodes and node unions implement this trait through the `#[node]` and `#[node_union]`
+/// macros by calling their own `visit_*` and `transform_*` methods, e.g. `visit_type_node`
+/// for a `TypeNode`. Fields that hold no nodes, such as names or docs, are skipped.
+pub trait Visitable: Sized {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V);
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self>;
+}
+
+/// The direct children of a node or a node union.
+///
+/// This is what the default `visit_*` and `transform_*` methods recurse into, and what
+/// overridden methods should call to keep walking down the tree. Transforming the
+/// children of a node returns `None` when one of its required children was deleted.
+pub trait NodeChildren: Sized {
+    fn visit_children<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V);
+    fn transform_children<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self>;
+}
+
+impl<U: Visitable> Visitable for Box<U> {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        self.as_ref().visit_with(visitor)
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        (*self).transform_with(transformer).map(Box::new)
+    }
+}
+
+/// Deleting an optional child only removes it from its parent.
+impl<U: Visitable> Visitable for Option<U> {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit_with(visitor)
+        }
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        Some(self.and_then(|node| node.transform_with(transformer)))
+    }
+}
+
+/// Deleting a child of a list only removes it from the list.
+impl<U: Visitable> Visitable for Vec<U> {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        for node in self {
+            node.visit_with(visitor)
+        }
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        Some(
+            self.into_iter()
+                .filter_map(|node| node.transform_with(transformer))
+                .collect(),
+        )
+    }
+}
+
+impl Visitable for UnknownNode {
+    fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        visitor.visit_unknown_node(self)
+    }
+
+    fn transform_with<T: NodeTransformer + ?Sized>(self, transformer: &mut T) -> Option<Self> {
+        transformer.transform_unknown_node(self)
+    }
+}
+
+impl NodeChildren for UnknownNode {
+    fn visit_children<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, _visitor: &mut V) {}
+
+    fn transform_children<T: NodeTransformer + ?Sized>(self, _transformer: &mut T) -> Option<Self> {
+        Some(self)
+    }
+}
+
+macro_rules! impl_attribute_visitable {
+    ($($attribute:ty),* $(,)?) => {
+        $(
+            impl Visitable for $attribute {
+                fn visit_with<'a, V: NodeVisitor<'a> + ?Sized>(&'a self, _visitor: &mut V) {}
+
+                fn transform_with<T: NodeTransformer + ?Sized>(
+                    self,
+                    _transformer: &mut T,
+                ) -> Option<Self> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_attribute_visitable!(
+    bool,
+    i32,
+    u32,
+    u64,
+    usize,
+    String,
+    serde_json::Value,
+    BytesEncoding,
+    CamelCaseString,
+    DefaultValueStrategy,
+    DisplaySkip,
+    Docs,
+    Endianness,
+    InstructionLifecycle,
+    IsSigner,
+    Number,
+    NumberFormat,
+    OptionalAccountStrategy,
+    PostOffsetStrategy,
+    PreOffsetStrategy,
+    ProgramOrigin,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+    use std::collections::BTreeSet;
+
+    /// Records every `visit_*` and `transform_*` method that gets called.
+    #[derive(Default)]
+    struct MethodRecorder {
+        calls: Vec<&'static str>,
+    }
+
+    macro_rules! record_every_method {
+        ($($visit:ident, $transform:ident: $node:ty;)*) => {
+            impl<'a> NodeVisitor<'a> for MethodRecorder {
+                fn visit_nested_type_node<T: NestedTypeLeafNode>(
+                    &mut self,
+                    node: &'a NestedTypeNode<T>,
+                ) {
+                    if !matches!(node, NestedTypeNode::Value(_)) {
+                        self.calls.push("visit_nested_type_node");
+                    }
+                    node.visit_children(self)
+                }
+
+                $(fn $visit(&mut self, node: &'a $node) {
+                    self.calls.push(stringify!($visit));
+                    node.visit_children(self)
+                })*
+            }
+
+            impl NodeTransformer for MethodRecorder {
+                fn transform_nested_type_node<T: NestedTypeLeafNode>(
+                    &mut self,
+                    node: NestedTypeNode<T>,
+                ) -> Option<NestedTypeNode<T>> {
+                    if !matches!(node, NestedTypeNode::Value(_)) {
+                        self.calls.push("transform_nested_type_node");
+                    }
+                    node.transform_children(self)
+                }
+
+                $(fn $transform(&mut self, node: $node) -> Option<$node> {
+                    self.calls.push(stringify!($transform));
+                    node.transform_children(self)
+                })*
+            }
+
+            const VISIT_METHODS: &[&str] = &[$(stringify!($visit)),*];
+            const TRANSFORM_METHODS: &[&str] = &[$(stringify!($transform)),*];
+        };
+    }
+
+    record_every_method! {
+        visit_account_bump_value_node, transform_account_bump_value_node: AccountBumpValueNode;
+        visit_account_field_value_node, transform_account_field_value_node: AccountFieldValueNode;
+        visit_account_link_node, transform_account_link_node: AccountLinkNode;
+        visit_account_node, transform_account_node: AccountNode;
+        visit_account_value_node, transform_account_value_node: AccountValueNode;
+        visit_amount_number_display_node, transform_amount_number_display_node: AmountNumberDisplayNode;
+        visit_amount_type_node, transform_amount_type_node: AmountTypeNode;
+        visit_argument_value_node, transform_argument_value_node: ArgumentValueNode;
+        visit_array_type_node, transform_array_type_node: ArrayTypeNode;
+        visit_array_value_node, transform_array_value_node: ArrayValueNode;
+        visit_boolean_type_node, transform_boolean_type_node: BooleanTypeNode;
+        visit_boolean_value_node, transform_boolean_value_node: BooleanValueNode;
+        visit_bytes_type_node, transform_bytes_type_node: BytesTypeNode;
+        visit_bytes_value_node, transform_bytes_value_node: BytesValueNode;
+        visit_conditional_value_node, transform_conditional_value_node: ConditionalValueNode;
+        visit_constant_discriminator_node, transform_constant_discriminator_node: ConstantDiscriminatorNode;
+        visit_constant_node, transform_constant_node: ConstantNode;
+        visit_constant_pda_seed_node, transform_constant_pda_seed_node: ConstantPdaSeedNode;
+        visit_constant_value_node, transform_constant_value_node: ConstantValueNode;
+        visit_date_time_number_display_node, transform_date_time_number_display_node: DateTimeNumberDisplayNode;
+        visit_date_time_type_node, transform_date_time_type_node: DateTimeTypeNode;
+        visit_defined_type_link_node, transform_defined_type_link_node: DefinedTypeLinkNode;
+        visit_defined_type_node, transform_defined_type_node: DefinedTypeNode;
+        visit_duration_number_display_node, transform_duration_number_display_node: DurationNumberDisplayNode;
+        visit_enum_empty_variant_type_node, transform_enum_empty_variant_type_node: EnumEmptyVariantTypeNode;
+        visit_enum_struct_variant_type_node, transform_enum_struct_variant_type_node: EnumStructVariantTypeNode;
+        visit_enum_tuple_variant_type_node, transform_enum_tuple_variant_type_node: EnumTupleVariantTypeNode;
+        visit_enum_type_node, transform_enum_type_node: EnumTypeNode;
+        visit_enum_value_node, transform_enum_value_node: EnumValueNode;
+        visit_enum_variant_display_node, transform_enum_variant_display_node: EnumVariantDisplayNode;
+        visit_error_node, transform_error_node: ErrorNode;
+        visit_event_node, transform_event_node: EventNode;
+        visit_field_discriminator_node, transform_field_discriminator_node: FieldDiscriminatorNode;
+        visit_fixed_count_node, transform_fixed_count_node: FixedCountNode;
+        visit_fixed_size_type_node, transform_fixed_size_type_node: FixedSizeTypeNode<TypeNode>;
+        visit_hidden_prefix_type_node, transform_hidden_prefix_type_node: HiddenPrefixTypeNode<TypeNode>;
+        visit_hidden_suffix_type_node, transform_hidden_suffix_type_node: HiddenSuffixTypeNode<TypeNode>;
+        visit_identity_value_node, transform_identity_value_node: IdentityValueNode;
+        visit_injected_value_node, transform_injected_value_node: InjectedValueNode;
+        visit_instruction_account_display_node, transform_instruction_account_display_node: InstructionAccountDisplayNode;
+        visit_instruction_account_link_node, transform_instruction_account_link_node: InstructionAccountLinkNode;
+        visit_instruction_account_node, transform_instruction_account_node: InstructionAccountNode;
+        visit_instruction_argument_link_node, transform_instruction_argument_link_node: InstructionArgumentLinkNode;
+        visit_instruction_argument_node, transform_instruction_argument_node: InstructionArgumentNode;
+        visit_instruction_byte_delta_node, transform_instruction_byte_delta_node: InstructionByteDeltaNode;
+        visit_instruction_display_node, transform_instruction_display_node: InstructionDisplayNode;
+        visit_instruction_link_node, transform_instruction_link_node: InstructionLinkNode;
+        visit_instruction_node, transform_instruction_node: InstructionNode;
+        visit_instruction_remaining_accounts_node, transform_instruction_remaining_accounts_node: InstructionRemainingAccountsNode;
+        visit_instruction_status_node, transform_instruction_status_node: InstructionStatusNode;
+        visit_map_entry_value_node, transform_map_entry_value_node: MapEntryValueNode;
+        visit_map_type_node, transform_map_type_node: MapTypeNode;
+        visit_map_value_node, transform_map_value_node: MapValueNode;
+        visit_none_value_node, transform_none_value_node: NoneValueNode;
+        visit_number_type_node, transform_number_type_node: NumberTypeNode;
+        visit_number_value_node, transform_number_value_node: NumberValueNode;
+        visit_option_type_node, transform_option_type_node: OptionTypeNode;
+        visit_payer_value_node, transform_payer_value_node: PayerValueNode;
+        visit_pda_link_node, transform_pda_link_node: PdaLinkNode;
+        visit_pda_node, transform_pda_node: PdaNode;
+        visit_pda_seed_value_node, transform_pda_seed_value_node: PdaSeedValueNode;
+        visit_pda_value_node, transform_pda_value_node: PdaValueNode;
+        visit_plugin_node, transform_plugin_node: PluginNode;
+        visit_post_offset_type_node, transform_post_offset_type_node: PostOffsetTypeNode<TypeNode>;
+        visit_pre_offset_type_node, transform_pre_offset_type_node: PreOffsetTypeNode<TypeNode>;
+        visit_prefixed_count_node, transform_prefixed_count_node: PrefixedCountNode;
+        visit_program_id_value_node, transform_program_id_value_node: ProgramIdValueNode;
+        visit_program_link_node, transform_program_link_node: ProgramLinkNode;
+        visit_program_node, transform_program_node: ProgramNode;
+        visit_provided_node, transform_provided_node: ProvidedNode;
+        visit_public_key_type_node, transform_public_key_type_node: PublicKeyTypeNode;
+        visit_public_key_value_node, transform_public_key_value_node: PublicKeyValueNode;
+        visit_remainder_count_node, transform_remainder_count_node: RemainderCountNode;
+        visit_remainder_option_type_node, transform_remainder_option_type_node: RemainderOptionTypeNode;
+        visit_resolver_value_node, transform_resolver_value_node: ResolverValueNode;
+        visit_root_node, transform_root_node: RootNode;
+        visit_sentinel_type_node, transform_sentinel_type_node: SentinelTypeNode<TypeNode>;
+        visit_set_type_node, transform_set_type_node: SetTypeNode;
+        visit_set_value_node, transform_set_value_node: SetValueNode;
+        visit_size_discriminator_node, transform_size_discriminator_node: SizeDiscriminatorNode;
+        visit_size_prefix_type_node, transform_size_prefix_type_node: SizePrefixTypeNode<TypeNode>;
+        visit_sol_amount_type_node, transform_sol_amount_type_node: SolAmountTypeNode;
+        visit_some_value_node, transform_some_value_node: SomeValueNode;
+        visit_string_display_node, transform_string_display_node: StringDisplayNode;
+        visit_string_type_node, transform_string_type_node: StringTypeNode;
+        visit_string_value_node, transform_string_value_node: StringValueNode;
+        visit_struct_field_display_node, transform_struct_field_display_node: StructFieldDisplayNode;
+        visit_struct_field_type_node, transform_struct_field_type_node: StructFieldTypeNode;
+        visit_struct_field_value_node, transform_struct_field_value_node: StructFieldValueNode;
+        visit_struct_type_node, transform_struct_type_node: StructTypeNode;
+        visit_struct_value_node, transform_struct_value_node: StructValueNode;
+        visit_tuple_type_node, transform_tuple_type_node: TupleTypeNode;
+        visit_tuple_value_node, transform_tuple_value_node: TupleValueNode;
+        visit_unknown_node, transform_unknown_node: UnknownNode;
+        visit_variable_pda_seed_node, transform_variable_pda_seed_node: VariablePdaSeedNode;
+        visit_zeroable_option_type_node, transform_zeroable_option_type_node: ZeroableOptionTypeNode;
+    }
+
+    fn number(format: &str) -> Value {
+        json!({ "kind": "numberTypeNode", "format": format, "endian": "le" })
+    }
+
+    fn constant(data: &str) -> Value {
+        json!({
+            "kind": "constantValueNode",
+            "type": { "kind": "bytesTypeNode" },
+            "value": { "kind": "bytesValueNode", "data": data, "encoding": "base16" },
+        })
+    }
+
+    fn program_link() -> Value {
+        json!({ "kind": "programLinkNode", "name": "splToken" })
+    }
+
+    fn field(name: &str, r#type: Value) -> Value {
+        json!({ "kind": "structFieldTypeNode", "name": name, "type": r#type })
+    }
+
+    fn account(name: &str, default_value: Value) -> Value {
+        json!({
+            "kind": "instructionAccountNode",
+            "name": name,
+            "isWritable": false,
+            "isSigner": false,
+            "defaultValue": default_value,
+        })
+    }
+
+    fn argument(name: &str, r#type: Value, default_value: Value) -> Value {
+        json!({
+            "kind": "instructionArgumentNode",
+            "name": name,
+            "type": r#type,
+            "defaultValue": default_value,
+        })
+    }
+
+    /// A root that holds at least one node of every kind, in every nested position.
+    fn get_full_root() -> RootNode {
+        let data = json!({
+            "kind": "fixedSizeTypeNode",
+            "size": 512,
+            "type": {
+                "kind": "structTypeNode",
+                "fields": [
+                    field("amount", json!({
+                        "kind": "amountTypeNode",
+                        "decimals": 2,
+                        "number": {
+                            "kind": "numberTypeNode",
+                            "format": "u64",
+                            "endian": "le",
+                            "display": {
+                                "kind": "amountNumberDisplayNode",
+                                "decimals": { "kind": "numberValueNode", "number": 2 },
+                                "unit": { "kind": "injectedValueNode", "key": "unit" },
+                            },
+                        },
+                    })),
+                    field("flags", json!({
+                        "kind": "arrayTypeNode",
+                        "item": { "kind": "booleanTypeNode", "size": number("u8") },
+                        "count": { "kind": "prefixedCountNode", "prefix": number("u32") },
+                    })),
+                    field("data", json!({
+                        "kind": "sizePrefixTypeNode",
+                        "type": { "kind": "bytesTypeNode" },
+                        "prefix": {
+                            "kind": "hiddenSuffixTypeNode",
+                            "type": number("u32"),
+                            "suffix": [constant("ff")],
+                        },
+                    })),
+                    field("createdAt", json!({
+                        "kind": "dateTimeTypeNode",
+                        "number": {
+                            "kind": "numberTypeNode",
+                            "format": "i64",
+                            "endian": "le",
+                            "display": { "kind": "dateTimeNumberDisplayNode" },
+                        },
+                    })),
+                    field("lockup", json!({
+                        "kind": "numberTypeNode",
+                        "format": "u64",
+                        "endian": "le",
+                        "display": { "kind": "durationNumberDisplayNode" },
+                    })),
+                    field("kind", json!({
+                        "kind": "definedTypeLinkNode",
+                        "name": "kind",
+                        "program": program_link(),
+                    })),
+                    field("labels", json!({
+                        "kind": "mapTypeNode",
+                        "key": { "kind": "publicKeyTypeNode" },
+                        "value": {
+                            "kind": "stringTypeNode",
+                            "encoding": "utf8",
+                            "display": { "kind": "stringDisplayNode", "sliceStart": 0 },
+                        },
+                        "count": { "kind": "remainderCountNode" },
+                    })),
+                    field("delegate", json!({
+                        "kind": "optionTypeNode",
+                        "item": { "kind": "publicKeyTypeNode" },
+                        "prefix": number("u8"),
+                    })),
+                    field("memo", json!({
+                        "kind": "remainderOptionTypeNode",
+                        "item": { "kind": "stringTypeNode", "encoding": "utf8" },
+                    })),
+                    field("tags", json!({
+                        "kind": "setTypeNode",
+                        "item": number("u16"),
+                        "count": { "kind": "fixedCountNode", "value": 4 },
+                    })),
+                    field("rent", json!({ "kind": "solAmountTypeNode", "number": number("u64") })),
+                    field("padding", json!({
+                        "kind": "tupleTypeNode",
+                        "items": [
+                            { "kind": "hiddenPrefixTypeNode", "type": number("u8"), "prefix": [constant("01")] },
+                            { "kind": "hiddenSuffixTypeNode", "type": number("u8"), "suffix": [constant("02")] },
+                            { "kind": "postOffsetTypeNode", "type": number("u8"), "offset": 1, "strategy": "relative" },
+                            { "kind": "preOffsetTypeNode", "type": number("u8"), "offset": 1, "strategy": "relative" },
+                            { "kind": "sentinelTypeNode", "type": { "kind": "bytesTypeNode" }, "sentinel": constant("00") },
+                            { "kind": "fixedSizeTypeNode", "type": { "kind": "bytesTypeNode" }, "size": 8 },
+                        ],
+                    })),
+                    {
+                        "kind": "structFieldTypeNode",
+                        "name": "closeAuthority",
+                        "type": {
+                            "kind": "zeroableOptionTypeNode",
+                            "item": { "kind": "publicKeyTypeNode" },
+                            "zeroValue": constant("00"),
+                        },
+                        "defaultValue": { "kind": "noneValueNode" },
+                        "display": { "kind": "structFieldDisplayNode", "label": "Close authority" },
+                    },
+                ],
+            },
+        });
+
+        let instruction = json!({
+            "kind": "instructionNode",
+            "name": "transfer",
+            "accounts": [
+                {
+                    "kind": "instructionAccountNode",
+                    "name": "source",
+                    "isWritable": true,
+                    "isSigner": false,
+                    "defaultValue": {
+                        "kind": "conditionalValueNode",
+                        "condition": { "kind": "accountValueNode", "name": "authority" },
+                        "value": { "kind": "publicKeyValueNode", "publicKey": "11111111111111111111111111111111" },
+                        "ifTrue": {
+                            "kind": "pdaValueNode",
+                            "pda": { "kind": "pdaLinkNode", "name": "vault", "program": program_link() },
+                            "seeds": [{
+                                "kind": "pdaSeedValueNode",
+                                "name": "owner",
+                                "value": { "kind": "argumentValueNode", "name": "owner" },
+                            }],
+                            "programId": { "kind": "accountValueNode", "name": "program" },
+                        },
+                        "ifFalse": {
+                            "kind": "resolverValueNode",
+                            "name": "resolveSource",
+                            "dependsOn": [
+                                { "kind": "accountValueNode", "name": "authority" },
+                                { "kind": "argumentValueNode", "name": "amount" },
+                            ],
+                        },
+                    },
+                    "accountLink": { "kind": "accountLinkNode", "name": "token", "program": program_link() },
+                    "display": { "kind": "instructionAccountDisplayNode", "label": "Source" },
+                },
+                account("bump", json!({ "kind": "accountBumpValueNode", "name": "source" })),
+                account("mint", json!({ "kind": "accountFieldValueNode", "account": "source", "path": "mint" })),
+                account("authority", json!({ "kind": "identityValueNode" })),
+                account("payer", json!({ "kind": "payerValueNode" })),
+                account("self", json!({ "kind": "programIdValueNode" })),
+                account("tokenProgram", program_link()),
+                account("vault", json!({
+                    "kind": "pdaValueNode",
+                    "pda": {
+                        "kind": "pdaNode",
+                        "name": "inlineVault",
+                        "seeds": [{ "kind": "variablePdaSeedNode", "name": "owner", "type": { "kind": "publicKeyTypeNode" } }],
+                    },
+                    "seeds": [],
+                })),
+            ],
+            "arguments": [
+                argument("items", json!({ "kind": "arrayTypeNode", "item": number("u8"), "count": { "kind": "remainderCountNode" } }), json!({
+                    "kind": "arrayValueNode",
+                    "items": [{ "kind": "numberValueNode", "number": 1 }],
+                })),
+                argument("enabled", json!({ "kind": "booleanTypeNode", "size": number("u8") }), json!({ "kind": "booleanValueNode", "boolean": true })),
+                argument("magic", json!({ "kind": "bytesTypeNode" }), constant("cafe")),
+                argument("kind", json!({ "kind": "definedTypeLinkNode", "name": "kind" }), json!({
+                    "kind": "enumValueNode",
+                    "enum": { "kind": "definedTypeLinkNode", "name": "kind" },
+                    "variant": "named",
+                    "value": {
+                        "kind": "structValueNode",
+                        "fields": [{ "kind": "structFieldValueNode", "name": "value", "value": { "kind": "stringValueNode", "string": "hello" } }],
+                    },
+                })),
+                argument("otherKind", json!({ "kind": "definedTypeLinkNode", "name": "kind" }), json!({
+                    "kind": "enumValueNode",
+                    "enum": { "kind": "definedTypeLinkNode", "name": "kind" },
+                    "variant": "unnamed",
+                    "value": { "kind": "tupleValueNode", "items": [{ "kind": "numberValueNode", "number": 1 }] },
+                })),
+                argument("note", json!({ "kind": "stringTypeNode", "encoding": "utf8" }), json!({
+                    "kind": "injectedValueNode",
+                    "key": "note",
+                    "fallback": { "kind": "stringValueNode", "string": "none" },
+                })),
+                argument("labels", json!({ "kind": "mapTypeNode", "key": number("u8"), "value": number("u8"), "count": { "kind": "remainderCountNode" } }), json!({
+                    "kind": "mapValueNode",
+                    "entries": [{
+                        "kind": "mapEntryValueNode",
+                        "key": { "kind": "numberValueNode", "number": 1 },
+                        "value": { "kind": "numberValueNode", "number": 2 },
+                    }],
+                })),
+                argument("tags", json!({ "kind": "setTypeNode", "item": number("u8"), "count": { "kind": "remainderCountNode" } }), json!({
+                    "kind": "setValueNode",
+                    "items": [{ "kind": "numberValueNode", "number": 3 }],
+                })),
+                {
+                    "kind": "instructionArgumentNode",
+                    "name": "limit",
+                    "type": { "kind": "optionTypeNode", "item": number("u64"), "prefix": number("u8") },
+                    "defaultValue": { "kind": "someValueNode", "value": { "kind": "numberValueNode", "number": 10 } },
+                    "display": { "kind": "structFieldDisplayNode", "label": "Limit" },
+                },
+            ],
+            "extraArguments": [
+                argument("extra", number("u8"), json!({ "kind": "numberValueNode", "number": 0 })),
+            ],
+            "remainingAccounts": [{
+                "kind": "instructionRemainingAccountsNode",
+                "value": { "kind": "argumentValueNode", "name": "signers" },
+                "display": { "kind": "instructionAccountDisplayNode", "label": "Signers" },
+            }],
+            "byteDeltas": [{
+                "kind": "instructionByteDeltaNode",
+                "withHeader": true,
+                "value": { "kind": "accountLinkNode", "name": "token" },
+            }],
+            "discriminators": [{ "kind": "fieldDiscriminatorNode", "name": "discriminator", "offset": 0 }],
+            "status": { "kind": "instructionStatusNode", "lifecycle": "live" },
+            "subInstructions": [{ "kind": "instructionNode", "name": "transferChecked" }],
+            "provides": [
+                {
+                    "kind": "providedNode",
+                    "name": "destination",
+                    "node": {
+                        "kind": "instructionAccountLinkNode",
+                        "name": "destination",
+                        "instruction": { "kind": "instructionLinkNode", "name": "transfer", "program": program_link() },
+                    },
+                },
+                {
+                    "kind": "providedNode",
+                    "name": "amount",
+                    "node": { "kind": "instructionArgumentLinkNode", "name": "amount" },
+                },
+            ],
+            "display": { "kind": "instructionDisplayNode", "intent": "Transfer tokens" },
+            "plugins": [{ "kind": "pluginNode", "name": "myPlugin" }],
+        });
+
+        let program = json!({
+            "kind": "programNode",
+            "name": "myProgram",
+            "publicKey": "11111111111111111111111111111111",
+            "version": "1.0.0",
+            "accounts": [{
+                "kind": "accountNode",
+                "name": "token",
+                "data": data,
+                "pda": { "kind": "pdaLinkNode", "name": "vault" },
+                "discriminators": [
+                    { "kind": "constantDiscriminatorNode", "offset": 0, "constant": constant("01") },
+                    { "kind": "sizeDiscriminatorNode", "size": 512 },
+                ],
+            }],
+            "instructions": [instruction],
+            "definedTypes": [{
+                "kind": "definedTypeNode",
+                "name": "kind",
+                "type": {
+                    "kind": "enumTypeNode",
+                    "variants": [
+                        {
+                            "kind": "enumEmptyVariantTypeNode",
+                            "name": "empty",
+                            "display": { "kind": "enumVariantDisplayNode", "label": "Empty" },
+                        },
+                        {
+                            "kind": "enumStructVariantTypeNode",
+                            "name": "named",
+                            "struct": { "kind": "structTypeNode", "fields": [field("value", json!({ "kind": "stringTypeNode", "encoding": "utf8" }))] },
+                        },
+                        {
+                            "kind": "enumTupleVariantTypeNode",
+                            "name": "unnamed",
+                            "tuple": { "kind": "tupleTypeNode", "items": [number("u8")] },
+                        },
+                    ],
+                    "size": number("u8"),
+                },
+            }],
+            "pdas": [{
+                "kind": "pdaNode",
+                "name": "vault",
+                "seeds": [
+                    { "kind": "constantPdaSeedNode", "type": { "kind": "stringTypeNode", "encoding": "utf8" }, "value": { "kind": "stringValueNode", "string": "vault" } },
+                    { "kind": "constantPdaSeedNode", "type": { "kind": "publicKeyTypeNode" }, "value": { "kind": "programIdValueNode" } },
+                    { "kind": "variablePdaSeedNode", "name": "owner", "type": { "kind": "publicKeyTypeNode" } },
+                ],
+            }],
+            "events": [{
+                "kind": "eventNode",
+                "name": "transferred",
+                "data": { "kind": "structTypeNode", "fields": [field("amount", number("u64"))] },
+                "discriminators": [{ "kind": "sizeDiscriminatorNode", "size": 8 }],
+            }],
+            "errors": [{ "kind": "errorNode", "name": "invalidAmount", "code": 6000, "message": "Invalid amount" }],
+            "constants": [{
+                "kind": "constantNode",
+                "name": "maxAmount",
+                "type": number("u64"),
+                "value": { "kind": "numberValueNode", "number": 100 },
+            }],
+        });
+
+        let mut root: RootNode = serde_json::from_value(json!({
+            "kind": "rootNode",
+            "standard": "codama",
+            "version": "1.0.0",
+            "program": program,
+            "additionalPrograms": [{
+                "kind": "programNode",
+                "name": "myDependency",
+                "publicKey": "22222222222222222222222222222222",
+                "version": "1.0.0",
+            }],
+        }))
+        .unwrap();
+        root.program.accounts[0]
+            .discriminators
+            .push(DiscriminatorNode::Unknown(
+                UnknownNode::new(json!({ "kind": "hashDiscriminatorNode" })).unwrap(),
+            ));
+        root
+    }
+
+    /// The number of nodes in the JSON representation of the given node.
+    fn count_nodes(value: &Value) -> usize {
+        match value {
+            Value::Array(items) => items.iter().map(count_nodes).sum(),
+            Value::Object(map) => {
+                let is_node = map.get("kind").is_some_and(Value::is_string);
+                usize::from(is_node) + map.values().map(count_nodes).sum::<usize>()
+            }
+            _ => 0,
+        }
+    }
+
+    fn all_methods(methods: &[&'static str], nested: &'static str) -> BTreeSet<&'static str> {
+        methods.iter().copied().chain([nested]).collect()
+    }
+
+    #[test]
+    fn visit_every_node_of_a_full_root() {
+        let root = get_full_root();
+        let mut visitor = MethodRecorder::default();
+        visitor.visit_root_node(&root);
+
+        let json = serde_json::to_value(&root).unwrap();
+        assert_eq!(visitor.calls.len(), count_nodes(&json));
+        assert_eq!(
+            visitor.calls.iter().copied().collect::<BTreeSet<_>>(),
+            all_methods(VISIT_METHODS, "visit_nested_type_node")
+        );
+    }
+
+    #[test]
+    fn transform_every_node_of_a_full_root() {
+        let root = get_full_root();
+        let mut transformer = MethodRecorder::default();
+        let transformed = transformer.transform_root_node(root.clone());
+
+        let json = serde_json::to_value(&root).unwrap();
+        assert_eq!(transformed, Some(root));
+        assert_eq!(transformer.calls.len(), count_nodes(&json));
+        assert_eq!(
+            transformer.calls.iter().copied().collect::<BTreeSet<_>>(),
+            all_methods(TRANSFORM_METHODS, "transform_nested_type_node")
+        );
+    }
+}
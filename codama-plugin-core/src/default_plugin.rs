@@ -2,9 +2,9 @@ use crate::KorokPlugin;
 use codama_errors::CodamaResult;
 use codama_korok_visitors::{
     ApplyDisplayVisitor, ApplyTypeModifiersVisitor, ApplyTypeOverridesVisitor,
-    CombineModulesVisitor, IdentifyFieldTypesVisitor, KorokVisitable, SetAccountsVisitor,
-    SetDefaultValuesVisitor, SetDefinedTypesVisitor, SetErrorsVisitor, SetEventsVisitor,
-    SetInstructionsVisitor, SetPdasVisitor, SetProgramMetadataVisitor,
+    CheckDefaultValuesVisitor, CombineModulesVisitor, IdentifyFieldTypesVisitor, KorokVisitable,
+    SetAccountsVisitor, SetDefaultValuesVisitor, SetDefinedTypesVisitor, SetErrorsVisitor,
+    SetEventsVisitor, SetInstructionsVisitor, SetPdasVisitor, SetProgramMetadataVisitor,
 };
 
 pub struct DefaultPlugin;
@@ -31,6 +31,7 @@ impl KorokPlugin for DefaultPlugin {
     fn on_root_node_set(&self, visitable: &mut dyn KorokVisitable) -> CodamaResult<()> {
         visitable.accept(&mut SetProgramMetadataVisitor::new())?;
         visitable.accept(&mut CombineModulesVisitor::new())?;
+        visitable.accept(&mut CheckDefaultValuesVisitor::new())?;
         Ok(())
     }
 }
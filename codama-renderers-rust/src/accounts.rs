@@ -0,0 +1,22 @@
+use crate::renderer::Renderer;
+use crate::utils::docs;
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{fixed_size, AccountNode, TypeNode};
+
+impl<'a> Renderer<'a> {
+    /// Renders the data of an account as a struct that checks its discriminators when read.
+    pub(crate) fn account(&mut self, account: &AccountNode) -> CodamaResult<()> {
+        let name = account.name.to_pascal_case();
+        self.declare(&name);
+        let data = self.nested(&account.data);
+        let conditions = self.discriminators(&account.name, &data.fields, &account.discriminators);
+        let position = self.item_position();
+        let item = self.struct_item(&name, &docs(&account.docs, ""), &data.fields)?;
+        self.insert_item(position, item);
+        let len = fixed_size(&TypeNode::Struct(data.clone()), &self.resolver);
+        let item = self.discriminated_impl(&name, &conditions, len);
+        self.insert_item(position + 1, item);
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::renderer::Renderer;
+use crate::utils::{bytes_literal, indent, to_screaming_snake_case};
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    encode, CamelCaseString, DiscriminatorNode, HasKind, StructFieldTypeNode, TypeNode, ValueNode,
+};
+
+impl<'a> Renderer<'a> {
+    /// Renders the discriminators of an account or an event as a list of conditions on
+    /// its `data`. Field discriminators are also rendered as `{NAME}_{FIELD}` constants.
+    pub(crate) fn discriminators(
+        &mut self,
+        name: &CamelCaseString,
+        fields: &[StructFieldTypeNode],
+        discriminators: &[DiscriminatorNode],
+    ) -> Vec<String> {
+        let mut conditions = vec![];
+        for discriminator in discriminators {
+            match discriminator {
+                DiscriminatorNode::Constant(node) => {
+                    let bytes =
+                        self.encode_discriminator(&node.constant.value, &node.constant.r#type);
+                    if let Some(bytes) = bytes {
+                        let range = range(node.offset, bytes.len());
+                        let bytes = bytes_literal(&bytes);
+                        conditions.push(format!("data.get({range}) == Some(&{bytes}[..])"));
+                    }
+                }
+                DiscriminatorNode::Field(node) => {
+                    let field = fields.iter().find(|field| field.name == node.name);
+                    let Some((field, value)) = field.and_then(|field| {
+                        field
+                            .default_value
+                            .as_ref()
+                            .as_ref()
+                            .map(|value| (field, value))
+                    }) else {
+                        self.report(format!(
+                            "the `{}` field discriminator has no default value and was ignored",
+                            node.name.as_str()
+                        ));
+                        continue;
+                    };
+                    if let Some(bytes) = self.encode_discriminator(value, &field.r#type) {
+                        let constant = format!(
+                            "{}_{}",
+                            to_screaming_snake_case(name),
+                            to_screaming_snake_case(&field.name)
+                        );
+                        let range = range(node.offset, bytes.len());
+                        self.push_item(format!(
+                            "pub const {constant}: [u8; {}] = {};\n",
+                            bytes.len(),
+                            bytes_literal(&bytes)
+                        ));
+                        conditions.push(format!("data.get({range}) == Some(&{constant}[..])"));
+                    }
+                }
+                DiscriminatorNode::Size(node) => {
+                    conditions.push(format!("data.len() == {}", node.size));
+                }
+                DiscriminatorNode::Unknown(node) => {
+                    self.report(format!("`{}` is unknown and was ignored", node.kind));
+                }
+            }
+        }
+        conditions
+    }
+
+    fn encode_discriminator(&mut self, value: &ValueNode, r#type: &TypeNode) -> Option<Vec<u8>> {
+        match encode(value, r#type, &self.resolver) {
+            Ok(bytes) => Some(bytes),
+            Err(error) => {
+                let kind = value.kind();
+                self.report(format!(
+                    "`{kind}` discriminator could not be encoded: {error}"
+                ));
+                None
+            }
+        }
+    }
+
+    /// Renders the `impl` block that reads a discriminated account or event from raw bytes.
+    pub(crate) fn discriminated_impl(
+        &self,
+        name: &str,
+        conditions: &[String],
+        len: Option<usize>,
+    ) -> String {
+        let mut members = vec![];
+        if let Some(len) = len {
+            members.push(format!("pub const LEN: usize = {len};\n"));
+        }
+        if !conditions.is_empty() {
+            members.push(format!(
+                "/// Whether the given data is discriminated as a `{name}`.\n\
+                 pub fn is_discriminated(data: &[u8]) -> bool {{\n{}}}\n",
+                indent(&conditions.join("\n    && "), "    ")
+            ));
+        }
+        let check = match conditions.is_empty() {
+            true => String::new(),
+            false => format!(
+                "if !Self::is_discriminated(data) {{\n    \
+                     return Err(std::io::Error::new(\n        \
+                         std::io::ErrorKind::InvalidData,\n        \
+                         \"invalid {name} discriminator\",\n    \
+                     ));\n\
+                 }}\n"
+            ),
+        };
+        members.push(format!(
+            "/// Deserializes a `{name}` from the given data.\n\
+             #[inline(always)]\n\
+             pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {{\n\
+             {}    let mut data = data;\n    \
+                 Self::deserialize(&mut data)\n\
+             }}\n",
+            indent(&check, "    ")
+        ));
+        let members = members
+            .iter()
+            .map(|member| indent(member, "    "))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("impl {name} {{\n{members}}}\n")
+    }
+}
+
+fn range(offset: u64, length: usize) -> String {
+    format!("{offset}..{}", offset + length as u64)
+}
@@ -0,0 +1,51 @@
+use crate::renderer::Renderer;
+use crate::utils::{docs, identifier, string_literal};
+use codama_nodes::ProgramNode;
+
+impl<'a> Renderer<'a> {
+    /// Renders the errors of a program as a `{Program}Error` enum of their codes.
+    pub(crate) fn program_errors(&mut self, program: &ProgramNode) {
+        let name = format!("{}Error", program.name.to_pascal_case());
+        self.declare(&name);
+        self.import("thiserror", "Error");
+
+        let mut variants = String::new();
+        let mut arms = String::new();
+        for error in &program.errors {
+            let variant = identifier(error.name.to_pascal_case());
+            let message = error.message.replace('{', "{{").replace('}', "}}");
+            variants.push_str(&docs(&error.docs, "    "));
+            variants.push_str(&format!(
+                "    /// {} - {}\n    #[error({})]\n    {variant} = {},\n",
+                error.code,
+                error.message.lines().next().unwrap_or_default(),
+                string_literal(&message),
+                error.code,
+            ));
+            arms.push_str(&format!(
+                "            {} => Some(Self::{variant}),\n",
+                error.code
+            ));
+        }
+
+        self.push_item(format!(
+            "#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]\n\
+             #[repr(u32)]\n\
+             pub enum {name} {{\n{variants}}}\n\
+             \n\
+             impl {name} {{\n    \
+                 /// The custom program error code of this error.\n    \
+                 pub fn code(self) -> u32 {{\n        \
+                     self as u32\n    \
+                 }}\n\
+             \n    \
+                 /// Returns the error of the given custom program error code, if any.\n    \
+                 pub fn from_code(code: u32) -> Option<Self> {{\n        \
+                     match code {{\n\
+             {arms}            _ => None,\n        \
+                     }}\n    \
+                 }}\n\
+             }}\n"
+        ));
+    }
+}
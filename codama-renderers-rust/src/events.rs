@@ -0,0 +1,28 @@
+use crate::renderer::Renderer;
+use crate::utils::docs;
+use codama_errors::CodamaResult;
+use codama_nodes::{EventNode, TypeNode};
+
+impl<'a> Renderer<'a> {
+    /// Renders the data of an event as a struct that checks its discriminators when decoded.
+    pub(crate) fn event(&mut self, event: &EventNode) -> CodamaResult<()> {
+        let name = event.name.to_pascal_case();
+        self.declare(&name);
+        let docs = docs(&event.docs, "");
+        let fields = match event.data.as_ref() {
+            TypeNode::Struct(node) => node.fields.as_slice(),
+            _ => &[],
+        };
+        let conditions = self.discriminators(&event.name, fields, &event.discriminators);
+        let position = self.item_position();
+        let item = match event.data.as_ref() {
+            TypeNode::Struct(node) => self.struct_item(&name, &docs, &node.fields)?,
+            TypeNode::Tuple(node) => self.tuple_struct_item(&name, &docs, &node.items)?,
+            data => self.tuple_struct_item(&name, &docs, std::slice::from_ref(data))?,
+        };
+        self.insert_item(position, item);
+        let item = self.discriminated_impl(&name, &conditions, None);
+        self.insert_item(position + 1, item);
+        Ok(())
+    }
+}
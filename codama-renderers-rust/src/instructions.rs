@@ -0,0 +1,750 @@
+use crate::pdas::{pda_function, SeedKind};
+use crate::renderer::{derive, Renderer};
+use crate::types::is_copy;
+use crate::utils::{docs, indent, snake_identifier, string_literal, to_screaming_snake_case};
+use crate::values::Expression;
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    CamelCaseString, DefaultValueStrategy, HasKind, InstructionAccountNode,
+    InstructionArgumentNode, InstructionInputValueNode, InstructionNode, IsSigner,
+    OptionalAccountStrategy, PdaSeedNode, PdaSeedValueValue, PdaValueNode, PdaValuePda,
+    StructFieldTypeNode, TypeNode, ValueNode,
+};
+use std::collections::BTreeSet;
+
+/// A reference to an account or an argument of an instruction,
+/// since both live in separate namespaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InputRef {
+    Account(CamelCaseString),
+    Argument(CamelCaseString),
+}
+
+impl InputRef {
+    fn name(&self) -> &CamelCaseString {
+        match self {
+            Self::Account(name) | Self::Argument(name) => name,
+        }
+    }
+
+    fn key(&self) -> (bool, &str) {
+        (matches!(self, Self::Argument(_)), self.name().as_str())
+    }
+}
+
+impl PartialOrd for InputRef {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InputRef {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// An account or an argument resolved by the instruction builder.
+struct Input<'b> {
+    reference: InputRef,
+    /// The name of the local variable holding the resolved input.
+    local: String,
+    kind: InputKind<'b>,
+    /// The rendered default value, if any, along with the inputs it depends on.
+    default: Option<(Expression, BTreeSet<InputRef>)>,
+}
+
+enum InputKind<'b> {
+    Account(&'b InstructionAccountNode),
+    Argument {
+        node: &'b InstructionArgumentNode,
+        rust_type: String,
+        /// Whether the argument is part of the instruction data, as opposed to extra arguments.
+        is_data: bool,
+    },
+}
+
+impl Input<'_> {
+    fn is_omitted(&self) -> bool {
+        match &self.kind {
+            InputKind::Argument { node, .. } => {
+                self.default.is_some()
+                    && node.default_value_strategy == Some(DefaultValueStrategy::Omitted)
+            }
+            InputKind::Account(_) => false,
+        }
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// Renders the instruction data, the accounts of an instruction and a builder that
+    /// resolves the default values of its inputs.
+    pub(crate) fn instruction(
+        &mut self,
+        instruction: &InstructionNode,
+        program_id: &str,
+    ) -> CodamaResult<()> {
+        let name = instruction.name.to_pascal_case();
+        let data_name = format!("{name}InstructionData");
+        let builder_name = format!("{name}Builder");
+        for declared in [&name, &data_name, &builder_name] {
+            self.declare(declared);
+        }
+        self.import("solana_instruction", "AccountMeta");
+        self.import("solana_instruction", "Instruction");
+        if !instruction.accounts.is_empty() {
+            self.import("solana_pubkey", "Pubkey");
+        }
+
+        let fields = instruction
+            .arguments
+            .iter()
+            .map(|argument| StructFieldTypeNode {
+                default_value: Box::new(
+                    argument
+                        .default_value
+                        .as_ref()
+                        .clone()
+                        .and_then(|value| ValueNode::try_from(value).ok()),
+                ),
+                ..StructFieldTypeNode::new(argument.name.clone(), argument.r#type.as_ref().clone())
+            })
+            .collect::<Vec<_>>();
+        self.discriminators(&instruction.name, &fields, &instruction.discriminators);
+
+        let mut inputs = self.instruction_inputs(instruction, &data_name)?;
+        let order = self.resolve_defaults(&mut inputs, program_id)?;
+
+        let data_fields = inputs
+            .iter()
+            .filter_map(|input| match &input.kind {
+                InputKind::Argument {
+                    node,
+                    rust_type,
+                    is_data: true,
+                } => {
+                    let field = snake_identifier(&node.name);
+                    Some(format!(
+                        "{}    pub {field}: {rust_type},\n",
+                        docs(&node.docs, "    ")
+                    ))
+                }
+                _ => None,
+            })
+            .collect::<String>();
+        let derives = derive(&self.borsh_derives());
+        self.push_item(format!(
+            "/// The data of the `{name}` instruction.\n{derives}pub struct {data_name} {{{}}}\n",
+            block(&data_fields)
+        ));
+
+        let accounts =
+            self.instruction_accounts(instruction, &inputs, &name, &data_name, program_id);
+        self.push_item(accounts);
+        let builder = self.instruction_builder(
+            instruction,
+            &inputs,
+            &order,
+            &name,
+            &data_name,
+            &builder_name,
+        );
+        self.push_item(builder);
+        Ok(())
+    }
+
+    fn instruction_inputs<'b>(
+        &mut self,
+        instruction: &'b InstructionNode,
+        data_name: &str,
+    ) -> CodamaResult<Vec<Input<'b>>> {
+        let account_locals = instruction
+            .accounts
+            .iter()
+            .map(|account| snake_identifier(&account.name))
+            .collect::<BTreeSet<_>>();
+        let mut inputs = instruction
+            .accounts
+            .iter()
+            .map(|account| Input {
+                reference: InputRef::Account(account.name.clone()),
+                local: snake_identifier(&account.name),
+                kind: InputKind::Account(account),
+                default: None,
+            })
+            .collect::<Vec<_>>();
+
+        let arguments = instruction
+            .arguments
+            .iter()
+            .map(|argument| (argument, true))
+            .chain(
+                instruction
+                    .extra_arguments
+                    .iter()
+                    .map(|argument| (argument, false)),
+            );
+        for (argument, is_data) in arguments {
+            let rust_type = self.scoped(argument.kind(), &argument.name, |this| {
+                let inline_name = format!("{data_name}{}", argument.name.to_pascal_case());
+                this.r#type(&inline_name, &argument.r#type)
+            })?;
+            let local = snake_identifier(&argument.name);
+            let local = match account_locals.contains(&local) {
+                true => format!("{}_arg", local.trim_start_matches("r#")),
+                false => local,
+            };
+            inputs.push(Input {
+                reference: InputRef::Argument(argument.name.clone()),
+                local,
+                kind: InputKind::Argument {
+                    node: argument,
+                    rust_type,
+                    is_data,
+                },
+                default: None,
+            });
+        }
+        Ok(inputs)
+    }
+
+    /// Renders the default values of all inputs and returns the order in which to resolve
+    /// them, so that every input is resolved after the inputs its default value depends on.
+    fn resolve_defaults(
+        &mut self,
+        inputs: &mut [Input],
+        program_id: &str,
+    ) -> CodamaResult<Vec<usize>> {
+        for index in 0..inputs.len() {
+            let (kind, name, default) = match &inputs[index].kind {
+                InputKind::Account(node) => (node.kind(), &node.name, node.default_value.as_ref()),
+                InputKind::Argument { node, .. } => {
+                    (node.kind(), &node.name, node.default_value.as_ref())
+                }
+            };
+            let Some(default) = default else {
+                continue;
+            };
+            let default = self.scoped(kind, name, |this| {
+                this.input_default(default, &inputs[index], inputs, program_id)
+            })?;
+            inputs[index].default = default;
+        }
+
+        let mut resolved = BTreeSet::new();
+        let mut order = Vec::with_capacity(inputs.len());
+        let mut pending = (0..inputs.len()).collect::<Vec<_>>();
+        loop {
+            let (ready, rest): (Vec<_>, Vec<_>) = pending.into_iter().partition(|index| {
+                inputs[*index]
+                    .default
+                    .as_ref()
+                    .is_none_or(|(_, dependencies)| dependencies.is_subset(&resolved))
+            });
+            pending = rest;
+            if ready.is_empty() {
+                break;
+            }
+            for index in ready {
+                resolved.insert(inputs[index].reference.clone());
+                order.push(index);
+            }
+        }
+        for index in pending {
+            let (kind, name) = match &inputs[index].kind {
+                InputKind::Account(node) => (node.kind(), &node.name),
+                InputKind::Argument { node, .. } => (node.kind(), &node.name),
+            };
+            self.scoped(kind, name, |this| {
+                this.report("circular default values are not supported and must be provided")
+            });
+            inputs[index].default = None;
+            order.push(index);
+        }
+        Ok(order)
+    }
+
+    /// Renders the default value of an input, returning `None` for unsupported default values.
+    fn input_default(
+        &mut self,
+        default: &InstructionInputValueNode,
+        input: &Input,
+        inputs: &[Input],
+        program_id: &str,
+    ) -> CodamaResult<Option<(Expression, BTreeSet<InputRef>)>> {
+        let is_public_key = match &input.kind {
+            InputKind::Account(_) => true,
+            InputKind::Argument { node, .. } => matches!(*node.r#type, TypeNode::PublicKey(_)),
+        };
+        let mut dependencies = BTreeSet::new();
+        let expression = match default {
+            InstructionInputValueNode::AccountValue(node) if is_public_key => {
+                let reference = InputRef::Account(node.name.clone());
+                let expression = self.public_key_reference(&reference, inputs);
+                dependencies.insert(reference);
+                expression.map(Expression::eager)
+            }
+            InstructionInputValueNode::ArgumentValue(node) => {
+                let reference = InputRef::Argument(node.name.clone());
+                let expression = match find_input(inputs, &reference) {
+                    Some(Input {
+                        local,
+                        kind: InputKind::Argument { node, .. },
+                        ..
+                    }) => Some(match is_copy(&node.r#type) {
+                        true => Expression::eager(local.clone()),
+                        false => Expression::lazy(format!("{local}.clone()")),
+                    }),
+                    _ => {
+                        self.report(format!("unknown argument `{}`", node.name.as_str()));
+                        None
+                    }
+                };
+                dependencies.insert(reference);
+                expression
+            }
+            InstructionInputValueNode::PdaValue(node) if is_public_key => {
+                self.pda_default(node, inputs, &mut dependencies)?
+            }
+            InstructionInputValueNode::ProgramIdValue(_) if is_public_key => {
+                Some(Expression::eager(program_id.to_string()))
+            }
+            InstructionInputValueNode::ProgramLink(node) if is_public_key => {
+                let program = self.resolver.resolve_program(node)?;
+                let constant = program_constant(&program.name);
+                self.import("crate::programs", &constant);
+                Some(Expression::eager(constant))
+            }
+            InstructionInputValueNode::PublicKeyValue(node) if is_public_key => {
+                self.import("solana_pubkey", "pubkey");
+                let literal = format!("pubkey!({})", string_literal(&node.public_key));
+                Some(Expression::eager(literal))
+            }
+            default => match (&input.kind, ValueNode::try_from(default.clone())) {
+                (InputKind::Argument { node, .. }, Ok(value)) => self.value(&value, &node.r#type),
+                _ => {
+                    self.report(format!(
+                        "`{}` default values are not supported and must be provided",
+                        default.kind()
+                    ));
+                    None
+                }
+            },
+        };
+        Ok(expression.map(|expression| (expression, dependencies)))
+    }
+
+    /// Renders a call to the function of a linked PDA, using the provided seed values.
+    fn pda_default(
+        &mut self,
+        node: &PdaValueNode,
+        inputs: &[Input],
+        dependencies: &mut BTreeSet<InputRef>,
+    ) -> CodamaResult<Option<Expression>> {
+        let PdaValuePda::PdaLink(link) = node.pda.as_ref() else {
+            self.report("PDAs that are not linked are not supported and must be provided");
+            return Ok(None);
+        };
+        if node.program_id.is_some() {
+            self.report("PDAs with custom program IDs are not supported and must be provided");
+            return Ok(None);
+        }
+        let pda = self.resolver.resolve_pda(link)?;
+        let mut arguments = vec![];
+        for seed in &pda.seeds {
+            let PdaSeedNode::Variable(seed) = seed else {
+                continue;
+            };
+            let Some(value) = node.seeds.iter().find(|value| value.name == seed.name) else {
+                self.report(format!("missing value for seed `{}`", seed.name.as_str()));
+                return Ok(None);
+            };
+            let kind = SeedKind::new(&seed.r#type);
+            let argument = match (value.value.as_ref(), &kind) {
+                (PdaSeedValueValue::Account(value), SeedKind::PublicKey) => {
+                    let reference = InputRef::Account(value.name.clone());
+                    let argument = self.public_key_reference(&reference, inputs);
+                    dependencies.insert(reference);
+                    argument.map(|argument| format!("&{argument}"))
+                }
+                (PdaSeedValueValue::Argument(value), kind) => {
+                    let reference = InputRef::Argument(value.name.clone());
+                    let argument = find_input(inputs, &reference).map(|input| match kind {
+                        SeedKind::Number(_) => input.local.clone(),
+                        _ => format!("&{}", input.local),
+                    });
+                    if argument.is_none() {
+                        self.report(format!("unknown argument `{}`", value.name.as_str()));
+                    }
+                    dependencies.insert(reference);
+                    argument
+                }
+                (PdaSeedValueValue::PublicKey(value), SeedKind::PublicKey) => {
+                    self.import("solana_pubkey", "pubkey");
+                    Some(format!("&pubkey!({})", string_literal(&value.public_key)))
+                }
+                (PdaSeedValueValue::String(value), SeedKind::String) => {
+                    Some(string_literal(&value.string))
+                }
+                (PdaSeedValueValue::Number(value), SeedKind::Number(_)) => self
+                    .value(&ValueNode::Number(*value), &seed.r#type)
+                    .map(|expression| expression.code),
+                (value, _) => {
+                    self.report(format!(
+                        "`{}` seed values are not supported and must be provided",
+                        value.kind()
+                    ));
+                    None
+                }
+            };
+            let Some(argument) = argument else {
+                return Ok(None);
+            };
+            arguments.push(argument);
+        }
+        let function = pda_function(pda);
+        self.import("crate::pdas", &function);
+        let call = format!("{function}({}).0", arguments.join(", "));
+        Ok(Some(Expression::lazy(call)))
+    }
+
+    /// Renders the public key of a required account resolved before the current input.
+    fn public_key_reference(&mut self, reference: &InputRef, inputs: &[Input]) -> Option<String> {
+        match find_input(inputs, reference) {
+            Some(Input {
+                local,
+                kind: InputKind::Account(account),
+                ..
+            }) if account.is_optional != Some(true) => Some(match account.is_signer {
+                IsSigner::Either => format!("{local}.0"),
+                _ => local.clone(),
+            }),
+            Some(_) => {
+                self.report("optional accounts cannot be used as default values");
+                None
+            }
+            None => {
+                let name = reference.name().as_str();
+                self.report(format!("unknown account `{name}`"));
+                None
+            }
+        }
+    }
+
+    /// Renders the accounts of an instruction as a struct that builds the instruction.
+    fn instruction_accounts(
+        &mut self,
+        instruction: &InstructionNode,
+        inputs: &[Input],
+        name: &str,
+        data_name: &str,
+        program_id: &str,
+    ) -> String {
+        let mut fields = String::new();
+        let mut metas = String::new();
+        for account in &instruction.accounts {
+            let field = snake_identifier(&account.name);
+            let is_optional = account.is_optional == Some(true)
+                && !inputs.iter().any(|input| {
+                    matches!(input.kind, InputKind::Account(node) if node.name == account.name)
+                        && input.default.is_some()
+                });
+            let value = match is_optional {
+                true => field.clone(),
+                false => format!("self.{field}"),
+            };
+            let (key, signer) = match account.is_signer {
+                IsSigner::True => (value, "true".to_string()),
+                IsSigner::False => (value, "false".to_string()),
+                IsSigner::Either => (format!("{value}.0"), format!("{value}.1")),
+            };
+            let constructor = match account.is_writable {
+                true => "new",
+                false => "new_readonly",
+            };
+            let meta = format!("accounts.push(AccountMeta::{constructor}({key}, {signer}));\n");
+            fields.push_str(&docs(&account.docs, "    "));
+            fields.push_str(&format!(
+                "    pub {field}: {},\n",
+                account_type(account, is_optional)
+            ));
+            if !is_optional {
+                metas.push_str(&meta);
+                continue;
+            }
+            let strategy = instruction
+                .optional_account_strategy
+                .unwrap_or(OptionalAccountStrategy::ProgramId);
+            metas.push_str(&format!(
+                "if let Some({field}) = self.{field} {{\n{}}}",
+                indent(&meta, "    ")
+            ));
+            metas.push_str(&match strategy {
+                OptionalAccountStrategy::Omitted => "\n".to_string(),
+                OptionalAccountStrategy::ProgramId => format!(
+                    " else {{\n    accounts.push(AccountMeta::new_readonly({program_id}, false));\n}}\n"
+                ),
+            });
+        }
+
+        let capacity = match instruction.accounts.len() {
+            0 => "remaining_accounts.len()".to_string(),
+            count => format!("{count} + remaining_accounts.len()"),
+        };
+        format!(
+            "/// The accounts of the `{name}` instruction.\n\
+             #[derive(Clone, Debug, PartialEq)]\n\
+             pub struct {name} {{{}}}\n\
+             \n\
+             impl {name} {{\n    \
+                 pub fn instruction(&self, data: {data_name}) -> Instruction {{\n        \
+                     self.instruction_with_remaining_accounts(data, &[])\n    \
+                 }}\n\
+             \n    \
+                 pub fn instruction_with_remaining_accounts(\n        \
+                     &self,\n        \
+                     data: {data_name},\n        \
+                     remaining_accounts: &[AccountMeta],\n    \
+                 ) -> Instruction {{\n        \
+                     let mut accounts = Vec::with_capacity({capacity});\n\
+             {}        \
+                     accounts.extend_from_slice(remaining_accounts);\n        \
+                     Instruction {{\n            \
+                         program_id: {program_id},\n            \
+                         accounts,\n            \
+                         data: borsh::to_vec(&data).unwrap(),\n        \
+                     }}\n    \
+                 }}\n\
+             }}\n",
+            block(&fields),
+            indent(&metas, "        "),
+        )
+    }
+
+    /// Renders a builder that resolves the inputs of an instruction before building it.
+    fn instruction_builder(
+        &mut self,
+        instruction: &InstructionNode,
+        inputs: &[Input],
+        order: &[usize],
+        name: &str,
+        data_name: &str,
+        builder_name: &str,
+    ) -> String {
+        let referenced = inputs
+            .iter()
+            .filter_map(|input| input.default.as_ref())
+            .flat_map(|(_, dependencies)| dependencies.iter())
+            .collect::<BTreeSet<_>>();
+        let mut fields = String::new();
+        let mut setters = vec!["pub fn new() -> Self {\n    Self::default()\n}\n".to_string()];
+        for input in inputs {
+            let field = snake_identifier(input.reference.name());
+            match &input.kind {
+                InputKind::Account(account) => {
+                    let is_optional = account.is_optional == Some(true) && input.default.is_none();
+                    let base = account_type(account, false);
+                    fields.push_str(&format!("    {field}: Option<{base}>,\n"));
+                    let (parameters, value) = match (is_optional, &account.is_signer) {
+                        (true, _) => (format!("{field}: Option<{base}>"), field.clone()),
+                        (false, IsSigner::Either) => (
+                            format!("{field}: Pubkey, as_signer: bool"),
+                            format!("Some(({field}, as_signer))"),
+                        ),
+                        (false, _) => (format!("{field}: Pubkey"), format!("Some({field})")),
+                    };
+                    setters.push(setter(&account.docs, &field, &parameters, &value));
+                }
+                InputKind::Argument {
+                    node,
+                    rust_type,
+                    is_data,
+                } => {
+                    if (!is_data && !referenced.contains(&input.reference)) || input.is_omitted() {
+                        continue;
+                    }
+                    fields.push_str(&format!("    {field}: Option<{rust_type}>,\n"));
+                    let parameters = format!("{field}: {rust_type}");
+                    let value = format!("Some({field})");
+                    setters.push(setter(&node.docs, &field, &parameters, &value));
+                }
+            }
+        }
+
+        let mut statements = String::new();
+        for input in order.iter().map(|index| &inputs[*index]) {
+            let field = snake_identifier(input.reference.name());
+            let local = &input.local;
+            let default = input.default.as_ref().map(|(expression, _)| Expression {
+                code: expression.code.clone(),
+                is_lazy: expression.is_lazy,
+            });
+            let value = match &input.kind {
+                InputKind::Account(account) => {
+                    let is_optional = account.is_optional == Some(true) && input.default.is_none();
+                    let default = default.map(|default| match account.is_signer {
+                        IsSigner::Either => Expression {
+                            code: format!("({}, false)", default.code),
+                            is_lazy: default.is_lazy,
+                        },
+                        _ => default,
+                    });
+                    match (is_optional, default) {
+                        (true, Some(default)) => match default.is_lazy {
+                            true => format!("self.{field}.or_else(|| Some({}))", default.code),
+                            false => format!("self.{field}.or(Some({}))", default.code),
+                        },
+                        (true, None) => format!("self.{field}"),
+                        (false, default) => unwrap(&format!("self.{field}"), &field, default),
+                    }
+                }
+                InputKind::Argument { node, is_data, .. } => {
+                    if !is_data && !referenced.contains(&input.reference) {
+                        continue;
+                    }
+                    if input.is_omitted() {
+                        default.map(|default| default.code).unwrap_or_default()
+                    } else {
+                        let option = match is_copy(&node.r#type) {
+                            true => format!("self.{field}"),
+                            false => format!("self.{field}.clone()"),
+                        };
+                        unwrap(&option, &field, default)
+                    }
+                }
+            };
+            statements.push_str(&format!("let {local} = {value};\n"));
+        }
+        setters.push(
+            "/// Adds an account to the end of the instruction accounts.\n\
+             #[inline(always)]\n\
+             pub fn add_remaining_account(&mut self, account: AccountMeta) -> &mut Self {\n    \
+                 self.__remaining_accounts.push(account);\n    \
+                 self\n\
+             }\n"
+            .to_string(),
+        );
+        setters.push(
+            "/// Adds accounts to the end of the instruction accounts.\n\
+             #[inline(always)]\n\
+             pub fn add_remaining_accounts(&mut self, accounts: &[AccountMeta]) -> &mut Self {\n    \
+                 self.__remaining_accounts.extend_from_slice(accounts);\n    \
+                 self\n\
+             }\n"
+                .to_string(),
+        );
+
+        let initializer = |kind: fn(&InputKind) -> bool| {
+            let fields = inputs
+                .iter()
+                .filter(|input| kind(&input.kind))
+                .map(|input| {
+                    let field = snake_identifier(input.reference.name());
+                    match field == input.local {
+                        true => format!("{field},\n"),
+                        false => format!("{field}: {},\n", input.local),
+                    }
+                })
+                .collect::<String>();
+            fields
+        };
+        let accounts = initializer(|kind| matches!(kind, InputKind::Account(_)));
+        let data = initializer(|kind| matches!(kind, InputKind::Argument { is_data: true, .. }));
+        setters.push(format!(
+            "/// Builds the instruction, resolving the default values of the inputs that are not set.\n\
+             ///\n\
+             /// Panics if a required input is not set.\n\
+             pub fn instruction(&self) -> Instruction {{\n\
+             {}    \
+                 {name} {{{}}}\n    \
+                 .instruction_with_remaining_accounts(\n        \
+                     {data_name} {{{}}},\n        \
+                     &self.__remaining_accounts,\n    \
+                 )\n\
+             }}\n",
+            indent(&statements, "    "),
+            indented_block(&accounts, "        ", "    "),
+            indented_block(&data, "            ", "        "),
+        ));
+        fields.push_str("    __remaining_accounts: Vec<AccountMeta>,\n");
+
+        let setters = setters
+            .iter()
+            .map(|setter| indent(setter, "    "))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{}#[derive(Clone, Debug, Default)]\n\
+             pub struct {builder_name} {{\n{fields}}}\n\
+             \n\
+             impl {builder_name} {{\n{setters}}}\n",
+            docs(&instruction.docs, ""),
+        )
+    }
+}
+
+fn find_input<'b, 'c>(inputs: &'c [Input<'b>], reference: &InputRef) -> Option<&'c Input<'b>> {
+    inputs.iter().find(|input| &input.reference == reference)
+}
+
+/// The name of the rendered constant holding the address of a program.
+pub(crate) fn program_constant(name: &CamelCaseString) -> String {
+    format!("{}_ID", to_screaming_snake_case(name))
+}
+
+fn account_type(account: &InstructionAccountNode, is_optional: bool) -> String {
+    let base = match account.is_signer {
+        IsSigner::Either => "(Pubkey, bool)",
+        _ => "Pubkey",
+    };
+    match is_optional {
+        true => format!("Option<{base}>"),
+        false => base.to_string(),
+    }
+}
+
+fn setter(docs_lines: &[String], field: &str, parameters: &str, value: &str) -> String {
+    format!(
+        "{}#[inline(always)]\n\
+         pub fn {field}(&mut self, {parameters}) -> &mut Self {{\n    \
+             self.{field} = {value};\n    \
+             self\n\
+         }}\n",
+        docs(docs_lines, ""),
+    )
+}
+
+/// Unwraps an optional builder field using its default value, or panics when it has none.
+fn unwrap(option: &str, field: &str, default: Option<Expression>) -> String {
+    match default {
+        Some(Expression {
+            code,
+            is_lazy: true,
+        }) => format!("{option}.unwrap_or_else(|| {code})"),
+        Some(Expression {
+            code,
+            is_lazy: false,
+        }) => format!("{option}.unwrap_or({code})"),
+        None => format!(
+            "{option}.expect({})",
+            string_literal(&format!("{} is not set", field.trim_start_matches("r#")))
+        ),
+    }
+}
+
+/// Wraps the given lines of a struct body, keeping empty bodies on a single line.
+fn block(lines: &str) -> String {
+    match lines.is_empty() {
+        true => String::new(),
+        false => format!("\n{lines}"),
+    }
+}
+
+/// Wraps the given fields of a struct expression at the given indentation.
+fn indented_block(fields: &str, indentation: &str, closing: &str) -> String {
+    match fields.is_empty() {
+        true => String::new(),
+        false => format!("\n{}{closing}", indent(fields, indentation)),
+    }
+}
@@ -0,0 +1,13 @@
+mod accounts;
+mod discriminators;
+mod errors;
+mod events;
+mod instructions;
+mod pdas;
+mod renderer;
+mod rust_client;
+mod types;
+mod utils;
+mod values;
+
+pub use rust_client::*;
@@ -0,0 +1,144 @@
+use crate::renderer::Renderer;
+use crate::types::number_type;
+use crate::utils::{bytes_literal, docs, snake_identifier};
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    encode, BytesEncoding, ConstantPdaSeedValue, Endianness, HasKind, NumberFormat, NumberTypeNode,
+    PdaNode, PdaSeedNode, TypeNode, ValueNode,
+};
+
+/// How a variable seed is passed to the rendered PDA function.
+pub(crate) enum SeedKind {
+    /// A `&Pubkey` parameter.
+    PublicKey,
+    /// A `&str` parameter.
+    String,
+    /// A `&[u8]` parameter.
+    Bytes,
+    /// A number parameter passed by value.
+    Number(NumberTypeNode),
+    /// A reference to any other type, serialized with Borsh.
+    Borsh,
+}
+
+impl SeedKind {
+    pub(crate) fn new(r#type: &TypeNode) -> Self {
+        match r#type {
+            TypeNode::PublicKey(_) => Self::PublicKey,
+            TypeNode::String(node) if node.encoding == BytesEncoding::Utf8 => Self::String,
+            TypeNode::Bytes(_) => Self::Bytes,
+            TypeNode::Number(node) if node.format != NumberFormat::ShortU16 => {
+                Self::Number(node.clone())
+            }
+            _ => Self::Borsh,
+        }
+    }
+}
+
+/// The name of the rendered function that finds the given PDA.
+pub(crate) fn pda_function(pda: &PdaNode) -> String {
+    format!("find_{}_pda", pda.name.to_snake_case())
+}
+
+impl<'a> Renderer<'a> {
+    /// Renders a function that finds the address and bump of a PDA from its variable seeds.
+    pub(crate) fn pda(&mut self, pda: &PdaNode, program_id: &str) -> CodamaResult<()> {
+        let function = pda_function(pda);
+        self.declare(&function);
+        self.import("solana_pubkey", "Pubkey");
+        let program_id = match &pda.program_id {
+            Some(address) => {
+                self.import("solana_pubkey", "pubkey");
+                format!("pubkey!({address:?})")
+            }
+            None => program_id.to_string(),
+        };
+
+        let position = self.item_position();
+        let mut parameters = vec![];
+        let mut seeds = vec![];
+        for seed in &pda.seeds {
+            match seed {
+                PdaSeedNode::Constant(seed) => match seed.value.as_ref() {
+                    ConstantPdaSeedValue::ProgramId(_) => {
+                        seeds.push(format!("{program_id}.as_ref()"));
+                    }
+                    value => {
+                        let bytes = ValueNode::try_from(value.clone())
+                            .and_then(|value| encode(&value, &seed.r#type, &self.resolver));
+                        match bytes {
+                            Ok(bytes) => seeds.push(seed_literal(&bytes)),
+                            Err(error) => self.report(format!(
+                                "`{}` seed could not be encoded and was ignored: {error}",
+                                value.kind()
+                            )),
+                        }
+                    }
+                },
+                PdaSeedNode::Variable(seed) => {
+                    let name = snake_identifier(&seed.name);
+                    let (parameter, bytes) = match SeedKind::new(&seed.r#type) {
+                        SeedKind::PublicKey => ("&Pubkey".to_string(), format!("{name}.as_ref()")),
+                        SeedKind::String => ("&str".to_string(), format!("{name}.as_bytes()")),
+                        SeedKind::Bytes => ("&[u8]".to_string(), name.clone()),
+                        SeedKind::Number(number) => {
+                            let bytes = match number.endian {
+                                Endianness::Le => format!("&{name}.to_le_bytes()"),
+                                Endianness::Be => format!("&{name}.to_be_bytes()"),
+                            };
+                            (number_type(number.format).to_string(), bytes)
+                        }
+                        SeedKind::Borsh => {
+                            let inline_name = format!(
+                                "{}{}",
+                                pda.name.to_pascal_case(),
+                                seed.name.to_pascal_case()
+                            );
+                            let rust_type = self.scoped(seed.kind(), &seed.name, |this| {
+                                this.r#type(&inline_name, &seed.r#type)
+                            })?;
+                            (
+                                format!("&{rust_type}"),
+                                format!("&borsh::to_vec({name}).unwrap()"),
+                            )
+                        }
+                    };
+                    parameters.push(format!("{name}: {parameter}"));
+                    seeds.push(bytes);
+                }
+            }
+        }
+
+        let seeds = match seeds.is_empty() {
+            true => "&[]".to_string(),
+            false => {
+                let seeds = seeds.iter().map(|seed| format!("            {seed},\n"));
+                format!("&[\n{}        ]", seeds.collect::<String>())
+            }
+        };
+        let item = format!(
+            "{}pub fn {function}({}) -> (Pubkey, u8) {{\n    \
+                 Pubkey::find_program_address(\n        \
+                     {seeds},\n        \
+                     &{program_id},\n    \
+                 )\n\
+             }}\n",
+            docs(&pda.docs, ""),
+            parameters.join(", "),
+        );
+        self.insert_item(position, item);
+        Ok(())
+    }
+}
+
+/// Renders constant seed bytes as a byte string when they are printable.
+fn seed_literal(bytes: &[u8]) -> String {
+    let is_printable = bytes
+        .iter()
+        .all(|byte| (b' '..=b'~').contains(byte) && !matches!(byte, b'"' | b'\\'));
+    match is_printable && !bytes.is_empty() {
+        true => format!("b\"{}\"", String::from_utf8_lossy(bytes)),
+        false => format!("&{}", bytes_literal(bytes)),
+    }
+}
@@ -0,0 +1,240 @@
+use crate::instructions::program_constant;
+use crate::renderer::{Renderer, RustFile};
+use crate::utils::identifier;
+use codama_errors::CodamaResult;
+use codama_export_issues::{ExportIssue, ExportIssueReporter};
+use codama_nodes::{HasKind, LinkResolver, ProgramNode, RootNode};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A Rust client crate rendered from a `RootNode`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RustClient {
+    /// The content of each rendered file, by path relative to the crate directory.
+    pub files: BTreeMap<String, String>,
+    /// The Codama constructs that could not be rendered,
+    /// or that were replaced by their closest Rust equivalent.
+    pub issues: Vec<RenderIssue>,
+}
+
+impl RustClient {
+    /// Writes the rendered files inside the given crate directory.
+    pub fn write<P: AsRef<Path>>(&self, directory: P) -> CodamaResult<()> {
+        for (path, content) in &self.files {
+            let path = directory.as_ref().join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+/// A Codama construct that could not be rendered as is.
+pub type RenderIssue = ExportIssue;
+
+/// Renders a Rust client crate for all programs of a `RootNode`.
+///
+/// The crate uses Borsh to encode defined types, accounts, instruction data and events,
+/// and derives bytemuck's `Pod` on structs whose memory layout matches their encoding.
+/// It also provides PDA helpers, instruction builders and an error enum per program.
+pub fn render_rust_client(root: &RootNode) -> CodamaResult<RustClient> {
+    let programs = std::iter::once(&root.program)
+        .chain(root.additional_programs.iter())
+        .collect::<Vec<_>>();
+    let mut renderer = Renderer::new(LinkResolver::new(root));
+    let mut modules = Modules::default();
+    for program in &programs {
+        renderer.resolver = LinkResolver::new(root).with_program(program);
+        renderer.scoped(program.kind(), &program.name, |this| {
+            this.program(program, &mut modules)
+        })?;
+    }
+
+    let mut files = BTreeMap::new();
+    let (_, file) = renderer.render_file(|this| {
+        this.import("solana_pubkey", "Pubkey");
+        for program in &programs {
+            let address = match program.public_key.is_empty() {
+                true => {
+                    this.scoped(program.kind(), &program.name, |this| {
+                        this.report(
+                            "the program has no public key, the default public key was used",
+                        )
+                    });
+                    "Pubkey::new_from_array([0; 32])".to_string()
+                }
+                false => {
+                    this.import("solana_pubkey", "pubkey");
+                    format!("pubkey!({:?})", program.public_key)
+                }
+            };
+            this.push_item(format!(
+                "pub const {}: Pubkey = {address};\n",
+                program_constant(&program.name)
+            ));
+        }
+    });
+    files.insert("src/programs.rs".to_string(), file.render());
+
+    let mut lib = RustFile::default();
+    let mut declarations = String::new();
+    for (module, files_by_name) in modules.0 {
+        if files_by_name.is_empty() {
+            continue;
+        }
+        declarations.push_str(&format!("pub mod {module};\n"));
+        let mut mod_file = RustFile::default();
+        let names = files_by_name
+            .iter()
+            .map(|(name, _)| identifier(name.clone()))
+            .collect::<Vec<_>>();
+        mod_file.push(names.iter().map(|name| format!("mod {name};\n")).collect());
+        mod_file.push(
+            names
+                .iter()
+                .map(|name| format!("pub use {name}::*;\n"))
+                .collect(),
+        );
+        files.insert(format!("src/{module}/mod.rs"), mod_file.render());
+        for (name, file) in files_by_name {
+            files.insert(format!("src/{module}/{name}.rs"), file.render());
+        }
+    }
+    declarations.push_str("pub mod programs;\n");
+    lib.push(declarations);
+    lib.push(format!(
+        "pub use programs::{} as ID;\n",
+        program_constant(&root.program.name)
+    ));
+    files.insert("src/lib.rs".to_string(), lib.render());
+    files.insert(
+        "Cargo.toml".to_string(),
+        cargo_toml(&root.program, &renderer),
+    );
+
+    Ok(RustClient {
+        files,
+        issues: renderer.issues,
+    })
+}
+
+/// The rendered files of each module of the crate, by file name.
+#[derive(Default)]
+struct Modules(BTreeMap<&'static str, Vec<(String, RustFile)>>);
+
+impl<'a> Renderer<'a> {
+    fn program(&mut self, program: &'a ProgramNode, modules: &mut Modules) -> CodamaResult<()> {
+        let program_id = program_constant(&program.name);
+        for defined_type in &program.defined_types {
+            let (result, file) = self.render_file(|this| {
+                this.scoped(defined_type.kind(), &defined_type.name, |this| {
+                    this.defined_type(defined_type)
+                })
+            });
+            result?;
+            self.add_file(modules, "types", defined_type.name.to_snake_case(), file);
+        }
+        for account in &program.accounts {
+            let (result, file) = self.render_file(|this| {
+                this.scoped(account.kind(), &account.name, |this| this.account(account))
+            });
+            result?;
+            self.add_file(modules, "accounts", account.name.to_snake_case(), file);
+        }
+        for pda in &program.pdas {
+            let (result, file) = self.render_file(|this| {
+                this.scoped(pda.kind(), &pda.name, |this| {
+                    if pda.program_id.is_none() {
+                        this.import("crate::programs", &program_id);
+                    }
+                    this.pda(pda, &program_id)
+                })
+            });
+            result?;
+            self.add_file(modules, "pdas", pda.name.to_snake_case(), file);
+        }
+        for instruction in &program.instructions {
+            let resolver = self.resolver.with_instruction(instruction);
+            let parent = std::mem::replace(&mut self.resolver, resolver);
+            let (result, file) = self.render_file(|this| {
+                this.scoped(instruction.kind(), &instruction.name, |this| {
+                    this.import("crate::programs", &program_id);
+                    this.instruction(instruction, &program_id)
+                })
+            });
+            self.resolver = parent;
+            result?;
+            self.add_file(
+                modules,
+                "instructions",
+                instruction.name.to_snake_case(),
+                file,
+            );
+        }
+        for event in &program.events {
+            let (result, file) = self.render_file(|this| {
+                this.scoped(event.kind(), &event.name, |this| this.event(event))
+            });
+            result?;
+            self.add_file(modules, "events", event.name.to_snake_case(), file);
+        }
+        if !program.errors.is_empty() {
+            let (_, file) = self.render_file(|this| this.program_errors(program));
+            self.add_file(modules, "errors", program.name.to_snake_case(), file);
+        }
+        Ok(())
+    }
+
+    /// Adds a rendered file to a module, unless another program already rendered it.
+    fn add_file(
+        &mut self,
+        modules: &mut Modules,
+        module: &'static str,
+        name: String,
+        file: RustFile,
+    ) {
+        let files = modules.0.entry(module).or_default();
+        if files.iter().any(|(existing, _)| *existing == name) {
+            self.report(format!(
+                "`{module}/{name}.rs` was already rendered by another node and was skipped"
+            ));
+            return;
+        }
+        files.push((name, file));
+    }
+}
+
+fn cargo_toml(program: &ProgramNode, renderer: &Renderer) -> String {
+    let mut dependencies = vec![r#"borsh = { version = "1.5", features = ["derive"] }"#];
+    if renderer.dependencies.contains("bytemuck") {
+        dependencies.push(
+            r#"bytemuck = { version = "1.16", features = ["derive", "min_const_generics"] }"#,
+        );
+    }
+    if renderer.dependencies.contains("solana_instruction") {
+        dependencies.push(r#"solana-instruction = "2.2""#);
+    }
+    dependencies.push(
+        r#"solana-pubkey = { version = "2.2", features = ["borsh", "bytemuck", "curve25519"] }"#,
+    );
+    if renderer.dependencies.contains("thiserror") {
+        dependencies.push(r#"thiserror = "2.0""#);
+    }
+    format!(
+        "[package]\n\
+         name = \"{}-client\"\n\
+         version = \"{}\"\n\
+         edition = \"2021\"\n\
+         \n\
+         [dependencies]\n\
+         {}\n",
+        program.name.to_kebab_case(),
+        match program.version.is_empty() {
+            true => "0.0.0",
+            false => &program.version,
+        },
+        dependencies.join("\n")
+    )
+}
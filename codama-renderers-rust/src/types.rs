@@ -0,0 +1,466 @@
+use crate::renderer::{derive, Renderer};
+use crate::utils::{docs, identifier, snake_identifier};
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    BytesEncoding, CountNode, DefinedTypeLinkNode, DefinedTypeNode, Endianness, EnumTypeNode,
+    EnumVariantTypeNode, HasKind, HasName, NestedTypeNode, NumberFormat, NumberTypeNode,
+    StringTypeNode, StructFieldTypeNode, TypeNode,
+};
+
+impl<'a> Renderer<'a> {
+    /// Renders a defined type as an item of its PascalCase name.
+    pub(crate) fn defined_type(&mut self, defined_type: &DefinedTypeNode) -> CodamaResult<()> {
+        let name = defined_type.name.to_pascal_case();
+        self.declare(&name);
+        let position = self.item_position();
+        let docs = docs(&defined_type.docs, "");
+        let item = match defined_type.r#type.as_ref() {
+            TypeNode::Struct(node) => self.struct_item(&name, &docs, &node.fields)?,
+            TypeNode::Enum(node) => self.enum_item(&name, &docs, node)?,
+            TypeNode::Tuple(node) => self.tuple_struct_item(&name, &docs, &node.items)?,
+            r#type => {
+                let rust_type = self.r#type(&name, r#type)?;
+                format!("{docs}pub type {name} = {rust_type};\n")
+            }
+        };
+        self.insert_item(position, item);
+        Ok(())
+    }
+
+    /// Renders a struct deriving Borsh, as well as bytemuck when its memory layout allows it.
+    pub(crate) fn struct_item(
+        &mut self,
+        name: &str,
+        docs: &str,
+        fields: &[StructFieldTypeNode],
+    ) -> CodamaResult<String> {
+        let fields_code = self.named_fields(name, fields, "    pub ")?;
+        let mut derives = self.borsh_derives();
+        let mut attributes = String::new();
+        if is_pod(fields) {
+            self.import("bytemuck", "Pod");
+            self.import("bytemuck", "Zeroable");
+            derives.insert(3, "Copy");
+            derives.extend(["Pod", "Zeroable"]);
+            attributes.push_str("#[repr(C)]\n");
+        }
+        let derives = derive(&derives);
+        Ok(match fields_code.is_empty() {
+            true => format!("{docs}{derives}{attributes}pub struct {name} {{}}\n"),
+            false => format!("{docs}{derives}{attributes}pub struct {name} {{\n{fields_code}}}\n"),
+        })
+    }
+
+    /// Renders a struct of unnamed fields deriving Borsh.
+    pub(crate) fn tuple_struct_item(
+        &mut self,
+        name: &str,
+        docs: &str,
+        items: &[TypeNode],
+    ) -> CodamaResult<String> {
+        let items = self.unnamed_fields(name, items, "pub ")?;
+        let derives = derive(&self.borsh_derives());
+        Ok(format!("{docs}{derives}pub struct {name}({items});\n"))
+    }
+
+    /// Renders an enum deriving Borsh, which only supports `u8` discriminators.
+    fn enum_item(&mut self, name: &str, docs: &str, node: &EnumTypeNode) -> CodamaResult<String> {
+        self.borsh_prefix(&node.size, NumberFormat::U8);
+        let is_scalar = node
+            .variants
+            .iter()
+            .all(|variant| matches!(variant, EnumVariantTypeNode::Empty(_)));
+        let has_discriminators = node
+            .variants
+            .iter()
+            .enumerate()
+            .any(|(index, variant)| variant.discriminator().is_some_and(|d| d != index as u32));
+        if has_discriminators && !is_scalar {
+            self.report(
+                "custom discriminators of enums with fields are not Borsh and were ignored",
+            );
+        }
+        let use_discriminant = has_discriminators && is_scalar;
+
+        let mut variants = String::new();
+        for (index, variant) in node.variants.iter().enumerate() {
+            let code = self.scoped(variant.kind(), variant.name(), |this| {
+                let variant_name = identifier(variant.name().to_pascal_case());
+                let inline_name = format!("{name}{}", variant.name().to_pascal_case());
+                let discriminator = variant.discriminator().unwrap_or(index as u32);
+                if discriminator > u8::MAX as u32 {
+                    this.report("discriminators that do not fit in a `u8` are not Borsh");
+                }
+                CodamaResult::Ok(match variant {
+                    EnumVariantTypeNode::Empty(_) if use_discriminant => {
+                        format!("    {variant_name} = {discriminator},\n")
+                    }
+                    EnumVariantTypeNode::Empty(_) => format!("    {variant_name},\n"),
+                    EnumVariantTypeNode::Struct(variant) => {
+                        let node = this.nested(&variant.r#struct);
+                        let fields = this.named_fields(&inline_name, &node.fields, "        ")?;
+                        match fields.is_empty() {
+                            true => format!("    {variant_name} {{}},\n"),
+                            false => format!("    {variant_name} {{\n{fields}    }},\n"),
+                        }
+                    }
+                    EnumVariantTypeNode::Tuple(variant) => {
+                        let node = this.nested(&variant.tuple);
+                        let items = this.unnamed_fields(&inline_name, &node.items, "")?;
+                        format!("    {variant_name}({items}),\n")
+                    }
+                })
+            })?;
+            variants.push_str(&code);
+        }
+
+        let mut derives = self.borsh_derives();
+        if is_scalar {
+            derives.insert(3, "Copy");
+            derives.insert(5, "Eq");
+        }
+        let derives = derive(&derives);
+        let attributes = match use_discriminant {
+            true => "#[borsh(use_discriminant = true)]\n",
+            false => "",
+        };
+        Ok(match variants.is_empty() {
+            true => format!("{docs}{derives}{attributes}pub enum {name} {{}}\n"),
+            false => format!("{docs}{derives}{attributes}pub enum {name} {{\n{variants}}}\n"),
+        })
+    }
+
+    /// Renders named fields on their own lines, where inline types are named `{Parent}{Field}`.
+    pub(crate) fn named_fields(
+        &mut self,
+        name: &str,
+        fields: &[StructFieldTypeNode],
+        prefix: &str,
+    ) -> CodamaResult<String> {
+        let indent = prefix.trim_end_matches("pub ");
+        fields
+            .iter()
+            .map(|field| {
+                self.scoped(field.kind(), &field.name, |this| {
+                    let inline_name = format!("{name}{}", field.name.to_pascal_case());
+                    let rust_type = this.r#type(&inline_name, &field.r#type)?;
+                    let docs = docs(&field.docs, indent);
+                    let field_name = snake_identifier(&field.name);
+                    Ok(format!("{docs}{prefix}{field_name}: {rust_type},\n"))
+                })
+            })
+            .collect()
+    }
+
+    /// Renders unnamed fields on a single line, where inline types are named `{Parent}{Index}`.
+    fn unnamed_fields(
+        &mut self,
+        name: &str,
+        items: &[TypeNode],
+        prefix: &str,
+    ) -> CodamaResult<String> {
+        let items = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let rust_type = self.r#type(&format!("{name}{index}"), item)?;
+                Ok(format!("{prefix}{rust_type}"))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(items.join(", "))
+    }
+
+    /// Maps a type to the Rust type with the same Borsh encoding, where `name` is used
+    /// to define inline structs and enums. Types that are not Borsh are reported and replaced.
+    pub(crate) fn r#type(&mut self, name: &str, r#type: &TypeNode) -> CodamaResult<String> {
+        Ok(match r#type {
+            TypeNode::Amount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::DateTime(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::SolAmount(node) => {
+                let number = self.nested(&node.number);
+                self.number(number)
+            }
+            TypeNode::Number(node) => self.number(node),
+            TypeNode::Boolean(node) => {
+                self.borsh_prefix(&node.size, NumberFormat::U8);
+                "bool".to_string()
+            }
+            TypeNode::PublicKey(_) => {
+                self.import("solana_pubkey", "Pubkey");
+                "Pubkey".to_string()
+            }
+            TypeNode::String(node) => {
+                self.report("strings without a `u32` prefix were rendered as prefixed strings");
+                string(node)
+            }
+            TypeNode::Bytes(_) => {
+                self.report("bytes without a `u32` prefix were rendered as prefixed bytes");
+                "Vec<u8>".to_string()
+            }
+            TypeNode::SizePrefix(node) => {
+                self.borsh_prefix(&node.prefix, NumberFormat::U32);
+                match node.r#type.as_ref() {
+                    TypeNode::String(string_node) => string(string_node),
+                    TypeNode::Bytes(_) => "Vec<u8>".to_string(),
+                    r#type => {
+                        self.report_kind(node.kind());
+                        self.r#type(name, r#type)?
+                    }
+                }
+            }
+            TypeNode::FixedSize(node) => match node.r#type.as_ref() {
+                TypeNode::String(_) | TypeNode::Bytes(_) => format!("[u8; {}]", node.size),
+                r#type => {
+                    self.report_kind(node.kind());
+                    self.r#type(name, r#type)?
+                }
+            },
+            TypeNode::Option(node) => {
+                let prefix = self.nested(&node.prefix);
+                if node.fixed == Some(true) || !prefix.is_le(NumberFormat::U8) {
+                    self.report("custom option prefixes were rendered as `Option`");
+                }
+                format!("Option<{}>", self.r#type(name, &node.item)?)
+            }
+            TypeNode::ZeroableOption(node) => {
+                self.report_kind_as_option(node.kind());
+                format!("Option<{}>", self.r#type(name, &node.item)?)
+            }
+            TypeNode::RemainderOption(node) => {
+                self.report_kind_as_option(node.kind());
+                format!("Option<{}>", self.r#type(name, &node.item)?)
+            }
+            TypeNode::Array(node) => {
+                let item = self.r#type(name, &node.item)?;
+                self.collection(&item, &node.count, format!("Vec<{item}>"))
+            }
+            // Sets are rendered as vectors since both share the same Borsh encoding.
+            TypeNode::Set(node) => {
+                let item = self.r#type(name, &node.item)?;
+                self.collection(&item, &node.count, format!("Vec<{item}>"))
+            }
+            TypeNode::Map(node) => {
+                let key = self.r#type(&format!("{name}Key"), &node.key)?;
+                let value = self.r#type(&format!("{name}Value"), &node.value)?;
+                let entry = format!("({key}, {value})");
+                let map = match is_ordered(&node.key) {
+                    true => {
+                        self.import("std::collections", "BTreeMap");
+                        format!("BTreeMap<{key}, {value}>")
+                    }
+                    false => format!("Vec<{entry}>"),
+                };
+                self.collection(&entry, &node.count, map)
+            }
+            TypeNode::Tuple(node) => match node.items.as_slice() {
+                [item] => format!("({},)", self.r#type(&format!("{name}0"), item)?),
+                items => format!("({})", self.unnamed_fields(name, items, "")?),
+            },
+            TypeNode::Struct(node) => {
+                let position = self.item_position();
+                let item = self.struct_item(name, "", &node.fields)?;
+                self.insert_item(position, item);
+                name.to_string()
+            }
+            TypeNode::Enum(node) => {
+                let position = self.item_position();
+                let item = self.enum_item(name, "", node)?;
+                self.insert_item(position, item);
+                name.to_string()
+            }
+            TypeNode::Link(node) => self.link(node)?,
+            TypeNode::Unknown(node) => {
+                self.report(format!(
+                    "`{}` is unknown and was rendered as `()`",
+                    node.kind
+                ));
+                "()".to_string()
+            }
+            TypeNode::HiddenPrefix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::HiddenSuffix(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::PreOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::PostOffset(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+            TypeNode::Sentinel(node) => {
+                self.report_kind(node.kind());
+                self.r#type(name, &node.r#type)?
+            }
+        })
+    }
+
+    /// Linked types are imported from the `types` module of the rendered crate.
+    fn link(&mut self, link: &DefinedTypeLinkNode) -> CodamaResult<String> {
+        let resolver = match &link.program {
+            Some(program) => self
+                .resolver
+                .with_program(self.resolver.resolve_program(program)?),
+            None => self.resolver,
+        };
+        resolver.resolve_defined_type(link)?;
+        let name = link.name.to_pascal_case();
+        self.import("crate::types", &name);
+        Ok(name)
+    }
+
+    fn collection(&mut self, item: &str, count: &CountNode, sequence: String) -> String {
+        match count {
+            CountNode::Fixed(count) => format!("[{item}; {}]", count.value),
+            CountNode::Prefixed(count) => {
+                self.borsh_prefix(&count.prefix, NumberFormat::U32);
+                sequence
+            }
+            CountNode::Remainder(_) => {
+                self.report(
+                    "remainder collections are not Borsh and were rendered with a `u32` prefix",
+                );
+                sequence
+            }
+        }
+    }
+
+    fn number(&mut self, node: &NumberTypeNode) -> String {
+        if node.endian == Endianness::Be {
+            self.report("big-endian numbers are not Borsh and were rendered as little-endian");
+        }
+        if node.format == NumberFormat::ShortU16 {
+            self.report("`shortU16` numbers are not Borsh and were rendered as `u16`");
+        }
+        number_type(node.format).to_string()
+    }
+
+    fn report_kind_as_option(&mut self, kind: &str) {
+        self.report(format!(
+            "`{kind}` is not Borsh and was rendered as `Option`"
+        ));
+    }
+}
+
+fn string(node: &StringTypeNode) -> String {
+    match node.encoding {
+        BytesEncoding::Utf8 => "String".to_string(),
+        _ => "Vec<u8>".to_string(),
+    }
+}
+
+pub(crate) fn number_type(format: NumberFormat) -> &'static str {
+    match format {
+        NumberFormat::U8 => "u8",
+        NumberFormat::U16 | NumberFormat::ShortU16 => "u16",
+        NumberFormat::U32 => "u32",
+        NumberFormat::U64 => "u64",
+        NumberFormat::U128 => "u128",
+        NumberFormat::I8 => "i8",
+        NumberFormat::I16 => "i16",
+        NumberFormat::I32 => "i32",
+        NumberFormat::I64 => "i64",
+        NumberFormat::I128 => "i128",
+        NumberFormat::F32 => "f32",
+        NumberFormat::F64 => "f64",
+    }
+}
+
+/// Whether the Rust type of the given type implements `Ord`, so it can key a `BTreeMap`.
+fn is_ordered(r#type: &TypeNode) -> bool {
+    match r#type {
+        TypeNode::Number(node) => !matches!(node.format, NumberFormat::F32 | NumberFormat::F64),
+        TypeNode::Amount(_) | TypeNode::DateTime(_) | TypeNode::SolAmount(_) => true,
+        TypeNode::Boolean(_) | TypeNode::PublicKey(_) | TypeNode::String(_) => true,
+        TypeNode::Bytes(_) | TypeNode::FixedSize(_) | TypeNode::SizePrefix(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether the Rust type of the given type implements `Copy`.
+pub(crate) fn is_copy(r#type: &TypeNode) -> bool {
+    match r#type {
+        TypeNode::Number(_) | TypeNode::Amount(_) | TypeNode::DateTime(_) => true,
+        TypeNode::SolAmount(_) | TypeNode::Boolean(_) | TypeNode::PublicKey(_) => true,
+        TypeNode::FixedSize(node) => matches!(
+            node.r#type.as_ref(),
+            TypeNode::String(_) | TypeNode::Bytes(_)
+        ),
+        TypeNode::Array(node) => {
+            matches!(node.count.as_ref(), CountNode::Fixed(_)) && is_copy(&node.item)
+        }
+        TypeNode::Option(node) => is_copy(&node.item),
+        TypeNode::Tuple(node) => node.items.iter().all(is_copy),
+        _ => false,
+    }
+}
+
+/// Whether a `#[repr(C)]` struct of the given fields has no padding and only holds
+/// plain old data, so it can derive bytemuck's `Pod` and be read from raw bytes.
+fn is_pod(fields: &[StructFieldTypeNode]) -> bool {
+    let mut size = 0;
+    let mut alignment = 1;
+    for field in fields {
+        let Some((field_size, field_alignment)) = pod_layout(&field.r#type) else {
+            return false;
+        };
+        if size % field_alignment != 0 {
+            return false;
+        }
+        size += field_size;
+        alignment = alignment.max(field_alignment);
+    }
+    !fields.is_empty() && size % alignment == 0
+}
+
+/// Returns the size and alignment of the Rust type of the given type when it is
+/// plain old data whose memory layout matches its encoding.
+fn pod_layout(r#type: &TypeNode) -> Option<(u64, u64)> {
+    let number = |node: &NumberTypeNode| {
+        if node.endian == Endianness::Be {
+            return None;
+        }
+        match node.format {
+            NumberFormat::U8 | NumberFormat::I8 => Some((1, 1)),
+            NumberFormat::U16 | NumberFormat::I16 => Some((2, 2)),
+            NumberFormat::U32 | NumberFormat::I32 | NumberFormat::F32 => Some((4, 4)),
+            NumberFormat::U64 | NumberFormat::I64 | NumberFormat::F64 => Some((8, 8)),
+            NumberFormat::U128 | NumberFormat::I128 => Some((16, 16)),
+            NumberFormat::ShortU16 => None,
+        }
+    };
+    let nested = |node: &NestedTypeNode<NumberTypeNode>| match node {
+        NestedTypeNode::Value(node) => number(node),
+        _ => None,
+    };
+    match r#type {
+        TypeNode::Number(node) => number(node),
+        TypeNode::Amount(node) => nested(&node.number),
+        TypeNode::DateTime(node) => nested(&node.number),
+        TypeNode::SolAmount(node) => nested(&node.number),
+        TypeNode::PublicKey(_) => Some((32, 1)),
+        TypeNode::FixedSize(node) => match node.r#type.as_ref() {
+            TypeNode::String(_) | TypeNode::Bytes(_) => Some((node.size as u64, 1)),
+            _ => None,
+        },
+        TypeNode::Array(node) => match node.count.as_ref() {
+            CountNode::Fixed(count) => {
+                let (size, alignment) = pod_layout(&node.item)?;
+                Some((size * count.value, alignment))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
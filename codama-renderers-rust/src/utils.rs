@@ -0,0 +1,65 @@
+use codama_nodes::CamelCaseString;
+
+/// Keywords that must be escaped to be used as Rust identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be used as raw identifiers.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+
+pub(crate) fn to_screaming_snake_case(name: &CamelCaseString) -> String {
+    name.to_snake_case().to_uppercase()
+}
+
+/// Escapes the given name so it can be used as a Rust identifier.
+pub(crate) fn identifier(name: String) -> String {
+    if RESERVED.contains(&name.as_str()) {
+        format!("{name}_")
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{name}")
+    } else {
+        name
+    }
+}
+
+/// The snake_case identifier of the given name, e.g. for fields, functions and modules.
+pub(crate) fn snake_identifier(name: &CamelCaseString) -> String {
+    identifier(name.to_snake_case())
+}
+
+/// Renders doc comments at the given indentation.
+pub(crate) fn docs(lines: &[String], indent: &str) -> String {
+    lines
+        .iter()
+        .map(|line| match line.is_empty() {
+            true => format!("{indent}///\n"),
+            false => format!("{indent}/// {line}\n"),
+        })
+        .collect()
+}
+
+/// Renders a list of bytes as an array literal.
+pub(crate) fn bytes_literal(bytes: &[u8]) -> String {
+    let bytes = bytes.iter().map(u8::to_string).collect::<Vec<_>>();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// Renders a string as a string literal.
+pub(crate) fn string_literal(value: &str) -> String {
+    format!("{value:?}")
+}
+
+/// Indents every non-empty line of the given code.
+pub(crate) fn indent(code: &str, indent: &str) -> String {
+    code.lines()
+        .map(|line| match line.is_empty() {
+            true => "\n".to_string(),
+            false => format!("{indent}{line}\n"),
+        })
+        .collect()
+}
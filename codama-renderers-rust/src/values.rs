@@ -0,0 +1,95 @@
+use crate::renderer::Renderer;
+use crate::utils::{bytes_literal, string_literal};
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{encode, BytesEncoding, Number, NumberFormat, TypeNode, ValueNode};
+
+/// A rendered Rust expression.
+pub(crate) struct Expression {
+    pub(crate) code: String,
+    /// Whether the expression should only be evaluated when needed, e.g. because it allocates.
+    pub(crate) is_lazy: bool,
+}
+
+impl Expression {
+    pub(crate) fn eager(code: String) -> Self {
+        Self {
+            code,
+            is_lazy: false,
+        }
+    }
+
+    pub(crate) fn lazy(code: String) -> Self {
+        Self {
+            code,
+            is_lazy: true,
+        }
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// Renders a value of the given type as a Rust expression. Values without a literal are
+    /// deserialized from their encoded bytes, and values that cannot be encoded are reported.
+    pub(crate) fn value(&mut self, value: &ValueNode, r#type: &TypeNode) -> Option<Expression> {
+        let bytes = match encode(value, r#type, &self.resolver) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                self.report(format!(
+                    "the value could not be encoded and was ignored: {error}"
+                ));
+                return None;
+            }
+        };
+        Some(match (value, r#type) {
+            (ValueNode::Boolean(node), TypeNode::Boolean(_)) => {
+                Expression::eager(node.boolean.to_string())
+            }
+            (ValueNode::Number(node), r#type) if is_number_literal(&node.number, r#type) => {
+                Expression::eager(number_literal(&node.number))
+            }
+            (ValueNode::PublicKey(node), TypeNode::PublicKey(_)) => {
+                self.import("solana_pubkey", "pubkey");
+                Expression::eager(format!("pubkey!({})", string_literal(&node.public_key)))
+            }
+            (ValueNode::String(node), r#type) if is_utf8_string(r#type) => {
+                Expression::lazy(format!("String::from({})", string_literal(&node.string)))
+            }
+            (_, TypeNode::FixedSize(node))
+                if matches!(
+                    node.r#type.as_ref(),
+                    TypeNode::Bytes(_) | TypeNode::String(_)
+                ) =>
+            {
+                Expression::eager(bytes_literal(&bytes))
+            }
+            _ => {
+                let bytes = bytes_literal(&bytes);
+                Expression::lazy(format!("borsh::from_slice(&{bytes}).unwrap()"))
+            }
+        })
+    }
+}
+
+fn number_literal(number: &Number) -> String {
+    match number {
+        Number::UnsignedInteger(value) => value.to_string(),
+        Number::SignedInteger(value) => value.to_string(),
+        Number::Float(value) => format!("{value:?}"),
+    }
+}
+
+/// Whether the number can be written as a literal of the Rust type of the given type.
+fn is_number_literal(number: &Number, r#type: &TypeNode) -> bool {
+    let Some(format) = r#type.number_format() else {
+        return false;
+    };
+    let is_float = matches!(format, NumberFormat::F32 | NumberFormat::F64);
+    matches!(number, Number::Float(_)) == is_float
+}
+
+fn is_utf8_string(r#type: &TypeNode) -> bool {
+    let is_utf8 = |r#type: &TypeNode| matches!(r#type, TypeNode::String(node) if node.encoding == BytesEncoding::Utf8);
+    match r#type {
+        TypeNode::SizePrefix(node) => is_utf8(&node.r#type),
+        r#type => is_utf8(r#type),
+    }
+}
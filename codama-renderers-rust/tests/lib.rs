@@ -0,0 +1 @@
+mod render_rust_client;
@@ -0,0 +1,483 @@
+use codama_nodes::{
+    AccountNode, AccountValueNode, ArgumentValueNode, ArrayTypeNode, BytesEncoding, BytesTypeNode,
+    BytesValueNode, ConstantDiscriminatorNode, ConstantPdaSeedNode, ConstantValueNode,
+    DefaultValueStrategy, DefinedTypeLinkNode, DefinedTypeNode, EnumEmptyVariantTypeNode,
+    EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EnumTypeNode, ErrorNode, EventNode,
+    FieldDiscriminatorNode, FixedSizeTypeNode, InstructionAccountNode, InstructionArgumentNode,
+    InstructionNode, IsSigner, MapTypeNode, NumberTypeNode, NumberValueNode, OptionTypeNode,
+    PayerValueNode, PdaLinkNode, PdaNode, PdaSeedValueNode, PdaValueNode, ProgramIdValueNode,
+    ProgramNode, PublicKeyTypeNode, PublicKeyValueNode, RootNode, SentinelTypeNode,
+    SizeDiscriminatorNode, SizePrefixTypeNode, StringTypeNode, StringValueNode,
+    StructFieldTypeNode, StructTypeNode, TupleTypeNode, VariablePdaSeedNode, I32, U16, U32, U64,
+    U8,
+};
+use codama_renderers_rust::{render_rust_client, RustClient};
+use pretty_assertions::assert_eq;
+
+fn get_root() -> RootNode {
+    let direction = EnumTypeNode::new(vec![
+        EnumEmptyVariantTypeNode::new("up").into(),
+        EnumEmptyVariantTypeNode::new("down").into(),
+    ]);
+    let counter = StructTypeNode::new(vec![
+        StructFieldTypeNode {
+            default_value: Box::new(Some(
+                BytesValueNode::new(BytesEncoding::Base16, "ffb004f5bcfd7c19").into(),
+            )),
+            default_value_strategy: Some(DefaultValueStrategy::Omitted),
+            ..StructFieldTypeNode::new(
+                "discriminator",
+                FixedSizeTypeNode::new(BytesTypeNode::new(), 8),
+            )
+        },
+        StructFieldTypeNode::new("authority", PublicKeyTypeNode::new()),
+        StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+        StructFieldTypeNode::new("direction", DefinedTypeLinkNode::new("direction")),
+    ]);
+    let increment = InstructionNode {
+        accounts: vec![
+            InstructionAccountNode {
+                default_value: Box::new(Some(
+                    PdaValueNode::new(
+                        PdaLinkNode::new("counter"),
+                        vec![PdaSeedValueNode::new(
+                            "authority",
+                            AccountValueNode::new("authority"),
+                        )],
+                    )
+                    .into(),
+                )),
+                ..InstructionAccountNode::new("counter", true, IsSigner::False)
+            },
+            InstructionAccountNode::new("authority", false, IsSigner::True),
+            InstructionAccountNode {
+                default_value: Box::new(Some(
+                    PublicKeyValueNode::new("11111111111111111111111111111111").into(),
+                )),
+                ..InstructionAccountNode::new("systemProgram", false, IsSigner::False)
+            },
+        ],
+        arguments: vec![
+            InstructionArgumentNode {
+                default_value: Box::new(Some(NumberValueNode::new(1u8).into())),
+                default_value_strategy: Some(DefaultValueStrategy::Omitted),
+                ..InstructionArgumentNode::new("discriminator", NumberTypeNode::le(U8))
+            },
+            InstructionArgumentNode {
+                default_value: Box::new(Some(NumberValueNode::new(1u64).into())),
+                ..InstructionArgumentNode::new("amount", NumberTypeNode::le(U64))
+            },
+        ],
+        name: "increment".into(),
+        ..InstructionNode::default()
+    };
+    let program = ProgramNode {
+        defined_types: vec![DefinedTypeNode::new("direction", direction)],
+        accounts: vec![AccountNode {
+            discriminators: vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+            ..AccountNode::new("counter", counter)
+        }],
+        pdas: vec![PdaNode::new(
+            "counter",
+            vec![
+                ConstantPdaSeedNode::new(StringTypeNode::utf8(), StringValueNode::new("counter"))
+                    .into(),
+                VariablePdaSeedNode::new("authority", PublicKeyTypeNode::new()).into(),
+            ],
+        )],
+        instructions: vec![increment],
+        events: vec![EventNode::new(
+            "incremented",
+            StructTypeNode::new(vec![
+                StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+                StructFieldTypeNode::new(
+                    "label",
+                    SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U32)),
+                ),
+            ]),
+        )],
+        errors: vec![ErrorNode::new("overflow", 6000, "The counter overflowed")],
+        ..ProgramNode::new("counter", "Counter111111111111111111111111111111111111")
+    };
+    RootNode::new(program)
+}
+
+fn get_vault_root() -> RootNode {
+    let config = StructTypeNode::new(vec![
+        StructFieldTypeNode::new("maxAmount", NumberTypeNode::le(U64)),
+        StructFieldTypeNode::new("authority", PublicKeyTypeNode::new()),
+        StructFieldTypeNode::new("seeds", ArrayTypeNode::fixed(NumberTypeNode::le(U16), 4)),
+    ]);
+    let action = EnumTypeNode::new(vec![
+        EnumEmptyVariantTypeNode::new("idle").into(),
+        EnumStructVariantTypeNode::new(
+            "deposit",
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "amount",
+                NumberTypeNode::le(U64),
+            )]),
+        )
+        .into(),
+        EnumTupleVariantTypeNode::new(
+            "swap",
+            TupleTypeNode::new(vec![
+                NumberTypeNode::le(U8).into(),
+                NumberTypeNode::le(I32).into(),
+            ]),
+        )
+        .into(),
+    ]);
+    let vault = StructTypeNode::new(vec![
+        StructFieldTypeNode::new("config", DefinedTypeLinkNode::new("config")),
+        StructFieldTypeNode::new("delegate", OptionTypeNode::new(PublicKeyTypeNode::new())),
+        StructFieldTypeNode::new(
+            "balances",
+            MapTypeNode::prefixed(
+                PublicKeyTypeNode::new(),
+                NumberTypeNode::le(U64),
+                NumberTypeNode::le(U32),
+            ),
+        ),
+        StructFieldTypeNode::new(
+            "history",
+            ArrayTypeNode::prefixed(DefinedTypeLinkNode::new("action"), NumberTypeNode::le(U32)),
+        ),
+        StructFieldTypeNode::new(
+            "label",
+            SentinelTypeNode::new(
+                StringTypeNode::utf8(),
+                ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+            ),
+        ),
+    ]);
+    let withdraw = InstructionNode {
+        name: "withdraw".into(),
+        discriminators: vec![ConstantDiscriminatorNode::new(
+            ConstantValueNode::new(NumberTypeNode::le(U8), NumberValueNode::new(7u8)),
+            0,
+        )
+        .into()],
+        accounts: vec![
+            InstructionAccountNode {
+                default_value: Box::new(Some(
+                    PdaValueNode::new(
+                        PdaLinkNode::new("vault"),
+                        vec![PdaSeedValueNode::new(
+                            "index",
+                            ArgumentValueNode::new("index"),
+                        )],
+                    )
+                    .into(),
+                )),
+                ..InstructionAccountNode::new("vault", true, IsSigner::False)
+            },
+            InstructionAccountNode::new("authority", false, IsSigner::Either),
+            InstructionAccountNode {
+                default_value: Box::new(Some(AccountValueNode::new("authority").into())),
+                ..InstructionAccountNode::new("destination", true, IsSigner::False)
+            },
+            InstructionAccountNode {
+                is_optional: Some(true),
+                ..InstructionAccountNode::new("referrer", false, IsSigner::False)
+            },
+            InstructionAccountNode {
+                default_value: Box::new(Some(PayerValueNode::new().into())),
+                ..InstructionAccountNode::new("payer", true, IsSigner::True)
+            },
+        ],
+        arguments: vec![
+            InstructionArgumentNode::new("action", DefinedTypeLinkNode::new("action")),
+            InstructionArgumentNode {
+                default_value: Box::new(Some(AccountValueNode::new("authority").into())),
+                ..InstructionArgumentNode::new("recipient", PublicKeyTypeNode::new())
+            },
+        ],
+        extra_arguments: vec![InstructionArgumentNode::new(
+            "index",
+            NumberTypeNode::le(U16),
+        )],
+        ..InstructionNode::default()
+    };
+    let program = ProgramNode {
+        defined_types: vec![
+            DefinedTypeNode::new("config", config),
+            DefinedTypeNode::new("action", action),
+        ],
+        accounts: vec![AccountNode {
+            discriminators: vec![SizeDiscriminatorNode::new(128).into()],
+            ..AccountNode::new("vault", vault)
+        }],
+        pdas: vec![PdaNode::new(
+            "vault",
+            vec![
+                ConstantPdaSeedNode::new(
+                    BytesTypeNode::new(),
+                    BytesValueNode::new(BytesEncoding::Base16, "00ff"),
+                )
+                .into(),
+                ConstantPdaSeedNode::new(PublicKeyTypeNode::new(), ProgramIdValueNode::new())
+                    .into(),
+                VariablePdaSeedNode::new("index", NumberTypeNode::be(U16)).into(),
+            ],
+        )],
+        instructions: vec![withdraw],
+        events: vec![EventNode {
+            discriminators: vec![ConstantDiscriminatorNode::new(
+                ConstantValueNode::bytes(BytesEncoding::Base16, "0102"),
+                0,
+            )
+            .into()],
+            ..EventNode::new(
+                "withdrawn",
+                TupleTypeNode::new(vec![
+                    FixedSizeTypeNode::new(BytesTypeNode::new(), 2).into(),
+                    NumberTypeNode::le(U64).into(),
+                ]),
+            )
+        }],
+        ..ProgramNode::new("vault", "Vau1t11111111111111111111111111111111111111")
+    };
+    RootNode::new(program)
+}
+
+fn get_issues(client: &RustClient) -> Vec<String> {
+    client.issues.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn render_files() {
+    let client = render_rust_client(&get_root()).unwrap();
+    assert_eq!(get_issues(&client), Vec::<String>::new());
+    assert_eq!(
+        client.files.keys().collect::<Vec<_>>(),
+        vec![
+            "Cargo.toml",
+            "src/accounts/counter.rs",
+            "src/accounts/mod.rs",
+            "src/errors/counter.rs",
+            "src/errors/mod.rs",
+            "src/events/incremented.rs",
+            "src/events/mod.rs",
+            "src/instructions/increment.rs",
+            "src/instructions/mod.rs",
+            "src/lib.rs",
+            "src/pdas/counter.rs",
+            "src/pdas/mod.rs",
+            "src/programs.rs",
+            "src/types/direction.rs",
+            "src/types/mod.rs",
+        ]
+    );
+    assert_eq!(
+        client.files["Cargo.toml"],
+        r#"[package]
+name = "counter-client"
+version = "0.0.0"
+edition = "2021"
+
+[dependencies]
+borsh = { version = "1.5", features = ["derive"] }
+solana-instruction = "2.2"
+solana-pubkey = { version = "2.2", features = ["borsh", "bytemuck", "curve25519"] }
+thiserror = "2.0"
+"#
+    );
+    assert_eq!(
+        client.files["src/lib.rs"],
+        "//! This code was AUTOGENERATED using the codama-rs library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun codama-rs to update it.
+
+pub mod accounts;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod pdas;
+pub mod types;
+pub mod programs;
+
+pub use programs::COUNTER_ID as ID;
+"
+    );
+    assert_eq!(
+        client.files["src/pdas/counter.rs"],
+        "//! This code was AUTOGENERATED using the codama-rs library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun codama-rs to update it.
+
+use crate::programs::COUNTER_ID;
+use solana_pubkey::Pubkey;
+
+pub fn find_counter_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b\"counter\",
+            authority.as_ref(),
+        ],
+        &COUNTER_ID,
+    )
+}
+"
+    );
+}
+
+#[test]
+fn render_account() {
+    let client = render_rust_client(&get_root()).unwrap();
+    let account = &client.files["src/accounts/counter.rs"];
+    assert!(account.contains(
+        "pub const COUNTER_DISCRIMINATOR: [u8; 8] = [255, 176, 4, 245, 188, 253, 124, 25];\n"
+    ));
+    assert!(account.contains(
+        "pub struct Counter {
+    pub discriminator: [u8; 8],
+    pub authority: Pubkey,
+    pub count: u64,
+    pub direction: Direction,
+}
+"
+    ));
+    assert!(account.contains("    pub const LEN: usize = 49;\n"));
+    assert!(account.contains("        data.get(0..8) == Some(&COUNTER_DISCRIMINATOR[..])\n"));
+}
+
+#[test]
+fn render_instruction_builder() {
+    let client = render_rust_client(&get_root()).unwrap();
+    let instruction = &client.files["src/instructions/increment.rs"];
+    assert!(instruction.contains(
+        "        accounts.push(AccountMeta::new(self.counter, false));
+        accounts.push(AccountMeta::new_readonly(self.authority, true));
+        accounts.push(AccountMeta::new_readonly(self.system_program, false));
+"
+    ));
+
+    // Defaults are resolved after the inputs they depend on.
+    assert!(instruction.contains(
+        "        let authority = self.authority.expect(\"authority is not set\");
+        let system_program = self.system_program.unwrap_or(pubkey!(\"11111111111111111111111111111111\"));
+        let discriminator = 1;
+        let amount = self.amount.unwrap_or(1);
+        let counter = self.counter.unwrap_or_else(|| find_counter_pda(&authority).0);
+"
+    ));
+
+    // Omitted arguments have no setter.
+    assert!(instruction.contains("    pub fn amount(&mut self, amount: u64) -> &mut Self {\n"));
+    assert!(!instruction.contains("pub fn discriminator("));
+}
+
+#[test]
+fn render_errors() {
+    let client = render_rust_client(&get_root()).unwrap();
+    let errors = &client.files["src/errors/counter.rs"];
+    assert!(errors.contains(
+        "pub enum CounterError {
+    /// 6000 - The counter overflowed
+    #[error(\"The counter overflowed\")]
+    Overflow = 6000,
+}
+"
+    ));
+    assert!(errors.contains("            6000 => Some(Self::Overflow),\n"));
+}
+
+#[test]
+fn render_types() {
+    let client = render_rust_client(&get_vault_root()).unwrap();
+    assert!(client.files["src/types/config.rs"].contains(
+        "#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Config {
+    pub max_amount: u64,
+    pub authority: Pubkey,
+    pub seeds: [u16; 4],
+}
+"
+    ));
+    assert!(client.files["src/types/action.rs"].contains(
+        "pub enum Action {
+    Idle,
+    Deposit {
+        amount: u64,
+    },
+    Swap(u8, i32),
+}
+"
+    ));
+    assert!(client.files["src/accounts/vault.rs"].contains(
+        "pub struct Vault {
+    pub config: Config,
+    pub delegate: Option<Pubkey>,
+    pub balances: BTreeMap<Pubkey, u64>,
+    pub history: Vec<Action>,
+    pub label: String,
+}
+"
+    ));
+    assert!(client.files["Cargo.toml"].contains("bytemuck = "));
+    assert!(!client.files["Cargo.toml"].contains("thiserror = "));
+}
+
+#[test]
+fn render_instruction_accounts() {
+    let client = render_rust_client(&get_vault_root()).unwrap();
+    let instruction = &client.files["src/instructions/withdraw.rs"];
+    assert!(instruction.contains(
+        "        accounts.push(AccountMeta::new_readonly(self.authority.0, self.authority.1));
+        accounts.push(AccountMeta::new(self.destination, false));
+        if let Some(referrer) = self.referrer {
+            accounts.push(AccountMeta::new_readonly(referrer, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(VAULT_ID, false));
+        }
+"
+    ));
+    assert!(instruction.contains(
+        "        let vault = self.vault.unwrap_or_else(|| find_vault_pda(index).0);
+        let destination = self.destination.unwrap_or(authority.0);
+"
+    ));
+    assert!(client.files["src/pdas/vault.rs"].contains(
+        "            &[0, 255],
+            VAULT_ID.as_ref(),
+            &index.to_be_bytes(),
+"
+    ));
+}
+
+#[test]
+fn render_unsupported_nodes() {
+    let client = render_rust_client(&get_vault_root()).unwrap();
+    assert_eq!(
+        get_issues(&client),
+        vec![
+            "[programNode]vault > [accountNode]vault > [structFieldTypeNode]label: `sentinelTypeNode` is not supported and was ignored",
+            "[programNode]vault > [accountNode]vault > [structFieldTypeNode]label: strings without a `u32` prefix were rendered as prefixed strings",
+            "[programNode]vault > [instructionNode]withdraw > [instructionAccountNode]payer: `payerValueNode` default values are not supported and must be provided",
+        ]
+    );
+}
+
+#[test]
+fn render_valid_rust_files() {
+    for root in [get_root(), get_vault_root()] {
+        let client = render_rust_client(&root).unwrap();
+        for (path, content) in &client.files {
+            if path.ends_with(".rs") {
+                assert!(syn::parse_file(content).is_ok(), "{path} is not valid Rust");
+            }
+        }
+    }
+}
+
+#[test]
+fn write_files() {
+    let client = render_rust_client(&get_root()).unwrap();
+    let directory = std::env::temp_dir().join("codama-renderers-rust-write-files");
+    let _ = std::fs::remove_dir_all(&directory);
+    client.write(&directory).unwrap();
+    let lib = std::fs::read_to_string(directory.join("src/lib.rs")).unwrap();
+    assert_eq!(lib, client.files["src/lib.rs"]);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,677 @@
+use crate::renderer::Renderer;
+use crate::types::is_bigint;
+use crate::utils::{access, bytes_literal, indent, indent_rest, property, string_literal};
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    encode, fixed_size, BytesEncoding, ConstantValueNode, CountNode, Endianness, EnumTypeNode,
+    EnumVariantTypeNode, HasKind, HasName, NestedTypeNode, NestedTypeNodeTrait, NumberFormat,
+    NumberTypeNode, TypeNode,
+};
+
+impl<'a> Renderer<'a> {
+    /// Renders the exported functions that write, read, encode and decode values of a named type.
+    pub(crate) fn codec_functions(
+        &mut self,
+        name: &str,
+        r#type: &TypeNode,
+    ) -> CodamaResult<String> {
+        self.locals = 0;
+        let write = self.write(r#type, "value", "writer")?;
+        let read = match r#type {
+            TypeNode::Enum(node) => self.read_enum(node, name)?,
+            r#type => self.read(r#type)?,
+        };
+        Ok(format!(
+            "export function write{name}(writer: Writer, value: {name}): void {{\n\
+             {}\
+             }}\n\
+             \n\
+             export function read{name}(reader: Reader): {name} {{\n\
+             \x20 return {};\n\
+             }}\n\
+             \n\
+             export function encode{name}(value: {name}): Uint8Array {{\n\
+             \x20 const writer: Writer = [];\n\
+             \x20 write{name}(writer, value);\n\
+             \x20 return new Uint8Array(writer);\n\
+             }}\n\
+             \n\
+             export function decode{name}(bytes: Uint8Array): {name} {{\n\
+             \x20 return read{name}({{ bytes, offset: 0 }});\n\
+             }}\n",
+            indent(&write, "  "),
+            indent_rest(&read, "  "),
+        ))
+    }
+
+    /// Renders the statements that write the given value to the given writer.
+    pub(crate) fn write(
+        &mut self,
+        r#type: &TypeNode,
+        value: &str,
+        writer: &str,
+    ) -> CodamaResult<String> {
+        Ok(match r#type {
+            TypeNode::Number(node) => self.write_number(node, value, writer),
+            TypeNode::Amount(node) => self.write_nested_number(&node.number, value, writer)?,
+            TypeNode::DateTime(node) => self.write_nested_number(&node.number, value, writer)?,
+            TypeNode::SolAmount(node) => self.write_nested_number(&node.number, value, writer)?,
+            TypeNode::Boolean(node) => {
+                self.write_nested_number(&node.size, &format!("{value} ? 1 : 0"), writer)?
+            }
+            TypeNode::PublicKey(_) => format!(
+                "{}({writer}, {}({value}));\n",
+                self.helper("writeBytes"),
+                self.helper("base58ToBytes")
+            ),
+            TypeNode::String(node) => format!(
+                "{}({writer}, {}({value}));\n",
+                self.helper("writeBytes"),
+                self.helper(to_bytes_helper(node.encoding))
+            ),
+            TypeNode::Bytes(_) => format!("{}({writer}, {value});\n", self.helper("writeBytes")),
+            TypeNode::FixedSize(node) => {
+                let bytes = self.local("bytes");
+                let inner = self.write(&node.r#type, value, &bytes)?;
+                format!(
+                    "{{\n  const {bytes}: Writer = [];\n{}  {}({writer}, {bytes}, {});\n}}\n",
+                    indent(&inner, "  "),
+                    self.helper("writeSized"),
+                    node.size
+                )
+            }
+            TypeNode::SizePrefix(node) => {
+                let bytes = self.local("bytes");
+                let inner = self.write(&node.r#type, value, &bytes)?;
+                let prefix =
+                    self.write_nested_number(&node.prefix, &format!("{bytes}.length"), writer)?;
+                format!(
+                    "{{\n  const {bytes}: Writer = [];\n{}{}  {}({writer}, {bytes});\n}}\n",
+                    indent(&inner, "  "),
+                    indent(&prefix, "  "),
+                    self.helper("writeBytes")
+                )
+            }
+            TypeNode::HiddenPrefix(node) => {
+                let prefix = self.constant_bytes(&node.prefix)?;
+                format!(
+                    "{}({writer}, {prefix});\n{}",
+                    self.helper("writeBytes"),
+                    self.write(&node.r#type, value, writer)?
+                )
+            }
+            TypeNode::HiddenSuffix(node) => {
+                let suffix = self.constant_bytes(&node.suffix)?;
+                format!(
+                    "{}{}({writer}, {suffix});\n",
+                    self.write(&node.r#type, value, writer)?,
+                    self.helper("writeBytes")
+                )
+            }
+            TypeNode::Sentinel(node) => {
+                let sentinel = self.constant_bytes(std::slice::from_ref(&node.sentinel))?;
+                format!(
+                    "{}{}({writer}, {sentinel});\n",
+                    self.write(&node.r#type, value, writer)?,
+                    self.helper("writeBytes")
+                )
+            }
+            TypeNode::PreOffset(node) => {
+                self.report_kind(r#type.kind());
+                self.write(&node.r#type, value, writer)?
+            }
+            TypeNode::PostOffset(node) => {
+                self.report_kind(r#type.kind());
+                self.write(&node.r#type, value, writer)?
+            }
+            TypeNode::Option(node) => {
+                let mut none = self.write_nested_number(&node.prefix, "0", writer)?;
+                if node.fixed == Some(true) {
+                    if let Some(size) = self.fixed_option_size(&node.item) {
+                        none.push_str(&format!(
+                            "{}({writer}, new Array({size}).fill(0));\n",
+                            self.helper("writeBytes")
+                        ));
+                    }
+                }
+                let some = format!(
+                    "{}{}",
+                    self.write_nested_number(&node.prefix, "1", writer)?,
+                    self.write(&node.item, value, writer)?
+                );
+                format!(
+                    "if ({value} === null) {{\n{}}} else {{\n{}}}\n",
+                    indent(&none, "  "),
+                    indent(&some, "  ")
+                )
+            }
+            TypeNode::ZeroableOption(node) => {
+                let zero = self.zero_value(&node.zero_value, &node.item)?;
+                let some = self.write(&node.item, value, writer)?;
+                format!(
+                    "if ({value} === null) {{\n  {}({writer}, {zero});\n}} else {{\n{}}}\n",
+                    self.helper("writeBytes"),
+                    indent(&some, "  ")
+                )
+            }
+            TypeNode::RemainderOption(node) => {
+                let some = self.write(&node.item, value, writer)?;
+                format!("if ({value} !== null) {{\n{}}}\n", indent(&some, "  "))
+            }
+            TypeNode::Array(node) => {
+                self.write_items(&node.item, &node.count, value, writer, "length")?
+            }
+            TypeNode::Set(node) => {
+                self.write_items(&node.item, &node.count, value, writer, "length")?
+            }
+            TypeNode::Map(node) => {
+                let count = self.write_count(&node.count, value, writer, "size")?;
+                let key = self.local("key");
+                let entry = self.local("value");
+                let items = format!(
+                    "{}{}",
+                    self.write(&node.key, &key, writer)?,
+                    self.write(&node.value, &entry, writer)?
+                );
+                format!(
+                    "{count}for (const [{key}, {entry}] of {value}) {{\n{}}}\n",
+                    indent(&items, "  ")
+                )
+            }
+            TypeNode::Tuple(node) => node
+                .items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| self.write(item, &format!("{value}[{index}]"), writer))
+                .collect::<CodamaResult<String>>()?,
+            TypeNode::Struct(node) => node
+                .fields
+                .iter()
+                .map(|field| {
+                    self.scoped(field.kind(), &field.name, |this| {
+                        this.write(&field.r#type, &access(value, &field.name), writer)
+                    })
+                })
+                .collect::<CodamaResult<String>>()?,
+            TypeNode::Enum(node) => self.write_enum(node, value, writer)?,
+            TypeNode::Link(node) => {
+                let name = format!("write{}", node.name.to_pascal_case());
+                if let Some(module) = self.link_module(node)? {
+                    self.import(&module, &name);
+                }
+                format!("{name}({writer}, {value});\n")
+            }
+            TypeNode::Unknown(node) => {
+                self.report_unknown(&node.kind);
+                String::new()
+            }
+        })
+    }
+
+    /// Renders an expression that reads a value of the given type from `reader`.
+    pub(crate) fn read(&mut self, r#type: &TypeNode) -> CodamaResult<String> {
+        Ok(match r#type {
+            TypeNode::Number(node) => self.read_number(node),
+            TypeNode::Amount(node) => self.read_nested_number(&node.number)?,
+            TypeNode::DateTime(node) => self.read_nested_number(&node.number)?,
+            TypeNode::SolAmount(node) => self.read_nested_number(&node.number)?,
+            TypeNode::Boolean(node) => {
+                let size = self.read_nested_number(&node.size)?;
+                format!("{}({size})", self.helper("readBoolean"))
+            }
+            TypeNode::PublicKey(_) => format!(
+                "{}({}(reader, 32))",
+                self.helper("bytesToBase58"),
+                self.helper("readBytes")
+            ),
+            TypeNode::String(node) => format!(
+                "{}({}(reader, {}(reader)))",
+                self.helper(from_bytes_helper(node.encoding)),
+                self.helper("readBytes"),
+                self.helper("remainingLength")
+            ),
+            TypeNode::Bytes(_) => format!(
+                "{}(reader, {}(reader))",
+                self.helper("readBytes"),
+                self.helper("remainingLength")
+            ),
+            TypeNode::FixedSize(node) => {
+                let inner = self.read(&node.r#type)?;
+                format!(
+                    "{}(reader, {}, (reader) => {})",
+                    self.helper("readSized"),
+                    node.size,
+                    inner
+                )
+            }
+            TypeNode::SizePrefix(node) => {
+                let size = self.read_length(&node.prefix)?;
+                let inner = self.read(&node.r#type)?;
+                format!(
+                    "{}(reader, {size}, (reader) => {inner})",
+                    self.helper("readSized")
+                )
+            }
+            TypeNode::HiddenPrefix(node) => {
+                let prefix = self.constant_bytes(&node.prefix)?;
+                let inner = self.read(&node.r#type)?;
+                format!("({}(reader, {prefix}), {inner})", self.helper("skipBytes"))
+            }
+            TypeNode::HiddenSuffix(node) => {
+                let suffix = self.constant_bytes(&node.suffix)?;
+                let inner = self.read(&node.r#type)?;
+                format!(
+                    "{}({inner}, {}(reader, {suffix}))",
+                    self.helper("first"),
+                    self.helper("skipBytes")
+                )
+            }
+            TypeNode::Sentinel(node) => {
+                let sentinel = self.constant_bytes(std::slice::from_ref(&node.sentinel))?;
+                let inner = self.read(&node.r#type)?;
+                format!(
+                    "{}(reader, {sentinel}, (reader) => {inner})",
+                    self.helper("readUntil")
+                )
+            }
+            TypeNode::PreOffset(node) => {
+                self.report_kind(r#type.kind());
+                self.read(&node.r#type)?
+            }
+            TypeNode::PostOffset(node) => {
+                self.report_kind(r#type.kind());
+                self.read(&node.r#type)?
+            }
+            TypeNode::Option(node) => {
+                let prefix = self.read_nested_number(&node.prefix)?;
+                let size = match node.fixed == Some(true) {
+                    true => self.fixed_option_size(&node.item),
+                    false => None,
+                };
+                let size = size.map_or("null".to_string(), |size| size.to_string());
+                let inner = self.read(&node.item)?;
+                format!(
+                    "{}(reader, {prefix}, {size}, () => {inner})",
+                    self.helper("readOption")
+                )
+            }
+            TypeNode::ZeroableOption(node) => {
+                let zero = self.zero_value(&node.zero_value, &node.item)?;
+                let inner = self.read(&node.item)?;
+                format!(
+                    "{}(reader, {zero}, () => {inner})",
+                    self.helper("readZeroable")
+                )
+            }
+            TypeNode::RemainderOption(node) => {
+                let inner = self.read(&node.item)?;
+                format!(
+                    "{}(reader) > 0 ? {inner} : null",
+                    self.helper("remainingLength")
+                )
+            }
+            TypeNode::Array(node) => {
+                let item = self.read(&node.item)?;
+                self.read_items(&node.count, "", &item)?
+            }
+            TypeNode::Set(node) => {
+                let item = self.read(&node.item)?;
+                self.read_items(&node.count, "", &item)?
+            }
+            TypeNode::Map(node) => {
+                let key_type = self.ts_type(&node.key)?;
+                let value_type = self.ts_type(&node.value)?;
+                let key = self.read(&node.key)?;
+                let value = self.read(&node.value)?;
+                let entries = self.read_items(
+                    &node.count,
+                    &format!(": [{key_type}, {value_type}]"),
+                    &format!("[{key}, {value}]"),
+                )?;
+                format!("new Map({entries})")
+            }
+            TypeNode::Tuple(node) => {
+                let ts_type = self.ts_type(r#type)?;
+                let items = node
+                    .items
+                    .iter()
+                    .map(|item| self.read(item))
+                    .collect::<CodamaResult<Vec<_>>>()?;
+                format!("[{}] as {ts_type}", items.join(", "))
+            }
+            TypeNode::Struct(node) => {
+                if node.fields.is_empty() {
+                    return Ok("{}".to_string());
+                }
+                let fields = node
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let read = self
+                            .scoped(field.kind(), &field.name, |this| this.read(&field.r#type))?;
+                        Ok(format!(
+                            "  {}: {},\n",
+                            property(&field.name),
+                            indent_rest(&read, "  ")
+                        ))
+                    })
+                    .collect::<CodamaResult<String>>()?;
+                format!("{{\n{fields}}}")
+            }
+            TypeNode::Enum(node) => {
+                let ts_type = self.ts_type(r#type)?;
+                self.read_enum(node, &ts_type)?
+            }
+            TypeNode::Link(node) => {
+                let name = format!("read{}", node.name.to_pascal_case());
+                if let Some(module) = self.link_module(node)? {
+                    self.import(&module, &name);
+                }
+                format!("{name}(reader)")
+            }
+            TypeNode::Unknown(node) => {
+                self.report_unknown(&node.kind);
+                "undefined".to_string()
+            }
+        })
+    }
+
+    fn write_number(&mut self, node: &NumberTypeNode, value: &str, writer: &str) -> String {
+        format!(
+            "{}({writer}, {}, {value}, {});\n",
+            self.helper("writeNumber"),
+            string_literal(number_format(node.format)),
+            node.endian == Endianness::Le
+        )
+    }
+
+    fn read_number(&mut self, node: &NumberTypeNode) -> String {
+        format!(
+            "{}(reader, {}, {})",
+            self.helper("readNumber"),
+            string_literal(number_format(node.format)),
+            node.endian == Endianness::Le
+        )
+    }
+
+    fn write_nested_number(
+        &mut self,
+        node: &NestedTypeNode<NumberTypeNode>,
+        value: &str,
+        writer: &str,
+    ) -> CodamaResult<String> {
+        self.write(&TypeNode::from(node.clone()), value, writer)
+    }
+
+    fn read_nested_number(
+        &mut self,
+        node: &NestedTypeNode<NumberTypeNode>,
+    ) -> CodamaResult<String> {
+        self.read(&TypeNode::from(node.clone()))
+    }
+
+    /// Reads a length prefix, which is always used as a `number`.
+    fn read_length(&mut self, node: &NestedTypeNode<NumberTypeNode>) -> CodamaResult<String> {
+        let length = self.read_nested_number(node)?;
+        Ok(match is_bigint(node.get_nested_type_node().format) {
+            true => format!("Number({length})"),
+            false => length,
+        })
+    }
+
+    /// Writes the number of items of a collection, or checks it for fixed counts.
+    fn write_count(
+        &mut self,
+        count: &CountNode,
+        value: &str,
+        writer: &str,
+        length: &str,
+    ) -> CodamaResult<String> {
+        Ok(match count {
+            CountNode::Fixed(count) => format!(
+                "if ({value}.{length} !== {0}) {{\n  throw new Error('Expected {0} items');\n}}\n",
+                count.value
+            ),
+            CountNode::Prefixed(count) => {
+                self.write_nested_number(&count.prefix, &format!("{value}.{length}"), writer)?
+            }
+            CountNode::Remainder(_) => String::new(),
+        })
+    }
+
+    fn write_items(
+        &mut self,
+        item: &TypeNode,
+        count: &CountNode,
+        value: &str,
+        writer: &str,
+        length: &str,
+    ) -> CodamaResult<String> {
+        let count = self.write_count(count, value, writer, length)?;
+        let local = self.local("item");
+        let item = self.write(item, &local, writer)?;
+        Ok(format!(
+            "{count}for (const {local} of {value}) {{\n{}}}\n",
+            indent(&item, "  ")
+        ))
+    }
+
+    /// Reads the items of a collection as an array, where `annotation` is
+    /// the return type annotation of the callback that reads each item.
+    fn read_items(
+        &mut self,
+        count: &CountNode,
+        annotation: &str,
+        item: &str,
+    ) -> CodamaResult<String> {
+        let length = match count {
+            CountNode::Fixed(count) => count.value.to_string(),
+            CountNode::Prefixed(count) => self.read_length(&count.prefix)?,
+            CountNode::Remainder(_) => {
+                return Ok(format!(
+                    "{}(reader, (){annotation} => {item})",
+                    self.helper("readRemainder")
+                ))
+            }
+        };
+        Ok(format!(
+            "Array.from({{ length: {length} }}, (){annotation} => {item})"
+        ))
+    }
+
+    /// Scalar enums are written from a lookup of their discriminators,
+    /// and data enums switch on their `__kind` to write their fields.
+    fn write_enum(
+        &mut self,
+        node: &EnumTypeNode,
+        value: &str,
+        writer: &str,
+    ) -> CodamaResult<String> {
+        if node.variants.is_empty() {
+            return Ok(String::new());
+        }
+        let is_scalar = node
+            .variants
+            .iter()
+            .all(|variant| matches!(variant, EnumVariantTypeNode::Empty(_)));
+        if is_scalar {
+            let discriminators = node
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    format!(
+                        "{}: {}",
+                        property(&variant.name().to_pascal_case()),
+                        discriminator(variant, index)
+                    )
+                })
+                .collect::<Vec<_>>();
+            let discriminator = format!("({{ {} }})[{value}]", discriminators.join(", "));
+            return self.write_nested_number(&node.size, &discriminator, writer);
+        }
+
+        let mut cases = String::new();
+        for (index, variant) in node.variants.iter().enumerate() {
+            let mut body = self.write_nested_number(
+                &node.size,
+                &discriminator(variant, index).to_string(),
+                writer,
+            )?;
+            match variant {
+                EnumVariantTypeNode::Empty(_) => {}
+                EnumVariantTypeNode::Struct(variant) => {
+                    let r#struct = TypeNode::from(variant.r#struct.clone());
+                    body.push_str(&self.write(&r#struct, value, writer)?);
+                }
+                EnumVariantTypeNode::Tuple(variant) => {
+                    let tuple = TypeNode::from(variant.tuple.clone());
+                    body.push_str(&self.write(&tuple, &format!("{value}.fields"), writer)?);
+                }
+            }
+            body.push_str("break;\n");
+            cases.push_str(&format!(
+                "case {}:\n{}",
+                string_literal(&variant.name().to_pascal_case()),
+                indent(&body, "  ")
+            ));
+        }
+        Ok(format!(
+            "switch ({value}.__kind) {{\n{}}}\n",
+            indent(&cases, "  ")
+        ))
+    }
+
+    /// Reads an enum by switching on its discriminator, inside a function that returns
+    /// the given type so that the kinds of its variants are not widened to `string`.
+    fn read_enum(&mut self, node: &EnumTypeNode, ts_type: &str) -> CodamaResult<String> {
+        let size = self.read_length(&node.size)?;
+        let is_scalar = node
+            .variants
+            .iter()
+            .all(|variant| matches!(variant, EnumVariantTypeNode::Empty(_)));
+        let mut cases = String::new();
+        for (index, variant) in node.variants.iter().enumerate() {
+            let kind = string_literal(&variant.name().to_pascal_case());
+            let value = match variant {
+                _ if is_scalar => kind,
+                EnumVariantTypeNode::Empty(_) => format!("{{ __kind: {kind} }}"),
+                EnumVariantTypeNode::Struct(variant) => {
+                    let r#struct = TypeNode::from(variant.r#struct.clone());
+                    let fields = self.read(&r#struct)?;
+                    match fields.strip_prefix("{\n") {
+                        Some(fields) => format!("{{\n  __kind: {kind},\n{fields}"),
+                        None => format!("{{ __kind: {kind} }}"),
+                    }
+                }
+                EnumVariantTypeNode::Tuple(variant) => {
+                    let tuple = TypeNode::from(variant.tuple.clone());
+                    let fields = self.read(&tuple)?;
+                    format!("{{ __kind: {kind}, fields: {fields} }}")
+                }
+            };
+            cases.push_str(&format!(
+                "case {}:\n  return {};\n",
+                discriminator(variant, index),
+                indent_rest(&value, "  ")
+            ));
+        }
+        cases.push_str("default:\n  throw new Error(`Invalid enum discriminator ${kind}`);\n");
+        Ok(format!(
+            "((kind: number): {} => {{\n  switch (kind) {{\n{}  }}\n}})({size})",
+            indent_rest(ts_type, "  "),
+            indent(&cases, "    ")
+        ))
+    }
+
+    /// Encodes constant values into an array literal of their bytes.
+    fn constant_bytes(&mut self, constants: &[ConstantValueNode]) -> CodamaResult<String> {
+        let mut bytes = vec![];
+        for constant in constants {
+            match encode(&constant.value, &constant.r#type, &self.resolver) {
+                Ok(encoded) => bytes.extend(encoded),
+                Err(error) => self.report(format!(
+                    "a constant could not be encoded and was ignored: {error}"
+                )),
+            }
+        }
+        Ok(bytes_literal(&bytes))
+    }
+
+    /// Returns the bytes of an absent zeroable option, which are zeroes by default.
+    fn zero_value(
+        &mut self,
+        zero_value: &Option<ConstantValueNode>,
+        item: &TypeNode,
+    ) -> CodamaResult<String> {
+        if let Some(zero_value) = zero_value {
+            return self.constant_bytes(std::slice::from_ref(zero_value));
+        }
+        match fixed_size(item, &self.resolver) {
+            Some(size) => Ok(bytes_literal(&vec![0; size])),
+            None => {
+                self.report(
+                    "zeroable options require items of a fixed size, no bytes were used for `null`",
+                );
+                Ok("[]".to_string())
+            }
+        }
+    }
+
+    fn fixed_option_size(&mut self, item: &TypeNode) -> Option<usize> {
+        let size = fixed_size(item, &self.resolver);
+        if size.is_none() {
+            self.report("fixed options require items of a fixed size, the option is not padded");
+        }
+        size
+    }
+
+    fn report_unknown(&mut self, kind: &str) {
+        self.report(format!(
+            "`{kind}` is unknown and cannot be encoded or decoded"
+        ));
+    }
+}
+
+fn discriminator(variant: &EnumVariantTypeNode, index: usize) -> usize {
+    variant
+        .discriminator()
+        .map_or(index, |discriminator| discriminator as usize)
+}
+
+fn number_format(format: NumberFormat) -> &'static str {
+    match format {
+        NumberFormat::U8 => "u8",
+        NumberFormat::U16 => "u16",
+        NumberFormat::U32 => "u32",
+        NumberFormat::U64 => "u64",
+        NumberFormat::U128 => "u128",
+        NumberFormat::I8 => "i8",
+        NumberFormat::I16 => "i16",
+        NumberFormat::I32 => "i32",
+        NumberFormat::I64 => "i64",
+        NumberFormat::I128 => "i128",
+        NumberFormat::F32 => "f32",
+        NumberFormat::F64 => "f64",
+        NumberFormat::ShortU16 => "shortU16",
+    }
+}
+
+fn to_bytes_helper(encoding: BytesEncoding) -> &'static str {
+    match encoding {
+        BytesEncoding::Base16 => "base16ToBytes",
+        BytesEncoding::Base58 => "base58ToBytes",
+        BytesEncoding::Base64 => "base64ToBytes",
+        BytesEncoding::Utf8 => "utf8ToBytes",
+    }
+}
+
+fn from_bytes_helper(encoding: BytesEncoding) -> &'static str {
+    match encoding {
+        BytesEncoding::Base16 => "bytesToBase16",
+        BytesEncoding::Base58 => "bytesToBase58",
+        BytesEncoding::Base64 => "bytesToBase64",
+        BytesEncoding::Utf8 => "bytesToUtf8",
+    }
+}
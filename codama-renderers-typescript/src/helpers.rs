@@ -0,0 +1,432 @@
+/// A function of the runtime rendered in files that include codecs.
+pub(crate) struct Helper {
+    pub(crate) name: &'static str,
+    /// The other helpers this helper calls.
+    pub(crate) dependencies: &'static [&'static str],
+    pub(crate) code: &'static str,
+}
+
+/// All helpers, in the order they are rendered.
+pub(crate) const HELPERS: &[Helper] = &[
+    Helper {
+        name: "writeBytes",
+        dependencies: &[],
+        code: "\
+function writeBytes(writer: Writer, bytes: ArrayLike<number>): void {
+  for (let index = 0; index < bytes.length; index++) {
+    writer.push(bytes[index]);
+  }
+}
+",
+    },
+    Helper {
+        name: "readBytes",
+        dependencies: &[],
+        code: "\
+function readBytes(reader: Reader, length: number): Uint8Array {
+  if (reader.offset + length > reader.bytes.length) {
+    throw new Error(`Expected ${length} bytes at offset ${reader.offset}`);
+  }
+  const bytes = reader.bytes.slice(reader.offset, reader.offset + length);
+  reader.offset += length;
+  return bytes;
+}
+",
+    },
+    Helper {
+        name: "remainingLength",
+        dependencies: &[],
+        code: "\
+function remainingLength(reader: Reader): number {
+  return reader.bytes.length - reader.offset;
+}
+",
+    },
+    Helper {
+        name: "writeNumber",
+        dependencies: &["writeBytes"],
+        code: "\
+function writeNumber(
+  writer: Writer,
+  format: NumberFormat | BigIntFormat,
+  value: number | bigint,
+  littleEndian: boolean,
+): void {
+  if (format === 'shortU16') {
+    let rest = Number(value);
+    do {
+      const byte = rest & 0x7f;
+      rest >>= 7;
+      writer.push(rest > 0 ? byte | 0x80 : byte);
+    } while (rest > 0);
+    return;
+  }
+  const bytes = new Uint8Array(NUMBER_SIZES[format]);
+  const view = new DataView(bytes.buffer);
+  switch (format) {
+    case 'u8':
+      view.setUint8(0, Number(value));
+      break;
+    case 'i8':
+      view.setInt8(0, Number(value));
+      break;
+    case 'u16':
+      view.setUint16(0, Number(value), littleEndian);
+      break;
+    case 'i16':
+      view.setInt16(0, Number(value), littleEndian);
+      break;
+    case 'u32':
+      view.setUint32(0, Number(value), littleEndian);
+      break;
+    case 'i32':
+      view.setInt32(0, Number(value), littleEndian);
+      break;
+    case 'f32':
+      view.setFloat32(0, Number(value), littleEndian);
+      break;
+    case 'f64':
+      view.setFloat64(0, Number(value), littleEndian);
+      break;
+    case 'u64':
+      view.setBigUint64(0, BigInt(value), littleEndian);
+      break;
+    case 'i64':
+      view.setBigInt64(0, BigInt(value), littleEndian);
+      break;
+    case 'u128':
+    case 'i128': {
+      const unsigned = BigInt.asUintN(128, BigInt(value));
+      const low = BigInt.asUintN(64, unsigned);
+      const high = unsigned >> 64n;
+      view.setBigUint64(littleEndian ? 0 : 8, low, littleEndian);
+      view.setBigUint64(littleEndian ? 8 : 0, high, littleEndian);
+      break;
+    }
+  }
+  writeBytes(writer, bytes);
+}
+",
+    },
+    Helper {
+        name: "readNumber",
+        dependencies: &["readBytes"],
+        code: "\
+function readNumber(reader: Reader, format: BigIntFormat, littleEndian: boolean): bigint;
+function readNumber(reader: Reader, format: NumberFormat, littleEndian: boolean): number;
+function readNumber(
+  reader: Reader,
+  format: NumberFormat | BigIntFormat,
+  littleEndian: boolean,
+): number | bigint {
+  if (format === 'shortU16') {
+    let value = 0;
+    for (let index = 0; index < 3; index++) {
+      const byte = readBytes(reader, 1)[0];
+      value |= (byte & 0x7f) << (index * 7);
+      if ((byte & 0x80) === 0) {
+        break;
+      }
+    }
+    return value;
+  }
+  const bytes = readBytes(reader, NUMBER_SIZES[format]);
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.length);
+  switch (format) {
+    case 'u8':
+      return view.getUint8(0);
+    case 'i8':
+      return view.getInt8(0);
+    case 'u16':
+      return view.getUint16(0, littleEndian);
+    case 'i16':
+      return view.getInt16(0, littleEndian);
+    case 'u32':
+      return view.getUint32(0, littleEndian);
+    case 'i32':
+      return view.getInt32(0, littleEndian);
+    case 'f32':
+      return view.getFloat32(0, littleEndian);
+    case 'f64':
+      return view.getFloat64(0, littleEndian);
+    case 'u64':
+      return view.getBigUint64(0, littleEndian);
+    case 'i64':
+      return view.getBigInt64(0, littleEndian);
+    case 'u128':
+    case 'i128': {
+      const low = view.getBigUint64(littleEndian ? 0 : 8, littleEndian);
+      const high = view.getBigUint64(littleEndian ? 8 : 0, littleEndian);
+      const unsigned = (high << 64n) | low;
+      return format === 'i128' ? BigInt.asIntN(128, unsigned) : unsigned;
+    }
+  }
+}
+",
+    },
+    Helper {
+        name: "readBoolean",
+        dependencies: &[],
+        code: "\
+function readBoolean(value: number | bigint): boolean {
+  if (Number(value) > 1) {
+    throw new Error(`Invalid boolean value ${value}`);
+  }
+  return Number(value) === 1;
+}
+",
+    },
+    Helper {
+        name: "writeSized",
+        dependencies: &["writeBytes"],
+        code: "\
+function writeSized(writer: Writer, bytes: Writer, size: number): void {
+  writeBytes(writer, bytes.slice(0, size));
+  for (let index = bytes.length; index < size; index++) {
+    writer.push(0);
+  }
+}
+",
+    },
+    Helper {
+        name: "readSized",
+        dependencies: &["readBytes"],
+        code: "\
+function readSized<T>(reader: Reader, size: number, read: (reader: Reader) => T): T {
+  return read({ bytes: readBytes(reader, size), offset: 0 });
+}
+",
+    },
+    Helper {
+        name: "readUntil",
+        dependencies: &["readBytes"],
+        code: "\
+function readUntil<T>(reader: Reader, sentinel: number[], read: (reader: Reader) => T): T {
+  for (let offset = reader.offset; offset + sentinel.length <= reader.bytes.length; offset++) {
+    if (sentinel.every((byte, index) => reader.bytes[offset + index] === byte)) {
+      const value = read({ bytes: readBytes(reader, offset - reader.offset), offset: 0 });
+      readBytes(reader, sentinel.length);
+      return value;
+    }
+  }
+  throw new Error('Could not find the sentinel bytes');
+}
+",
+    },
+    Helper {
+        name: "skipBytes",
+        dependencies: &["readBytes"],
+        code: "\
+function skipBytes(reader: Reader, expected: number[]): void {
+  const bytes = readBytes(reader, expected.length);
+  if (expected.some((byte, index) => bytes[index] !== byte)) {
+    throw new Error('Bytes do not match the expected constant');
+  }
+}
+",
+    },
+    Helper {
+        name: "first",
+        dependencies: &[],
+        code: "\
+function first<T>(value: T, ..._ignored: unknown[]): T {
+  return value;
+}
+",
+    },
+    Helper {
+        name: "readOption",
+        dependencies: &["readBytes"],
+        code: "\
+function readOption<T>(
+  reader: Reader,
+  prefix: number | bigint,
+  size: number | null,
+  read: () => T,
+): T | null {
+  if (Number(prefix) === 1) {
+    return read();
+  }
+  if (Number(prefix) !== 0) {
+    throw new Error(`Invalid option prefix ${prefix}`);
+  }
+  if (size !== null) {
+    readBytes(reader, size);
+  }
+  return null;
+}
+",
+    },
+    Helper {
+        name: "readZeroable",
+        dependencies: &["readBytes"],
+        code: "\
+function readZeroable<T>(reader: Reader, zero: number[], read: () => T): T | null {
+  if (zero.every((byte, index) => reader.bytes[reader.offset + index] === byte)) {
+    readBytes(reader, zero.length);
+    return null;
+  }
+  return read();
+}
+",
+    },
+    Helper {
+        name: "readRemainder",
+        dependencies: &["remainingLength"],
+        code: "\
+function readRemainder<T>(reader: Reader, read: () => T): Array<T> {
+  const items: Array<T> = [];
+  while (remainingLength(reader) > 0) {
+    items.push(read());
+  }
+  return items;
+}
+",
+    },
+    Helper {
+        name: "utf8ToBytes",
+        dependencies: &[],
+        code: "\
+function utf8ToBytes(value: string): Uint8Array {
+  return new TextEncoder().encode(value);
+}
+",
+    },
+    Helper {
+        name: "bytesToUtf8",
+        dependencies: &[],
+        code: "\
+function bytesToUtf8(bytes: Uint8Array): string {
+  return new TextDecoder().decode(bytes);
+}
+",
+    },
+    Helper {
+        name: "base16ToBytes",
+        dependencies: &[],
+        code: "\
+function base16ToBytes(value: string): Uint8Array {
+  const bytes = new Uint8Array(Math.ceil(value.length / 2));
+  for (let index = 0; index < bytes.length; index++) {
+    bytes[index] = parseInt(value.slice(index * 2, index * 2 + 2), 16);
+  }
+  return bytes;
+}
+",
+    },
+    Helper {
+        name: "bytesToBase16",
+        dependencies: &[],
+        code: "\
+function bytesToBase16(bytes: Uint8Array): string {
+  return Array.from(bytes, (byte) => byte.toString(16).padStart(2, '0')).join('');
+}
+",
+    },
+    Helper {
+        name: "base58ToBytes",
+        dependencies: &[],
+        code: "\
+function base58ToBytes(value: string): Uint8Array {
+  const bytes: number[] = [];
+  for (const char of value) {
+    let carry = BASE58_ALPHABET.indexOf(char);
+    if (carry < 0) {
+      throw new Error(`Invalid base58 character ${char}`);
+    }
+    for (let index = 0; index < bytes.length; index++) {
+      carry += bytes[index] * 58;
+      bytes[index] = carry & 0xff;
+      carry >>= 8;
+    }
+    while (carry > 0) {
+      bytes.push(carry & 0xff);
+      carry >>= 8;
+    }
+  }
+  for (const char of value) {
+    if (char !== '1') {
+      break;
+    }
+    bytes.push(0);
+  }
+  return new Uint8Array(bytes.reverse());
+}
+",
+    },
+    Helper {
+        name: "bytesToBase58",
+        dependencies: &[],
+        code: "\
+function bytesToBase58(bytes: Uint8Array): string {
+  const digits: number[] = [];
+  for (const byte of bytes) {
+    let carry = byte;
+    for (let index = 0; index < digits.length; index++) {
+      carry += digits[index] << 8;
+      digits[index] = carry % 58;
+      carry = Math.floor(carry / 58);
+    }
+    while (carry > 0) {
+      digits.push(carry % 58);
+      carry = Math.floor(carry / 58);
+    }
+  }
+  let result = '';
+  for (const byte of bytes) {
+    if (byte !== 0) {
+      break;
+    }
+    result += '1';
+  }
+  return result + digits.reverse().map((digit) => BASE58_ALPHABET[digit]).join('');
+}
+",
+    },
+    Helper {
+        name: "base64ToBytes",
+        dependencies: &[],
+        code: "\
+function base64ToBytes(value: string): Uint8Array {
+  return Uint8Array.from(atob(value), (char) => char.charCodeAt(0));
+}
+",
+    },
+    Helper {
+        name: "bytesToBase64",
+        dependencies: &[],
+        code: "\
+function bytesToBase64(bytes: Uint8Array): string {
+  return btoa(String.fromCharCode(...bytes));
+}
+",
+    },
+];
+
+/// The constants and types used by the helpers, rendered when any of them is used.
+pub(crate) const NUMBER_DECLARATIONS: &str = "\
+type NumberFormat = 'u8' | 'u16' | 'u32' | 'i8' | 'i16' | 'i32' | 'f32' | 'f64' | 'shortU16';
+type BigIntFormat = 'u64' | 'u128' | 'i64' | 'i128';
+
+const NUMBER_SIZES = {
+  u8: 1,
+  u16: 2,
+  u32: 4,
+  u64: 8,
+  u128: 16,
+  i8: 1,
+  i16: 2,
+  i32: 4,
+  i64: 8,
+  i128: 16,
+  f32: 4,
+  f64: 8,
+} as const;
+";
+
+pub(crate) const BASE58_ALPHABET: &str =
+    "const BASE58_ALPHABET = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';\n";
+
+pub(crate) fn find_helper(name: &str) -> Option<&'static Helper> {
+    HELPERS.iter().find(|helper| helper.name == name)
+}
@@ -0,0 +1,9 @@
+mod codecs;
+mod helpers;
+mod renderer;
+mod types;
+mod typescript_types;
+mod utils;
+mod values;
+
+pub use typescript_types::*;
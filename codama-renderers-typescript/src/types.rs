@@ -0,0 +1,269 @@
+use crate::renderer::Renderer;
+use crate::utils::{docs, indent, indent_rest, property, string_literal};
+use codama_errors::CodamaResult;
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{
+    DefinedTypeLinkNode, EnumTypeNode, EnumVariantTypeNode, HasKind, HasName, NestedTypeNodeTrait,
+    NumberFormat, StructFieldTypeNode, TypeNode,
+};
+
+/// A member of a rendered object type.
+pub(crate) struct Member {
+    pub(crate) docs: String,
+    pub(crate) name: String,
+    pub(crate) r#type: String,
+    pub(crate) is_optional: bool,
+}
+
+impl Member {
+    pub(crate) fn new(name: String, r#type: String) -> Self {
+        Self {
+            docs: String::new(),
+            name,
+            r#type,
+            is_optional: false,
+        }
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// Renders an exported type alias, along with its codecs when they are enabled.
+    pub(crate) fn named_type(
+        &mut self,
+        name: &str,
+        lines: &[String],
+        r#type: &TypeNode,
+    ) -> CodamaResult<()> {
+        if !self.declare(name) {
+            self.report(format!(
+                "`{name}` was already rendered by another node and was skipped"
+            ));
+            return Ok(());
+        }
+        let ts_type = self.ts_type(r#type)?;
+        self.push_item(format!(
+            "{}{};\n",
+            docs(lines, ""),
+            assign(&format!("export type {name} ="), &ts_type)
+        ));
+        if self.options.codecs {
+            let codecs = self.codec_functions(name, r#type)?;
+            self.push_item(codecs);
+        }
+        Ok(())
+    }
+
+    /// Maps a type to the TypeScript type of its values.
+    /// Wrappers that only affect how values are encoded are transparent.
+    pub(crate) fn ts_type(&mut self, r#type: &TypeNode) -> CodamaResult<String> {
+        Ok(match r#type {
+            TypeNode::Number(node) => number_type(node.format).to_string(),
+            TypeNode::Amount(node) => number_type(node.number.get_nested_type_node().format).into(),
+            TypeNode::DateTime(node) => {
+                number_type(node.number.get_nested_type_node().format).into()
+            }
+            TypeNode::SolAmount(node) => {
+                number_type(node.number.get_nested_type_node().format).into()
+            }
+            TypeNode::Boolean(_) => "boolean".to_string(),
+            TypeNode::PublicKey(_) => {
+                self.use_address();
+                "Address".to_string()
+            }
+            TypeNode::String(_) => "string".to_string(),
+            TypeNode::Bytes(_) => "Uint8Array".to_string(),
+            TypeNode::FixedSize(node) => self.ts_type(&node.r#type)?,
+            TypeNode::SizePrefix(node) => self.ts_type(&node.r#type)?,
+            TypeNode::HiddenPrefix(node) => self.ts_type(&node.r#type)?,
+            TypeNode::HiddenSuffix(node) => self.ts_type(&node.r#type)?,
+            TypeNode::PreOffset(node) => self.ts_type(&node.r#type)?,
+            TypeNode::PostOffset(node) => self.ts_type(&node.r#type)?,
+            TypeNode::Sentinel(node) => self.ts_type(&node.r#type)?,
+            TypeNode::Option(node) => format!("{} | null", self.ts_type(&node.item)?),
+            TypeNode::ZeroableOption(node) => format!("{} | null", self.ts_type(&node.item)?),
+            TypeNode::RemainderOption(node) => format!("{} | null", self.ts_type(&node.item)?),
+            TypeNode::Array(node) => format!("Array<{}>", self.ts_type(&node.item)?),
+            TypeNode::Set(node) => format!("Array<{}>", self.ts_type(&node.item)?),
+            TypeNode::Map(node) => format!(
+                "Map<{}, {}>",
+                self.ts_type(&node.key)?,
+                self.ts_type(&node.value)?
+            ),
+            TypeNode::Tuple(node) => {
+                let items = node
+                    .items
+                    .iter()
+                    .map(|item| self.ts_type(item))
+                    .collect::<CodamaResult<Vec<_>>>()?;
+                format!("[{}]", items.join(", "))
+            }
+            TypeNode::Struct(node) => {
+                let members = self.members(&node.fields)?;
+                object_type(&members, false)
+            }
+            TypeNode::Enum(node) => self.enum_type(node)?,
+            TypeNode::Link(node) => self.link(node)?,
+            TypeNode::Unknown(node) => {
+                self.report(format!(
+                    "`{}` is unknown and was rendered as `unknown`",
+                    node.kind
+                ));
+                "unknown".to_string()
+            }
+        })
+    }
+
+    pub(crate) fn members(&mut self, fields: &[StructFieldTypeNode]) -> CodamaResult<Vec<Member>> {
+        fields
+            .iter()
+            .map(|field| {
+                let r#type = self.scoped(field.kind(), &field.name, |this| {
+                    this.ts_type(&field.r#type)
+                })?;
+                Ok(Member {
+                    docs: docs(&field.docs, ""),
+                    ..Member::new(field.name.to_string(), r#type)
+                })
+            })
+            .collect()
+    }
+
+    /// Scalar enums are unions of their variant names, and data enums are unions
+    /// of objects discriminated by their `__kind`, where tuple variants use `fields`.
+    fn enum_type(&mut self, node: &EnumTypeNode) -> CodamaResult<String> {
+        if node.variants.is_empty() {
+            return Ok("never".to_string());
+        }
+        let is_scalar = node
+            .variants
+            .iter()
+            .all(|variant| matches!(variant, EnumVariantTypeNode::Empty(_)));
+        let variants = node
+            .variants
+            .iter()
+            .map(|variant| {
+                let kind = string_literal(&variant.name().to_pascal_case());
+                if is_scalar {
+                    return Ok(kind);
+                }
+                let mut members = vec![Member::new("__kind".to_string(), kind)];
+                match variant {
+                    EnumVariantTypeNode::Empty(_) => {}
+                    EnumVariantTypeNode::Struct(variant) => {
+                        let node = variant.r#struct.get_nested_type_node();
+                        members.extend(self.members(&node.fields)?);
+                    }
+                    EnumVariantTypeNode::Tuple(variant) => {
+                        let tuple = TypeNode::from(variant.tuple.clone());
+                        members.push(Member::new("fields".to_string(), self.ts_type(&tuple)?));
+                    }
+                }
+                Ok(object_type(&members, true))
+            })
+            .collect::<CodamaResult<Vec<_>>>()?;
+        Ok(union(&variants))
+    }
+
+    /// Types of other programs are imported from their rendered file.
+    fn link(&mut self, link: &DefinedTypeLinkNode) -> CodamaResult<String> {
+        let name = link.name.to_pascal_case();
+        if let Some(module) = self.link_module(link)? {
+            self.import(&module, &format!("type {name}"));
+        }
+        Ok(name)
+    }
+
+    /// Resolves a linked type and returns the module it must be imported
+    /// from, if it is defined by another program than the current one.
+    pub(crate) fn link_module(
+        &mut self,
+        link: &DefinedTypeLinkNode,
+    ) -> CodamaResult<Option<String>> {
+        let Some(program_link) = &link.program else {
+            self.resolver.resolve_defined_type(link)?;
+            return Ok(None);
+        };
+        let program = self.resolver.resolve_program(program_link)?;
+        self.resolver
+            .with_program(program)
+            .resolve_defined_type(link)?;
+        match self.resolver.program() {
+            Some(current) if current.name == program.name => Ok(None),
+            _ => Ok(Some(format!("./{}", program.name.to_kebab_case()))),
+        }
+    }
+}
+
+/// Renders an object type, on a single line when requested and possible.
+pub(crate) fn object_type(members: &[Member], prefer_inline: bool) -> String {
+    if members.is_empty() {
+        return "{}".to_string();
+    }
+    let rendered = members
+        .iter()
+        .map(|member| {
+            let optional = if member.is_optional { "?" } else { "" };
+            assign(
+                &format!("{}{optional}:", property(&member.name)),
+                &member.r#type,
+            )
+        })
+        .collect::<Vec<_>>();
+    let inline = format!("{{ {} }}", rendered.join("; "));
+    let is_inline = prefer_inline
+        && inline.len() <= 80
+        && !inline.contains('\n')
+        && members.iter().all(|member| member.docs.is_empty());
+    if is_inline {
+        return inline;
+    }
+    let members = members
+        .iter()
+        .zip(rendered)
+        .map(|(member, rendered)| {
+            format!(
+                "{}  {};\n",
+                indent(&member.docs, "  "),
+                indent_rest(&rendered, "  ")
+            )
+        })
+        .collect::<String>();
+    format!("{{\n{members}}}")
+}
+
+/// Renders a union on a single line when it is short enough,
+/// or with one member per line otherwise.
+fn union(members: &[String]) -> String {
+    let inline = members.join(" | ");
+    if inline.len() <= 80 && !inline.contains('\n') {
+        return inline;
+    }
+    members
+        .iter()
+        .map(|member| format!("\n  | {}", indent_rest(member, "    ")))
+        .collect()
+}
+
+/// Renders a type after the given prefix, e.g. `export type Name =` or `field:`,
+/// where types that start on a new line, such as long unions, are not preceded by a space.
+pub(crate) fn assign(prefix: &str, ts_type: &str) -> String {
+    match ts_type.starts_with('\n') {
+        true => format!("{prefix}{ts_type}"),
+        false => format!("{prefix} {ts_type}"),
+    }
+}
+
+pub(crate) fn number_type(format: NumberFormat) -> &'static str {
+    match is_bigint(format) {
+        true => "bigint",
+        false => "number",
+    }
+}
+
+/// Whether numbers of the given format are represented as a `bigint`.
+pub(crate) fn is_bigint(format: NumberFormat) -> bool {
+    matches!(
+        format,
+        NumberFormat::U64 | NumberFormat::U128 | NumberFormat::I64 | NumberFormat::I128
+    )
+}
@@ -0,0 +1,220 @@
+use crate::renderer::Renderer;
+use crate::types::{object_type, Member};
+use crate::utils::{access, docs, indent_rest, property};
+use codama_errors::CodamaResult;
+use codama_export_issues::{ExportIssue, ExportIssueReporter};
+use codama_nodes::{
+    DefaultValueStrategy, HasKind, InstructionNode, LinkResolver, ProgramNode, RootNode,
+    StructFieldTypeNode, StructTypeNode, TypeNode, ValueNode,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Options of the TypeScript renderer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TypeScriptOptions {
+    /// Whether to also render plain encoder and decoder functions for every type,
+    /// in which case `.ts` files are rendered instead of `.d.ts` declaration files.
+    pub codecs: bool,
+}
+
+/// TypeScript files rendered from a `RootNode`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeScriptTypes {
+    /// The content of each rendered file, by path relative to the output directory.
+    pub files: BTreeMap<String, String>,
+    /// The Codama constructs that could not be rendered,
+    /// or that were replaced by their closest TypeScript equivalent.
+    pub issues: Vec<RenderIssue>,
+}
+
+impl TypeScriptTypes {
+    /// Writes the rendered files inside the given directory.
+    pub fn write<P: AsRef<Path>>(&self, directory: P) -> CodamaResult<()> {
+        for (path, content) in &self.files {
+            let path = directory.as_ref().join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+/// A Codama construct that could not be rendered as is.
+pub type RenderIssue = ExportIssue;
+
+/// Renders TypeScript types for all programs of a `RootNode`, with one file per program.
+///
+/// Every defined type, account, instruction argument struct and event is rendered as an
+/// exported type. Instructions whose arguments have default values also get an `Args`
+/// type where these arguments are optional.
+pub fn render_typescript_types(
+    root: &RootNode,
+    options: TypeScriptOptions,
+) -> CodamaResult<TypeScriptTypes> {
+    let programs = std::iter::once(&root.program).chain(root.additional_programs.iter());
+    let extension = match options.codecs {
+        true => "ts",
+        false => "d.ts",
+    };
+    let mut renderer = Renderer::new(LinkResolver::new(root), options);
+    let mut files = BTreeMap::new();
+    for program in programs {
+        renderer.resolver = LinkResolver::new(root).with_program(program);
+        let (result, file) = renderer.render_file(|this| {
+            this.scoped(program.kind(), &program.name, |this| this.program(program))
+        });
+        result?;
+        let path = format!("{}.{extension}", program.name.to_kebab_case());
+        if files.contains_key(&path) {
+            renderer.scoped(program.kind(), &program.name, |this| {
+                this.report(format!(
+                    "`{path}` was already rendered by another node and was skipped"
+                ))
+            });
+            continue;
+        }
+        files.insert(path, file.render(options));
+    }
+
+    Ok(TypeScriptTypes {
+        files,
+        issues: renderer.issues,
+    })
+}
+
+impl<'a> Renderer<'a> {
+    fn program(&mut self, program: &'a ProgramNode) -> CodamaResult<()> {
+        for defined_type in &program.defined_types {
+            self.scoped(defined_type.kind(), &defined_type.name, |this| {
+                this.named_type(
+                    &defined_type.name.to_pascal_case(),
+                    &defined_type.docs,
+                    &defined_type.r#type,
+                )
+            })?;
+        }
+        for account in &program.accounts {
+            self.scoped(account.kind(), &account.name, |this| {
+                this.named_type(
+                    &account.name.to_pascal_case(),
+                    &account.docs,
+                    &TypeNode::from(account.data.clone()),
+                )
+            })?;
+        }
+        for instruction in &program.instructions {
+            let resolver = self.resolver.with_instruction(instruction);
+            let parent = std::mem::replace(&mut self.resolver, resolver);
+            let result = self.scoped(instruction.kind(), &instruction.name, |this| {
+                this.instruction(instruction)
+            });
+            self.resolver = parent;
+            result?;
+        }
+        for event in &program.events {
+            self.scoped(event.kind(), &event.name, |this| {
+                this.named_type(&event.name.to_pascal_case(), &event.docs, &event.data)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Renders the arguments of an instruction as its `InstructionData` type, along with
+    /// an `InstructionDataArgs` type when some arguments have a default value.
+    fn instruction(&mut self, instruction: &InstructionNode) -> CodamaResult<()> {
+        let name = instruction.name.to_pascal_case();
+        let data_name = format!("{name}InstructionData");
+        let fields = instruction
+            .arguments
+            .iter()
+            .map(|argument| StructFieldTypeNode {
+                docs: argument.docs.clone(),
+                ..StructFieldTypeNode::new(argument.name.clone(), argument.r#type.as_ref().clone())
+            })
+            .collect::<Vec<_>>();
+        self.named_type(
+            &data_name,
+            &[format!("The data of the `{name}` instruction.")],
+            &TypeNode::Struct(StructTypeNode::new(fields)),
+        )?;
+
+        let mut members = vec![];
+        let mut values = vec![];
+        let mut has_defaults = false;
+        for argument in &instruction.arguments {
+            let default_value = argument
+                .default_value
+                .as_ref()
+                .clone()
+                .and_then(|value| ValueNode::try_from(value).ok());
+            let default_value = match (default_value, self.options.codecs) {
+                (Some(value), true) => self.value(&value, &argument.r#type),
+                (Some(_), false) => Some(String::new()),
+                (None, _) => None,
+            };
+            let field = argument.name.to_string();
+            let is_omitted = default_value.is_some()
+                && argument.default_value_strategy == Some(DefaultValueStrategy::Omitted);
+            let arg = access("args", &field);
+            let value = match &default_value {
+                Some(value) if is_omitted => value.clone(),
+                Some(value) if is_nullable(&argument.r#type) => {
+                    format!("{arg} === undefined ? {value} : {arg}")
+                }
+                Some(value) => format!("{arg} ?? {value}"),
+                None => arg,
+            };
+            values.push(format!(
+                "    {}: {},\n",
+                property(&field),
+                indent_rest(&value, "    ")
+            ));
+            if !is_omitted {
+                members.push(Member {
+                    docs: docs(&argument.docs, ""),
+                    is_optional: default_value.is_some(),
+                    ..Member::new(field, self.ts_type(&argument.r#type)?)
+                });
+            }
+            has_defaults |= default_value.is_some();
+        }
+        if !has_defaults {
+            return Ok(());
+        }
+
+        let args_name = format!("{data_name}Args");
+        if !self.declare(&args_name) {
+            self.report(format!(
+                "`{args_name}` was already rendered by another node and was skipped"
+            ));
+            return Ok(());
+        }
+        self.push_item(format!(
+            "/** The arguments of the `{name}` instruction, where arguments with a default value are optional. */\n\
+             export type {args_name} = {};\n",
+            object_type(&members, false)
+        ));
+        if self.options.codecs {
+            self.push_item(format!(
+                "/** Fills the default values of the arguments of the `{name}` instruction. */\n\
+                 export function get{data_name}(args: {args_name}): {data_name} {{\n\
+                 \x20 return {{\n\
+                 {}\
+                 \x20 }};\n\
+                 }}\n",
+                values.concat()
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn is_nullable(r#type: &TypeNode) -> bool {
+    matches!(
+        r#type,
+        TypeNode::Option(_) | TypeNode::ZeroableOption(_) | TypeNode::RemainderOption(_)
+    )
+}
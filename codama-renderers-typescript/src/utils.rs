@@ -0,0 +1,88 @@
+/// Renders a property name, quoting it when it is not a valid identifier.
+pub(crate) fn property(name: &str) -> String {
+    match is_identifier(name) {
+        true => name.to_string(),
+        false => string_literal(name),
+    }
+}
+
+/// Renders an access to the given property of a value.
+pub(crate) fn access(value: &str, name: &str) -> String {
+    match is_identifier(name) {
+        true => format!("{value}.{name}"),
+        false => format!("{value}[{}]", string_literal(name)),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_' || char == '$')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+}
+
+/// Renders JSDoc comments at the given indentation.
+pub(crate) fn docs(lines: &[String], indent: &str) -> String {
+    match lines {
+        [] => String::new(),
+        [line] => format!("{indent}/** {line} */\n"),
+        lines => {
+            let lines = lines
+                .iter()
+                .map(|line| match line.is_empty() {
+                    true => format!("{indent} *\n"),
+                    false => format!("{indent} * {line}\n"),
+                })
+                .collect::<String>();
+            format!("{indent}/**\n{lines}{indent} */\n")
+        }
+    }
+}
+
+/// Renders a string as a single-quoted string literal.
+pub(crate) fn string_literal(value: &str) -> String {
+    let mut literal = String::from("'");
+    for char in value.chars() {
+        match char {
+            '\'' => literal.push_str("\\'"),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            char if char.is_control() => literal.push_str(&format!("\\u{:04x}", char as u32)),
+            char => literal.push(char),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// Renders a list of bytes as an array literal.
+pub(crate) fn bytes_literal(bytes: &[u8]) -> String {
+    let bytes = bytes.iter().map(u8::to_string).collect::<Vec<_>>();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// Indents every non-empty line of the given code.
+pub(crate) fn indent(code: &str, indent: &str) -> String {
+    code.lines()
+        .map(|line| match line.is_empty() {
+            true => "\n".to_string(),
+            false => format!("{indent}{line}\n"),
+        })
+        .collect()
+}
+
+/// Indents every line but the first one, so that multi-line code
+/// can be embedded in a line that is already indented.
+pub(crate) fn indent_rest(code: &str, indent: &str) -> String {
+    code.lines()
+        .enumerate()
+        .map(|(index, line)| match index == 0 || line.is_empty() {
+            true => line.to_string(),
+            false => format!("{indent}{line}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
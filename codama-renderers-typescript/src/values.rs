@@ -0,0 +1,64 @@
+use crate::renderer::Renderer;
+use crate::types::is_bigint;
+use crate::utils::{bytes_literal, string_literal};
+use codama_export_issues::ExportIssueReporter;
+use codama_nodes::{encode, BytesEncoding, Number, NumberFormat, TypeNode, ValueNode};
+
+impl<'a> Renderer<'a> {
+    /// Renders a value of the given type as a TypeScript expression. Values without a literal
+    /// are decoded from their encoded bytes, and values that cannot be encoded are reported.
+    pub(crate) fn value(&mut self, value: &ValueNode, r#type: &TypeNode) -> Option<String> {
+        let bytes = match encode(value, r#type, &self.resolver) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                self.report(format!(
+                    "the value could not be encoded and was ignored: {error}"
+                ));
+                return None;
+            }
+        };
+        Some(match (value, r#type) {
+            (ValueNode::Boolean(node), TypeNode::Boolean(_)) => node.boolean.to_string(),
+            (ValueNode::Number(node), r#type) if r#type.number_format().is_some() => {
+                number_literal(&node.number, r#type.number_format()?)
+            }
+            (ValueNode::PublicKey(node), TypeNode::PublicKey(_)) => {
+                string_literal(&node.public_key)
+            }
+            (ValueNode::String(node), r#type) if is_utf8_string(r#type) => {
+                string_literal(&node.string)
+            }
+            (ValueNode::None(_), TypeNode::Option(_) | TypeNode::ZeroableOption(_)) => {
+                "null".to_string()
+            }
+            _ => {
+                let read = self.read(r#type).ok()?;
+                format!(
+                    "((reader: Reader) => {read})({{ bytes: new Uint8Array({}), offset: 0 }})",
+                    bytes_literal(&bytes)
+                )
+            }
+        })
+    }
+}
+
+fn number_literal(number: &Number, format: NumberFormat) -> String {
+    let literal = match number {
+        Number::UnsignedInteger(value) => value.to_string(),
+        Number::SignedInteger(value) => value.to_string(),
+        Number::Float(value) if is_bigint(format) => (*value as i128).to_string(),
+        Number::Float(value) => format!("{value:?}"),
+    };
+    match is_bigint(format) {
+        true => format!("{literal}n"),
+        false => literal,
+    }
+}
+
+fn is_utf8_string(r#type: &TypeNode) -> bool {
+    let is_utf8 = |r#type: &TypeNode| matches!(r#type, TypeNode::String(node) if node.encoding == BytesEncoding::Utf8);
+    match r#type {
+        TypeNode::SizePrefix(node) => is_utf8(&node.r#type),
+        r#type => is_utf8(r#type),
+    }
+}
@@ -0,0 +1 @@
+mod render_typescript_types;
@@ -0,0 +1,426 @@
+use codama_nodes::{
+    AccountNode, ArrayTypeNode, BytesEncoding, BytesTypeNode, BytesValueNode, ConstantValueNode,
+    DefaultValueStrategy, DefinedTypeLinkNode, DefinedTypeNode, EnumEmptyVariantTypeNode,
+    EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EnumTypeNode, EventNode,
+    FixedSizeTypeNode, InstructionArgumentNode, InstructionNode, MapTypeNode, NoneValueNode,
+    NumberTypeNode, NumberValueNode, OptionTypeNode, PreOffsetTypeNode, ProgramLinkNode,
+    ProgramNode, PublicKeyTypeNode, RootNode, SentinelTypeNode, SizePrefixTypeNode, StringTypeNode,
+    StructFieldTypeNode, StructTypeNode, TupleTypeNode, I32, U16, U32, U64, U8,
+};
+use codama_renderers_typescript::{render_typescript_types, TypeScriptOptions, TypeScriptTypes};
+use pretty_assertions::assert_eq;
+
+fn get_root() -> RootNode {
+    let direction = EnumTypeNode::new(vec![
+        EnumEmptyVariantTypeNode::new("up").into(),
+        EnumEmptyVariantTypeNode::new("down").into(),
+    ]);
+    let counter = StructTypeNode::new(vec![
+        StructFieldTypeNode {
+            default_value: Box::new(Some(
+                BytesValueNode::new(BytesEncoding::Base16, "ffb004f5bcfd7c19").into(),
+            )),
+            default_value_strategy: Some(DefaultValueStrategy::Omitted),
+            ..StructFieldTypeNode::new(
+                "discriminator",
+                FixedSizeTypeNode::new(BytesTypeNode::new(), 8),
+            )
+        },
+        StructFieldTypeNode::new("authority", PublicKeyTypeNode::new()),
+        StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+        StructFieldTypeNode::new("direction", DefinedTypeLinkNode::new("direction")),
+    ]);
+    let increment = InstructionNode {
+        arguments: vec![
+            InstructionArgumentNode {
+                default_value: Box::new(Some(NumberValueNode::new(1u8).into())),
+                default_value_strategy: Some(DefaultValueStrategy::Omitted),
+                ..InstructionArgumentNode::new("discriminator", NumberTypeNode::le(U8))
+            },
+            InstructionArgumentNode {
+                docs: vec!["The amount to add to the counter.".to_string()].into(),
+                default_value: Box::new(Some(NumberValueNode::new(1u64).into())),
+                ..InstructionArgumentNode::new("amount", NumberTypeNode::le(U64))
+            },
+        ],
+        name: "increment".into(),
+        ..InstructionNode::default()
+    };
+    let program = ProgramNode {
+        defined_types: vec![DefinedTypeNode::new("direction", direction)],
+        accounts: vec![AccountNode {
+            docs: vec!["Tracks a count per authority.".to_string()].into(),
+            ..AccountNode::new("counter", counter)
+        }],
+        instructions: vec![increment],
+        events: vec![EventNode::new(
+            "incremented",
+            StructTypeNode::new(vec![
+                StructFieldTypeNode::new("count", NumberTypeNode::le(U64)),
+                StructFieldTypeNode::new(
+                    "label",
+                    SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U32)),
+                ),
+            ]),
+        )],
+        ..ProgramNode::new("counter", "Counter111111111111111111111111111111111111")
+    };
+    RootNode::new(program)
+}
+
+fn get_vault_root() -> RootNode {
+    let config = StructTypeNode::new(vec![
+        StructFieldTypeNode::new("maxAmount", NumberTypeNode::le(U64)),
+        StructFieldTypeNode::new("authority", PublicKeyTypeNode::new()),
+        StructFieldTypeNode::new("seeds", ArrayTypeNode::fixed(NumberTypeNode::le(U16), 4)),
+    ]);
+    let action = EnumTypeNode::new(vec![
+        EnumEmptyVariantTypeNode::new("idle").into(),
+        EnumStructVariantTypeNode::new(
+            "deposit",
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "amount",
+                NumberTypeNode::le(U64),
+            )]),
+        )
+        .into(),
+        EnumTupleVariantTypeNode::new(
+            "swap",
+            TupleTypeNode::new(vec![
+                NumberTypeNode::le(U8).into(),
+                NumberTypeNode::le(I32).into(),
+            ]),
+        )
+        .into(),
+    ]);
+    let vault = StructTypeNode::new(vec![
+        StructFieldTypeNode::new("config", DefinedTypeLinkNode::new("config")),
+        StructFieldTypeNode::new("delegate", OptionTypeNode::new(PublicKeyTypeNode::new())),
+        StructFieldTypeNode::new(
+            "balances",
+            MapTypeNode::prefixed(
+                PublicKeyTypeNode::new(),
+                NumberTypeNode::le(U64),
+                NumberTypeNode::le(U32),
+            ),
+        ),
+        StructFieldTypeNode::new(
+            "history",
+            ArrayTypeNode::prefixed(DefinedTypeLinkNode::new("action"), NumberTypeNode::le(U32)),
+        ),
+        StructFieldTypeNode::new(
+            "label",
+            SentinelTypeNode::new(
+                StringTypeNode::utf8(),
+                ConstantValueNode::bytes(BytesEncoding::Base16, "ff"),
+            ),
+        ),
+        StructFieldTypeNode::new(
+            "padding",
+            PreOffsetTypeNode::relative(NumberTypeNode::le(U8), 2),
+        ),
+    ]);
+    let withdraw = InstructionNode {
+        name: "withdraw".into(),
+        arguments: vec![
+            InstructionArgumentNode::new("action", DefinedTypeLinkNode::new("action")),
+            InstructionArgumentNode {
+                default_value: Box::new(Some(NoneValueNode::new().into())),
+                ..InstructionArgumentNode::new("memo", OptionTypeNode::new(NumberTypeNode::le(U8)))
+            },
+        ],
+        ..InstructionNode::default()
+    };
+    let program = ProgramNode {
+        defined_types: vec![
+            DefinedTypeNode::new("config", config),
+            DefinedTypeNode::new("action", action),
+        ],
+        accounts: vec![AccountNode::new("vault", vault)],
+        instructions: vec![withdraw],
+        events: vec![EventNode::new(
+            "withdrawn",
+            TupleTypeNode::new(vec![
+                FixedSizeTypeNode::new(BytesTypeNode::new(), 2).into(),
+                NumberTypeNode::le(U64).into(),
+            ]),
+        )],
+        ..ProgramNode::new("vault", "Vau1t11111111111111111111111111111111111111")
+    };
+    RootNode::new(program)
+}
+
+fn get_issues(types: &TypeScriptTypes) -> Vec<String> {
+    types.issues.iter().map(ToString::to_string).collect()
+}
+
+fn get_linked_root() -> RootNode {
+    let token = ProgramNode {
+        defined_types: vec![DefinedTypeNode::new(
+            "mintInfo",
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "decimals",
+                NumberTypeNode::le(U8),
+            )]),
+        )],
+        ..ProgramNode::new("splToken", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+    };
+    let program = ProgramNode {
+        defined_types: vec![DefinedTypeNode::new(
+            "pool",
+            StructTypeNode::new(vec![StructFieldTypeNode::new(
+                "mint",
+                DefinedTypeLinkNode::new_from_program("mintInfo", ProgramLinkNode::new("splToken")),
+            )]),
+        )],
+        accounts: vec![AccountNode::new("pool", StructTypeNode::new(vec![]))],
+        ..ProgramNode::new("pools", "Poo1s11111111111111111111111111111111111111")
+    };
+    RootNode {
+        additional_programs: vec![token],
+        ..RootNode::new(program)
+    }
+}
+
+#[test]
+fn render_declaration_files() {
+    let types = render_typescript_types(&get_root(), TypeScriptOptions::default()).unwrap();
+    assert_eq!(get_issues(&types), Vec::<String>::new());
+    assert_eq!(types.files.keys().collect::<Vec<_>>(), vec!["counter.d.ts"]);
+    assert_eq!(
+        types.files["counter.d.ts"],
+        "/**
+ * This code was AUTOGENERATED using the codama-rs library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun codama-rs to update it.
+ */
+
+/** A base58-encoded public key. */
+export type Address = string;
+
+export type Direction = 'Up' | 'Down';
+
+/** Tracks a count per authority. */
+export type Counter = {
+  discriminator: Uint8Array;
+  authority: Address;
+  count: bigint;
+  direction: Direction;
+};
+
+/** The data of the `Increment` instruction. */
+export type IncrementInstructionData = {
+  discriminator: number;
+  /** The amount to add to the counter. */
+  amount: bigint;
+};
+
+/** The arguments of the `Increment` instruction, where arguments with a default value are optional. */
+export type IncrementInstructionDataArgs = {
+  /** The amount to add to the counter. */
+  amount?: bigint;
+};
+
+export type Incremented = {
+  count: bigint;
+  label: string;
+};
+"
+    );
+}
+
+#[test]
+fn render_enums() {
+    let types = render_typescript_types(&get_vault_root(), TypeScriptOptions::default()).unwrap();
+    let file = &types.files["vault.d.ts"];
+    assert!(file.contains(
+        "export type Action =
+  | { __kind: 'Idle' }
+  | { __kind: 'Deposit'; amount: bigint }
+  | { __kind: 'Swap'; fields: [number, number] };
+"
+    ));
+    assert!(file.contains(
+        "export type Vault = {
+  config: Config;
+  delegate: Address | null;
+  balances: Map<Address, bigint>;
+  history: Array<Action>;
+  label: string;
+  padding: number;
+};
+"
+    ));
+    assert!(file.contains("export type Withdrawn = [Uint8Array, bigint];\n"));
+}
+
+#[test]
+fn render_instruction_args() {
+    let options = TypeScriptOptions { codecs: true };
+    let types = render_typescript_types(&get_vault_root(), options).unwrap();
+    let file = &types.files["vault.ts"];
+    assert!(file.contains(
+        "export type WithdrawInstructionDataArgs = {
+  action: Action;
+  memo?: number | null;
+};
+"
+    ));
+
+    // Nullable arguments only use their default value when they are not provided.
+    assert!(file.contains(
+        "export function getWithdrawInstructionData(args: WithdrawInstructionDataArgs): WithdrawInstructionData {
+  return {
+    action: args.action,
+    memo: args.memo === undefined ? null : args.memo,
+  };
+}
+"
+    ));
+
+    // Omitted arguments always use their default value.
+    let types = render_typescript_types(&get_root(), options).unwrap();
+    assert!(types.files["counter.ts"].contains(
+        "  return {
+    discriminator: 1,
+    amount: args.amount ?? 1n,
+  };
+"
+    ));
+}
+
+#[test]
+fn render_codecs() {
+    let options = TypeScriptOptions { codecs: true };
+    let types = render_typescript_types(&get_root(), options).unwrap();
+    assert_eq!(get_issues(&types), Vec::<String>::new());
+    assert_eq!(types.files.keys().collect::<Vec<_>>(), vec!["counter.ts"]);
+    let file = &types.files["counter.ts"];
+    assert!(file.contains(
+        "export function writeCounter(writer: Writer, value: Counter): void {
+  {
+    const bytes1: Writer = [];
+    writeBytes(bytes1, value.discriminator);
+    writeSized(writer, bytes1, 8);
+  }
+  writeBytes(writer, base58ToBytes(value.authority));
+  writeNumber(writer, 'u64', value.count, true);
+  writeDirection(writer, value.direction);
+}
+"
+    ));
+    assert!(file.contains(
+        "export function readDirection(reader: Reader): Direction {
+  return ((kind: number): Direction => {
+    switch (kind) {
+      case 0:
+        return 'Up';
+      case 1:
+        return 'Down';
+      default:
+        throw new Error(`Invalid enum discriminator ${kind}`);
+    }
+  })(readNumber(reader, 'u8', true));
+}
+"
+    ));
+    assert!(file.contains(
+        "export function decodeCounter(bytes: Uint8Array): Counter {
+  return readCounter({ bytes, offset: 0 });
+}
+"
+    ));
+
+    // Only the helpers used by the codecs are rendered.
+    assert!(file.contains("function readSized<T>("));
+    assert!(!file.contains("function readUntil<T>("));
+}
+
+#[test]
+fn render_data_enum_codecs() {
+    let options = TypeScriptOptions { codecs: true };
+    let types = render_typescript_types(&get_vault_root(), options).unwrap();
+    let file = &types.files["vault.ts"];
+    assert!(file.contains(
+        "  switch (value.__kind) {
+    case 'Idle':
+      writeNumber(writer, 'u8', 0, true);
+      break;
+    case 'Deposit':
+      writeNumber(writer, 'u8', 1, true);
+      writeNumber(writer, 'u64', value.amount, true);
+      break;
+    case 'Swap':
+      writeNumber(writer, 'u8', 2, true);
+      writeNumber(writer, 'u8', value.fields[0], true);
+      writeNumber(writer, 'i32', value.fields[1], true);
+      break;
+  }
+"
+    ));
+    assert!(file.contains(
+        "      case 1:
+        return {
+          __kind: 'Deposit',
+          amount: readNumber(reader, 'u64', true),
+        };
+"
+    ));
+    assert!(file.contains(
+        "  for (const [key1, value2] of value.balances) {
+    writeBytes(writer, base58ToBytes(key1));
+    writeNumber(writer, 'u64', value2, true);
+  }
+"
+    ));
+    assert!(file.contains(
+        "    label: readUntil(reader, [255], (reader) => bytesToUtf8(readBytes(reader, remainingLength(reader)))),\n"
+    ));
+}
+
+#[test]
+fn render_unsupported_nodes() {
+    let types = render_typescript_types(&get_vault_root(), TypeScriptOptions::default()).unwrap();
+    assert_eq!(get_issues(&types), Vec::<String>::new());
+
+    let options = TypeScriptOptions { codecs: true };
+    let types = render_typescript_types(&get_vault_root(), options).unwrap();
+    assert_eq!(
+        get_issues(&types),
+        vec![
+            "[programNode]vault > [accountNode]vault > [structFieldTypeNode]padding: `preOffsetTypeNode` is not supported by the rendered codecs and was ignored",
+        ]
+    );
+}
+
+#[test]
+fn render_linked_programs() {
+    let types = render_typescript_types(&get_linked_root(), TypeScriptOptions::default()).unwrap();
+    assert_eq!(
+        types.files.keys().collect::<Vec<_>>(),
+        vec!["pools.d.ts", "spl-token.d.ts"]
+    );
+    assert!(types.files["pools.d.ts"].contains("import type { MintInfo } from './spl-token';\n"));
+    assert_eq!(
+        get_issues(&types),
+        vec![
+            "[programNode]pools > [accountNode]pool: `Pool` was already rendered by another node and was skipped",
+        ]
+    );
+
+    let options = TypeScriptOptions { codecs: true };
+    let types = render_typescript_types(&get_linked_root(), options).unwrap();
+    assert!(types.files["pools.ts"]
+        .contains("import { type MintInfo, readMintInfo, writeMintInfo } from './spl-token';\n"));
+}
+
+#[test]
+fn write_files() {
+    let types = render_typescript_types(&get_root(), TypeScriptOptions::default()).unwrap();
+    let directory = std::env::temp_dir().join("codama-renderers-typescript-write-files");
+    let _ = std::fs::remove_dir_all(&directory);
+    types.write(&directory).unwrap();
+    let file = std::fs::read_to_string(directory.join("counter.d.ts")).unwrap();
+    assert_eq!(file, types.files["counter.d.ts"]);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
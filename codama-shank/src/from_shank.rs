@@ -0,0 +1,381 @@
+use codama_errors::{CodamaError, CodamaResult};
+use codama_nodes::{
+    AccountNode, ArrayTypeNode, BooleanTypeNode, BytesEncoding, BytesTypeNode, ConstantValueNode,
+    DefaultValueStrategy, DefinedTypeLinkNode, DefinedTypeNode, Docs, EnumEmptyVariantTypeNode,
+    EnumStructVariantTypeNode, EnumTupleVariantTypeNode, EnumTypeNode, EnumVariantTypeNode,
+    ErrorNode, FieldDiscriminatorNode, InstructionAccountNode, InstructionArgumentNode,
+    InstructionNode, IsSigner, MapTypeNode, NumberFormat, NumberTypeNode, NumberValueNode,
+    OptionTypeNode, OptionalAccountStrategy, ProgramNode, ProgramOrigin, PublicKeyTypeNode,
+    RootNode, SetTypeNode, SizePrefixTypeNode, StringTypeNode, StructFieldTypeNode, StructTypeNode,
+    TupleTypeNode, TypeNode, ZeroableOptionTypeNode, F32, F64, I128, I16, I32, I64, I8, U128, U16,
+    U32, U64, U8,
+};
+use serde::Deserialize;
+use serde_json::Number as JsonNumber;
+
+/// Converts a Shank IDL into a `RootNode`.
+pub fn root_node_from_shank(json: &str) -> CodamaResult<RootNode> {
+    Ok(RootNode::new(program_node_from_shank(json)?))
+}
+
+/// Converts a Shank IDL into a `ProgramNode`, e.g. to add
+/// it to the additional programs of an existing `RootNode`.
+pub fn program_node_from_shank(json: &str) -> CodamaResult<ProgramNode> {
+    let idl: Idl = serde_json::from_str(json)?;
+    let address = idl.metadata.and_then(|metadata| metadata.address);
+
+    Ok(ProgramNode {
+        origin: Some(ProgramOrigin::Shank),
+        version: idl.version,
+        docs: idl.docs.into(),
+        accounts: idl
+            .accounts
+            .iter()
+            .map(|account| {
+                let TypeNode::Struct(data) = type_def_node(&account.r#type)? else {
+                    return Err(CodamaError::InvalidIdl(format!(
+                        "account `{}` must be a struct with named fields",
+                        account.name
+                    )));
+                };
+                Ok(AccountNode {
+                    docs: account.docs.clone().into(),
+                    ..AccountNode::new(account.name.as_str(), data)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        instructions: idl
+            .instructions
+            .iter()
+            .map(instruction_node)
+            .collect::<CodamaResult<_>>()?,
+        defined_types: idl
+            .types
+            .iter()
+            .map(|def| {
+                Ok(DefinedTypeNode {
+                    docs: def.docs.clone().into(),
+                    ..DefinedTypeNode::new(def.name.as_str(), type_def_node(&def.r#type)?)
+                })
+            })
+            .collect::<CodamaResult<_>>()?,
+        errors: idl
+            .errors
+            .into_iter()
+            .map(|error| ErrorNode::new(error.name, error.code, error.msg.unwrap_or_default()))
+            .collect(),
+        ..ProgramNode::new(idl.name, address.unwrap_or_default())
+    })
+}
+
+#[derive(Deserialize)]
+struct Idl {
+    version: String,
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    #[serde(default)]
+    instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    accounts: Vec<IdlTypeDef>,
+    #[serde(default)]
+    types: Vec<IdlTypeDef>,
+    #[serde(default)]
+    errors: Vec<IdlErrorCode>,
+    metadata: Option<IdlMetadata>,
+}
+
+#[derive(Deserialize)]
+struct IdlMetadata {
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IdlInstruction {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    accounts: Vec<IdlAccount>,
+    args: Vec<IdlField>,
+    discriminant: Option<IdlDiscriminant>,
+    /// Optional accounts are omitted instead of replaced by the program ID.
+    #[serde(default)]
+    legacy_optional_accounts_strategy: bool,
+}
+
+#[derive(Deserialize)]
+struct IdlDiscriminant {
+    r#type: IdlType,
+    value: JsonNumber,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IdlAccount {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    desc: Option<String>,
+    is_mut: bool,
+    is_signer: bool,
+    #[serde(default)]
+    is_optional_signer: bool,
+    #[serde(default, alias = "optional")]
+    is_optional: bool,
+}
+
+#[derive(Deserialize)]
+struct IdlField {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    r#type: IdlType,
+}
+
+#[derive(Deserialize)]
+struct IdlErrorCode {
+    code: u32,
+    name: String,
+    msg: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdlTypeDef {
+    name: String,
+    #[serde(default)]
+    docs: Vec<String>,
+    r#type: IdlTypeDefTy,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: Vec<IdlField>,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdlEnumFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Deserialize)]
+struct IdlEnumVariant {
+    name: String,
+    fields: Option<IdlEnumFields>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    Bytes,
+    String,
+    PublicKey,
+    Defined(String),
+    Option(Box<IdlType>),
+    #[serde(rename = "coption")]
+    COption(Box<IdlType>),
+    /// An option using a sentinel value, all zeroes by default, to represent `None`.
+    FixedSizeOption {
+        inner: Box<IdlType>,
+        sentinel: Option<Vec<u8>>,
+    },
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, u64),
+    Tuple(Vec<IdlType>),
+    HashMap(Box<IdlType>, Box<IdlType>),
+    #[serde(rename = "bTreeMap")]
+    BTreeMap(Box<IdlType>, Box<IdlType>),
+    HashSet(Box<IdlType>),
+    #[serde(rename = "bTreeSet")]
+    BTreeSet(Box<IdlType>),
+}
+
+fn instruction_node(instruction: &IdlInstruction) -> CodamaResult<InstructionNode> {
+    let discriminator = instruction
+        .discriminant
+        .as_ref()
+        .map(discriminator_argument)
+        .transpose()?;
+    let arguments = instruction
+        .args
+        .iter()
+        .map(|arg| {
+            Ok(InstructionArgumentNode {
+                docs: arg.docs.clone().into(),
+                ..InstructionArgumentNode::new(arg.name.as_str(), type_node(&arg.r#type)?)
+            })
+        })
+        .collect::<CodamaResult<Vec<_>>>()?;
+
+    Ok(InstructionNode {
+        name: instruction.name.as_str().into(),
+        docs: instruction.docs.clone().into(),
+        optional_account_strategy: instruction
+            .legacy_optional_accounts_strategy
+            .then_some(OptionalAccountStrategy::Omitted),
+        accounts: instruction.accounts.iter().map(account_node).collect(),
+        discriminators: match discriminator {
+            Some(_) => vec![FieldDiscriminatorNode::new("discriminator", 0).into()],
+            None => vec![],
+        },
+        arguments: discriminator.into_iter().chain(arguments).collect(),
+        ..InstructionNode::default()
+    })
+}
+
+/// Shank instructions are identified by a number preceding their arguments.
+fn discriminator_argument(discriminant: &IdlDiscriminant) -> CodamaResult<InstructionArgumentNode> {
+    let r#type = type_node(&discriminant.r#type)?;
+    if !matches!(r#type, TypeNode::Number(_)) {
+        return Err(CodamaError::InvalidIdl(
+            "instruction discriminants must be numbers".to_string(),
+        ));
+    }
+    Ok(InstructionArgumentNode {
+        default_value_strategy: Some(DefaultValueStrategy::Omitted),
+        default_value: Box::new(Some(
+            NumberValueNode::new(discriminant.value.clone()).into(),
+        )),
+        ..InstructionArgumentNode::new("discriminator", r#type)
+    })
+}
+
+fn account_node(account: &IdlAccount) -> InstructionAccountNode {
+    let is_signer = match (account.is_signer, account.is_optional_signer) {
+        (_, true) => IsSigner::Either,
+        (is_signer, false) => is_signer.into(),
+    };
+    let mut docs: Docs = account.docs.clone().into();
+    if let Some(desc) = &account.desc {
+        docs.push(desc.as_str());
+    }
+    InstructionAccountNode {
+        is_optional: account.is_optional.then_some(true),
+        docs,
+        ..InstructionAccountNode::new(account.name.as_str(), account.is_mut, is_signer)
+    }
+}
+
+fn struct_field_node(field: &IdlField) -> CodamaResult<StructFieldTypeNode> {
+    Ok(StructFieldTypeNode {
+        docs: field.docs.clone().into(),
+        ..StructFieldTypeNode::new(field.name.as_str(), type_node(&field.r#type)?)
+    })
+}
+
+fn struct_node(fields: &[IdlField]) -> CodamaResult<StructTypeNode> {
+    Ok(StructTypeNode::new(
+        fields
+            .iter()
+            .map(struct_field_node)
+            .collect::<CodamaResult<_>>()?,
+    ))
+}
+
+fn type_def_node(def: &IdlTypeDefTy) -> CodamaResult<TypeNode> {
+    Ok(match def {
+        IdlTypeDefTy::Struct { fields } => struct_node(fields)?.into(),
+        IdlTypeDefTy::Enum { variants } => EnumTypeNode::new(
+            variants
+                .iter()
+                .map(|variant| {
+                    let name = variant.name.as_str();
+                    Ok::<EnumVariantTypeNode, CodamaError>(match &variant.fields {
+                        None => EnumEmptyVariantTypeNode::new(name).into(),
+                        Some(IdlEnumFields::Named(fields)) => {
+                            EnumStructVariantTypeNode::new(name, struct_node(fields)?).into()
+                        }
+                        Some(IdlEnumFields::Tuple(items)) => EnumTupleVariantTypeNode::new(
+                            name,
+                            TupleTypeNode::new(
+                                items.iter().map(type_node).collect::<CodamaResult<_>>()?,
+                            ),
+                        )
+                        .into(),
+                    })
+                })
+                .collect::<CodamaResult<_>>()?,
+        )
+        .into(),
+    })
+}
+
+fn type_node(r#type: &IdlType) -> CodamaResult<TypeNode> {
+    Ok(match r#type {
+        IdlType::Bool => BooleanTypeNode::default().into(),
+        IdlType::U8 => number(U8),
+        IdlType::I8 => number(I8),
+        IdlType::U16 => number(U16),
+        IdlType::I16 => number(I16),
+        IdlType::U32 => number(U32),
+        IdlType::I32 => number(I32),
+        IdlType::F32 => number(F32),
+        IdlType::U64 => number(U64),
+        IdlType::I64 => number(I64),
+        IdlType::F64 => number(F64),
+        IdlType::U128 => number(U128),
+        IdlType::I128 => number(I128),
+        IdlType::Bytes => {
+            SizePrefixTypeNode::new(BytesTypeNode::new(), NumberTypeNode::le(U32)).into()
+        }
+        IdlType::String => {
+            SizePrefixTypeNode::new(StringTypeNode::utf8(), NumberTypeNode::le(U32)).into()
+        }
+        IdlType::PublicKey => PublicKeyTypeNode::new().into(),
+        IdlType::Defined(name) => DefinedTypeLinkNode::new(name.as_str()).into(),
+        IdlType::Option(item) => OptionTypeNode::new(type_node(item)?).into(),
+        IdlType::COption(item) => OptionTypeNode {
+            prefix: NumberTypeNode::le(U32).into(),
+            ..OptionTypeNode::fixed(type_node(item)?)
+        }
+        .into(),
+        IdlType::FixedSizeOption { inner, sentinel } => ZeroableOptionTypeNode {
+            zero_value: sentinel.as_ref().map(|sentinel| {
+                let data = sentinel.iter().map(|byte| format!("{byte:02x}"));
+                ConstantValueNode::bytes(BytesEncoding::Base16, data.collect::<String>())
+            }),
+            ..ZeroableOptionTypeNode::new(type_node(inner)?)
+        }
+        .into(),
+        IdlType::Vec(item) => {
+            ArrayTypeNode::prefixed(type_node(item)?, NumberTypeNode::le(U32)).into()
+        }
+        IdlType::Array(item, size) => ArrayTypeNode::fixed(type_node(item)?, *size).into(),
+        IdlType::Tuple(items) => {
+            TupleTypeNode::new(items.iter().map(type_node).collect::<CodamaResult<_>>()?).into()
+        }
+        IdlType::HashMap(key, value) | IdlType::BTreeMap(key, value) => {
+            MapTypeNode::prefixed(type_node(key)?, type_node(value)?, NumberTypeNode::le(U32))
+                .into()
+        }
+        IdlType::HashSet(item) | IdlType::BTreeSet(item) => {
+            SetTypeNode::prefixed(type_node(item)?, NumberTypeNode::le(U32)).into()
+        }
+    })
+}
+
+fn number(format: NumberFormat) -> TypeNode {
+    NumberTypeNode::le(format).into()
+}
@@ -0,0 +1,3 @@
+mod from_shank;
+
+pub use from_shank::*;
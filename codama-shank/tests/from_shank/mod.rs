@@ -0,0 +1,137 @@
+use crate::get_path;
+use codama_errors::CodamaError;
+use codama_nodes::{
+    validate, DefaultValueStrategy, DefinedTypeLinkNode, Docs, ErrorNode, FieldDiscriminatorNode,
+    InstructionAccountNode, InstructionArgumentNode, IsSigner, MapTypeNode, NestedTypeNodeTrait,
+    NumberTypeNode, NumberValueNode, OptionTypeNode, OptionalAccountStrategy, ProgramOrigin,
+    PublicKeyTypeNode, RootNode, StructFieldTypeNode, TypeNode, ZeroableOptionTypeNode, U32, U64,
+    U8,
+};
+use codama_shank::{program_node_from_shank, root_node_from_shank};
+use pretty_assertions::assert_eq;
+
+fn get_root() -> RootNode {
+    let json = std::fs::read_to_string(get_path("from_shank/idl.json")).unwrap();
+    root_node_from_shank(&json).unwrap()
+}
+
+#[test]
+fn program() {
+    let root = get_root();
+    let program = &root.program;
+    assert_eq!(program.name.as_str(), "vault");
+    assert_eq!(
+        program.public_key,
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    );
+    assert_eq!(program.version, "0.2.0");
+    assert_eq!(program.origin, Some(ProgramOrigin::Shank));
+    assert_eq!(
+        program.errors,
+        vec![ErrorNode::new("invalidOwner", 0, "Invalid vault owner")]
+    );
+    assert_eq!(validate(&root), vec![]);
+}
+
+#[test]
+fn instructions() {
+    let root = get_root();
+    let deposit = &root.program.instructions[0];
+    assert_eq!(deposit.name.as_str(), "deposit");
+    assert_eq!(
+        deposit.optional_account_strategy,
+        Some(OptionalAccountStrategy::Omitted)
+    );
+    assert_eq!(
+        deposit.accounts,
+        vec![
+            InstructionAccountNode {
+                docs: Docs::new().add_doc("The vault account"),
+                ..InstructionAccountNode::new("vault", true, false)
+            },
+            InstructionAccountNode::new("owner", false, true),
+            InstructionAccountNode::new("delegate", false, IsSigner::Either),
+            InstructionAccountNode {
+                is_optional: Some(true),
+                ..InstructionAccountNode::new("referrer", false, false)
+            },
+        ]
+    );
+    assert_eq!(
+        deposit.arguments,
+        vec![
+            InstructionArgumentNode {
+                default_value_strategy: Some(DefaultValueStrategy::Omitted),
+                default_value: Box::new(Some(NumberValueNode::new(1u8).into())),
+                ..InstructionArgumentNode::new("discriminator", NumberTypeNode::le(U8))
+            },
+            InstructionArgumentNode::new("depositArgs", DefinedTypeLinkNode::new("depositArgs")),
+        ]
+    );
+    assert_eq!(
+        deposit.discriminators,
+        vec![FieldDiscriminatorNode::new("discriminator", 0).into()]
+    );
+    assert_eq!(root.program.instructions[1].optional_account_strategy, None);
+}
+
+#[test]
+fn options_and_collections() {
+    let root = get_root();
+    let fields = &root.program.accounts[0].data.get_nested_type_node().fields;
+    assert_eq!(
+        fields[2],
+        StructFieldTypeNode::new(
+            "delegate",
+            OptionTypeNode {
+                prefix: NumberTypeNode::le(U32).into(),
+                ..OptionTypeNode::fixed(PublicKeyTypeNode::new())
+            }
+        )
+    );
+    assert_eq!(
+        fields[3],
+        StructFieldTypeNode::new(
+            "authority",
+            ZeroableOptionTypeNode::new(PublicKeyTypeNode::new())
+        )
+    );
+    assert_eq!(
+        fields[4],
+        StructFieldTypeNode::new(
+            "balances",
+            MapTypeNode::prefixed(
+                PublicKeyTypeNode::new(),
+                NumberTypeNode::le(U64),
+                NumberTypeNode::le(U32)
+            )
+        )
+    );
+    let deposit_args = &root.program.defined_types[0];
+    let TypeNode::Struct(deposit_args) = deposit_args.r#type.as_ref() else {
+        panic!("expected a struct");
+    };
+    assert!(matches!(
+        *deposit_args.fields[1].r#type,
+        TypeNode::Option(_)
+    ));
+    assert!(matches!(*deposit_args.fields[2].r#type, TypeNode::Tuple(_)));
+}
+
+#[test]
+fn invalid_idl() {
+    let json = r#"{
+        "version": "0.1.0",
+        "name": "vault",
+        "instructions": [{
+            "name": "deposit",
+            "accounts": [],
+            "args": [],
+            "discriminant": { "type": "publicKey", "value": 0 }
+        }]
+    }"#;
+    assert!(matches!(
+        program_node_from_shank(json),
+        Err(CodamaError::InvalidIdl(_))
+    ));
+}
@@ -2,7 +2,12 @@ import { pascalCase } from '@codama/fragments';
 import { addFragmentImports, type Fragment, fragment, mergeFragments } from '@codama/fragments/rust';
 import type { Spec, UnionSpec } from '@codama/spec';
 
-import { flattenNodeUnion, getRegisteredOnlyLeafKinds, getRegisteredUnionStripSuffix } from '../unions';
+import {
+    flattenNodeUnion,
+    getRegisteredOnlyLeafKinds,
+    getRegisteredUnionStripSuffix,
+    getUnknownVariantSuffix,
+} from '../unions';
 import { use } from './helpers';
 
 /**
@@ -54,8 +59,14 @@ export function getRegisteredUnionPageFragment(union: UnionSpec, spec: Spec): Fr
         }
     }
 
+    // The `Unknown` variant is standalone so the `RegisteredNodes` derive
+    // bridges it between both enums.
+    const unknownSuffix = getUnknownVariantSuffix(union, spec);
     const standaloneLines = mergeFragments(
-        standaloneVariants.map(v => fragment`${v.name}(${use(`crate::${pascalCase(v.kind)}`)}),`),
+        [
+            ...standaloneVariants.map(v => fragment`${v.name}(${use(`crate::${pascalCase(v.kind)}`)}),`),
+            ...(unknownSuffix ? [fragment`Unknown(${use('crate::UnknownNode')}),`] : []),
+        ],
         parts => parts.join('\n'),
     );
     const registeredLines = mergeFragments(
@@ -67,10 +78,12 @@ export function getRegisteredUnionPageFragment(union: UnionSpec, spec: Spec): Fr
     // visual split between standalone and `#[registered]` variants.
     const body = mergeFragments([standaloneLines, registeredLines], parts => parts.join('\n\n'));
 
+    const nodeUnionArguments = unknownSuffix ? `(unknown_suffix = "${unknownSuffix}")` : '';
+
     // The `RegisteredNodes` derive macro expansion calls `.kind()` on
     // each variant, which requires `HasKind` to be in scope.
     return addFragmentImports(
-        fragment`#[derive(${use('codama_nodes_derive::RegisteredNodes')})]\n#[${use('codama_nodes_derive::node_union')}]\npub enum ${enumName} {\n${body}\n}`,
+        fragment`#[derive(${use('codama_nodes_derive::RegisteredNodes')})]\n#[${use('codama_nodes_derive::node_union')}${nodeUnionArguments}]\npub enum ${enumName} {\n${body}\n}`,
         ['crate::HasKind'],
     );
 }
@@ -2,7 +2,13 @@ import { pascalCase } from '@codama/fragments';
 import { type Fragment, fragment, mergeFragments } from '@codama/fragments/rust';
 import type { NodeSpec, Spec, UnionSpec } from '@codama/spec';
 
-import { flattenNodeUnion, getInlineUnionStripSuffix, getReferencedUnionNames, isInlineUnion } from '../unions';
+import {
+    flattenNodeUnion,
+    getInlineUnionStripSuffix,
+    getReferencedUnionNames,
+    getUnknownVariantSuffix,
+    isInlineUnion,
+} from '../unions';
 import { getUnionHasNameImplFragment } from './hasNameImpl';
 import { use } from './helpers';
 
@@ -14,6 +20,9 @@ import { use } from './helpers';
  *      alphabetically. Variant name = pascalCase(kind) minus the
  *      union's implied node suffix (e.g. `accountLinkNode` in
  *      `linkNode` → `Account`).
+ *      An `Unknown(UnknownNode)` variant and its `unknown_suffix` are
+ *      added to unions that nest extensible unions
+ *      ({@link getUnknownVariantSuffix}).
  *   2. `impl HasName for XxxUnion { … }` when every member node has a
  *      `name: stringIdentifier()` attribute.
  */
@@ -21,7 +30,7 @@ export function getUnionPageFragment(union: UnionSpec, spec: Spec): Fragment {
     const unionName = pascalCase(union.name);
     const variants = buildVariants(union, spec);
 
-    const enumFragment = buildEnumFragment(unionName, variants);
+    const enumFragment = buildEnumFragment(unionName, variants, getUnknownVariantSuffix(union, spec));
     const hasNameFragment = getUnionHasNameImplFragment(unionName, variants);
 
     const blocks: (Fragment | undefined)[] = [enumFragment, hasNameFragment];
@@ -74,10 +83,18 @@ function variantNameForNode(nodeKind: string, suffix: string): string {
     return pascal.endsWith(suffix) ? pascal.slice(0, pascal.length - suffix.length) : pascal;
 }
 
-function buildEnumFragment(unionName: string, variants: readonly UnionVariant[]): Fragment {
+function buildEnumFragment(
+    unionName: string,
+    variants: readonly UnionVariant[],
+    unknownSuffix: string | undefined,
+): Fragment {
     const lines = mergeFragments(
-        variants.map(v => fragment`${v.name}(${use(`crate::${pascalCase(v.node.kind)}`)}),`),
+        [
+            ...variants.map(v => fragment`${v.name}(${use(`crate::${pascalCase(v.node.kind)}`)}),`),
+            ...(unknownSuffix ? [fragment`Unknown(${use('crate::UnknownNode')}),`] : []),
+        ],
         parts => parts.join('\n'),
     );
-    return fragment`#[${use('codama_nodes_derive::node_union')}]\npub enum ${unionName} {\n${lines}\n}`;
+    const nodeUnionArguments = unknownSuffix ? `(unknown_suffix = "${unknownSuffix}")` : '';
+    return fragment`#[${use('codama_nodes_derive::node_union')}${nodeUnionArguments}]\npub enum ${unionName} {\n${lines}\n}`;
 }
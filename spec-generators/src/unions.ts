@@ -66,6 +66,36 @@ export function getRegisteredOnlyLeafKinds(union: UnionSpec, spec: Spec): readon
     return [...flattenNodeUnion(twin, spec)].map(n => n.kind).filter(k => !standaloneKinds.has(k));
 }
 
+/**
+ * Spec unions that newer spec versions may extend with new node kinds.
+ */
+const EXTENSIBLE_UNION_NAMES: ReadonlySet<string> = new Set(['discriminatorNode', 'valueNode']);
+
+/**
+ * The `unknown_suffix` of the emitted enum of `union` when it gets an
+ * `Unknown(UnknownNode)` variant, which keeps node kinds unknown to the
+ * Rust crate when deserializing leniently, e.g. `'ValueNode'`. That is
+ * the case for the extensible unions and for every union that nests one
+ * of them (e.g. `constantPdaSeedValue` nests `valueNode`), since the
+ * former convert into the latter. Only unknown kinds ending with that
+ * suffix are kept by the union.
+ */
+export function getUnknownVariantSuffix(union: UnionSpec, spec: Spec): string | undefined {
+    const unionByName = new Map(spec.categories.flatMap(c => c.unions).map(u => [u.name, u]));
+    const visited = new Set<string>();
+    const stack: string[] = [union.name];
+    while (stack.length > 0) {
+        const name = stack.pop();
+        if (name === undefined || visited.has(name)) continue;
+        if (EXTENSIBLE_UNION_NAMES.has(name)) return pascalCase(name);
+        visited.add(name);
+        for (const m of unionByName.get(name)?.members ?? []) {
+            if (m.kind === 'union') stack.push(m.name);
+        }
+    }
+    return undefined;
+}
+
 /**
  * `true` when `union` is an inline / synthetic union: it has no
  * `registered<PascalCase>` sibling anywhere in the spec AND it is
@@ -6,6 +6,7 @@ import {
     getEmittableUnions,
     getInlineUnionStripSuffix,
     getRegisteredOnlyLeafKinds,
+    getUnknownVariantSuffix,
     isRegisteredCategoryUnion,
 } from '../src/unions';
 
@@ -119,3 +120,19 @@ describe('flattenNodeUnion', () => {
         ]);
     });
 });
+
+describe('getUnknownVariantSuffix', () => {
+    it('returns the suffix of the extensible union nested by the union', () => {
+        const valueNode = valueCategory.unions.find(u => u.name === 'valueNode')!;
+        const constantPdaSeedValue = pdaSeedCategory.unions.find(u => u.name === 'constantPdaSeedValue')!;
+        expect(getUnknownVariantSuffix(valueNode, spec)).toBe('ValueNode');
+        expect(getUnknownVariantSuffix(constantPdaSeedValue, spec)).toBe('ValueNode');
+    });
+
+    it('is undefined for other unions', () => {
+        const linkNode = linkCategory.unions.find(u => u.name === 'linkNode')!;
+        const pdaSeedNode = pdaSeedCategory.unions.find(u => u.name === 'pdaSeedNode')!;
+        expect(getUnknownVariantSuffix(linkNode, spec)).toBeUndefined();
+        expect(getUnknownVariantSuffix(pdaSeedNode, spec)).toBeUndefined();
+    });
+});